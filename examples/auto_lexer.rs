@@ -17,9 +17,9 @@ gazelle! {
             RPAREN = r"\)"
         }
 
-        expr = expr PLUS term => add | term => term;
-        term = term STAR factor => mul | factor => factor;
-        factor = NUM => num | LPAREN expr RPAREN => paren;
+        expr = expr PLUS term => add | term;
+        term = term STAR factor => mul | factor;
+        factor = NUM | LPAREN expr RPAREN;
     }
 }
 
@@ -35,28 +35,15 @@ impl calc::Types for Eval {
 
 impl gazelle::Action<calc::Expr<Self>> for Eval {
     fn build(&mut self, node: calc::Expr<Self>) -> Result<i64, gazelle::ParseError> {
-        Ok(match node {
-            calc::Expr::Add(l, r) => l + r,
-            calc::Expr::Term(t) => t,
-        })
+        let calc::Expr::Add(l, r) = node;
+        Ok(l + r)
     }
 }
 
 impl gazelle::Action<calc::Term<Self>> for Eval {
     fn build(&mut self, node: calc::Term<Self>) -> Result<i64, gazelle::ParseError> {
-        Ok(match node {
-            calc::Term::Mul(l, r) => l * r,
-            calc::Term::Factor(f) => f,
-        })
-    }
-}
-
-impl gazelle::Action<calc::Factor<Self>> for Eval {
-    fn build(&mut self, node: calc::Factor<Self>) -> Result<i64, gazelle::ParseError> {
-        Ok(match node {
-            calc::Factor::Num(n) => n,
-            calc::Factor::Paren(e) => e,
-        })
+        let calc::Term::Mul(l, r) = node;
+        Ok(l * r)
     }
 }
 
@@ -20,12 +20,15 @@ gazelle! {
         item = NUM COMMA => with_comma | NUM => without_comma;
 
         // nums: one or more numbers (for testing +)
+        #[allow(unreachable)]
         nums = NUM+ => nums;
 
         // opt_num: optional number followed by semi
+        #[allow(unreachable)]
         opt_num = NUM? SEMI => opt;
 
         // semis: zero or more semicolons (untyped terminal with *)
+        #[allow(unreachable)]
         semis = SEMI* => semis;
     }
 }
@@ -12,6 +12,7 @@
 //!   NAME:value     - terminal with value
 //!   NAME@<5        - terminal with left-assoc precedence 5
 //!   NAME:value@>3  - terminal with value and right-assoc precedence 3
+//!   NAME@=2        - terminal with non-assoc precedence 2
 //!   ;              - expression separator (prints result, resets parser)
 //!
 //! Example with included files:
@@ -35,7 +36,7 @@ gazelle! {
         terminals {
             IDENT: _,
             NUM: _,
-            COLON, AT, LT, GT, SEMI
+            COLON, AT, LT, GT, EQ, SEMI
         }
 
         sentences = sentence* => sentences;
@@ -46,7 +47,7 @@ gazelle! {
         colon_value = COLON value => colon_value;
         value = IDENT => ident | NUM => num;
 
-        assoc = LT => left | GT => right;
+        assoc = LT => left | GT => right | EQ => non_assoc;
         at_precedence = AT assoc NUM => at_prec;
     }
 }
@@ -165,6 +166,7 @@ impl<'a> gazelle::Action<token_format::Tokens<Self>> for Actions<'a> {
                     match assoc {
                         token_format::Assoc::Left => Precedence::Left(level),
                         token_format::Assoc::Right => Precedence::Right(level),
+                        token_format::Assoc::NonAssoc => Precedence::NonAssoc(level),
                     }
                 });
 
@@ -178,7 +180,10 @@ impl<'a> gazelle::Action<token_format::Tokens<Self>> for Actions<'a> {
                 };
 
                 parser.values.push(value);
-                parser.cst.push(token)?;
+                if let Err(e) = parser.cst.push(token) {
+                    let msg = parser.cst.format_error(&e, self.compiled, None, None);
+                    return Err(ActionError::Runtime(msg));
+                }
                 Ok(parser)
             }
         }
@@ -232,6 +237,7 @@ fn run() -> Result<(), String> {
                 '@' => token_format::Terminal::At,
                 '<' => token_format::Terminal::Lt,
                 '>' => token_format::Terminal::Gt,
+                '=' => token_format::Terminal::Eq,
                 ';' => token_format::Terminal::Semi,
                 _ => token_format::Terminal::Ident(c.to_string()),
             }
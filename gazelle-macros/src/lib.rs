@@ -77,50 +77,129 @@ pub fn gazelle(input: TokenStream) -> TokenStream {
 fn parse_and_generate(input: proc_macro2::TokenStream) -> Result<proc_macro2::TokenStream, String> {
     let (visibility, name, source) = lex_token_stream(input)?;
 
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| "CARGO_MANIFEST_DIR not set".to_string())?;
+    let manifest_dir = std::path::Path::new(&manifest_dir);
+
+    // Every file read, tracked below for recompilation
+    let mut files = Vec::new();
+    // The grammar file and its text, to locate errors by line and column
+    let mut located = None;
+    // Where lint warnings point: the tokens of an inline grammar, each
+    // passed to the parser with its index as span, or else the file path
+    let mut token_spans = Vec::new();
+    let mut source_span = proc_macro2::Span::call_site();
     let grammar_def = match source {
         GrammarSource::Inline(tokens) => {
             if tokens.is_empty() {
                 return Err("Empty grammar".to_string());
             }
-            gazelle::meta::parse_tokens_typed(tokens)?
+            let mut indexed = Vec::with_capacity(tokens.len());
+            for (i, (token, span)) in tokens.into_iter().enumerate() {
+                indexed.push((token, i..i + 1));
+                token_spans.push(span);
+            }
+            gazelle::meta::parse_located_tokens(indexed, |path| {
+                read_grammar_file(&manifest_dir.join(path), &mut files)
+            })
         }
-        GrammarSource::File(path) => {
-            let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
-                .map_err(|_| "CARGO_MANIFEST_DIR not set".to_string())?;
-            let full_path = std::path::Path::new(&manifest_dir).join(&path);
-            let content = std::fs::read_to_string(&full_path)
-                .map_err(|e| format!("Failed to read {}: {}", full_path.display(), e))?;
-            let grammar_def = gazelle::parse_grammar(&content)?;
-
-            // Emit include_bytes! so cargo tracks the file for recompilation
-            let ctx = gazelle::codegen::CodegenContext::from_grammar(
-                &grammar_def,
-                &name,
-                &visibility,
-                true,
-            )?;
-            let mut tokens = gazelle::codegen::generate_tokens(&ctx)?;
-            let abs = full_path
-                .canonicalize()
-                .map_err(|e| format!("Failed to canonicalize {}: {}", full_path.display(), e))?;
-            let abs_str = abs.to_str().ok_or("Non-UTF8 path")?;
-            let include: proc_macro2::TokenStream =
-                format!("const _: &[u8] = include_bytes!({:?});", abs_str)
-                    .parse()
-                    .map_err(|e| format!("Failed to generate include_bytes: {}", e))?;
-            tokens.extend(include);
-            return Ok(tokens);
+        GrammarSource::File(path, span) => {
+            source_span = span;
+            let full_path = manifest_dir.join(&path);
+            let content = read_grammar_file(&full_path, &mut files)?;
+            // Imports are relative to the grammar file
+            let dir = full_path.parent().unwrap_or(manifest_dir);
+            let grammar = gazelle::parse_grammar_with(&content, |path| {
+                read_grammar_file(&dir.join(path), &mut files)
+            });
+            located = Some((path, content));
+            grammar
         }
     };
+    let locate = |e: gazelle::GrammarError| match (&located, &e.span) {
+        (Some((path, content)), Some(_)) => format!("{}:{}", path, e.format(content)),
+        _ => e.message,
+    };
+
+    let grammar_def = grammar_def.map_err(locate)?;
 
     let ctx =
-        gazelle::codegen::CodegenContext::from_grammar(&grammar_def, &name, &visibility, true)?;
-    gazelle::codegen::generate_tokens(&ctx)
+        gazelle::codegen::CodegenContext::from_grammar(&grammar_def, &name, &visibility, true)
+            .map_err(locate)?;
+    let mut tokens = gazelle::codegen::generate_tokens(&ctx).map_err(locate)?;
+
+    // Proc macros cannot emit warnings on stable, but using a deprecated
+    // item can: each lint warning becomes a deprecation note, located at
+    // the symbol's declaration
+    for warning in gazelle::lint::lint(&grammar_def).map_err(locate)? {
+        let span = match &warning.span {
+            Some(span) if located.is_none() => token_spans.get(span.start).copied(),
+            _ => None,
+        };
+        let message = match (&located, &warning.span) {
+            (Some((path, content)), Some(_)) => format!("{}:{}", path, warning.format(content)),
+            _ => warning.message,
+        };
+        let note = format!(
+            "{} (allow with `#![allow({})]` in the grammar)",
+            message,
+            warning.kind.name()
+        );
+        let warning: proc_macro2::TokenStream = format!(
+            "const _: () = {{ #[deprecated(note = {:?})] struct GrammarWarning; let _ = GrammarWarning; }};",
+            note
+        )
+        .parse()
+        .map_err(|e| format!("Failed to generate warning: {}", e))?;
+        tokens.extend(respan(warning, span.unwrap_or(source_span)));
+    }
+
+    // Emit include_bytes! so cargo tracks the files for recompilation
+    for path in files {
+        let abs = path
+            .canonicalize()
+            .map_err(|e| format!("Failed to canonicalize {}: {}", path.display(), e))?;
+        let abs_str = abs.to_str().ok_or("Non-UTF8 path")?;
+        let include: proc_macro2::TokenStream =
+            format!("const _: &[u8] = include_bytes!({:?});", abs_str)
+                .parse()
+                .map_err(|e| format!("Failed to generate include_bytes: {}", e))?;
+        tokens.extend(include);
+    }
+    Ok(tokens)
+}
+
+/// Give every token of `stream` the location `span`.
+fn respan(stream: proc_macro2::TokenStream, span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    stream
+        .into_iter()
+        .map(|mut tt| {
+            if let TokenTree::Group(g) = &tt {
+                let mut group = proc_macro2::Group::new(g.delimiter(), respan(g.stream(), span));
+                group.set_span(span);
+                return TokenTree::Group(group);
+            }
+            tt.set_span(span);
+            tt
+        })
+        .collect()
+}
+
+/// Read a grammar file, recording its path.
+fn read_grammar_file(
+    path: &std::path::Path,
+    files: &mut Vec<std::path::PathBuf>,
+) -> Result<String, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    files.push(path.to_path_buf());
+    Ok(content)
 }
 
 enum GrammarSource {
-    Inline(Vec<Terminal<AstBuilder>>),
-    File(String),
+    Inline(Vec<(Terminal<AstBuilder>, proc_macro2::Span)>),
+    /// A grammar file's path, with the location of the path literal.
+    File(String, proc_macro2::Span),
 }
 
 /// Lex a proc_macro2::TokenStream into Terminals.
@@ -176,7 +255,7 @@ fn lex_token_stream(
                 // Strip surrounding quotes
                 if s.starts_with('"') && s.ends_with('"') {
                     let path = s[1..s.len() - 1].to_string();
-                    return Ok((visibility, name, GrammarSource::File(path)));
+                    return Ok((visibility, name, GrammarSource::File(path, lit.span())));
                 }
                 return Err(format!("Expected string literal after `=`, got {}", s));
             }
@@ -199,11 +278,23 @@ fn lex_token_stream(
 
     let mut tokens = Vec::new();
     let mut inner_iter = content.into_iter().peekable();
-    lex_tokens(&mut inner_iter, &mut tokens)?;
+    lex_tokens(&mut inner_iter, &mut tokens, false)?;
 
     Ok((visibility, name, GrammarSource::Inline(tokens)))
 }
 
+fn before_path(iter: &mut std::iter::Peekable<proc_macro2::token_stream::IntoIter>) -> bool {
+    matches!(iter.peek(), Some(TokenTree::Literal(l)) if l.to_string().starts_with('"'))
+}
+
+fn string_token(value: String, in_terminals: bool) -> Terminal<AstBuilder> {
+    if in_terminals {
+        Terminal::Regex(value)
+    } else {
+        Terminal::Literal(value)
+    }
+}
+
 fn unescape_string(s: &str) -> Result<String, String> {
     let mut out = String::new();
     let mut chars = s.chars();
@@ -215,6 +306,7 @@ fn unescape_string(s: &str) -> Result<String, String> {
                 Some('r') => out.push('\r'),
                 Some('\\') => out.push('\\'),
                 Some('"') => out.push('"'),
+                Some('\'') => out.push('\''),
                 Some('0') => out.push('\0'),
                 Some(other) => {
                     // Pass through other escapes (like \+, \*, etc.) as-is for regex
@@ -230,38 +322,95 @@ fn unescape_string(s: &str) -> Result<String, String> {
     Ok(out)
 }
 
+/// Lex grammar tokens. String literals are regex patterns inside the
+/// `terminals { ... }` block (`in_terminals`) and literal terminals elsewhere.
 fn lex_tokens(
     iter: &mut std::iter::Peekable<proc_macro2::token_stream::IntoIter>,
-    tokens: &mut Vec<Terminal<AstBuilder>>,
+    tokens: &mut Vec<(Terminal<AstBuilder>, proc_macro2::Span)>,
+    in_terminals: bool,
 ) -> Result<(), String> {
     while let Some(tt) = iter.next() {
         match tt {
             TokenTree::Ident(id) => {
                 let s = id.to_string();
+                let span = id.span();
                 match s.as_str() {
-                    "start" => tokens.push(Terminal::KwStart),
-                    "terminals" => tokens.push(Terminal::KwTerminals),
-                    "prec" => tokens.push(Terminal::KwPrec),
-                    "expect" => tokens.push(Terminal::KwExpect),
+                    "start" => tokens.push((Terminal::KwStart, span)),
+                    "terminals" => tokens.push((Terminal::KwTerminals, span)),
+                    "prec" => tokens.push((Terminal::KwPrec, span)),
+                    "expect" => tokens.push((Terminal::KwExpect, span)),
+                    "mode" => tokens.push((Terminal::KwMode, span)),
+                    // `import` and `extends` are keywords only before a path,
+                    // so they stay usable as names
+                    "import" if before_path(iter) => tokens.push((Terminal::KwImport, span)),
+                    "extends" if before_path(iter) => tokens.push((Terminal::KwExtends, span)),
 
-                    "_" => tokens.push(Terminal::Underscore),
-                    _ => tokens.push(Terminal::Ident(s)),
+                    "_" => tokens.push((Terminal::Underscore, span)),
+                    _ => tokens.push((Terminal::Ident(s), span)),
                 }
             }
             TokenTree::Punct(p) => {
                 let c = p.as_char();
+                let span = p.span();
                 match c {
-                    '{' => tokens.push(Terminal::Lbrace),
-                    '}' => tokens.push(Terminal::Rbrace),
-                    ',' => tokens.push(Terminal::Comma),
-                    '|' => tokens.push(Terminal::Pipe),
-                    ';' => tokens.push(Terminal::Semi),
-                    '?' => tokens.push(Terminal::Question),
-                    '*' => tokens.push(Terminal::Star),
-                    '+' => tokens.push(Terminal::Plus),
-                    '%' => tokens.push(Terminal::Percent),
+                    '{' => tokens.push((Terminal::Lbrace, span)),
+                    '}' => tokens.push((Terminal::Rbrace, span)),
+                    ',' => tokens.push((Terminal::Comma, span)),
+                    // |= and -= (rule edits)
+                    '|' | '-'
+                        if p.spacing() == proc_macro2::Spacing::Joint
+                            && matches!(iter.peek(), Some(TokenTree::Punct(p2)) if p2.as_char() == '=') =>
+                    {
+                        iter.next();
+                        let edit = if c == '|' {
+                            Terminal::PipeEq
+                        } else {
+                            Terminal::MinusEq
+                        };
+                        tokens.push((edit, span));
+                    }
+                    // Docs before `|` document the alternative after it
+                    '|' => {
+                        let docs = tokens
+                            .iter()
+                            .rev()
+                            .take_while(|(tok, _)| matches!(tok, Terminal::Doc(_)))
+                            .count();
+                        tokens.insert(tokens.len() - docs, (Terminal::Pipe, span));
+                    }
+                    // Attributes, with `///` doc comments arriving as `#[doc = "..."]`
+                    '#' => {
+                        let inner =
+                            matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '!');
+                        if inner {
+                            iter.next();
+                        }
+                        let attr = match iter.next() {
+                            Some(TokenTree::Group(g))
+                                if g.delimiter() == proc_macro2::Delimiter::Bracket =>
+                            {
+                                g.stream()
+                            }
+                            _ => return Err("Expected '[' after '#'".into()),
+                        };
+                        let attr = if inner {
+                            Terminal::InnerAttr(attr.to_string())
+                        } else if let Some(doc) = doc_attr(attr.clone())? {
+                            Terminal::Doc(doc)
+                        } else {
+                            Terminal::Attr(attr.to_string())
+                        };
+                        tokens.push((attr, span));
+                    }
+                    ';' => tokens.push((Terminal::Semi, span)),
+                    '?' => tokens.push((Terminal::Question, span)),
+                    '*' => tokens.push((Terminal::Star, span)),
+                    '+' => tokens.push((Terminal::Plus, span)),
+                    '%' => tokens.push((Terminal::Percent, span)),
+                    '<' => tokens.push((Terminal::Lt, span)),
+                    '>' => tokens.push((Terminal::Gt, span)),
                     ':' => {
-                        tokens.push(Terminal::Colon);
+                        tokens.push((Terminal::Colon, span));
                     }
                     '=' => {
                         // Check for => (fat arrow)
@@ -270,47 +419,54 @@ fn lex_tokens(
                             && p2.as_char() == '>'
                         {
                             iter.next();
-                            tokens.push(Terminal::FatArrow);
+                            tokens.push((Terminal::FatArrow, span));
                             continue;
                         }
-                        tokens.push(Terminal::Eq);
+                        tokens.push((Terminal::Eq, span));
                     }
                     _ => return Err(format!("Unexpected punctuation: {}", c)),
                 }
             }
             TokenTree::Group(g) => match g.delimiter() {
                 proc_macro2::Delimiter::Brace => {
-                    tokens.push(Terminal::Lbrace);
+                    let terminals = matches!(tokens.last(), Some((Terminal::KwTerminals, _)));
+                    tokens.push((Terminal::Lbrace, g.span_open()));
                     let mut inner_iter = g.stream().into_iter().peekable();
-                    lex_tokens(&mut inner_iter, tokens)?;
-                    tokens.push(Terminal::Rbrace);
+                    lex_tokens(&mut inner_iter, tokens, terminals)?;
+                    tokens.push((Terminal::Rbrace, g.span_close()));
                 }
                 proc_macro2::Delimiter::Parenthesis => {
-                    tokens.push(Terminal::Lparen);
+                    tokens.push((Terminal::Lparen, g.span_open()));
                     let mut inner_iter = g.stream().into_iter().peekable();
-                    lex_tokens(&mut inner_iter, tokens)?;
-                    tokens.push(Terminal::Rparen);
+                    lex_tokens(&mut inner_iter, tokens, in_terminals)?;
+                    tokens.push((Terminal::Rparen, g.span_close()));
                 }
                 _ => return Err(format!("Unexpected group delimiter: {:?}", g.delimiter())),
             },
             TokenTree::Literal(lit) => {
                 let s = lit.to_string();
+                let span = lit.span();
                 // Check if it's a number (integer literal)
                 if s.chars().all(|c| c.is_ascii_digit()) {
-                    tokens.push(Terminal::Num(s));
+                    tokens.push((Terminal::Num(s), span));
                 } else if s.starts_with('"') {
                     // Regular string literal — strip quotes and unescape
                     let inner = &s[1..s.len() - 1];
                     let value = unescape_string(inner)
                         .map_err(|e| format!("Invalid string literal: {}", e))?;
-                    tokens.push(Terminal::Regex(value));
+                    tokens.push((string_token(value, in_terminals), span));
                 } else if s.starts_with("r\"") || s.starts_with("r#") {
                     // Raw string literal — strip r, hashes, and quotes
                     let after_r = &s[1..];
                     let hashes = after_r.bytes().take_while(|&b| b == b'#').count();
                     // Skip hashes + opening quote, remove closing quote + hashes
                     let inner = &after_r[hashes + 1..after_r.len() - hashes - 1];
-                    tokens.push(Terminal::Regex(inner.to_string()));
+                    tokens.push((string_token(inner.to_string(), in_terminals), span));
+                } else if s.starts_with('\'') {
+                    // Char literal — a literal terminal
+                    let value = unescape_string(&s[1..s.len() - 1])
+                        .map_err(|e| format!("Invalid char literal: {}", e))?;
+                    tokens.push((Terminal::Literal(value), span));
                 } else {
                     return Err(format!("Unexpected literal in grammar: {}", s));
                 }
@@ -319,3 +475,34 @@ fn lex_tokens(
     }
     Ok(())
 }
+
+/// The text of a `doc = "..."` attribute, or `None` for other attributes.
+fn doc_attr(stream: proc_macro2::TokenStream) -> Result<Option<String>, String> {
+    let mut iter = stream.into_iter();
+    let (
+        Some(TokenTree::Ident(id)),
+        Some(TokenTree::Punct(eq)),
+        Some(TokenTree::Literal(lit)),
+        None,
+    ) = (iter.next(), iter.next(), iter.next(), iter.next())
+    else {
+        return Ok(None);
+    };
+    if id != "doc" || eq.as_char() != '=' {
+        return Ok(None);
+    }
+    let s = lit.to_string();
+    if s.starts_with('"') {
+        unescape_string(&s[1..s.len() - 1])
+            .map(Some)
+            .map_err(|e| format!("Invalid doc comment: {}", e))
+    } else if s.starts_with("r\"") || s.starts_with("r#") {
+        let after_r = &s[1..];
+        let hashes = after_r.bytes().take_while(|&b| b == b'#').count();
+        Ok(Some(
+            after_r[hashes + 1..after_r.len() - hashes - 1].to_string(),
+        ))
+    } else {
+        Err(format!("Invalid doc comment: {}", s))
+    }
+}
@@ -30,6 +30,7 @@ impl Action<simple::S<Self>> for SimpleActionsImpl {
 }
 
 #[test]
+#[allow(clippy::let_unit_value)]
 fn test_simple_grammar_types() {
     let mut parser = simple::Parser::<SimpleActionsImpl>::new();
     let mut actions = SimpleActionsImpl;
@@ -359,3 +360,1142 @@ fn test_file_include() {
     let result = parser.finish(&mut actions).map_err(|(_, e)| e).unwrap();
     assert_eq!(result, 3);
 }
+
+// Test a grammar that is only LR(1) after inlining: `decl` and `call` both
+// reduce a NAME, and only the token after COLON decides which.
+gazelle! {
+    grammar inlined {
+        start stmt;
+        terminals {
+            NAME: _,
+            COLON,
+            TYPE,
+            ARGS
+        }
+
+        stmt = decl COLON TYPE => typed
+             | call COLON ARGS => called;
+
+        #[inline] decl = NAME => decl;
+        #[inline] call = NAME => call;
+    }
+}
+
+struct InlinedActionsImpl {
+    built: Vec<String>,
+}
+
+impl inlined::Types for InlinedActionsImpl {
+    type Error = gazelle::ParseError;
+    type Name = String;
+    type Stmt = String;
+    type Decl = String;
+    type Call = String;
+}
+
+impl Action<inlined::Stmt<Self>> for InlinedActionsImpl {
+    fn build(&mut self, node: inlined::Stmt<Self>) -> Result<String, gazelle::ParseError> {
+        self.built.push("stmt".to_string());
+        Ok(match node {
+            inlined::Stmt::Typed(d) => format!("typed({d})"),
+            inlined::Stmt::Called(c) => format!("called({c})"),
+        })
+    }
+}
+
+impl Action<inlined::Decl<Self>> for InlinedActionsImpl {
+    fn build(&mut self, node: inlined::Decl<Self>) -> Result<String, gazelle::ParseError> {
+        self.built.push("decl".to_string());
+        let inlined::Decl::Decl(name) = node;
+        Ok(format!("decl {name}"))
+    }
+}
+
+impl Action<inlined::Call<Self>> for InlinedActionsImpl {
+    fn build(&mut self, node: inlined::Call<Self>) -> Result<String, gazelle::ParseError> {
+        self.built.push("call".to_string());
+        let inlined::Call::Call(name) = node;
+        Ok(format!("call {name}"))
+    }
+}
+
+#[test]
+fn test_inlined_nonterminals_keep_their_nodes() {
+    for (last, expected, order) in [
+        (inlined::Terminal::Type, "typed(decl x)", ["decl", "stmt"]),
+        (inlined::Terminal::Args, "called(call x)", ["call", "stmt"]),
+    ] {
+        let mut parser = inlined::Parser::<InlinedActionsImpl>::new();
+        let mut actions = InlinedActionsImpl { built: Vec::new() };
+
+        parser
+            .push(inlined::Terminal::Name("x".to_string()), &mut actions)
+            .unwrap();
+        parser.push(inlined::Terminal::Colon, &mut actions).unwrap();
+        parser.push(last, &mut actions).unwrap();
+
+        let result = parser.finish(&mut actions).map_err(|(_, e)| e).unwrap();
+        assert_eq!(result, expected);
+        assert_eq!(actions.built, order);
+    }
+}
+
+// Test split tokens: the lexer resolves the dangling else by choosing between
+// ELSE (shift, binds to the inner IF) and its reduce-leaning twin (`Else_Reduce`, binds
+// to the outer IF).
+gazelle! {
+    grammar dangling {
+        start stmt;
+        mode split;
+        terminals {
+            IF,
+            ELSE,
+            X
+        }
+
+        stmt = IF stmt => if_then
+             | IF stmt ELSE stmt => if_else
+             | X => x;
+    }
+}
+
+struct DanglingActionsImpl;
+
+impl dangling::Types for DanglingActionsImpl {
+    type Error = gazelle::ParseError;
+    type Stmt = String;
+}
+
+impl Action<dangling::Stmt<Self>> for DanglingActionsImpl {
+    fn build(&mut self, node: dangling::Stmt<Self>) -> Result<String, gazelle::ParseError> {
+        Ok(match node {
+            dangling::Stmt::IfThen(s) => format!("if({s})"),
+            dangling::Stmt::IfElse(t, e) => format!("if({t}, {e})"),
+            dangling::Stmt::X => "x".to_string(),
+        })
+    }
+}
+
+#[test]
+fn test_split_tokens() {
+    let parse = |else_token: fn() -> dangling::Terminal<DanglingActionsImpl>| {
+        let mut parser = dangling::Parser::<DanglingActionsImpl>::new();
+        let mut actions = DanglingActionsImpl;
+        for token in [
+            dangling::Terminal::If,
+            dangling::Terminal::If,
+            dangling::Terminal::X,
+            else_token(),
+            dangling::Terminal::X,
+        ] {
+            parser.push(token, &mut actions).unwrap();
+        }
+        parser.finish(&mut actions).map_err(|(_, e)| e).unwrap()
+    };
+
+    assert_eq!(parse(|| dangling::Terminal::Else), "if(if(x, x))");
+    assert_eq!(parse(|| dangling::Terminal::Else_Reduce), "if(if(x), x)");
+}
+
+// Test rule precedence: unary minus binds tighter than `+` but looser than `*`.
+gazelle! {
+    grammar unary {
+        start expr;
+        terminals {
+            NUM: _,
+            MINUS,
+            prec OP: _
+        }
+
+        expr = expr OP expr => binop
+             | MINUS expr => neg prec(2, right)
+             | NUM => num;
+    }
+}
+
+struct UnaryActionsImpl;
+
+impl unary::Types for UnaryActionsImpl {
+    type Error = gazelle::ParseError;
+    type Num = i32;
+    type Op = char;
+    type Expr = String;
+}
+
+impl Action<unary::Expr<Self>> for UnaryActionsImpl {
+    fn build(&mut self, node: unary::Expr<Self>) -> Result<String, gazelle::ParseError> {
+        Ok(match node {
+            unary::Expr::Binop(l, op, r) => format!("({l} {op} {r})"),
+            unary::Expr::Neg(e) => format!("-{e}"),
+            unary::Expr::Num(n) => n.to_string(),
+        })
+    }
+}
+
+#[test]
+fn test_rule_precedence() {
+    let parse = |op: char, prec: gazelle::Precedence| {
+        let mut parser = unary::Parser::<UnaryActionsImpl>::new();
+        let mut actions = UnaryActionsImpl;
+        for token in [
+            unary::Terminal::Minus,
+            unary::Terminal::Num(1),
+            unary::Terminal::Op(op, prec),
+            unary::Terminal::Num(2),
+        ] {
+            parser.push(token, &mut actions).unwrap();
+        }
+        parser.finish(&mut actions).map_err(|(_, e)| e).unwrap()
+    };
+
+    assert_eq!(parse('+', gazelle::Precedence::Left(1)), "(-1 + 2)");
+    assert_eq!(parse('*', gazelle::Precedence::Left(3)), "-(1 * 2)");
+}
+
+// Test multiple start symbols: one table, an entry point per start symbol.
+gazelle! {
+    grammar entries {
+        start stmts, expr;
+        terminals {
+            NUM: _,
+            PLUS,
+            SEMI
+        }
+
+        stmts = stmts expr SEMI => append | _ => empty;
+        expr = expr PLUS NUM => add | NUM => num;
+    }
+}
+
+struct EntriesActionsImpl;
+
+impl entries::Types for EntriesActionsImpl {
+    type Error = gazelle::ParseError;
+    type Num = i32;
+    type Stmts = Vec<i32>;
+    type Expr = i32;
+}
+
+impl Action<entries::Stmts<Self>> for EntriesActionsImpl {
+    fn build(&mut self, node: entries::Stmts<Self>) -> Result<Vec<i32>, gazelle::ParseError> {
+        Ok(match node {
+            entries::Stmts::Append(mut v, e) => {
+                v.push(e);
+                v
+            }
+            entries::Stmts::Empty => Vec::new(),
+        })
+    }
+}
+
+impl Action<entries::Expr<Self>> for EntriesActionsImpl {
+    fn build(&mut self, node: entries::Expr<Self>) -> Result<i32, gazelle::ParseError> {
+        Ok(match node {
+            entries::Expr::Add(l, r) => l + r,
+            entries::Expr::Num(n) => n,
+        })
+    }
+}
+
+#[test]
+fn test_multiple_start_symbols() {
+    let mut actions = EntriesActionsImpl;
+
+    let mut parser = entries::Parser::<EntriesActionsImpl>::new_expr();
+    for token in [
+        entries::Terminal::Num(1),
+        entries::Terminal::Plus,
+        entries::Terminal::Num(2),
+    ] {
+        parser.push(token, &mut actions).unwrap();
+    }
+    assert_eq!(
+        parser
+            .finish_expr(&mut actions)
+            .map_err(|(_, e)| e)
+            .unwrap(),
+        3
+    );
+
+    // `new`/`finish` use the first start symbol.
+    let mut parser = entries::Parser::<EntriesActionsImpl>::new();
+    for token in [
+        entries::Terminal::Num(1),
+        entries::Terminal::Semi,
+        entries::Terminal::Num(2),
+        entries::Terminal::Plus,
+        entries::Terminal::Num(3),
+        entries::Terminal::Semi,
+    ] {
+        parser.push(token, &mut actions).unwrap();
+    }
+    assert_eq!(
+        parser.finish(&mut actions).map_err(|(_, e)| e).unwrap(),
+        [1, 5]
+    );
+
+    // A statement is not an expression.
+    let mut parser = entries::Parser::<EntriesActionsImpl>::new_expr();
+    parser
+        .push(entries::Terminal::Num(1), &mut actions)
+        .unwrap();
+    assert!(parser.push(entries::Terminal::Semi, &mut actions).is_err());
+
+    // Finishing for another entry point is an error, not a panic.
+    let mut parser = entries::Parser::<EntriesActionsImpl>::new_expr();
+    parser
+        .push(entries::Terminal::Num(1), &mut actions)
+        .unwrap();
+    let (parser, err) = parser.finish(&mut actions).unwrap_err();
+    assert!(err.is_wrong_entry());
+    assert_eq!(
+        parser.format_error(&err, None, None),
+        "finished for a different start symbol than the parser was created for"
+    );
+}
+
+// Test parameterized rules: each application is expanded into its own rule.
+gazelle! {
+    grammar params {
+        start call;
+        terminals {
+            IDENT: _,
+            NUM: _,
+            LPAREN,
+            RPAREN,
+            COMMA
+        }
+
+        comma_list<T> = comma_list<T> COMMA T => more | T => one;
+        paren<T> = LPAREN T RPAREN => paren;
+        call = IDENT paren<comma_list<NUM>> => call;
+    }
+}
+
+struct ParamsActionsImpl;
+
+impl params::Types for ParamsActionsImpl {
+    type Error = gazelle::ParseError;
+    type Ident = String;
+    type Num = i32;
+    type CommaListNum = Vec<i32>;
+    type ParenCommaListNum = Vec<i32>;
+    type Call = String;
+}
+
+impl Action<params::CommaListNum<Self>> for ParamsActionsImpl {
+    fn build(&mut self, node: params::CommaListNum<Self>) -> Result<Vec<i32>, gazelle::ParseError> {
+        Ok(match node {
+            params::CommaListNum::More(mut v, n) => {
+                v.push(n);
+                v
+            }
+            params::CommaListNum::One(n) => vec![n],
+        })
+    }
+}
+
+impl Action<params::ParenCommaListNum<Self>> for ParamsActionsImpl {
+    fn build(
+        &mut self,
+        node: params::ParenCommaListNum<Self>,
+    ) -> Result<Vec<i32>, gazelle::ParseError> {
+        let params::ParenCommaListNum::Paren(args) = node;
+        Ok(args)
+    }
+}
+
+impl Action<params::Call<Self>> for ParamsActionsImpl {
+    fn build(&mut self, node: params::Call<Self>) -> Result<String, gazelle::ParseError> {
+        let params::Call::Call(name, args) = node;
+        Ok(format!("{}{:?}", name, args))
+    }
+}
+
+#[test]
+fn test_parameterized_rules() {
+    let mut actions = ParamsActionsImpl;
+    let mut parser = params::Parser::<ParamsActionsImpl>::new();
+    for token in [
+        params::Terminal::Ident("f".to_string()),
+        params::Terminal::Lparen,
+        params::Terminal::Num(1),
+        params::Terminal::Comma,
+        params::Terminal::Num(2),
+        params::Terminal::Rparen,
+    ] {
+        parser.push(token, &mut actions).unwrap();
+    }
+    assert_eq!(
+        parser.finish(&mut actions).map_err(|(_, e)| e).unwrap(),
+        "f[1, 2]"
+    );
+}
+
+// Test groups: a single typed symbol collapses to its type, several form a tuple.
+gazelle! {
+    grammar groups {
+        start list;
+        terminals {
+            IDENT: _,
+            NUM: _,
+            COMMA,
+            EQ,
+            AT,
+            LPAREN,
+            RPAREN
+        }
+
+        list = item (COMMA item)* => list;
+        item = IDENT (EQ NUM)? => named
+             | (LPAREN NUM RPAREN | NUM) => num
+             | AT (IDENT NUM)+ => pairs;
+    }
+}
+
+struct GroupsActionsImpl;
+
+impl groups::Types for GroupsActionsImpl {
+    type Error = gazelle::ParseError;
+    type Ident = String;
+    type Num = i32;
+    type List = Vec<String>;
+    type Item = String;
+}
+
+impl Action<groups::List<Self>> for GroupsActionsImpl {
+    fn build(&mut self, node: groups::List<Self>) -> Result<Vec<String>, gazelle::ParseError> {
+        let groups::List::List(first, rest) = node;
+        Ok(std::iter::once(first).chain(rest).collect())
+    }
+}
+
+impl Action<groups::Item<Self>> for GroupsActionsImpl {
+    fn build(&mut self, node: groups::Item<Self>) -> Result<String, gazelle::ParseError> {
+        Ok(match node {
+            groups::Item::Named(name, value) => format!("{}={:?}", name, value),
+            groups::Item::Num(n) => n.to_string(),
+            groups::Item::Pairs(pairs) => format!("{:?}", pairs),
+        })
+    }
+}
+
+#[test]
+fn test_groups() {
+    use groups::Terminal as T;
+
+    let mut actions = GroupsActionsImpl;
+    let mut parser = groups::Parser::<GroupsActionsImpl>::new();
+    for token in [
+        T::Ident("a".to_string()),
+        T::Eq,
+        T::Num(1),
+        T::Comma,
+        T::Ident("b".to_string()),
+        T::Comma,
+        T::Lparen,
+        T::Num(2),
+        T::Rparen,
+        T::Comma,
+        T::Num(3),
+        T::Comma,
+        T::At,
+        T::Ident("x".to_string()),
+        T::Num(4),
+        T::Ident("y".to_string()),
+        T::Num(5),
+    ] {
+        parser.push(token, &mut actions).unwrap();
+    }
+    assert_eq!(
+        parser.finish(&mut actions).map_err(|(_, e)| e).unwrap(),
+        ["a=Some(1)", "b=None", "2", "3", r#"[("x", 4), ("y", 5)]"#]
+    );
+}
+
+// Test importing rules and terminals from another file
+gazelle! {
+    grammar imported {
+        start expr;
+        import "tests/common/term.gzl";
+        terminals {
+            STAR
+        }
+
+        expr = expr STAR term => mul
+             | term => term;
+    }
+}
+
+struct ImportedActionsImpl;
+
+impl imported::Types for ImportedActionsImpl {
+    type Error = gazelle::ParseError;
+    type Num = i32;
+    type Expr = i32;
+    type Term = i32;
+}
+
+impl Action<imported::Expr<Self>> for ImportedActionsImpl {
+    fn build(&mut self, node: imported::Expr<Self>) -> Result<i32, gazelle::ParseError> {
+        Ok(match node {
+            imported::Expr::Mul(left, right) => left * right,
+            imported::Expr::Term(t) => t,
+        })
+    }
+}
+
+impl Action<imported::Term<Self>> for ImportedActionsImpl {
+    fn build(&mut self, node: imported::Term<Self>) -> Result<i32, gazelle::ParseError> {
+        let imported::Term::Literal(n) = node;
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_import() {
+    use imported::Terminal as T;
+
+    let mut parser = imported::Parser::<ImportedActionsImpl>::new();
+    let mut actions = ImportedActionsImpl;
+    for token in [T::Num(6), T::Star, T::Num(7)] {
+        parser.push(token, &mut actions).unwrap();
+    }
+    assert_eq!(parser.finish(&mut actions).map_err(|(_, e)| e).unwrap(), 42);
+}
+
+// Test extending a base grammar with new terminals and alternatives
+gazelle! {
+    grammar extended {
+        extends "tests/test.gzl";
+        terminals {
+            MINUS
+        }
+
+        expr |= expr MINUS term => sub;
+    }
+}
+
+struct ExtendedActionsImpl;
+
+impl extended::Types for ExtendedActionsImpl {
+    type Error = gazelle::ParseError;
+    type Num = i32;
+    type Expr = i32;
+    type Term = i32;
+}
+
+impl Action<extended::Expr<Self>> for ExtendedActionsImpl {
+    fn build(&mut self, node: extended::Expr<Self>) -> Result<i32, gazelle::ParseError> {
+        Ok(match node {
+            extended::Expr::Add(left, right) => left + right,
+            extended::Expr::TermToExpr(t) => t,
+            extended::Expr::Sub(left, right) => left - right,
+        })
+    }
+}
+
+impl Action<extended::Term<Self>> for ExtendedActionsImpl {
+    fn build(&mut self, node: extended::Term<Self>) -> Result<i32, gazelle::ParseError> {
+        let extended::Term::Literal(n) = node;
+        Ok(n)
+    }
+}
+
+#[test]
+fn test_extends() {
+    use extended::Terminal as T;
+
+    let mut parser = extended::Parser::<ExtendedActionsImpl>::new();
+    let mut actions = ExtendedActionsImpl;
+    // 10 - 3 + 2
+    for token in [T::Num(10), T::Minus, T::Num(3), T::Plus, T::Num(2)] {
+        parser.push(token, &mut actions).unwrap();
+    }
+    assert_eq!(parser.finish(&mut actions).map_err(|(_, e)| e).unwrap(), 9);
+}
+
+// Test labeled alternatives: struct-like enum variants.
+gazelle! {
+    grammar labeled {
+        start expr;
+        terminals {
+            NUM: _,
+            LPAREN,
+            RPAREN,
+            prec OP: _
+        }
+
+        expr = lhs:expr op:OP rhs:expr => binop
+             | LPAREN inner:expr RPAREN => paren
+             | NUM => num;
+    }
+}
+
+struct LabeledActionsImpl;
+
+impl labeled::Types for LabeledActionsImpl {
+    type Error = gazelle::ParseError;
+    type Num = i32;
+    type Op = char;
+    type Expr = i32;
+}
+
+impl Action<labeled::Expr<Self>> for LabeledActionsImpl {
+    fn build(&mut self, node: labeled::Expr<Self>) -> Result<i32, gazelle::ParseError> {
+        Ok(match node {
+            labeled::Expr::Binop { lhs, op: '-', rhs } => lhs - rhs,
+            labeled::Expr::Binop { lhs, rhs, .. } => lhs * rhs,
+            labeled::Expr::Paren { inner } => inner,
+            labeled::Expr::Num(n) => n,
+        })
+    }
+}
+
+#[test]
+fn test_labeled_alternatives() {
+    use labeled::Terminal as T;
+
+    let mut actions = LabeledActionsImpl;
+    let mut parser = labeled::Parser::<LabeledActionsImpl>::new();
+    // 10 - (2 * 3)
+    for token in [
+        T::Num(10),
+        T::Op('-', gazelle::Precedence::Left(1)),
+        T::Lparen,
+        T::Num(2),
+        T::Op('*', gazelle::Precedence::Left(2)),
+        T::Num(3),
+        T::Rparen,
+    ] {
+        parser.push(token, &mut actions).unwrap();
+    }
+    assert_eq!(parser.finish(&mut actions).map_err(|(_, e)| e).unwrap(), 4);
+
+    let node = labeled::Expr::<LabeledActionsImpl>::Binop {
+        lhs: 1,
+        op: '+',
+        rhs: 2,
+    };
+    assert_eq!(format!("{:?}", node), "Binop { lhs: 1, op: '+', rhs: 2 }");
+}
+
+#[test]
+fn test_generated_precedence_groups() {
+    use gazelle::{Associativity, PrecedenceGroups};
+    use labeled::Terminal as T;
+    use std::sync::Arc;
+
+    let mut groups = PrecedenceGroups::new();
+    groups.declare("Additive", Associativity::Left).unwrap();
+    groups
+        .declare("Multiplicative", Associativity::Left)
+        .unwrap();
+    groups.higher_than("Multiplicative", "Additive").unwrap();
+    let groups = Arc::new(groups);
+    let add = groups.get("Additive").unwrap();
+    let mul = groups.get("Multiplicative").unwrap();
+
+    let parse = |groups: Option<Arc<PrecedenceGroups>>| {
+        let mut actions = LabeledActionsImpl;
+        let mut parser = labeled::Parser::<LabeledActionsImpl>::new();
+        if let Some(groups) = groups {
+            parser.set_precedence_groups(groups);
+        }
+        // 10 - 2 * 3
+        for token in [
+            T::Num(10),
+            T::Op('-', add),
+            T::Num(2),
+            T::Op('*', mul),
+            T::Num(3),
+        ] {
+            parser.push(token, &mut actions)?;
+        }
+        parser.finish(&mut actions).map_err(|(_, e)| e)
+    };
+    assert_eq!(parse(Some(groups.clone())).unwrap(), 4);
+    assert!(parse(None).unwrap_err().is_mismatched_precedence());
+}
+
+// Test lists with a trailing separator (`%%`) and terminated lists (`;`).
+gazelle! {
+    grammar lists {
+        start block;
+        terminals {
+            NUM: _,
+            COMMA,
+            SEMI,
+            LBRACK,
+            RBRACK
+        }
+
+        block = (stmt ; SEMI)* => block;
+        stmt = LBRACK (NUM %% COMMA) RBRACK => array;
+    }
+}
+
+struct ListsActionsImpl;
+
+impl lists::Types for ListsActionsImpl {
+    type Error = gazelle::ParseError;
+    type Num = i32;
+    type Stmt = Vec<i32>;
+    type Block = Vec<Vec<i32>>;
+}
+
+impl Action<lists::Block<Self>> for ListsActionsImpl {
+    fn build(&mut self, node: lists::Block<Self>) -> Result<Vec<Vec<i32>>, gazelle::ParseError> {
+        let lists::Block::Block(stmts) = node;
+        Ok(stmts)
+    }
+}
+
+impl Action<lists::Stmt<Self>> for ListsActionsImpl {
+    fn build(&mut self, node: lists::Stmt<Self>) -> Result<Vec<i32>, gazelle::ParseError> {
+        let lists::Stmt::Array(nums) = node;
+        Ok(nums)
+    }
+}
+
+#[test]
+fn test_separated_and_terminated_lists() {
+    use lists::Terminal as T;
+
+    let parse = |tokens: Vec<T<ListsActionsImpl>>| {
+        let mut actions = ListsActionsImpl;
+        let mut parser = lists::Parser::<ListsActionsImpl>::new();
+        for token in tokens {
+            parser.push(token, &mut actions).ok()?;
+        }
+        parser.finish(&mut actions).ok()
+    };
+
+    // [1, 2,]; [3];
+    assert_eq!(
+        parse(vec![
+            T::Lbrack,
+            T::Num(1),
+            T::Comma,
+            T::Num(2),
+            T::Comma,
+            T::Rbrack,
+            T::Semi,
+            T::Lbrack,
+            T::Num(3),
+            T::Rbrack,
+            T::Semi,
+        ]),
+        Some(vec![vec![1, 2], vec![3]])
+    );
+    assert_eq!(parse(vec![]), Some(vec![]));
+    // The terminator is required after every statement
+    assert_eq!(parse(vec![T::Lbrack, T::Num(1), T::Rbrack]), None);
+    // Only one trailing separator
+    assert_eq!(
+        parse(vec![
+            T::Lbrack,
+            T::Num(1),
+            T::Comma,
+            T::Comma,
+            T::Rbrack,
+            T::Semi
+        ]),
+        None
+    );
+}
+
+// Test alternatives without a variant name, which pass their value through.
+gazelle! {
+    grammar passthrough {
+        start expr;
+        terminals {
+            NUM: _,
+            MINUS,
+            LPAREN,
+            RPAREN
+        }
+
+        expr = expr MINUS atom => sub | atom;
+        atom = NUM | LPAREN expr RPAREN;
+    }
+}
+
+struct PassthroughActionsImpl;
+
+// Values passed through convert with `From`: `atom = NUM` needs `i64: From<i32>`
+impl passthrough::Types for PassthroughActionsImpl {
+    type Error = gazelle::ParseError;
+    type Num = i32;
+    type Expr = i64;
+    type Atom = i64;
+}
+
+impl Action<passthrough::Expr<Self>> for PassthroughActionsImpl {
+    fn build(&mut self, node: passthrough::Expr<Self>) -> Result<i64, gazelle::ParseError> {
+        let passthrough::Expr::Sub(l, r) = node;
+        Ok(l - r)
+    }
+}
+
+#[test]
+fn test_passthrough_alternatives() {
+    use passthrough::Terminal as T;
+
+    let mut actions = PassthroughActionsImpl;
+    let mut parser = passthrough::Parser::<PassthroughActionsImpl>::new();
+    // 10 - (4 - 1)
+    for token in [
+        T::Num(10),
+        T::Minus,
+        T::Lparen,
+        T::Num(4),
+        T::Minus,
+        T::Num(1),
+        T::Rparen,
+    ] {
+        parser.push(token, &mut actions).unwrap();
+    }
+    assert_eq!(parser.finish(&mut actions).map_err(|(_, e)| e).unwrap(), 7);
+}
+
+// Test permutation terms: items in any order, with counts.
+gazelle! {
+    grammar permutation {
+        start decl;
+        terminals {
+            STORAGE: _,
+            QUAL: _,
+            TYPE: _,
+            IDENT: _,
+            INLINE,
+            SEMI
+        }
+
+        decl = { STORAGE? QUAL* TYPE+ INLINE? } IDENT SEMI => decl
+             | { IDENT QUAL } SEMI => pair;
+    }
+}
+
+struct PermutationActionsImpl;
+
+impl permutation::Types for PermutationActionsImpl {
+    type Error = gazelle::ParseError;
+    type Storage = &'static str;
+    type Qual = &'static str;
+    type Type = &'static str;
+    type Ident = &'static str;
+    type Decl = String;
+}
+
+impl Action<permutation::Decl<Self>> for PermutationActionsImpl {
+    fn build(&mut self, node: permutation::Decl<Self>) -> Result<String, gazelle::ParseError> {
+        Ok(match node {
+            permutation::Decl::Decl((storage, quals, types, inline), name) => format!(
+                "{:?} {:?} {:?} {} {}",
+                storage,
+                quals,
+                types,
+                inline.is_some(),
+                name
+            ),
+            permutation::Decl::Pair((name, qual)) => format!("{} {}", name, qual),
+        })
+    }
+}
+
+#[test]
+fn test_permutation() {
+    use permutation::Terminal as T;
+
+    let parse = |tokens: Vec<T<PermutationActionsImpl>>| -> Result<String, String> {
+        let mut actions = PermutationActionsImpl;
+        let mut parser = permutation::Parser::<PermutationActionsImpl>::new();
+        for token in tokens {
+            parser
+                .push(token, &mut actions)
+                .map_err(|e| format!("{:?}", e))?;
+        }
+        parser
+            .finish(&mut actions)
+            .map_err(|(_, e)| format!("{:?}", e))
+    };
+
+    assert_eq!(
+        parse(vec![
+            T::Qual("const"),
+            T::Type("long"),
+            T::Storage("static"),
+            T::Inline,
+            T::Type("int"),
+            T::Ident("x"),
+            T::Semi,
+        ])
+        .unwrap(),
+        r#"Some("static") ["const"] ["long", "int"] true x"#
+    );
+    assert_eq!(
+        parse(vec![T::Type("int"), T::Ident("y"), T::Semi]).unwrap(),
+        r#"None [] ["int"] false y"#
+    );
+    // Required exactly once, in either order
+    assert_eq!(
+        parse(vec![T::Qual("const"), T::Ident("p"), T::Semi]).unwrap(),
+        "p const"
+    );
+    assert_eq!(
+        parse(vec![T::Ident("p"), T::Qual("const"), T::Semi]).unwrap(),
+        "p const"
+    );
+    assert!(parse(vec![T::Ident("p"), T::Semi]).is_err());
+    // A type is required, and storage may appear only once
+    assert!(parse(vec![T::Storage("static"), T::Ident("x"), T::Semi]).is_err());
+    assert!(
+        parse(vec![
+            T::Storage("static"),
+            T::Storage("extern"),
+            T::Type("int"),
+            T::Ident("x"),
+            T::Semi,
+        ])
+        .is_err()
+    );
+}
+
+// Test quoted literal terminals used directly in rules.
+gazelle! {
+    grammar literals {
+        start stmts;
+        terminals {
+            IDENT: _ = "[a-z]+"
+        }
+
+        stmts = stmt* => stmts;
+        stmt = "while" IDENT '{' stmts '}' => while_loop
+             | IDENT "+=" IDENT ';' => add_assign;
+    }
+}
+
+struct LiteralsActionsImpl;
+
+impl literals::Types for LiteralsActionsImpl {
+    type Error = gazelle::ParseError;
+    type Ident = String;
+    type Stmts = Vec<String>;
+    type Stmt = String;
+}
+
+impl Action<literals::Stmts<Self>> for LiteralsActionsImpl {
+    fn build(&mut self, node: literals::Stmts<Self>) -> Result<Vec<String>, gazelle::ParseError> {
+        let literals::Stmts::Stmts(stmts) = node;
+        Ok(stmts)
+    }
+}
+
+impl Action<literals::Stmt<Self>> for LiteralsActionsImpl {
+    fn build(&mut self, node: literals::Stmt<Self>) -> Result<String, gazelle::ParseError> {
+        Ok(match node {
+            literals::Stmt::WhileLoop(cond, body) => format!("while {} [{}]", cond, body.join(" ")),
+            literals::Stmt::AddAssign(l, r) => format!("{}+={}", l, r),
+        })
+    }
+}
+
+#[test]
+fn test_literal_terminals() {
+    use literals::{Lexed, RawToken, Terminal as T};
+
+    let input = "while x { y += z; }";
+    let mut scanner = gazelle::lexer::Scanner::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        scanner.skip_whitespace();
+        let Some((lexed, span)) = literals::next_token(&mut scanner) else {
+            break;
+        };
+        tokens.push(match lexed {
+            Lexed::Token(t) => t,
+            Lexed::Raw(RawToken::Ident) => T::Ident(input[span].to_string()),
+        });
+    }
+    assert!(scanner.at_end());
+    assert!(matches!(tokens[0], T::While));
+    assert!(matches!(tokens[2], T::Lbrace));
+    assert!(matches!(tokens[4], T::PlusEq));
+    assert!(matches!(tokens[6], T::Semi));
+
+    let mut actions = LiteralsActionsImpl;
+    let mut parser = literals::Parser::<LiteralsActionsImpl>::new();
+    for token in tokens {
+        parser.push(token, &mut actions).unwrap();
+    }
+    assert_eq!(
+        parser.finish(&mut actions).map_err(|(_, e)| e).unwrap(),
+        ["while x [y+=z]"]
+    );
+
+    // Literals keep their quotes in error messages.
+    let mut parser = literals::Parser::<LiteralsActionsImpl>::new();
+    parser.push(T::Ident("y".into()), &mut actions).unwrap();
+    let err = parser.push(T::Semi, &mut actions).unwrap_err();
+    let msg = parser.format_error(&err, None, None);
+    assert!(msg.contains(r#"expected: "+=""#), "{}", msg);
+}
+
+// Doc comments on terminals, rules and alternatives; elsewhere they are
+// plain comments
+gazelle! {
+    grammar documented {
+        /// Documents nothing.
+        start sum;
+        terminals {
+            /// An integer.
+            NUM: _,
+            PLUS
+        }
+
+        /// A sum of numbers.
+        sum = /// Two or more numbers.
+              sum PLUS /// Nor does this.
+              NUM => add
+            /// A single number.
+            | NUM => num;
+    }
+}
+
+struct DocumentedActionsImpl;
+
+impl documented::Types for DocumentedActionsImpl {
+    type Error = gazelle::ParseError;
+    type Num = i32;
+    type Sum = i32;
+}
+
+impl Action<documented::Sum<Self>> for DocumentedActionsImpl {
+    fn build(&mut self, node: documented::Sum<Self>) -> Result<i32, gazelle::ParseError> {
+        Ok(match node {
+            documented::Sum::Add(l, r) => l + r,
+            documented::Sum::Num(n) => n,
+        })
+    }
+}
+
+#[test]
+fn test_doc_comments() {
+    use documented::Terminal as T;
+
+    let mut actions = DocumentedActionsImpl;
+    let mut parser = documented::Parser::<DocumentedActionsImpl>::new();
+    for token in [T::Num(1), T::Plus, T::Num(2), T::Plus, T::Num(3)] {
+        parser.push(token, &mut actions).unwrap();
+    }
+    assert_eq!(parser.finish(&mut actions).map_err(|(_, e)| e).unwrap(), 6);
+}
+
+// Attributes on the generated enums: derives for the whole grammar, for the
+// Terminal enum and for one rule
+gazelle! {
+    grammar derived {
+        #![derive(Clone, PartialEq)]
+        start expr;
+        terminals {
+            #![derive(Debug, Eq, Hash)]
+            NUM: _,
+            PLUS,
+            LPAREN,
+            RPAREN
+        }
+
+        #[derive(Eq, Hash, PartialOrd, Ord)]
+        expr = expr PLUS NUM => add
+             | NUM => num
+             | LPAREN inner:expr RPAREN => paren;
+    }
+}
+
+struct DerivedCst;
+
+impl derived::Types for DerivedCst {
+    type Error = gazelle::ParseError;
+    type Num = i64;
+    type Expr = Box<derived::Expr<Self>>;
+}
+
+fn parse_derived(tokens: Vec<derived::Terminal<DerivedCst>>) -> Box<derived::Expr<DerivedCst>> {
+    let mut parser = derived::Parser::<DerivedCst>::new();
+    for token in tokens {
+        parser.push(token, &mut DerivedCst).unwrap();
+    }
+    parser.finish(&mut DerivedCst).map_err(|(_, e)| e).unwrap()
+}
+
+#[test]
+fn test_derived_traits() {
+    use derived::{Expr, Terminal as T};
+    use std::collections::HashSet;
+
+    // Works on a recursive CST, where #[derive] would need cyclic bounds
+    let sum = parse_derived(vec![T::Num(1), T::Plus, T::Num(2)]);
+    let paren = parse_derived(vec![T::Lparen, T::Num(1), T::Rparen]);
+    assert_eq!(sum.clone(), sum);
+    assert_ne!(sum, paren);
+    assert!(matches!(&*paren, Expr::Paren { inner } if **inner == Expr::Num(1)));
+    // Variants compare in declaration order, then by fields
+    assert!(sum < paren);
+    assert!(Expr::<DerivedCst>::Num(1) < Expr::Num(2));
+    let set: HashSet<_> = [sum.clone(), paren, sum].into_iter().collect();
+    assert_eq!(set.len(), 2);
+
+    let num = T::<DerivedCst>::Num(3);
+    assert_eq!(format!("{:?}", num), "Num(3)");
+    assert_eq!(num.clone(), T::Num(3));
+    let set: HashSet<_> = [T::<DerivedCst>::Plus, T::Plus, T::Num(3)]
+        .into_iter()
+        .collect();
+    assert_eq!(set.len(), 2);
+}
+
+// Lints are allowed in the grammar; the lint names never reach the
+// generated code, while Rust lints in the same `allow` still do
+gazelle! {
+    grammar allowed {
+        #![allow(unused_terminal)]
+        start s;
+        terminals {
+            A,
+            UNUSED
+        }
+
+        s = A => a;
+
+        #[allow(unreachable, dead_code)]
+        orphan = A A => pair;
+    }
+}
+
+struct Allowed;
+
+impl allowed::Types for Allowed {
+    type Error = gazelle::ParseError;
+    type S = ();
+    type Orphan = ();
+}
+
+impl gazelle::Action<allowed::S<Self>> for Allowed {
+    fn build(&mut self, _: allowed::S<Self>) -> Result<(), gazelle::ParseError> {
+        Ok(())
+    }
+}
+
+impl gazelle::Action<allowed::Orphan<Self>> for Allowed {
+    fn build(&mut self, _: allowed::Orphan<Self>) -> Result<(), gazelle::ParseError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_allowed_lints() {
+    let mut parser = allowed::Parser::<Allowed>::new();
+    parser.push(allowed::Terminal::A, &mut Allowed).unwrap();
+    assert!(parser.finish(&mut Allowed).is_ok());
+}
@@ -0,0 +1,267 @@
+//! User attributes on the generated enums.
+//!
+//! Derives of the standard traits are implemented here rather than passed to
+//! `#[derive]`, which would bound each impl on `A` and on the associated
+//! types of the fields. For a recursive tree such as
+//! `type Expr = Box<Expr<Self>>` that bound is cyclic. Instead the associated
+//! types get the derived trait as a bound in `Types`, and the impls need only
+//! `A: Types`. Other attributes, including derives of other traits, are kept
+//! as written.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+
+/// Derivable standard traits, with their paths.
+const STD_TRAITS: &[(&str, &str)] = &[
+    ("Clone", "std::clone::Clone"),
+    ("Copy", "std::marker::Copy"),
+    ("Debug", "std::fmt::Debug"),
+    ("PartialEq", "std::cmp::PartialEq"),
+    ("Eq", "std::cmp::Eq"),
+    ("PartialOrd", "std::cmp::PartialOrd"),
+    ("Ord", "std::cmp::Ord"),
+    ("Hash", "std::hash::Hash"),
+];
+
+/// The attributes of one generated enum.
+#[derive(Default)]
+pub(super) struct EnumAttrs {
+    /// Attributes to emit on the enum.
+    pub attrs: Vec<TokenStream>,
+    /// Derived standard traits, implemented by [`impls`].
+    pub std_traits: Vec<&'static str>,
+    /// Every derived trait, as a bound for the associated types of the fields.
+    pub bounds: Vec<TokenStream>,
+}
+
+impl EnumAttrs {
+    /// Parse attributes written without their `#[` and `]`.
+    pub fn parse(attrs: &[String]) -> Result<Self, String> {
+        let mut out = EnumAttrs::default();
+        for attr in attrs {
+            let invalid = |e: syn::Error| format!("invalid attribute #[{}]: {}", attr, e);
+            let meta: syn::Meta = syn::parse_str(attr).map_err(invalid)?;
+            let derive = match &meta {
+                syn::Meta::List(list) if list.path.is_ident("derive") => list,
+                _ => {
+                    out.attrs.push(quote! { #[#meta] });
+                    continue;
+                }
+            };
+            let paths = derive
+                .parse_args_with(Punctuated::<syn::Path, syn::Token![,]>::parse_terminated)
+                .map_err(invalid)?;
+            let mut others = Vec::new();
+            for path in paths {
+                match std_trait(&path) {
+                    Some((name, _)) if out.std_traits.contains(&name) => {}
+                    Some((name, std_path)) => {
+                        out.std_traits.push(name);
+                        out.bounds.push(std_path.parse().unwrap());
+                    }
+                    None => {
+                        out.bounds.push(quote! { #path });
+                        others.push(path);
+                    }
+                }
+            }
+            if !others.is_empty() {
+                out.attrs.push(quote! { #[derive(#(#others),*)] });
+            }
+        }
+        Ok(out)
+    }
+}
+
+/// The standard trait `path` names, as written (`Clone`) or in full
+/// (`std::clone::Clone`).
+fn std_trait(path: &syn::Path) -> Option<(&'static str, &'static str)> {
+    let first = &path.segments.first()?.ident;
+    if path.segments.len() > 1 && first != "std" && first != "core" {
+        return None;
+    }
+    let last = &path.segments.last()?.ident;
+    STD_TRAITS.iter().copied().find(|(name, _)| last == name)
+}
+
+/// A variant of a generated enum.
+pub(super) struct Variant {
+    pub name: syn::Ident,
+    /// Field labels of a struct-like variant.
+    pub labels: Option<Vec<syn::Ident>>,
+    /// Number of fields.
+    pub fields: usize,
+}
+
+impl Variant {
+    /// The variant with its fields bound to `{prefix}0`, `{prefix}1`, ...
+    fn pattern(&self, prefix: &str) -> (TokenStream, Vec<syn::Ident>) {
+        let bindings: Vec<_> = (0..self.fields)
+            .map(|i| format_ident!("{}{}", prefix, i))
+            .collect();
+        (self.construct(&bindings), bindings)
+    }
+
+    /// The variant built from one expression per field.
+    fn construct<T: quote::ToTokens>(&self, fields: &[T]) -> TokenStream {
+        let name = &self.name;
+        match &self.labels {
+            Some(labels) => quote! { Self::#name { #(#labels: #fields),* } },
+            None if fields.is_empty() => quote! { Self::#name },
+            None => quote! { Self::#name(#(#fields),*) },
+        }
+    }
+}
+
+/// Impls of the derived standard traits `traits` for `enum_ident<A>`.
+pub(super) fn impls(
+    enum_ident: &syn::Ident,
+    types_trait: &syn::Ident,
+    variants: &[Variant],
+    traits: &[&str],
+) -> TokenStream {
+    let mut impls = Vec::new();
+    for &name in traits {
+        let body = match name {
+            "Clone" => clone_body(variants),
+            "Debug" => debug_body(variants),
+            "PartialEq" => partial_eq_body(variants),
+            "PartialOrd" => cmp_body(variants, true),
+            "Ord" => cmp_body(variants, false),
+            "Hash" => hash_body(variants),
+            _ => quote! {},
+        };
+        let (_, path) = STD_TRAITS.iter().find(|(n, _)| *n == name).unwrap();
+        let path: TokenStream = path.parse().unwrap();
+        impls.push(quote! {
+            impl<A: #types_trait> #path for #enum_ident<A> {
+                #body
+            }
+        });
+    }
+    quote! { #(#impls)* }
+}
+
+fn clone_body(variants: &[Variant]) -> TokenStream {
+    let arms = variants.iter().map(|v| {
+        let (pattern, bindings) = v.pattern("a");
+        let clones: Vec<_> = bindings
+            .iter()
+            .map(|b| quote! { std::clone::Clone::clone(#b) })
+            .collect();
+        let value = v.construct(&clones);
+        quote! { #pattern => #value }
+    });
+    quote! {
+        fn clone(&self) -> Self {
+            match self { #(#arms,)* }
+        }
+    }
+}
+
+fn debug_body(variants: &[Variant]) -> TokenStream {
+    let arms = variants.iter().map(|v| {
+        let (pattern, bindings) = v.pattern("a");
+        let name = v.name.to_string();
+        let value = match &v.labels {
+            Some(labels) => {
+                let labels = labels.iter().map(|l| l.to_string());
+                quote! { f.debug_struct(#name)#(.field(#labels, #bindings))*.finish() }
+            }
+            None if bindings.is_empty() => quote! { f.write_str(#name) },
+            None => quote! { f.debug_tuple(#name)#(.field(#bindings))*.finish() },
+        };
+        quote! { #pattern => #value }
+    });
+    quote! {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self { #(#arms,)* }
+        }
+    }
+}
+
+fn partial_eq_body(variants: &[Variant]) -> TokenStream {
+    let arms = variants.iter().map(|v| {
+        let (a, a_bindings) = v.pattern("a");
+        let (b, b_bindings) = v.pattern("b");
+        let value = if a_bindings.is_empty() {
+            quote! { true }
+        } else {
+            quote! { #(#a_bindings == #b_bindings)&&* }
+        };
+        quote! { (#a, #b) => #value }
+    });
+    quote! {
+        #[allow(unreachable_patterns)]
+        fn eq(&self, other: &Self) -> bool {
+            match (self, other) {
+                #(#arms,)*
+                _ => false,
+            }
+        }
+    }
+}
+
+/// `partial_cmp` (`partial`) or `cmp`: variants in declaration order, then
+/// fields in order.
+fn cmp_body(variants: &[Variant], partial: bool) -> TokenStream {
+    let indices = variants.iter().enumerate().map(|(i, v)| {
+        let name = &v.name;
+        quote! { Self::#name { .. } => #i }
+    });
+    let (method, equal, result) = if partial {
+        (
+            quote! { std::cmp::PartialOrd::partial_cmp },
+            quote! { Some(std::cmp::Ordering::Equal) },
+            quote! { Option<std::cmp::Ordering> },
+        )
+    } else {
+        (
+            quote! { std::cmp::Ord::cmp },
+            quote! { std::cmp::Ordering::Equal },
+            quote! { std::cmp::Ordering },
+        )
+    };
+    let arms = variants.iter().map(|v| {
+        let (a, a_bindings) = v.pattern("a");
+        let (b, b_bindings) = v.pattern("b");
+        quote! {
+            (#a, #b) => {
+                #(match #method(#a_bindings, #b_bindings) {
+                    #equal => {}
+                    ord => return ord,
+                })*
+                #equal
+            }
+        }
+    });
+    let name = if partial {
+        quote! { partial_cmp }
+    } else {
+        quote! { cmp }
+    };
+    quote! {
+        #[allow(unreachable_patterns)]
+        fn #name(&self, other: &Self) -> #result {
+            let index = |v: &Self| match v { #(#indices,)* };
+            match (self, other) {
+                #(#arms)*
+                _ => #method(&index(self), &index(other)),
+            }
+        }
+    }
+}
+
+fn hash_body(variants: &[Variant]) -> TokenStream {
+    let arms = variants.iter().map(|v| {
+        let (pattern, bindings) = v.pattern("a");
+        quote! { #pattern => { #(std::hash::Hash::hash(#bindings, state);)* } }
+    });
+    quote! {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            std::hash::Hash::hash(&std::mem::discriminant(self), state);
+            match self { #(#arms)* }
+        }
+    }
+}
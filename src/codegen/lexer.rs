@@ -178,3 +178,22 @@ fn generate_mixed(
         }
     })
 }
+
+/// Escape `literal` so that it matches only itself as a lexer pattern.
+pub(crate) fn escape_literal(literal: &str) -> String {
+    let mut out = String::with_capacity(literal.len());
+    for c in literal.chars() {
+        match c {
+            '\\' | '|' | '(' | ')' | '[' | ']' | '*' | '+' | '?' | '.' | '^' | '$' | '{' | '}'
+            | '-' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
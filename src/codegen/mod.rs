@@ -2,18 +2,27 @@
 //!
 //! This module generates Rust source code for type-safe LR parsers.
 
+mod derive;
 mod lexer;
 mod parser;
 mod reduction;
 mod table;
 mod terminal;
+mod tree_sitter;
+
+pub use tree_sitter::{PrecLevel, to_tree_sitter};
 
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 
-use crate::grammar::Grammar;
+use crate::grammar::{Grammar, GrammarError, GrammarErrorKind, Span};
 use crate::lr::{GrammarInternal, to_grammar_internal};
 
+/// `#[doc]` attributes for doc comment lines.
+fn doc_attrs(lines: &[String]) -> Vec<TokenStream> {
+    lines.iter().map(|line| quote! { #[doc = #line] }).collect()
+}
+
 /// A terminal with a regex pattern for automatic lexer generation.
 #[derive(Debug, Clone)]
 pub struct TerminalPattern {
@@ -45,8 +54,8 @@ pub struct CodegenContext {
     /// paths (`gazelle::`) which requires `use ... as gazelle;` in scope.
     pub use_absolute_path: bool,
 
-    /// Start symbol name.
-    pub start_symbol: String,
+    /// Start symbol names; the first is the default entry point.
+    pub start_symbols: Vec<String>,
 
     /// Expected reduce/reduce conflicts (0 = none expected, error if different).
     pub expect_rr: usize,
@@ -56,6 +65,9 @@ pub struct CodegenContext {
     /// Patterned terminals for automatic lexer generation.
     /// Each entry: (name, pattern, has_type, is_prec).
     pub terminal_patterns: Vec<TerminalPattern>,
+
+    /// Source location of each rule name, for reporting conflicts.
+    pub(crate) rule_spans: std::collections::HashMap<String, Span>,
 }
 
 impl CodegenContext {
@@ -65,20 +77,35 @@ impl CodegenContext {
         name: &str,
         visibility: &str,
         use_absolute_path: bool,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, GrammarError> {
         let grammar = to_grammar_internal(grammar_def)?;
 
-        let terminal_patterns: Vec<TerminalPattern> = grammar_def
-            .terminals
-            .iter()
-            .filter_map(|t| {
-                t.pattern.as_ref().map(|p| TerminalPattern {
-                    name: t.name.clone(),
-                    pattern: p.clone(),
-                    has_type: t.has_type,
-                    is_prec: t.is_prec,
-                })
+        // Literal terminals come first: a lower index wins equal-length
+        // matches, so `"while"` beats an identifier pattern.
+        let literals = grammar
+            .symbols
+            .terminal_ids()
+            .map(|id| grammar.symbols.name(id))
+            .filter(|name| crate::lr::is_literal(name))
+            .map(|name| TerminalPattern {
+                name: name.to_string(),
+                pattern: lexer::escape_literal(&crate::lr::literal_text(name)),
+                has_type: false,
+                is_prec: false,
+            });
+        let declared = grammar_def.terminals.iter().filter_map(|t| {
+            t.pattern.as_ref().map(|p| TerminalPattern {
+                name: t.name.clone(),
+                pattern: p.clone(),
+                has_type: t.has_type,
+                is_prec: t.is_prec,
             })
+        });
+        let terminal_patterns: Vec<TerminalPattern> = literals.chain(declared).collect();
+        let rule_spans = grammar_def
+            .rules
+            .iter()
+            .filter_map(|r| Some((r.name.clone(), r.span.clone()?)))
             .collect();
 
         Ok(CodegenContext {
@@ -86,10 +113,11 @@ impl CodegenContext {
             visibility: visibility.to_string(),
             name: name.to_string(),
             use_absolute_path,
-            start_symbol: grammar_def.start.clone(),
+            start_symbols: grammar_def.starts().cloned().collect(),
             expect_rr: grammar_def.expect_rr,
             expect_sr: grammar_def.expect_sr,
             terminal_patterns,
+            rule_spans,
         })
     }
 
@@ -111,6 +139,32 @@ impl CodegenContext {
         }
     }
 
+    /// Terminals that appear in the generated `Terminal` enum: all but EOF
+    /// and the entry markers of a grammar with several start symbols.
+    pub fn terminal_ids(&self) -> impl Iterator<Item = crate::SymbolId> + '_ {
+        self.grammar
+            .symbols
+            .terminal_ids()
+            .skip(1)
+            .filter(|&id| !self.grammar.is_entry_marker(id))
+    }
+
+    /// The `Terminal` variant of a terminal. A reduce-leaning twin is named
+    /// after its original, e.g. `Else_Reduce` for `ELSE`.
+    pub fn terminal_variant(&self, id: crate::SymbolId) -> syn::Ident {
+        match self.grammar.twin_of(id) {
+            Some(original) => format_ident!(
+                "{}{}",
+                crate::lr::to_camel_case(self.grammar.symbols.name(original)),
+                crate::lr::REDUCE_TWIN_VARIANT_SUFFIX
+            ),
+            None => format_ident!(
+                "{}",
+                crate::lr::to_camel_case(self.grammar.symbols.name(id))
+            ),
+        }
+    }
+
     /// Get a symbol's type by name.
     pub fn get_type(&self, name: &str) -> Option<&String> {
         let sym = self.grammar.symbols.get(name)?;
@@ -119,16 +173,47 @@ impl CodegenContext {
 }
 
 /// Generate bare parser items (no module wrapper).
-pub fn generate_items(ctx: &CodegenContext) -> Result<TokenStream, String> {
+pub fn generate_items(ctx: &CodegenContext) -> Result<TokenStream, GrammarError> {
+    let invalid = |e: String| GrammarError::new(GrammarErrorKind::Invalid, e);
     let (compiled, info) = table::build_table(ctx)?;
 
-    let table_statics = table::generate_table_statics(ctx, &compiled, &info);
-    let terminal_code = terminal::generate(ctx, &info);
-    let parser_code = parser::generate(ctx, &info)?;
+    // Reductions are generated against the rules the table was built from.
+    let rewritten;
+    let ctx = if compiled.grammar.rewrite.is_some() {
+        rewritten = CodegenContext {
+            grammar: compiled.grammar.clone(),
+            ..ctx.clone()
+        };
+        &rewritten
+    } else {
+        ctx
+    };
+
+    let table_statics = table::generate_table_statics(ctx, &compiled, &info).map_err(invalid)?;
+    let terminal_attrs = derive::EnumAttrs::parse(&ctx.grammar.terminal_attrs).map_err(invalid)?;
+    // A `Precedence` has no order of its own: comparing two is a question of
+    // binding strength, which the parser decides
+    let prec_terminal = ctx
+        .terminal_ids()
+        .find(|&id| ctx.grammar.symbols.is_prec_terminal(id));
+    let ordered = terminal_attrs
+        .std_traits
+        .iter()
+        .find(|t| matches!(**t, "PartialOrd" | "Ord"));
+    if let (Some(id), Some(t)) = (prec_terminal, ordered) {
+        return Err(invalid(format!(
+            "cannot derive {} for Terminal: prec terminal '{}' carries a Precedence, \
+             which has no order",
+            t,
+            ctx.grammar.symbols.name(id)
+        )));
+    }
+    let terminal_code = terminal::generate(ctx, &info, &terminal_attrs);
+    let parser_code = parser::generate(ctx, &info, &terminal_attrs).map_err(invalid)?;
 
     let lexer_code = match lexer::generate(ctx) {
         Some(Ok(tokens)) => tokens,
-        Some(Err(e)) => return Err(e),
+        Some(Err(e)) => return Err(invalid(e)),
         None => TokenStream::new(),
     };
 
@@ -144,26 +229,44 @@ pub fn generate_items(ctx: &CodegenContext) -> Result<TokenStream, String> {
 }
 
 /// Convert a grammar to Bison/yacc format (.y).
-pub fn to_yacc(grammar_def: &Grammar) -> Result<String, String> {
+pub fn to_yacc(grammar_def: &Grammar) -> Result<String, GrammarError> {
     let grammar = to_grammar_internal(grammar_def)?;
     let symbols = &grammar.symbols;
     let mut out = String::new();
 
-    // %token declarations (skip EOF at index 0)
+    // %token declarations (skip EOF at index 0). Char literals need no
+    // declaration; string literals are aliases of a named token.
     out.push_str("%token");
+    let mut aliases = Vec::new();
     for i in 1..symbols.num_terminals() {
+        let id = crate::SymbolId::new(i);
+        let name = symbols.name(id);
+        if grammar.is_entry_marker(id) || grammar.twin_of(id).is_some() || name.starts_with('\'') {
+            continue;
+        }
+        if name.starts_with('"') {
+            let token = format!("LIT_{}", crate::lr::to_camel_case(name).to_uppercase());
+            aliases.push(format!("%token {} {}\n", token, name));
+            continue;
+        }
         out.push(' ');
-        out.push_str(symbols.name(crate::SymbolId::new(i)));
+        out.push_str(name);
     }
     out.push('\n');
+    for alias in aliases {
+        out.push_str(&alias);
+    }
 
-    // %start
-    out.push_str(&format!("\n%start {}\n", grammar_def.start));
+    // %start (Bison 3.8 accepts several start symbols)
+    out.push_str(&format!(
+        "\n%start {}\n",
+        grammar_def.starts().cloned().collect::<Vec<_>>().join(" ")
+    ));
     out.push_str("\n%%\n");
 
-    // Group rules by lhs (skip augmented start rule at index 0)
+    // Group rules by lhs (skip the augmented start rules)
     let mut rule_groups: Vec<(crate::SymbolId, Vec<&crate::lr::Rule>)> = Vec::new();
-    for rule in grammar.rules.iter().skip(1) {
+    for rule in grammar.rules.iter().skip(grammar.num_entries()) {
         let lhs_id = rule.lhs.id();
         if let Some(group) = rule_groups.last_mut().filter(|(id, _)| *id == lhs_id) {
             group.1.push(rule);
@@ -194,9 +297,7 @@ pub fn to_yacc(grammar_def: &Grammar) -> Result<String, String> {
 }
 
 /// Generate all code wrapped in a module.
-pub fn generate_tokens(ctx: &CodegenContext) -> Result<TokenStream, String> {
-    use quote::format_ident;
-
+pub fn generate_tokens(ctx: &CodegenContext) -> Result<TokenStream, GrammarError> {
     let items = generate_items(ctx)?;
     let mod_name = format_ident!("{}", ctx.name);
     let vis: TokenStream = ctx.visibility.parse().unwrap_or_default();
@@ -4,12 +4,17 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
 use super::CodegenContext;
+use super::derive::{self, EnumAttrs};
 use super::reduction::{self, ReductionInfo, SymbolKind, typed_symbol_indices};
 use super::table::CodegenTableInfo;
-use crate::lr::AltAction;
+use crate::lr::{AltAction, Recipe};
 
 /// Generate the parser wrapper, trait, and related types.
-pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo) -> Result<TokenStream, String> {
+pub fn generate(
+    ctx: &CodegenContext,
+    info: &CodegenTableInfo,
+    terminal_attrs: &EnumAttrs,
+) -> Result<TokenStream, String> {
     let vis: TokenStream = "pub".parse().unwrap();
     let terminal_enum = format_ident!("Terminal");
     let types_trait = format_ident!("Types");
@@ -47,22 +52,37 @@ pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo) -> Result<TokenSt
         })
         .collect();
 
-    // Get start non-terminal name and its type annotation
-    let start_nt = &ctx.start_symbol;
-    let start_type_annotation = typed_non_terminals
-        .iter()
-        .find(|(name, _)| name == start_nt)
-        .map(|(_, ty)| ty.clone());
-    let start_field = format_ident!("__{}", start_nt.to_lowercase());
+    // Terminal payloads must implement the traits derived for `Terminal`
+    let mut bounds = AssocBounds::new();
+    for id in ctx.terminal_ids() {
+        if let Some(type_name) = ctx.grammar.types.get(&id).and_then(|t| t.as_ref()) {
+            add_bounds(&mut bounds, type_name, &terminal_attrs.bounds);
+        }
+    }
+
+    // Rules convert the values their unnamed alternatives pass through
+    for info in &reductions {
+        if let Some((from, to)) = &info.conversion {
+            let from = synthetic_type_to_tokens_with_prefix(from, true);
+            add_bounds(&mut bounds, to, &[quote! { std::convert::From<#from> }]);
+        }
+    }
 
     // Generate components
-    let enum_code =
-        generate_nonterminal_enums(ctx, &reductions, &typed_non_terminals, &types_trait, &vis);
+    let enum_code = generate_nonterminal_enums(
+        ctx,
+        &reductions,
+        &typed_non_terminals,
+        &types_trait,
+        &vis,
+        &mut bounds,
+    )?;
     let (traits_code, reducer_bounds) = generate_traits(
         ctx,
         &types_trait,
         &typed_non_terminals,
         &reductions,
+        &bounds,
         &vis,
         &gazelle_crate_path,
     );
@@ -73,56 +93,156 @@ pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo) -> Result<TokenSt
         generate_reduction_arms(ctx, &reductions, &value_union, &typed_non_terminals);
     let drop_arms = generate_drop_arms(ctx, info);
 
-    // Generate finish method based on whether start symbol has a type
-    let finish_method = if let Some(start_type) = start_type_annotation {
-        let start_type_ident = format_ident!("{}", start_type);
+    // Constructors and finish methods, one pair per entry point. With several
+    // start symbols, `new`/`finish` use the first and each start symbol also
+    // gets its own `new_<name>`/`finish_<name>`.
+    let num_entries = ctx.grammar.num_entries();
+    let accept_other = if num_entries > 1 {
+        let n = proc_macro2::Literal::usize_unsuffixed(num_entries);
         quote! {
-            pub fn finish(mut self, actions: &mut A) -> Result<A::#start_type_ident, (Self, A::Error)> {
-                loop {
-                    match self.parser.maybe_reduce(None) {
-                        Ok(Some((0, _, _))) => {
-                            let union_val = self.value_stack.pop().unwrap();
-                            return Ok(unsafe { std::mem::ManuallyDrop::into_inner(union_val.#start_field) });
-                        }
-                        Ok(Some((rule, _, start_idx))) => {
-                            if let Err(e) = self.do_reduce(rule, start_idx, actions) {
-                                return Err((self, e));
-                            }
-                        }
-                        Ok(None) => unreachable!(),
-                        Err(e) => {
-                            self.drain_values();
-                            self.parser.restore_checkpoint();
-                            return Err((self, e.into()));
+            Ok(Some((rule, _, _))) if rule < #n => {
+                return Err((self, #gazelle_crate_path::ParseError::wrong_entry().into()));
+            }
+        }
+    } else {
+        quote! {}
+    };
+    let entries: Vec<(String, Option<u32>)> = if num_entries > 1 {
+        ctx.grammar
+            .entries()
+            .map(|(marker, start)| {
+                (
+                    ctx.grammar.symbols.name(start.id()).to_string(),
+                    Some(marker.0),
+                )
+            })
+            .collect()
+    } else {
+        vec![(ctx.start_symbols[0].clone(), None)]
+    };
+    let mut entry_methods = Vec::new();
+    for (i, (start_nt, marker)) in entries.iter().enumerate() {
+        let accept_rule = proc_macro2::Literal::usize_unsuffixed(i);
+        let new_parser = match marker {
+            Some(marker) => quote! {
+                #gazelle_crate_path::Parser::for_entry(#table_mod::TABLE, #gazelle_crate_path::SymbolId::new(#marker))
+            },
+            None => quote! { #gazelle_crate_path::Parser::new(#table_mod::TABLE) },
+        };
+        let start_type_annotation = typed_non_terminals
+            .iter()
+            .find(|(name, _)| name == start_nt)
+            .map(|(_, ty)| ty.clone());
+        let start_field = format_ident!("__{}", start_nt.to_lowercase());
+        let (result_type, accept_value) = match start_type_annotation {
+            Some(start_type) => {
+                let start_type_ident = format_ident!("{}", start_type);
+                (
+                    quote! { A::#start_type_ident },
+                    quote! {
+                        let union_val = self.value_stack.pop().unwrap();
+                        return Ok(unsafe { std::mem::ManuallyDrop::into_inner(union_val.#start_field) });
+                    },
+                )
+            }
+            None => (
+                quote! { () },
+                quote! {
+                    self.value_stack.pop();
+                    return Ok(());
+                },
+            ),
+        };
+        let finish_body = quote! {
+            loop {
+                match self.parser.maybe_reduce(None) {
+                    Ok(Some((#accept_rule, _, _))) => {
+                        #accept_value
+                    }
+                    #accept_other
+                    Ok(Some((rule, _, start_idx))) => {
+                        if let Err(e) = self.do_reduce(rule, start_idx, actions) {
+                            return Err((self, e));
                         }
                     }
+                    Ok(None) => unreachable!(),
+                    Err(e) => {
+                        self.drain_values();
+                        self.parser.restore_checkpoint();
+                        return Err((self, e.into()));
+                    }
                 }
             }
+        };
+        let constructor = quote! {
+            Self {
+                parser: #new_parser,
+                value_stack: Vec::new(),
+            }
+        };
+
+        if i == 0 {
+            entry_methods.push((
+                quote! {
+                    /// Create a new parser instance.
+                    pub fn new() -> Self {
+                        #constructor
+                    }
+                },
+                quote! {
+                    /// Finish parsing and return the result.
+                    pub fn finish(mut self, actions: &mut A) -> Result<#result_type, (Self, A::Error)> {
+                        #finish_body
+                    }
+                },
+            ));
+        }
+        if marker.is_some() {
+            let new_ident = format_ident!("new_{}", start_nt);
+            let finish_ident = format_ident!("finish_{}", start_nt);
+            let new_doc = format!(" Create a new parser for the `{start_nt}` entry point.");
+            let finish_doc = format!(
+                " Finish parsing and return the `{start_nt}`. For a parser not created with `{new_ident}` this is an error (see `ParseError::is_wrong_entry`)."
+            );
+            entry_methods.push((
+                quote! {
+                    #[doc = #new_doc]
+                    pub fn #new_ident() -> Self {
+                        #constructor
+                    }
+                },
+                quote! {
+                    #[doc = #finish_doc]
+                    pub fn #finish_ident(mut self, actions: &mut A) -> Result<#result_type, (Self, A::Error)> {
+                        #finish_body
+                    }
+                },
+            ));
         }
+    }
+    let (constructors, finish_methods): (Vec<_>, Vec<_>) = entry_methods.into_iter().unzip();
+
+    let accept_check = if num_entries > 1 {
+        let n = proc_macro2::Literal::usize_unsuffixed(num_entries);
+        quote! { rule < #n }
     } else {
+        quote! { rule == 0 }
+    };
+    let num_entries_lit = proc_macro2::Literal::usize_unsuffixed(num_entries);
+
+    // Tokens of `prec` terminals may carry precedence groups
+    let groups_method = if ctx
+        .terminal_ids()
+        .any(|id| ctx.grammar.symbols.is_prec_terminal(id))
+    {
         quote! {
-            pub fn finish(mut self, actions: &mut A) -> Result<(), (Self, A::Error)> {
-                loop {
-                    match self.parser.maybe_reduce(None) {
-                        Ok(Some((0, _, _))) => {
-                            self.value_stack.pop();
-                            return Ok(());
-                        }
-                        Ok(Some((rule, _, start_idx))) => {
-                            if let Err(e) = self.do_reduce(rule, start_idx, actions) {
-                                return Err((self, e));
-                            }
-                        }
-                        Ok(None) => unreachable!(),
-                        Err(e) => {
-                            self.drain_values();
-                            self.parser.restore_checkpoint();
-                            return Err((self, e.into()));
-                        }
-                    }
-                }
+            /// Compare the precedence groups of `prec` terminals by the relations of `groups`.
+            pub fn set_precedence_groups(&mut self, groups: std::sync::Arc<#gazelle_crate_path::PrecedenceGroups>) {
+                self.parser.set_precedence_groups(groups);
             }
         }
+    } else {
+        quote! {}
     };
 
     Ok(quote! {
@@ -139,13 +259,9 @@ pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo) -> Result<TokenSt
         }
 
         impl<A: #types_trait> #parser_struct<A> {
-            /// Create a new parser instance.
-            pub fn new() -> Self {
-                Self {
-                    parser: #gazelle_crate_path::Parser::new(#table_mod::TABLE),
-                    value_stack: Vec::new(),
-                }
-            }
+            #(#constructors)*
+
+            #groups_method
 
             /// Get the current parser state.
             pub fn state(&self) -> usize {
@@ -219,14 +335,13 @@ pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo) -> Result<TokenSt
                 Ok(())
             }
 
-            /// Finish parsing and return the result.
-            #finish_method
+            #(#finish_methods)*
 
             fn do_reduce(&mut self, rule: usize, start_idx: usize, actions: &mut A) -> Result<(), A::Error> {
-                if rule == 0 { return Ok(()); }
+                if #accept_check { return Ok(()); }
 
                 actions.set_token_range(start_idx, self.parser.token_count());
-                let original_rule_idx = rule - 1;
+                let original_rule_idx = rule - #num_entries_lit;
 
                 let value = match original_rule_idx {
                     #(#reduction_arms)*
@@ -257,17 +372,18 @@ pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo) -> Result<TokenSt
 /// satisfy Rust's unused type parameter check. Since `Infallible` is uninhabited,
 /// `min_exhaustive_patterns` (stable since 1.82) means the variant never needs
 /// to be matched.
-fn generate_nonterminal_enums(
-    ctx: &CodegenContext,
+fn generate_nonterminal_enums<'a>(
+    ctx: &'a CodegenContext,
     reductions: &[ReductionInfo],
-    typed_non_terminals: &[(String, String)],
+    typed_non_terminals: &'a [(String, String)],
     types_trait: &syn::Ident,
     vis: &TokenStream,
-) -> TokenStream {
+    bounds: &mut AssocBounds<'a>,
+) -> Result<TokenStream, String> {
     let mut enums = Vec::new();
 
     // Map from terminal name to associated type name
-    let terminal_assoc_types: std::collections::BTreeMap<&str, &str> = ctx
+    let terminal_assoc_types: std::collections::BTreeMap<&str, &'a str> = ctx
         .grammar
         .symbols
         .terminal_ids()
@@ -279,7 +395,7 @@ fn generate_nonterminal_enums(
         .collect();
 
     // Map from non-terminal name to result type
-    let nt_result_types: std::collections::HashMap<&str, &str> = typed_non_terminals
+    let nt_result_types: std::collections::HashMap<&str, &'a str> = typed_non_terminals
         .iter()
         .map(|(name, result_type)| (name.as_str(), result_type.as_str()))
         .collect();
@@ -298,6 +414,14 @@ fn generate_nonterminal_enums(
 
     for (nt_name, variants) in &nt_variants {
         let enum_ident = enum_name(nt_name);
+        let enum_docs = super::doc_attrs(symbol_doc(ctx, nt_name));
+        let attrs = ctx
+            .grammar
+            .symbols
+            .get_id(nt_name)
+            .and_then(|id| ctx.grammar.attrs.get(&id));
+        let attrs = EnumAttrs::parse(attrs.map_or(&[], Vec::as_slice))?;
+        let user_attrs = &attrs.attrs;
 
         let variant_defs: Vec<_> = variants
             .iter()
@@ -306,6 +430,7 @@ fn generate_nonterminal_enums(
                     "{}",
                     crate::lr::to_camel_case(info.variant_name.as_ref().unwrap())
                 );
+                let docs = super::doc_attrs(&variant_doc(info));
                 let fields: Vec<_> = typed_symbol_indices(&info.rhs_symbols)
                     .iter()
                     .map(|&idx| {
@@ -314,21 +439,30 @@ fn generate_nonterminal_enums(
                     })
                     .collect();
 
-                if fields.is_empty() {
-                    quote! { #variant_name }
+                if let Some(labels) = info.field_labels() {
+                    quote! { #(#docs)* #variant_name { #(#labels: #fields),* } }
+                } else if fields.is_empty() {
+                    quote! { #(#docs)* #variant_name }
                 } else {
-                    quote! { #variant_name(#(#fields),*) }
+                    quote! { #(#docs)* #variant_name(#(#fields),*) }
                 }
             })
             .collect();
 
-        // Check if any variant field type actually references the type parameter A.
-        let uses_a = variants.iter().any(|info| {
-            typed_symbol_indices(&info.rhs_symbols).iter().any(|&idx| {
-                let sym = &info.rhs_symbols[idx];
-                symbol_references_a(sym, &nt_result_types, &terminal_assoc_types, ctx)
+        // The associated types of A that the variant fields use.
+        let assoc_types: Vec<&str> = variants
+            .iter()
+            .flat_map(|info| {
+                typed_symbol_indices(&info.rhs_symbols)
+                    .into_iter()
+                    .map(|idx| &info.rhs_symbols[idx])
             })
-        });
+            .flat_map(|sym| symbol_assoc_types(sym, &nt_result_types, &terminal_assoc_types, ctx))
+            .collect();
+        let uses_a = !assoc_types.is_empty();
+        for assoc in assoc_types {
+            add_bounds(bounds, assoc, &attrs.bounds);
+        }
 
         // All enums get <A>. If A isn't used in fields, add uninhabited phantom variant.
         let (phantom_variant, phantom_arm) = if !uses_a {
@@ -349,7 +483,12 @@ fn generate_nonterminal_enums(
             let field_count = field_indices.len();
             let variant_str = variant_name.to_string();
 
-            if field_count == 0 {
+            if let Some(labels) = info.field_labels() {
+                let label_strs: Vec<_> = labels.iter().map(|l| l.to_string()).collect();
+                quote! {
+                    Self::#variant_name { #(#labels),* } => f.debug_struct(#variant_str)#(.field(#label_strs, #labels))*.finish()
+                }
+            } else if field_count == 0 {
                 quote! { Self::#variant_name => f.write_str(#variant_str) }
             } else {
                 let bindings: Vec<_> = (0..field_count).map(|i| format_ident!("f{}", i)).collect();
@@ -360,7 +499,36 @@ fn generate_nonterminal_enums(
             }
         }).collect();
 
+        // Derived standard traits; Debug is always implemented above.
+        let mut derive_variants: Vec<_> = variants
+            .iter()
+            .map(|info| derive::Variant {
+                name: format_ident!(
+                    "{}",
+                    crate::lr::to_camel_case(info.variant_name.as_ref().unwrap())
+                ),
+                labels: info.field_labels(),
+                fields: typed_symbol_indices(&info.rhs_symbols).len(),
+            })
+            .collect();
+        if !uses_a {
+            derive_variants.push(derive::Variant {
+                name: format_ident!("_Phantom"),
+                labels: None,
+                fields: 2,
+            });
+        }
+        let std_traits: Vec<_> = attrs
+            .std_traits
+            .iter()
+            .copied()
+            .filter(|&t| t != "Debug")
+            .collect();
+        let derive_impls = derive::impls(&enum_ident, types_trait, &derive_variants, &std_traits);
+
         enums.push(quote! {
+            #(#enum_docs)*
+            #(#user_attrs)*
             #vis enum #enum_ident<A: #types_trait> {
                 #(#variant_defs),*
                 #phantom_variant
@@ -371,10 +539,51 @@ fn generate_nonterminal_enums(
                     match self { #(#debug_arms,)* #phantom_arm }
                 }
             }
+
+            #derive_impls
         });
     }
 
-    quote! { #(#enums)* }
+    Ok(quote! { #(#enums)* })
+}
+
+/// Extra trait bounds for associated types of `Types`, from derives on the
+/// enums that use them.
+type AssocBounds<'a> = std::collections::BTreeMap<&'a str, Vec<TokenStream>>;
+
+fn add_bounds<'a>(bounds: &mut AssocBounds<'a>, assoc: &'a str, traits: &[TokenStream]) {
+    let entry = bounds.entry(assoc).or_default();
+    for t in traits {
+        if !entry.iter().any(|b| b.to_string() == t.to_string()) {
+            entry.push(t.clone());
+        }
+    }
+}
+
+/// Doc comment lines of a terminal or rule.
+fn symbol_doc<'a>(ctx: &'a CodegenContext, name: &str) -> &'a [String] {
+    ctx.grammar
+        .symbols
+        .get_id(name)
+        .and_then(|id| ctx.grammar.docs.get(&id))
+        .map_or(&[], Vec::as_slice)
+}
+
+/// Doc comment lines of an enum variant: the alternative's own doc
+/// comment, then its production.
+fn variant_doc(info: &ReductionInfo) -> Vec<String> {
+    let AltAction::Named {
+        doc, production, ..
+    } = &info.action
+    else {
+        return Vec::new();
+    };
+    let mut lines = doc.clone();
+    if !lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines.push(format!(" `{}`", production));
+    lines
 }
 
 /// Convert a symbol to its field type tokens for use in an enum variant.
@@ -405,41 +614,30 @@ fn symbol_to_field_type(
     }
 }
 
-/// Check if a symbol's field type would reference the generic parameter A.
-fn symbol_references_a(
+/// The associated types of `A` that a symbol's field type mentions.
+fn symbol_assoc_types<'a>(
     sym: &reduction::SymbolInfo,
-    nt_result_types: &std::collections::HashMap<&str, &str>,
-    terminal_assoc_types: &std::collections::BTreeMap<&str, &str>,
-    ctx: &CodegenContext,
-) -> bool {
+    nt_result_types: &std::collections::HashMap<&str, &'a str>,
+    terminal_assoc_types: &std::collections::BTreeMap<&str, &'a str>,
+    ctx: &'a CodegenContext,
+) -> Vec<&'a str> {
     if sym.kind == SymbolKind::NonTerminal {
-        if nt_result_types.contains_key(sym.name.as_str()) {
+        if let Some(&result_type) = nt_result_types.get(sym.name.as_str()) {
             // Non-synthetic typed NT -> A::ResultType
-            true
+            vec![result_type]
         } else if sym.name.starts_with("__") {
-            if let Some(result_type) = ctx.get_type(&sym.name) {
-                // Synthetic types like Vec<Foo> reference A if Foo is not "()"
-                let inner = result_type
-                    .strip_prefix("Option<")
-                    .and_then(|s| s.strip_suffix('>'))
-                    .or_else(|| {
-                        result_type
-                            .strip_prefix("Vec<")
-                            .and_then(|s| s.strip_suffix('>'))
-                    });
-                match inner {
-                    Some("()") => false,
-                    Some(_) => true,
-                    None => false,
-                }
-            } else {
-                false
-            }
+            // Synthetic types like Vec<Foo> mention A unless only units
+            ctx.get_type(&sym.name)
+                .map_or(Vec::new(), |ty| synthetic_assoc_types(ty).collect())
         } else {
-            false
+            Vec::new()
         }
     } else {
-        terminal_assoc_types.contains_key(sym.name.as_str())
+        terminal_assoc_types
+            .get(sym.name.as_str())
+            .into_iter()
+            .copied()
+            .collect()
     }
 }
 
@@ -448,11 +646,22 @@ fn generate_traits(
     types_trait: &syn::Ident,
     typed_non_terminals: &[(String, String)],
     reductions: &[ReductionInfo],
+    bounds: &AssocBounds,
     vis: &TokenStream,
     gazelle_crate_path: &TokenStream,
 ) -> (TokenStream, Vec<TokenStream>) {
     let mut assoc_types = Vec::new();
     let mut seen_types = std::collections::HashSet::new();
+    let debug = quote! { std::fmt::Debug }.to_string();
+    let extra_bounds = |type_name: &str| -> Vec<TokenStream> {
+        bounds
+            .get(type_name)
+            .into_iter()
+            .flatten()
+            .filter(|b| b.to_string() != debug)
+            .cloned()
+            .collect()
+    };
 
     // Terminal associated types - use payload type name directly
     for id in ctx.grammar.symbols.terminal_ids().skip(1) {
@@ -460,15 +669,19 @@ fn generate_traits(
             && seen_types.insert(type_name.as_str())
         {
             let type_ident = format_ident!("{}", type_name);
-            assoc_types.push(quote! { type #type_ident: std::fmt::Debug; });
+            let docs = super::doc_attrs(ctx.grammar.docs.get(&id).map_or(&[], Vec::as_slice));
+            let extra = extra_bounds(type_name);
+            assoc_types.push(quote! { #(#docs)* type #type_ident: std::fmt::Debug #(+ #extra)*; });
         }
     }
 
     // Non-terminal associated types (deduplicated by result_type)
-    for (_, result_type) in typed_non_terminals {
+    for (name, result_type) in typed_non_terminals {
         if seen_types.insert(result_type.as_str()) {
             let type_name = format_ident!("{}", result_type);
-            assoc_types.push(quote! { type #type_name: std::fmt::Debug; });
+            let docs = super::doc_attrs(symbol_doc(ctx, name));
+            let extra = extra_bounds(result_type);
+            assoc_types.push(quote! { #(#docs)* type #type_name: std::fmt::Debug #(+ #extra)*; });
         }
     }
 
@@ -535,6 +748,9 @@ fn generate_value_union(
 
     // Terminals with payloads - use payload type name as associated type
     for id in ctx.grammar.symbols.terminal_ids().skip(1) {
+        if ctx.grammar.twin_of(id).is_some() {
+            continue;
+        }
         if let Some(type_name) = ctx.grammar.types.get(&id).and_then(|t| t.as_ref()) {
             let name = ctx.grammar.symbols.name(id);
             let field_name = format_ident!("__{}", name.to_lowercase());
@@ -569,42 +785,33 @@ fn generate_value_union(
 
 /// Convert a synthetic type like "Option<Foo>" or "Vec<Bar>" to tokens with associated type.
 fn synthetic_type_to_tokens_with_prefix(type_str: &str, use_self: bool) -> TokenStream {
-    if let Some(inner) = type_str
-        .strip_prefix("Option<")
-        .and_then(|s| s.strip_suffix('>'))
-    {
-        if inner == "()" {
-            quote! { Option<()> }
-        } else {
-            let inner_ident = format_ident!("{}", inner);
-            if use_self {
-                quote! { Option<Self::#inner_ident> }
-            } else {
-                quote! { Option<A::#inner_ident> }
-            }
-        }
-    } else if let Some(inner) = type_str
-        .strip_prefix("Vec<")
-        .and_then(|s| s.strip_suffix('>'))
-    {
-        if inner == "()" {
-            quote! { Vec<()> }
-        } else {
-            let inner_ident = format_ident!("{}", inner);
-            if use_self {
-                quote! { Vec<Self::#inner_ident> }
-            } else {
-                quote! { Vec<A::#inner_ident> }
-            }
-        }
-    } else {
-        let ident = format_ident!("{}", type_str);
-        if use_self {
-            quote! { Self::#ident }
-        } else {
-            quote! { A::#ident }
+    // Synthetic types nest `Option`, `Vec` and tuples around associated types,
+    // e.g. `Vec<(Expr, Num)>`; qualify the latter with `A::` or `Self::`.
+    let prefix = if use_self { "Self::" } else { "A::" };
+    let mut qualified = String::new();
+    let mut rest = type_str;
+    while let Some(start) = rest.find(|c: char| c.is_alphanumeric() || c == '_') {
+        qualified.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        if !matches!(&rest[..end], "Option" | "Vec") {
+            qualified.push_str(prefix);
         }
+        qualified.push_str(&rest[..end]);
+        rest = &rest[end..];
     }
+    qualified.push_str(rest);
+    let ty: syn::Type = syn::parse_str(&qualified).expect("invalid synthetic type");
+    quote! { #ty }
+}
+
+/// The associated types a synthetic type such as `Vec<Expr>` mentions.
+fn synthetic_assoc_types(type_str: &str) -> impl Iterator<Item = &str> {
+    type_str
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|ident| !matches!(*ident, "" | "Option" | "Vec"))
 }
 
 fn generate_terminal_shift_arms(
@@ -614,15 +821,20 @@ fn generate_terminal_shift_arms(
 ) -> Vec<TokenStream> {
     let mut arms = Vec::new();
 
-    for id in ctx.grammar.symbols.terminal_ids().skip(1) {
+    for id in ctx.terminal_ids() {
         let name = ctx.grammar.symbols.name(id);
-        let variant_name = format_ident!("{}", crate::lr::to_camel_case(name));
+        let variant_name = ctx.terminal_variant(id);
         let ty = ctx.grammar.types.get(&id).and_then(|t| t.as_ref());
         let is_prec = ctx.grammar.symbols.is_prec_terminal(id);
+        // A reduce-leaning twin stores its payload as the original terminal.
+        let value_name = ctx
+            .grammar
+            .symbols
+            .name(ctx.grammar.twin_of(id).unwrap_or(id));
 
         match (is_prec, ty.is_some()) {
             (false, true) => {
-                let field_name = format_ident!("__{}", name.to_lowercase());
+                let field_name = format_ident!("__{}", value_name.to_lowercase());
                 arms.push(quote! {
                     #terminal_enum::#variant_name(v) => {
                         self.value_stack.push(
@@ -676,14 +888,19 @@ fn generate_reduction_arms(
 
     let mut arms = Vec::new();
 
-    for (idx, info) in reductions.iter().enumerate() {
-        let lhs_field = format_ident!("__{}", info.non_terminal.to_lowercase());
+    // Arms follow the (possibly rewritten) parse rules; each rule's recipe says
+    // which source reductions rebuild its value.
+    let num_entries = ctx.grammar.num_entries();
+    for (idx, rule) in ctx.grammar.rules[num_entries..].iter().enumerate() {
+        let rhs_symbols = reduction::rhs_symbols(ctx, rule);
+        let lhs_name = ctx.grammar.symbols.name(rule.lhs.id());
+        let lhs_field = format_ident!("__{}", lhs_name.to_lowercase());
         let idx_lit = idx;
 
         // Build the pop and extract statements
         let mut stmts = Vec::new();
 
-        for (i, sym) in info.rhs_symbols.iter().enumerate().rev() {
+        for (i, sym) in rhs_symbols.iter().enumerate().rev() {
             let pop_expr = quote! { self.value_stack.pop().unwrap() };
 
             if sym.ty.is_some() {
@@ -709,92 +926,38 @@ fn generate_reduction_arms(
             }
         }
 
-        // Check if NT has a result type
-        let has_result_type = ctx
-            .grammar
-            .symbols
-            .non_terminal_ids()
-            .find(|&id| ctx.grammar.symbols.name(id) == info.non_terminal)
-            .and_then(|id| ctx.grammar.types.get(&id)?.as_ref())
-            .is_some();
+        let mut values = rhs_symbols
+            .iter()
+            .enumerate()
+            .map(|(i, sym)| sym.ty.as_ref().map(|_| format_ident!("v{}", i)));
+        let Recipe::Build(source, parts) = ctx.grammar.recipe(idx + num_entries) else {
+            unreachable!("rule recipes always build a source rule");
+        };
+        let args = recipe_args(
+            ctx,
+            reductions,
+            &parts,
+            &mut values,
+            &mut stmts,
+            &gazelle_crate_path,
+        );
 
         // Generate result based on reduction kind
-        let result = if let Some(variant_name) = &info.variant_name {
-            // Non-terminal with enum variant: construct variant, call reduce
-            let enum_name = enum_name(&info.non_terminal);
-            let variant_ident = format_ident!("{}", crate::lr::to_camel_case(variant_name));
-            let args: Vec<_> = typed_symbol_indices(&info.rhs_symbols)
-                .iter()
-                .map(|sym_idx| format_ident!("v{}", sym_idx))
-                .collect();
-
-            let node_expr = if args.is_empty() {
-                quote! { #enum_name::#variant_ident }
-            } else {
-                quote! { #enum_name::#variant_ident(#(#args),*) }
-            };
-
-            if has_result_type {
-                quote! { #value_union { #lhs_field: std::mem::ManuallyDrop::new(
-                    #gazelle_crate_path::Action::build(actions, #node_expr)?
-                ) } }
-            } else {
-                // Untyped NT with => name — side-effect reduction
-                quote! { {
-                    #gazelle_crate_path::Action::build(actions, #node_expr)?;
-                    #value_union { __unit: () }
-                } }
-            }
-        } else {
-            match &info.action {
-                AltAction::Named(_) => {
-                    quote! { #value_union { __unit: () } }
-                }
-                AltAction::OptSome => {
-                    let is_unit = info
-                        .rhs_symbols
-                        .first()
-                        .map(|s| s.ty.is_none())
-                        .unwrap_or(true);
-                    if is_unit {
-                        quote! { #value_union { #lhs_field: std::mem::ManuallyDrop::new(Some(())) } }
-                    } else {
-                        quote! { #value_union { #lhs_field: std::mem::ManuallyDrop::new(Some(v0)) } }
-                    }
-                }
-                AltAction::OptNone => {
-                    quote! { #value_union { #lhs_field: std::mem::ManuallyDrop::new(None) } }
-                }
-                AltAction::VecEmpty => {
-                    quote! { #value_union { #lhs_field: std::mem::ManuallyDrop::new(Vec::new()) } }
-                }
-                AltAction::VecSingle => {
-                    let is_unit = info
-                        .rhs_symbols
-                        .first()
-                        .map(|s| s.ty.is_none())
-                        .unwrap_or(true);
-                    if is_unit {
-                        quote! { #value_union { #lhs_field: std::mem::ManuallyDrop::new(vec![()]) } }
-                    } else {
-                        quote! { #value_union { #lhs_field: std::mem::ManuallyDrop::new(vec![v0]) } }
-                    }
-                }
-                AltAction::VecAppend => {
-                    let last_idx = info.rhs_symbols.len() - 1;
-                    let is_unit = info
-                        .rhs_symbols
-                        .get(last_idx)
-                        .map(|s| s.ty.is_none())
-                        .unwrap_or(true);
-                    if is_unit {
-                        quote! { { let mut v0 = v0; v0.push(()); #value_union { #lhs_field: std::mem::ManuallyDrop::new(v0) } } }
-                    } else {
-                        let elem_var = format_ident!("v{}", last_idx);
-                        quote! { { let mut v0 = v0; v0.push(#elem_var); #value_union { #lhs_field: std::mem::ManuallyDrop::new(v0) } } }
-                    }
-                }
+        let value = reduction_value(
+            &reductions[source - num_entries],
+            &args,
+            &gazelle_crate_path,
+        );
+        let result = match value {
+            Some(expr) if has_result_type(ctx, rule.lhs) => {
+                quote! { #value_union { #lhs_field: std::mem::ManuallyDrop::new(#expr) } }
             }
+            // Untyped NT with => name — side-effect reduction
+            Some(expr) => quote! { {
+                #expr;
+                #value_union { __unit: () }
+            } },
+            None => quote! { #value_union { __unit: () } },
         };
 
         arms.push(quote! {
@@ -808,17 +971,190 @@ fn generate_reduction_arms(
     arms
 }
 
+/// Evaluate the parts of a recipe to argument values for a source reduction.
+///
+/// `Value` parts take the next popped RHS value; nested `Build` parts perform
+/// the inner source reduction first and bind its result to a fresh variable.
+fn recipe_args(
+    ctx: &CodegenContext,
+    reductions: &[ReductionInfo],
+    parts: &[Recipe],
+    values: &mut impl Iterator<Item = Option<syn::Ident>>,
+    stmts: &mut Vec<TokenStream>,
+    gazelle_crate_path: &TokenStream,
+) -> Vec<Option<syn::Ident>> {
+    parts
+        .iter()
+        .map(|part| match part {
+            Recipe::Value => values
+                .next()
+                .expect("recipe consumes more values than popped"),
+            Recipe::Build(source, parts) => {
+                let args = recipe_args(ctx, reductions, parts, values, stmts, gazelle_crate_path);
+                let value = reduction_value(
+                    &reductions[source - ctx.grammar.num_entries()],
+                    &args,
+                    gazelle_crate_path,
+                )?;
+                if has_result_type(ctx, ctx.grammar.source_rules()[*source].lhs) {
+                    let var_name = format_ident!("r{}", stmts.len());
+                    stmts.push(quote! { let #var_name = #value; });
+                    Some(var_name)
+                } else {
+                    stmts.push(quote! { #value; });
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// The expression a source reduction evaluates to, given its (typed) arguments.
+///
+/// Returns `None` for reductions that neither produce a value nor call an action.
+fn reduction_value(
+    info: &ReductionInfo,
+    args: &[Option<syn::Ident>],
+    gazelle_crate_path: &TokenStream,
+) -> Option<TokenStream> {
+    let arg = |i: usize| match args.get(i) {
+        Some(Some(var)) => quote! { #var },
+        _ => quote! { () },
+    };
+
+    if let Some(variant_name) = &info.variant_name {
+        // Non-terminal with enum variant: construct variant, call reduce
+        let enum_name = enum_name(&info.non_terminal);
+        let variant_ident = format_ident!("{}", crate::lr::to_camel_case(variant_name));
+        let fields: Vec<_> = args.iter().flatten().collect();
+
+        let node_expr = if let Some(labels) = info.field_labels() {
+            quote! { #enum_name::#variant_ident { #(#labels: #fields),* } }
+        } else if fields.is_empty() {
+            quote! { #enum_name::#variant_ident }
+        } else {
+            quote! { #enum_name::#variant_ident(#(#fields),*) }
+        };
+        return Some(quote! { #gazelle_crate_path::Action::build(actions, #node_expr)? });
+    }
+
+    Some(match &info.action {
+        AltAction::OptSome => {
+            let v = arg(0);
+            quote! { Some(#v) }
+        }
+        AltAction::OptNone => quote! { None },
+        AltAction::VecEmpty => quote! { Vec::new() },
+        AltAction::VecSingle => {
+            let v = arg(0);
+            quote! { vec![#v] }
+        }
+        AltAction::VecKeep => {
+            let v = arg(0);
+            quote! { #v }
+        }
+        AltAction::VecAppend => {
+            let v0 = arg(0);
+            let elem = arg(args.len() - 1);
+            quote! { { let mut v0 = #v0; v0.push(#elem); v0 } }
+        }
+        // An unnamed alternative passes its one typed value through
+        AltAction::Named { .. } if info.conversion.is_some() => {
+            let (from, to) = info.conversion.as_ref().unwrap();
+            let from = synthetic_type_to_tokens_with_prefix(from, false);
+            let to = synthetic_type_to_tokens_with_prefix(to, false);
+            let v = args.iter().flatten().next()?;
+            quote! { <#to as std::convert::From<#from>>::from(#v) }
+        }
+        AltAction::Named { .. } | AltAction::Tuple => {
+            match args.iter().flatten().collect::<Vec<_>>()[..] {
+                [] => return None,
+                [v] => quote! { #v },
+                ref fields => quote! { (#(#fields),*) },
+            }
+        }
+        AltAction::PermFirst(vecs, _) if vecs.is_empty() => return None,
+        AltAction::PermFirst(vecs, slot) => {
+            let empty = perm_slots(vecs);
+            perm_add(quote! { (#(#empty,)*) }, *slot, arg(0))
+        }
+        AltAction::PermAdd(slot) => {
+            let v0 = args[0].as_ref()?;
+            perm_add(quote! { #v0 }, *slot, arg(1))
+        }
+        AltAction::PermFinish(unwrap) => {
+            let v0 = args[0].as_ref()?;
+            let slots: Vec<_> = unwrap
+                .iter()
+                .enumerate()
+                .map(|(i, &unwrap)| {
+                    let i = syn::Index::from(i);
+                    if unwrap {
+                        quote! { #v0.#i.unwrap() }
+                    } else {
+                        quote! { #v0.#i }
+                    }
+                })
+                .collect();
+            match &slots[..] {
+                [slot] => quote! { #slot },
+                _ => quote! { (#(#slots),*) },
+            }
+        }
+        AltAction::PermEmpty(vecs) => match &perm_slots(vecs)[..] {
+            [] => return None,
+            [slot] => quote! { #slot },
+            slots => quote! { (#(#slots),*) },
+        },
+    })
+}
+
+/// The empty slots of a permutation, `true` marking a `Vec`.
+fn perm_slots(vecs: &[bool]) -> Vec<TokenStream> {
+    vecs.iter()
+        .map(|&is_vec| {
+            if is_vec {
+                quote! { Vec::new() }
+            } else {
+                quote! { None }
+            }
+        })
+        .collect()
+}
+
+/// The permutation value `acc` with `item` put in its slot, if it has one.
+fn perm_add(acc: TokenStream, slot: Option<(usize, bool)>, item: TokenStream) -> TokenStream {
+    let Some((i, is_vec)) = slot else {
+        return acc;
+    };
+    let i = syn::Index::from(i);
+    if is_vec {
+        quote! { { let mut acc = #acc; acc.#i.push(#item); acc } }
+    } else {
+        quote! { { let mut acc = #acc; acc.#i = Some(#item); acc } }
+    }
+}
+
+fn has_result_type(ctx: &CodegenContext, symbol: crate::lr::Symbol) -> bool {
+    ctx.grammar
+        .types
+        .get(&symbol.id())
+        .and_then(|t| t.as_ref())
+        .is_some()
+}
+
 fn generate_drop_arms(ctx: &CodegenContext, info: &CodegenTableInfo) -> Vec<TokenStream> {
     let mut arms = Vec::new();
 
-    // Terminals with payloads
+    // Terminals with payloads (twins are never on the stack; they shift their original)
     for id in ctx.grammar.symbols.terminal_ids().skip(1) {
-        if ctx
-            .grammar
-            .types
-            .get(&id)
-            .and_then(|t| t.as_ref())
-            .is_some()
+        if ctx.grammar.twin_of(id).is_none()
+            && ctx
+                .grammar
+                .types
+                .get(&id)
+                .and_then(|t| t.as_ref())
+                .is_some()
         {
             let name = ctx.grammar.symbols.name(id);
             if let Some((_, table_id)) = info.terminal_ids.iter().find(|(n, _)| n == name) {
@@ -855,3 +1191,77 @@ fn generate_drop_arms(ctx: &CodegenContext, info: &CodegenTableInfo) -> Vec<Toke
 fn enum_name(nt_name: &str) -> syn::Ident {
     format_ident!("{}", crate::lr::to_camel_case(nt_name))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::super::{CodegenContext, generate_tokens};
+
+    #[test]
+    fn test_doc_comments() {
+        let src = "start sum;\nterminals {\n/// An integer.\nNUM: _, PLUS }\n\
+                   /// A sum.\nsum = sum PLUS NUM => add\n/// A single number.\n| NUM => num;";
+        let grammar = crate::parse_grammar(src).unwrap();
+        let ctx = CodegenContext::from_grammar(&grammar, "g", "", false).unwrap();
+        let code = generate_tokens(&ctx).unwrap().to_string();
+        for doc in [
+            r#"# [doc = " An integer."] Num"#,
+            r#"# [doc = " A sum."] pub enum Sum"#,
+            r#"# [doc = " `sum PLUS NUM`"] Add"#,
+            r#"# [doc = " A single number."] # [doc = ""] # [doc = " `NUM`"] Num"#,
+            r#"# [doc = " A sum."] type Sum"#,
+        ] {
+            assert!(code.contains(doc), "missing {:?} in {}", doc, code);
+        }
+    }
+
+    #[test]
+    fn test_literal_separators() {
+        for (list, name) in [
+            ("(A % ',')", "____a_sep_comma"),
+            ("(A %% ',')", "____a_sep_comma_trailing"),
+            ("(A ; ';')*", "____a_term_semi_star"),
+            ("(A ; ';')", "____a_term_semi"),
+        ] {
+            let src = format!("start s; terminals {{ A: _ }} s = {} => s;", list);
+            let grammar = crate::parse_grammar(&src).unwrap();
+            let ctx = CodegenContext::from_grammar(&grammar, "g", "", false).unwrap();
+            let code = generate_tokens(&ctx).unwrap().to_string();
+            assert!(code.contains(name), "missing {} in {}", name, code);
+        }
+        // A rule and a terminal that read the same keep separate lists
+        let src = "start s; terminals { X: _, B } s = x* X* => s; x = B => x;";
+        let grammar = crate::parse_grammar(src).unwrap();
+        let ctx = CodegenContext::from_grammar(&grammar, "g", "", false).unwrap();
+        let code = generate_tokens(&ctx).unwrap().to_string();
+        assert!(code.contains("____x_star_2"), "{}", code);
+    }
+
+    #[test]
+    fn test_literal_type_arguments() {
+        let src = "start s; terminals { A: _ } sep<T, S> = (T % S) => list; \
+                   s = sep<A, ','> => s;";
+        let grammar = crate::parse_grammar(src).unwrap();
+        let ctx = CodegenContext::from_grammar(&grammar, "g", "", false).unwrap();
+        let code = generate_tokens(&ctx).unwrap().to_string();
+        assert!(code.contains("pub enum SepAComma"), "{}", code);
+    }
+
+    #[test]
+    fn test_ord_with_prec_terminal() {
+        let src = "start e; terminals { #![derive(PartialEq, PartialOrd)] NUM, prec OP } \
+                   e = e OP e => bin | NUM => num;";
+        let grammar = crate::parse_grammar(src).unwrap();
+        let ctx = CodegenContext::from_grammar(&grammar, "g", "", false).unwrap();
+        let err = generate_tokens(&ctx).unwrap_err();
+        assert_eq!(
+            err.message,
+            "cannot derive PartialOrd for Terminal: prec terminal 'OP' carries a Precedence, \
+             which has no order"
+        );
+
+        let src = src.replace("prec OP", "OP").replace("e OP e", "e OP NUM");
+        let grammar = crate::parse_grammar(&src).unwrap();
+        let ctx = CodegenContext::from_grammar(&grammar, "g", "", false).unwrap();
+        assert!(generate_tokens(&ctx).is_ok());
+    }
+}
@@ -1,7 +1,7 @@
 //! Reduction analysis for code generation.
 
 use super::CodegenContext;
-use crate::lr::AltAction;
+use crate::lr::{AltAction, Rule};
 
 /// Information about a reduction for code generation.
 #[derive(Debug, Clone)]
@@ -14,6 +14,26 @@ pub struct ReductionInfo {
     pub variant_name: Option<String>,
     /// All RHS symbols with their types (for stack manipulation).
     pub rhs_symbols: Vec<SymbolInfo>,
+    /// For an alternative without a variant name, the types `(from, to)`
+    /// when the value it passes through has another type than the rule's
+    /// and converts with `From`.
+    pub conversion: Option<(String, String)>,
+}
+
+impl ReductionInfo {
+    /// Field names of the enum variant, one per typed symbol, if the
+    /// alternative is labeled (`lhs:expr op:OP rhs:expr`).
+    pub fn field_labels(&self) -> Option<Vec<syn::Ident>> {
+        let AltAction::Named { labels, .. } = &self.action else {
+            return None;
+        };
+        let fields: Vec<_> = typed_symbol_indices(&self.rhs_symbols)
+            .into_iter()
+            .filter_map(|i| labels.get(i)?.as_ref())
+            .map(|label| quote::format_ident!("{}", label))
+            .collect();
+        (!fields.is_empty()).then_some(fields)
+    }
 }
 
 /// Information about a symbol in a reduction RHS.
@@ -38,27 +58,27 @@ pub fn analyze_reductions(ctx: &CodegenContext) -> Result<Vec<ReductionInfo>, St
     let grammar = &ctx.grammar;
     let mut result = Vec::new();
 
-    // Skip rule 0 (__start -> original_start)
-    for rule in &grammar.rules[1..] {
+    // Skip the accept rules (__start -> start). Enums and variants follow the
+    // grammar as written, not any internal rewrite of it.
+    for rule in &grammar.source_rules()[grammar.num_entries()..] {
         let nt_name = grammar.symbols.name(rule.lhs.id()).to_string();
         let is_synthetic = nt_name.starts_with("__");
-
-        // Build symbol info for this alternative
-        let mut rhs_symbols = Vec::new();
-        for sym in &rule.rhs {
-            let sym_name = grammar.symbols.name(sym.id()).to_string();
-            let kind = determine_symbol_kind(ctx, sym);
-            let ty = symbol_type(ctx, sym);
-            rhs_symbols.push(SymbolInfo {
-                name: sym_name,
-                ty,
-                kind,
-            });
-        }
+        let rhs_symbols = rhs_symbols(ctx, rule);
 
         // Determine variant name
         let variant_name = match &rule.action {
-            AltAction::Named(name) if !is_synthetic && !name.is_empty() => Some(name.clone()),
+            AltAction::Named { name, .. } if !is_synthetic && !name.is_empty() => {
+                Some(name.clone())
+            }
+            _ => None,
+        };
+
+        let conversion = match &rule.action {
+            AltAction::Named { name, .. } if !is_synthetic && name.is_empty() => {
+                let from = rhs_symbols.iter().find_map(|s| s.ty.clone());
+                let to = symbol_type(ctx, &rule.lhs);
+                from.zip(to).filter(|(from, to)| from != to)
+            }
             _ => None,
         };
 
@@ -67,6 +87,7 @@ pub fn analyze_reductions(ctx: &CodegenContext) -> Result<Vec<ReductionInfo>, St
             action: rule.action.clone(),
             variant_name,
             rhs_symbols,
+            conversion,
         });
     }
 
@@ -87,6 +108,18 @@ pub fn analyze_reductions(ctx: &CodegenContext) -> Result<Vec<ReductionInfo>, St
     Ok(result)
 }
 
+/// Build symbol info for each RHS symbol of a rule.
+pub fn rhs_symbols(ctx: &CodegenContext, rule: &Rule) -> Vec<SymbolInfo> {
+    rule.rhs
+        .iter()
+        .map(|sym| SymbolInfo {
+            name: ctx.grammar.symbols.name(sym.id()).to_string(),
+            ty: symbol_type(ctx, sym),
+            kind: determine_symbol_kind(ctx, sym),
+        })
+        .collect()
+}
+
 /// Get the type for a symbol (terminal payload type or non-terminal result type).
 fn symbol_type(ctx: &CodegenContext, sym: &crate::lr::Symbol) -> Option<String> {
     ctx.grammar.types.get(&sym.id())?.clone()
@@ -5,7 +5,8 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
-use crate::grammar::SymbolId;
+use crate::grammar::{GrammarError, GrammarErrorKind, SymbolId};
+use crate::runtime::{Precedence, RulePrec};
 use crate::table::CompiledTable;
 
 use super::CodegenContext;
@@ -19,8 +20,17 @@ pub struct CodegenTableInfo {
 }
 
 /// Build parse tables and extract codegen info from a [`CodegenContext`].
-pub fn build_table(ctx: &CodegenContext) -> Result<(CompiledTable, CodegenTableInfo), String> {
-    let compiled = CompiledTable::build_from_internal(&ctx.grammar);
+///
+/// If the grammar has unexpected conflicts, it is first rewritten internally
+/// (see [`crate::rewrite`]); the returned table then holds the rewritten grammar.
+///
+/// Conflict counts that differ from the grammar's `expect` declarations are
+/// an [`ExpectMismatch`](GrammarErrorKind::ExpectMismatch), located at the
+/// rule of the first conflicting reduction.
+pub fn build_table(
+    ctx: &CodegenContext,
+) -> Result<(CompiledTable, CodegenTableInfo), GrammarError> {
+    let compiled = crate::rewrite::build_table(&ctx.grammar, ctx.expect_rr, ctx.expect_sr);
 
     // Count conflicts by type
     let rr_count = compiled
@@ -87,7 +97,19 @@ pub fn build_table(ctx: &CodegenContext) -> Result<(CompiledTable, CodegenTableI
             }
         }
 
-        return Err(errors.join("\n\n"));
+        let span = compiled.conflicts.iter().find_map(|c| {
+            let rule = match c {
+                crate::table::Conflict::ShiftReduce { reduce_rule, .. } => *reduce_rule,
+                crate::table::Conflict::ReduceReduce { rule1, .. } => *rule1,
+            };
+            let lhs = compiled.grammar.rules.get(rule)?.lhs;
+            ctx.rule_spans
+                .get(compiled.grammar.symbols.name(lhs.id()))
+                .cloned()
+        });
+        return Err(
+            GrammarError::new(GrammarErrorKind::ExpectMismatch, errors.join("\n\n")).at(span),
+        );
     }
 
     // Build terminal ID map (skip EOF at index 0)
@@ -117,7 +139,7 @@ pub fn generate_table_statics(
     ctx: &CodegenContext,
     compiled: &CompiledTable,
     info: &CodegenTableInfo,
-) -> TokenStream {
+) -> Result<TokenStream, String> {
     let mod_name = format_ident!("__table");
     let gazelle_crate_path = ctx.gazelle_crate_path_tokens();
 
@@ -198,7 +220,31 @@ pub fn generate_table_statics(
         })
         .collect();
 
-    quote! {
+    // Rule precedences from `prec(...)`; omitted entirely when unused.
+    let rule_prec: Vec<_> = compiled
+        .rule_prec()
+        .iter()
+        .map(|&(rule, prec)| {
+            let prec = match prec {
+                RulePrec::Fixed(p) => {
+                    let p = precedence_tokens(&gazelle_crate_path, p)?;
+                    quote! { #gazelle_crate_path::RulePrec::Fixed(#p) }
+                }
+                RulePrec::Position(pos) => quote! { #gazelle_crate_path::RulePrec::Position(#pos) },
+            };
+            Ok(quote! { (#rule, #prec) })
+        })
+        .collect::<Result<_, String>>()?;
+    let (rule_prec_static, with_rule_prec) = if rule_prec.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        (
+            quote! { pub static RULE_PREC: &[(u32, #gazelle_crate_path::RulePrec)] = &[#(#rule_prec),*]; },
+            quote! { .with_rule_prec(RULE_PREC) },
+        )
+    };
+
+    Ok(quote! {
         #[doc(hidden)]
         mod #mod_name {
             #use_stmt
@@ -211,6 +257,7 @@ pub fn generate_table_statics(
             pub static STATE_SYMBOL: &[u32] = &[#(#state_symbols),*];
             pub static DEFAULT_REDUCE: &[u32] = &[#(#default_reduce),*];
             pub static DEFAULT_GOTO: &[u32] = &[#(#default_goto),*];
+            #rule_prec_static
             pub const NUM_STATES: usize = #num_states;
             pub const NUM_TERMINALS: u32 = #num_terminals;
             #[allow(dead_code)]
@@ -233,7 +280,7 @@ pub fn generate_table_statics(
             pub static TABLE: #gazelle_crate_path::ParseTable<'static> = #gazelle_crate_path::ParseTable::new(
                 DATA, CHECK, ACTION_BASE, GOTO_BASE,
                 RULES, NUM_TERMINALS, DEFAULT_REDUCE, DEFAULT_GOTO,
-            );
+            )#with_rule_prec;
 
             pub static ERROR_INFO: #gazelle_crate_path::ErrorInfo<'static> = #gazelle_crate_path::ErrorInfo {
                 symbol_names: SYMBOL_NAMES,
@@ -242,5 +289,49 @@ pub fn generate_table_statics(
                 state_symbols: STATE_SYMBOL,
             };
         }
+    })
+}
+
+fn precedence_tokens(
+    gazelle_crate_path: &TokenStream,
+    prec: Precedence,
+) -> Result<TokenStream, String> {
+    Ok(match prec {
+        Precedence::Left(l) => quote! { #gazelle_crate_path::Precedence::Left(#l) },
+        Precedence::Right(l) => quote! { #gazelle_crate_path::Precedence::Right(#l) },
+        Precedence::NonAssoc(l) => quote! { #gazelle_crate_path::Precedence::NonAssoc(#l) },
+        // Groups are created at runtime and have no literal form.
+        _ => {
+            return Err(format!(
+                "precedence {:?} can't be written into a generated table",
+                prec
+            ));
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::GrammarErrorKind;
+
+    #[test]
+    fn test_expect_mismatch_location() {
+        let src = "start s;\nterminals { A }\ns = s s => pair | A => a;";
+        let grammar = crate::parse_grammar(src).unwrap();
+        let ctx = CodegenContext::from_grammar(&grammar, "g", "", false).unwrap();
+        let Err(err) = build_table(&ctx) else {
+            panic!("conflict not reported");
+        };
+        assert_eq!(err.kind, GrammarErrorKind::ExpectMismatch);
+        assert!(
+            err.message
+                .contains("1 shift/reduce conflict(s) (expected 0)")
+        );
+        assert!(
+            err.format(src).starts_with("3:1: Grammar has"),
+            "{}",
+            err.format(src)
+        );
     }
 }
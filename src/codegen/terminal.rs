@@ -4,10 +4,11 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
 use super::CodegenContext;
+use super::derive::{self, EnumAttrs};
 use super::table::CodegenTableInfo;
 
 /// Generate the terminal enum and its implementations.
-pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo) -> TokenStream {
+pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo, attrs: &EnumAttrs) -> TokenStream {
     let vis: TokenStream = "pub".parse().unwrap();
     let terminal_enum = format_ident!("Terminal");
     let types_trait = format_ident!("Types");
@@ -24,29 +25,38 @@ pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo) -> TokenStream {
 
     // Build enum variants
     let mut variants = Vec::new();
+    let mut derive_variants = Vec::new();
 
-    for id in ctx.grammar.symbols.terminal_ids().skip(1) {
-        let name = ctx.grammar.symbols.name(id);
-        let variant_name = format_ident!("{}", crate::lr::to_camel_case(name));
+    for id in ctx.terminal_ids() {
+        let variant_name = ctx.terminal_variant(id);
         let ty = ctx.grammar.types.get(&id).and_then(|t| t.as_ref());
         let is_prec = ctx.grammar.symbols.is_prec_terminal(id);
+        let mut docs = super::doc_attrs(ctx.grammar.docs.get(&id).map_or(&[], Vec::as_slice));
+        if ctx.grammar.twin_of(id).is_some() {
+            docs.push(quote! { #[allow(non_camel_case_types)] });
+        }
+        derive_variants.push(derive::Variant {
+            name: variant_name.clone(),
+            labels: None,
+            fields: usize::from(is_prec) + usize::from(ty.is_some()),
+        });
 
         match (is_prec, ty) {
             (false, Some(type_name)) => {
                 let assoc_type = format_ident!("{}", type_name);
-                variants.push(quote! { #variant_name(A::#assoc_type) });
+                variants.push(quote! { #(#docs)* #variant_name(A::#assoc_type) });
             }
             (false, None) => {
-                variants.push(quote! { #variant_name });
+                variants.push(quote! { #(#docs)* #variant_name });
             }
             (true, Some(type_name)) => {
                 let assoc_type = format_ident!("{}", type_name);
                 variants.push(
-                    quote! { #variant_name(A::#assoc_type, #gazelle_crate_path::Precedence) },
+                    quote! { #(#docs)* #variant_name(A::#assoc_type, #gazelle_crate_path::Precedence) },
                 );
             }
             (true, None) => {
-                variants.push(quote! { #variant_name(#gazelle_crate_path::Precedence) });
+                variants.push(quote! { #(#docs)* #variant_name(#gazelle_crate_path::Precedence) });
             }
         }
     }
@@ -56,6 +66,18 @@ pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo) -> TokenStream {
         #[doc(hidden)]
         __Phantom(std::marker::PhantomData<A>)
     });
+    derive_variants.push(derive::Variant {
+        name: format_ident!("__Phantom"),
+        labels: None,
+        fields: 1,
+    });
+    let user_attrs = &attrs.attrs;
+    let derive_impls = derive::impls(
+        &terminal_enum,
+        &types_trait,
+        &derive_variants,
+        &attrs.std_traits,
+    );
 
     // Build symbol_id match arms
     let symbol_id_arms = build_symbol_id_arms(ctx, info, &gazelle_crate_path, has_typed_terminals);
@@ -68,10 +90,13 @@ pub fn generate(ctx: &CodegenContext, info: &CodegenTableInfo) -> TokenStream {
 
     quote! {
         /// Terminal symbols for the parser.
+        #(#user_attrs)*
         #vis enum #terminal_enum<A: #types_trait> {
             #(#variants),*
         }
 
+        #derive_impls
+
         impl<A: #types_trait> #terminal_enum<A> {
             /// Get the symbol ID for this terminal.
             pub fn symbol_id(&self) -> #gazelle_crate_path::SymbolId {
@@ -105,9 +130,9 @@ fn build_symbol_id_arms(
 ) -> Vec<TokenStream> {
     let mut arms = Vec::new();
 
-    for id in ctx.grammar.symbols.terminal_ids().skip(1) {
+    for id in ctx.terminal_ids() {
         let name = ctx.grammar.symbols.name(id);
-        let variant_name = format_ident!("{}", crate::lr::to_camel_case(name));
+        let variant_name = ctx.terminal_variant(id);
         let ty = ctx.grammar.types.get(&id).and_then(|t| t.as_ref());
         let is_prec = ctx.grammar.symbols.is_prec_terminal(id);
         let table_id = info
@@ -138,9 +163,9 @@ fn build_to_token_arms(
 ) -> Vec<TokenStream> {
     let mut arms = Vec::new();
 
-    for id in ctx.grammar.symbols.terminal_ids().skip(1) {
+    for id in ctx.terminal_ids() {
         let name = ctx.grammar.symbols.name(id);
-        let variant_name = format_ident!("{}", crate::lr::to_camel_case(name));
+        let variant_name = ctx.terminal_variant(id);
         let ty = ctx.grammar.types.get(&id).and_then(|t| t.as_ref());
         let is_prec = ctx.grammar.symbols.is_prec_terminal(id);
 
@@ -175,9 +200,8 @@ fn build_to_token_arms(
 fn build_precedence_arms(ctx: &CodegenContext, _has_typed_terminals: bool) -> Vec<TokenStream> {
     let mut arms = Vec::new();
 
-    for id in ctx.grammar.symbols.terminal_ids().skip(1) {
-        let name = ctx.grammar.symbols.name(id);
-        let variant_name = format_ident!("{}", crate::lr::to_camel_case(name));
+    for id in ctx.terminal_ids() {
+        let variant_name = ctx.terminal_variant(id);
         let ty = ctx.grammar.types.get(&id).and_then(|t| t.as_ref());
         let is_prec = ctx.grammar.symbols.is_prec_terminal(id);
 
@@ -0,0 +1,480 @@
+//! Export of a grammar as a tree-sitter `grammar.js`.
+//!
+//! Modifiers become `optional`, `repeat` and `repeat1`, separated and
+//! terminated lists the equivalent `seq`s, and terminal patterns `token(...)`
+//! rules. Terminals without a pattern are left to an external scanner.
+//!
+//! Tree-sitter fixes operator precedence in the grammar, while a `prec`
+//! terminal gets its precedence from the lexer. A [`PrecLevel`] table gives
+//! the operators each `prec` terminal stands for, and an alternative with a
+//! `prec` terminal becomes one `prec.left`/`prec.right` choice per level.
+//! Permutations become a repetition of their items, without the counts.
+
+use std::collections::HashMap;
+
+use crate::grammar::{
+    Alt, AltPrec, Grammar, GrammarError, GrammarErrorKind, Spanned, Term, TerminalDef,
+};
+use crate::lr::{expand_parameterized, is_literal, literal_text, to_grammar_internal};
+use crate::{Associativity, Precedence};
+
+/// The precedence of one operator of a `prec` terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrecLevel {
+    /// The `prec` terminal.
+    pub terminal: String,
+    /// The operator's text, as the lexer matches it.
+    pub text: String,
+    /// The precedence the lexer pushes for the operator.
+    pub prec: Precedence,
+}
+
+impl PrecLevel {
+    /// Parse a precedence table with one operator per line, written
+    /// `TERMINAL TEXT left|right|nonassoc LEVEL`:
+    ///
+    /// ```text
+    /// # terminal  text  assoc  level
+    /// OP          +     left   1
+    /// OP          *     left   2
+    /// ```
+    ///
+    /// Blank lines and lines starting with `#` are skipped.
+    pub fn parse_table(source: &str) -> Result<Vec<PrecLevel>, GrammarError> {
+        let mut levels = Vec::new();
+        let mut start = 0;
+        for line in source.split_inclusive('\n') {
+            let span = start..start + line.trim_end().len();
+            start += line.len();
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || {
+                GrammarError::new(
+                    GrammarErrorKind::Syntax,
+                    "Expected 'TERMINAL TEXT left|right|nonassoc LEVEL'",
+                )
+                .at(Some(span.clone()))
+            };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let &[terminal, text, assoc, level] = fields.as_slice() else {
+                return Err(error());
+            };
+            let level: u8 = level.parse().map_err(|_| error())?;
+            let prec = match assoc {
+                "left" => Precedence::Left(level),
+                "right" => Precedence::Right(level),
+                "nonassoc" => Precedence::NonAssoc(level),
+                _ => return Err(error()),
+            };
+            levels.push(PrecLevel {
+                terminal: terminal.to_string(),
+                text: text.to_string(),
+                prec,
+            });
+        }
+        Ok(levels)
+    }
+}
+
+/// Convert a grammar to a tree-sitter `grammar.js` named `name`, with the
+/// operators of its `prec` terminals given by `levels`.
+pub fn to_tree_sitter(
+    grammar_def: &Grammar,
+    name: &str,
+    levels: &[PrecLevel],
+) -> Result<String, GrammarError> {
+    to_grammar_internal(grammar_def)?;
+    let grammar = expand_parameterized(grammar_def)?;
+    let invalid = |message: String| GrammarError::new(GrammarErrorKind::Invalid, message);
+
+    let mut operators: HashMap<&str, Vec<&PrecLevel>> = HashMap::new();
+    for level in levels {
+        match grammar.terminals.iter().find(|t| t.name == level.terminal) {
+            Some(t) if t.is_prec => operators.entry(&t.name).or_default().push(level),
+            _ => {
+                return Err(invalid(format!(
+                    "'{}' in the precedence table is not a prec terminal",
+                    level.terminal
+                )));
+            }
+        }
+    }
+    if let Some(t) = grammar
+        .terminals
+        .iter()
+        .find(|t| t.is_prec && !operators.contains_key(t.name.as_str()))
+    {
+        return Err(invalid(format!(
+            "The precedence table has no operators for prec terminal '{}'",
+            t.name
+        ))
+        .at(t.span.clone()));
+    }
+    let writer = Writer { operators };
+
+    let mut out = String::new();
+    out.push_str("module.exports = grammar({\n");
+    out.push_str(&format!("  name: {},\n", js_string(name)));
+
+    let externals: Vec<&TerminalDef> = grammar
+        .terminals
+        .iter()
+        .filter(|t| !t.is_prec && t.pattern.is_none())
+        .collect();
+    if !externals.is_empty() {
+        out.push_str("\n  externals: $ => [\n");
+        for t in externals {
+            out.push_str(&format!("    $.{},\n", t.name));
+        }
+        out.push_str("  ],\n");
+    }
+
+    // Tree-sitter's first rule is the root
+    out.push_str("\n  rules: {\n");
+    let start = &grammar.start;
+    let rules = grammar
+        .rules
+        .iter()
+        .filter(|r| &r.name == start)
+        .chain(grammar.rules.iter().filter(|r| &r.name != start));
+    let mut first = true;
+    for rule in rules {
+        if !first {
+            out.push('\n');
+        }
+        first = false;
+        push_doc(&mut out, &rule.doc);
+        let choices: Vec<String> = rule.alts.iter().flat_map(|a| writer.alt(a)).collect();
+        match choices.as_slice() {
+            [single] => out.push_str(&format!("    {}: $ => {},\n", rule.name, single)),
+            _ => {
+                out.push_str(&format!("    {}: $ => choice(\n", rule.name));
+                for choice in &choices {
+                    out.push_str(&format!("      {},\n", choice));
+                }
+                out.push_str("    ),\n");
+            }
+        }
+    }
+    for t in &grammar.terminals {
+        let Some(pattern) = t.pattern.as_ref().filter(|_| !t.is_prec) else {
+            continue;
+        };
+        out.push('\n');
+        push_doc(&mut out, &t.doc);
+        out.push_str(&format!(
+            "    {}: $ => token({}),\n",
+            t.name,
+            js_regex(pattern)
+        ));
+    }
+    out.push_str("  },\n");
+    out.push_str("});\n");
+    Ok(out)
+}
+
+fn push_doc(out: &mut String, doc: &[String]) {
+    for line in doc {
+        out.push_str(&format!("    //{}\n", line));
+    }
+}
+
+struct Writer<'a> {
+    /// The operators of each `prec` terminal.
+    operators: HashMap<&'a str, Vec<&'a PrecLevel>>,
+}
+
+impl Writer<'_> {
+    /// The choices for an alternative: one per precedence level of its
+    /// deciding `prec` terminal, or the alternative itself.
+    fn alt(&self, alt: &Alt) -> Vec<String> {
+        let is_prec = |term: &Term, name: Option<&str>| match term {
+            Term::Symbol(s) => {
+                self.operators.contains_key(s.as_str()) && name.is_none_or(|name| s == name)
+            }
+            _ => false,
+        };
+        // Like the parser, the last prec terminal decides unless the
+        // alternative names one
+        let deciding = match &alt.prec {
+            Some(AltPrec::Fixed(prec)) => return vec![wrap_prec(prec, &self.seq(alt, None))],
+            Some(AltPrec::Symbol(name)) => alt.terms.iter().rposition(|t| is_prec(t, Some(name))),
+            None => None,
+        };
+        let Some(pos) = deciding.or_else(|| alt.terms.iter().rposition(|t| is_prec(t, None)))
+        else {
+            return vec![self.seq(alt, None)];
+        };
+        let Term::Symbol(terminal) = &alt.terms[pos].node else {
+            unreachable!()
+        };
+        // Operators grouped by precedence, in table order
+        let mut groups: Vec<(Precedence, Vec<&str>)> = Vec::new();
+        for op in &self.operators[terminal.as_str()] {
+            match groups.iter_mut().find(|(prec, _)| *prec == op.prec) {
+                Some((_, texts)) => texts.push(&op.text),
+                None => groups.push((op.prec, vec![&op.text])),
+            }
+        }
+        groups
+            .iter()
+            .map(|(prec, texts)| {
+                let texts: Vec<String> = texts.iter().map(|t| js_string(t)).collect();
+                wrap_prec(prec, &self.seq(alt, Some((pos, choice(texts)))))
+            })
+            .collect()
+    }
+
+    /// The alternative's terms in sequence, with the term at an optional
+    /// position replaced.
+    fn seq(&self, alt: &Alt, replace: Option<(usize, String)>) -> String {
+        let mut items = Vec::new();
+        for (i, term) in alt.terms.iter().enumerate() {
+            if *term == Term::Empty {
+                continue;
+            }
+            let item = match &replace {
+                Some((pos, item)) if *pos == i => item.clone(),
+                _ => self.term(term),
+            };
+            match alt.labels.get(i).and_then(Option::as_ref) {
+                Some(label) => items.push(format!("field({}, {})", js_string(label), item)),
+                None => items.push(item),
+            }
+        }
+        sequence(items)
+    }
+
+    fn term(&self, term: &Term) -> String {
+        match term {
+            Term::Symbol(s) => self.symbol(s),
+            Term::Apply { .. } => unreachable!("applications are expanded first"),
+            Term::Optional(s) => format!("optional({})", self.symbol(s)),
+            Term::ZeroOrMore(s) => format!("repeat({})", self.symbol(s)),
+            Term::OneOrMore(s) => format!("repeat1({})", self.symbol(s)),
+            Term::SeparatedBy { symbol, sep } => {
+                let (symbol, sep) = (self.symbol(symbol), self.symbol(sep));
+                format!("seq({}, repeat(seq({}, {})))", symbol, sep, symbol)
+            }
+            Term::SeparatedTrailing { symbol, sep } => {
+                let (symbol, sep) = (self.symbol(symbol), self.symbol(sep));
+                format!(
+                    "seq({}, repeat(seq({}, {})), optional({}))",
+                    symbol, sep, symbol, sep
+                )
+            }
+            Term::TerminatedBy { symbol, terminator } => format!(
+                "repeat1(seq({}, {}))",
+                self.symbol(symbol),
+                self.symbol(terminator)
+            ),
+            Term::ZeroOrMoreTerminatedBy { symbol, terminator } => format!(
+                "repeat(seq({}, {}))",
+                self.symbol(symbol),
+                self.symbol(terminator)
+            ),
+            Term::Group(alts) => self.group(alts),
+            Term::OptionalGroup(alts) => format!("optional({})", self.group(alts)),
+            Term::ZeroOrMoreGroup(alts) => format!("repeat({})", self.group(alts)),
+            Term::OneOrMoreGroup(alts) => format!("repeat1({})", self.group(alts)),
+            Term::Permutation(items) => {
+                let required = items
+                    .iter()
+                    .any(|t| matches!(t.node, Term::Symbol(_) | Term::OneOrMore(_)));
+                let symbols: Vec<String> = items
+                    .iter()
+                    .filter_map(|t| match &t.node {
+                        Term::Symbol(s)
+                        | Term::Optional(s)
+                        | Term::ZeroOrMore(s)
+                        | Term::OneOrMore(s) => Some(self.symbol(s)),
+                        _ => None,
+                    })
+                    .collect();
+                let repeat = if required { "repeat1" } else { "repeat" };
+                format!("{}({})", repeat, choice(symbols))
+            }
+            Term::Empty => "blank()".to_string(),
+        }
+    }
+
+    fn group(&self, alts: &[Vec<Spanned<Term>>]) -> String {
+        let seqs = alts
+            .iter()
+            .map(|terms| {
+                let items = terms
+                    .iter()
+                    .filter(|t| **t != Term::Empty)
+                    .map(|t| self.term(t))
+                    .collect();
+                sequence(items)
+            })
+            .collect();
+        choice(seqs)
+    }
+
+    fn symbol(&self, name: &str) -> String {
+        if is_literal(name) {
+            return js_string(&literal_text(name));
+        }
+        match self.operators.get(name) {
+            Some(ops) => choice(ops.iter().map(|op| js_string(&op.text)).collect()),
+            None => format!("$.{}", name),
+        }
+    }
+}
+
+fn sequence(items: Vec<String>) -> String {
+    match items.len() {
+        0 => "blank()".to_string(),
+        1 => items.into_iter().next().unwrap(),
+        _ => format!("seq({})", items.join(", ")),
+    }
+}
+
+fn choice(mut items: Vec<String>) -> String {
+    items.dedup();
+    match items.len() {
+        1 => items.pop().unwrap(),
+        _ => format!("choice({})", items.join(", ")),
+    }
+}
+
+fn wrap_prec(prec: &Precedence, body: &str) -> String {
+    let level = prec.level();
+    match prec.associativity() {
+        Associativity::Left => format!("prec.left({}, {})", level, body),
+        Associativity::Right => format!("prec.right({}, {})", level, body),
+        Associativity::NonAssoc => format!("prec({}, {})", level, body),
+    }
+}
+
+/// A single-quoted JavaScript string.
+fn js_string(text: &str) -> String {
+    let mut out = String::from('\'');
+    for c in text.chars() {
+        match c {
+            '\'' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('\'');
+    out
+}
+
+/// A JavaScript regex literal for a terminal pattern.
+fn js_regex(pattern: &str) -> String {
+    let mut out = String::from('/');
+    let mut escaped = false;
+    for c in pattern.chars() {
+        if c == '/' && !escaped {
+            out.push('\\');
+        }
+        escaped = c == '\\' && !escaped;
+        out.push(c);
+    }
+    out.push('/');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_sitter() {
+        let src = r#"
+start stmts;
+terminals {
+    /// A number.
+    NUM: _ = /[0-9]+/,
+    STR = /"[^"\/]*"/,
+    IDENT: _,
+    prec OP: _
+}
+
+/// A statement list.
+stmts = (stmt ; ';')* => stmts;
+stmt = IDENT '=' expr => assign
+     | "print" (expr % ',') => print;
+expr = lhs:expr op:OP rhs:expr => binop
+     | '-' expr => neg prec(3, right)
+     | '(' expr ')' => paren
+     | NUM => num
+     | STR => str
+     | _ => empty;
+"#;
+        let grammar = crate::parse_grammar(src).unwrap();
+        let table =
+            "# terminal text assoc level\nOP + left 1\nOP - left 1\nOP * left 2\n\nOP ^ right 4\n";
+        let levels = PrecLevel::parse_table(table).unwrap();
+        assert_eq!(
+            to_tree_sitter(&grammar, "calc", &levels).unwrap(),
+            r#"module.exports = grammar({
+  name: 'calc',
+
+  externals: $ => [
+    $.IDENT,
+  ],
+
+  rules: {
+    // A statement list.
+    stmts: $ => repeat(seq($.stmt, ';')),
+
+    stmt: $ => choice(
+      seq($.IDENT, '=', $.expr),
+      seq('print', seq($.expr, repeat(seq(',', $.expr)))),
+    ),
+
+    expr: $ => choice(
+      prec.left(1, seq(field('lhs', $.expr), field('op', choice('+', '-')), field('rhs', $.expr))),
+      prec.left(2, seq(field('lhs', $.expr), field('op', '*'), field('rhs', $.expr))),
+      prec.right(4, seq(field('lhs', $.expr), field('op', '^'), field('rhs', $.expr))),
+      prec.right(3, seq('-', $.expr)),
+      seq('(', $.expr, ')'),
+      $.NUM,
+      $.STR,
+      blank(),
+    ),
+
+    // A number.
+    NUM: $ => token(/[0-9]+/),
+
+    STR: $ => token(/"[^"\/]*"/),
+  },
+});
+"#
+        );
+    }
+
+    #[test]
+    fn test_prec_table_errors() {
+        let err = PrecLevel::parse_table("OP + left 1\nOP * up 2\n").unwrap_err();
+        assert_eq!(
+            err.format("OP + left 1\nOP * up 2\n"),
+            "2:1: Expected 'TERMINAL TEXT left|right|nonassoc LEVEL'"
+        );
+
+        let grammar =
+            crate::parse_grammar("start s; terminals { prec OP, A } s = s OP s => op | A => a;")
+                .unwrap();
+        let levels = PrecLevel::parse_table("A + left 1").unwrap();
+        assert_eq!(
+            to_tree_sitter(&grammar, "g", &levels).unwrap_err().message,
+            "'A' in the precedence table is not a prec terminal"
+        );
+        assert_eq!(
+            to_tree_sitter(&grammar, "g", &[]).unwrap_err().message,
+            "The precedence table has no operators for prec terminal 'OP'"
+        );
+    }
+}
@@ -0,0 +1,550 @@
+//! Railroad diagrams of grammar rules, as SVG.
+//!
+//! Each rule is drawn as a choice of its alternatives. `?` and `*` add a
+//! bypass, `+` and `*` a loop back, and `%` a loop back through the
+//! separator. Terminals are rounded boxes, rules square boxes that link to
+//! the rule's own diagram.
+//!
+//! ```
+//! use gazelle::diagram::{index_html, rule_svg};
+//!
+//! let grammar = gazelle::parse_grammar(r#"
+//!     start list;
+//!     terminals { NUM }
+//!     list = '[' (item % ',') ']' => list;
+//!     item = NUM => num | list => nested;
+//! "#).unwrap();
+//! let svg = rule_svg(&grammar, &grammar.rules[0]);
+//! assert!(svg.contains(r#"<a href="item.svg">"#));
+//! let html = index_html(&grammar, "Lists");
+//! assert!(html.contains(r##"<a href="#item">"##));
+//! ```
+
+use std::fmt::Write;
+
+use crate::grammar::{Grammar, Rule, Spanned, Term};
+use crate::lr::{is_literal, literal_text};
+
+const BOX_HEIGHT: i32 = 22;
+const CHAR_WIDTH: i32 = 8;
+/// Padding between a box's text and its sides.
+const TEXT_PADDING: i32 = 10;
+/// Radius of the curves where lines branch and join.
+const ARC: i32 = 10;
+/// Horizontal space between the items of a sequence.
+const GAP: i32 = 10;
+/// Vertical space between the branches of a choice or loop.
+const ROW_GAP: i32 = 10;
+const MARGIN: i32 = 20;
+
+const STYLE: &str = "\
+path { fill: none; stroke: #333; stroke-width: 1.5; }
+rect { stroke: #333; stroke-width: 1.5; }
+rect.terminal { fill: #fec; }
+rect.nonterminal { fill: #def; }
+text { font: 13px monospace; text-anchor: middle; }
+a text { text-decoration: underline; }";
+
+/// The railroad diagram of `rule` as a standalone SVG document. Rules it
+/// refers to link to their own diagrams, `{name}.svg`.
+pub fn rule_svg(grammar: &Grammar, rule: &Rule) -> String {
+    let mut out = String::new();
+    write_svg(
+        &mut out,
+        grammar,
+        rule,
+        &|name| format!("{}.svg", name),
+        true,
+    );
+    out
+}
+
+/// An HTML page with the diagrams of all rules of `grammar`, each under a
+/// heading with its doc comment. Rules link to each other within the page.
+pub fn index_html(grammar: &Grammar, title: &str) -> String {
+    let mut out = String::new();
+    out.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(out, "<title>{}</title>", escape(title));
+    let _ = writeln!(out, "<style>\n{}\n</style>", STYLE);
+    out.push_str("</head>\n<body>\n");
+    let _ = writeln!(out, "<h1>{}</h1>", escape(title));
+    for rule in &grammar.rules {
+        let _ = writeln!(out, "<section id=\"{}\">", rule.name);
+        let _ = writeln!(out, "<h2>{}</h2>", escape(&rule_title(rule)));
+        if !rule.doc.is_empty() {
+            let doc: Vec<&str> = rule.doc.iter().map(|line| line.trim()).collect();
+            let _ = writeln!(out, "<p>{}</p>", escape(&doc.join(" ")));
+        }
+        write_svg(&mut out, grammar, rule, &|name| format!("#{}", name), false);
+        out.push_str("</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// `name` or `name<T, ...>`.
+fn rule_title(rule: &Rule) -> String {
+    if rule.params.is_empty() {
+        rule.name.clone()
+    } else {
+        format!("{}<{}>", rule.name, rule.params.join(", "))
+    }
+}
+
+fn write_svg(
+    out: &mut String,
+    grammar: &Grammar,
+    rule: &Rule,
+    link: &dyn Fn(&str) -> String,
+    standalone: bool,
+) {
+    let builder = Builder { grammar, link };
+    let node = builder.rule(rule);
+    let (width, up, down) = node.size();
+    let total_width = 2 * MARGIN + 2 * GAP + width;
+    let total_height = 2 * MARGIN + up + down;
+    let y = MARGIN + up;
+    let _ = write!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"railroad\""
+    );
+    let _ = writeln!(
+        out,
+        " width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+        w = total_width,
+        h = total_height
+    );
+    if standalone {
+        let _ = writeln!(out, "<title>{}</title>", escape(&rule_title(rule)));
+        let _ = writeln!(out, "<style>\n{}\n</style>", STYLE);
+    }
+    // Entry and exit bars
+    let end = MARGIN + 2 * GAP + width;
+    path(out, format!("M{} {}v16", MARGIN, y - 8));
+    path(out, format!("M{} {}h{}", MARGIN, y, GAP));
+    node.draw(out, MARGIN + GAP, y);
+    path(out, format!("M{} {}h{}", end - GAP, y, GAP));
+    path(out, format!("M{} {}v16", end, y - 8));
+    out.push_str("</svg>\n");
+}
+
+fn path(out: &mut String, d: String) {
+    let _ = writeln!(out, "<path d=\"{}\"/>", d);
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// ============================================================================
+// Diagram tree
+// ============================================================================
+
+/// A piece of a railroad diagram. Each is drawn from a main line entering
+/// on its left and leaving on its right.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Terminal(String),
+    NonTerminal {
+        text: String,
+        link: Option<String>,
+    },
+    /// A straight line.
+    Skip,
+    Sequence(Vec<Node>),
+    /// Branches stacked below the first, which stays on the main line.
+    Choice(Vec<Node>),
+    /// `item` on the main line, with a line looping back below it through
+    /// `sep`.
+    Loop {
+        item: Box<Node>,
+        sep: Box<Node>,
+    },
+}
+
+impl Node {
+    fn optional(node: Node) -> Node {
+        Node::Choice(vec![Node::Skip, node])
+    }
+
+    fn one_or_more(item: Node, sep: Node) -> Node {
+        Node::Loop {
+            item: Box::new(item),
+            sep: Box::new(sep),
+        }
+    }
+
+    /// Width, and height above and below the main line.
+    fn size(&self) -> (i32, i32, i32) {
+        match self {
+            Node::Terminal(text) | Node::NonTerminal { text, .. } => {
+                (box_width(text), BOX_HEIGHT / 2, BOX_HEIGHT / 2)
+            }
+            Node::Skip => (0, 0, 0),
+            Node::Sequence(items) => {
+                let sizes: Vec<_> = items.iter().map(Node::size).collect();
+                let gaps = GAP * (items.len() as i32 - 1).max(0);
+                (
+                    sizes.iter().map(|s| s.0).sum::<i32>() + gaps,
+                    sizes.iter().map(|s| s.1).max().unwrap_or(0),
+                    sizes.iter().map(|s| s.2).max().unwrap_or(0),
+                )
+            }
+            Node::Choice(branches) => {
+                let offsets = branch_offsets(branches);
+                let (_, up, _) = branches[0].size();
+                let last = branches.last().unwrap().size();
+                let width = branches.iter().map(|b| b.size().0).max().unwrap();
+                (width + 4 * ARC, up, offsets.last().unwrap() + last.2)
+            }
+            Node::Loop { item, sep } => {
+                let (item_width, up, _) = item.size();
+                let (sep_width, _, sep_down) = sep.size();
+                let width = item_width.max(sep_width) + 2 * ARC;
+                (width, up, loop_offset(item, sep) + sep_down)
+            }
+        }
+    }
+
+    /// Draw the node with its main line entering at (`x`, `y`).
+    fn draw(&self, out: &mut String, x: i32, y: i32) {
+        match self {
+            Node::Terminal(text) => draw_box(out, x, y, text, "terminal", 11),
+            Node::NonTerminal { text, link } => {
+                if let Some(link) = link {
+                    let _ = writeln!(out, "<a href=\"{}\">", escape(link));
+                }
+                draw_box(out, x, y, text, "nonterminal", 0);
+                if link.is_some() {
+                    out.push_str("</a>\n");
+                }
+            }
+            Node::Skip => {}
+            Node::Sequence(items) => {
+                let mut x = x;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        path(out, format!("M{} {}h{}", x, y, GAP));
+                        x += GAP;
+                    }
+                    item.draw(out, x, y);
+                    x += item.size().0;
+                }
+            }
+            Node::Choice(branches) => {
+                let width = self.size().0;
+                let right = x + width;
+                for (branch, dy) in branches.iter().zip(branch_offsets(branches)) {
+                    let branch_width = branch.size().0;
+                    let by = y + dy;
+                    if dy == 0 {
+                        path(out, format!("M{} {}h{}", x, y, 2 * ARC));
+                    } else {
+                        path(
+                            out,
+                            format!(
+                                "M{} {}a{r} {r} 0 0 1 {r} {r}V{}a{r} {r} 0 0 0 {r} {r}",
+                                x,
+                                y,
+                                by - ARC,
+                                r = ARC
+                            ),
+                        );
+                    }
+                    branch.draw(out, x + 2 * ARC, by);
+                    let end = x + 2 * ARC + branch_width;
+                    if dy == 0 {
+                        path(out, format!("M{} {}H{}", end, y, right));
+                    } else {
+                        path(
+                            out,
+                            format!(
+                                "M{} {}H{}a{r} {r} 0 0 0 {r} -{r}V{}a{r} {r} 0 0 1 {r} -{r}",
+                                end,
+                                by,
+                                right - 2 * ARC,
+                                y + ARC,
+                                r = ARC
+                            ),
+                        );
+                    }
+                }
+            }
+            Node::Loop { item, sep } => {
+                let width = self.size().0;
+                let right = x + width;
+                let (item_width, _, _) = item.size();
+                let (sep_width, _, _) = sep.size();
+                let ly = y + loop_offset(item, sep);
+                path(out, format!("M{} {}h{}", x, y, ARC));
+                item.draw(out, x + ARC, y);
+                path(out, format!("M{} {}H{}", x + ARC + item_width, y, right));
+                // Back from the right end through the separator, centered
+                let sep_x = x + ARC + (width - 2 * ARC - sep_width) / 2;
+                path(
+                    out,
+                    format!(
+                        "M{} {}a{r} {r} 0 0 1 {r} {r}V{}a{r} {r} 0 0 1 -{r} {r}H{}",
+                        right - ARC,
+                        y,
+                        ly - ARC,
+                        sep_x + sep_width,
+                        r = ARC
+                    ),
+                );
+                sep.draw(out, sep_x, ly);
+                path(
+                    out,
+                    format!(
+                        "M{} {}H{}a{r} {r} 0 0 1 -{r} -{r}V{}a{r} {r} 0 0 1 {r} -{r}",
+                        sep_x,
+                        ly,
+                        x + ARC,
+                        y + ARC,
+                        r = ARC
+                    ),
+                );
+            }
+        }
+    }
+}
+
+fn box_width(text: &str) -> i32 {
+    text.chars().count() as i32 * CHAR_WIDTH + 2 * TEXT_PADDING
+}
+
+fn draw_box(out: &mut String, x: i32, y: i32, text: &str, class: &str, radius: i32) {
+    let width = box_width(text);
+    let _ = writeln!(
+        out,
+        "<rect class=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"/>",
+        class,
+        x,
+        y - BOX_HEIGHT / 2,
+        width,
+        BOX_HEIGHT,
+        radius
+    );
+    let _ = writeln!(
+        out,
+        "<text x=\"{}\" y=\"{}\">{}</text>",
+        x + width / 2,
+        y + 4,
+        escape(text)
+    );
+}
+
+/// How far below the main line each branch of a choice runs.
+fn branch_offsets(branches: &[Node]) -> Vec<i32> {
+    let mut offsets = vec![0];
+    let mut down = branches[0].size().2;
+    for (i, branch) in branches.iter().enumerate().skip(1) {
+        let (_, up, branch_down) = branch.size();
+        let offset = (down + ROW_GAP + up).max(offsets[i - 1] + 2 * ARC);
+        offsets.push(offset);
+        down = offset + branch_down;
+    }
+    offsets
+}
+
+/// How far below the main line the line back of a loop runs.
+fn loop_offset(item: &Node, sep: &Node) -> i32 {
+    (item.size().2 + ROW_GAP + sep.size().1).max(2 * ARC)
+}
+
+// ============================================================================
+// From rules
+// ============================================================================
+
+struct Builder<'a> {
+    grammar: &'a Grammar,
+    link: &'a dyn Fn(&str) -> String,
+}
+
+impl Builder<'_> {
+    fn rule(&self, rule: &Rule) -> Node {
+        let mut alts: Vec<Node> = rule.alts.iter().map(|a| self.seq(&a.terms)).collect();
+        if alts.len() == 1 {
+            alts.pop().unwrap()
+        } else {
+            Node::Choice(alts)
+        }
+    }
+
+    fn seq(&self, terms: &[Spanned<Term>]) -> Node {
+        let mut items: Vec<Node> = terms
+            .iter()
+            .filter(|t| **t != Term::Empty)
+            .map(|t| self.term(t))
+            .collect();
+        match items.len() {
+            0 => Node::Skip,
+            1 => items.pop().unwrap(),
+            _ => Node::Sequence(items),
+        }
+    }
+
+    fn group(&self, alts: &[Vec<Spanned<Term>>]) -> Node {
+        let mut branches: Vec<Node> = alts.iter().map(|terms| self.seq(terms)).collect();
+        if branches.len() == 1 {
+            branches.pop().unwrap()
+        } else {
+            Node::Choice(branches)
+        }
+    }
+
+    fn term(&self, term: &Term) -> Node {
+        match term {
+            Term::Symbol(s) => self.symbol(s),
+            // An application such as `comma_list<expr>` links to its rule
+            Term::Apply { name, .. } => Node::NonTerminal {
+                text: crate::format::term_text(term),
+                link: self
+                    .grammar
+                    .rules
+                    .iter()
+                    .any(|r| r.name == *name)
+                    .then(|| (self.link)(name)),
+            },
+            Term::Optional(s) => Node::optional(self.symbol(s)),
+            Term::ZeroOrMore(s) => Node::optional(Node::one_or_more(self.symbol(s), Node::Skip)),
+            Term::OneOrMore(s) => Node::one_or_more(self.symbol(s), Node::Skip),
+            Term::SeparatedBy { symbol, sep } => {
+                Node::one_or_more(self.symbol(symbol), self.symbol(sep))
+            }
+            Term::SeparatedTrailing { symbol, sep } => Node::Sequence(vec![
+                Node::one_or_more(self.symbol(symbol), self.symbol(sep)),
+                Node::optional(self.symbol(sep)),
+            ]),
+            Term::TerminatedBy { symbol, terminator } => Node::one_or_more(
+                Node::Sequence(vec![self.symbol(symbol), self.symbol(terminator)]),
+                Node::Skip,
+            ),
+            Term::ZeroOrMoreTerminatedBy { symbol, terminator } => {
+                Node::optional(Node::one_or_more(
+                    Node::Sequence(vec![self.symbol(symbol), self.symbol(terminator)]),
+                    Node::Skip,
+                ))
+            }
+            Term::Group(alts) => self.group(alts),
+            Term::OptionalGroup(alts) => Node::optional(self.group(alts)),
+            Term::ZeroOrMoreGroup(alts) => {
+                Node::optional(Node::one_or_more(self.group(alts), Node::Skip))
+            }
+            Term::OneOrMoreGroup(alts) => Node::one_or_more(self.group(alts), Node::Skip),
+            // Items in any order: a loop over a choice of them
+            Term::Permutation(items) => {
+                let branches = items
+                    .iter()
+                    .filter_map(|item| match &item.node {
+                        Term::Symbol(s)
+                        | Term::Optional(s)
+                        | Term::ZeroOrMore(s)
+                        | Term::OneOrMore(s) => Some(self.symbol(s)),
+                        _ => None,
+                    })
+                    .collect();
+                let required = items
+                    .iter()
+                    .any(|t| matches!(t.node, Term::Symbol(_) | Term::OneOrMore(_)));
+                let node = Node::one_or_more(Node::Choice(branches), Node::Skip);
+                if required { node } else { Node::optional(node) }
+            }
+            Term::Empty => Node::Skip,
+        }
+    }
+
+    fn symbol(&self, name: &str) -> Node {
+        if is_literal(name) {
+            return Node::Terminal(literal_text(name));
+        }
+        if self.grammar.terminals.iter().any(|t| t.name == name) {
+            return Node::Terminal(name.to_string());
+        }
+        // A parameter of a parameterized rule links nowhere
+        let link = self
+            .grammar
+            .rules
+            .iter()
+            .any(|r| r.name == name)
+            .then(|| (self.link)(name));
+        Node::NonTerminal {
+            text: name.to_string(),
+            link,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terminal(text: &str) -> Node {
+        Node::Terminal(text.to_string())
+    }
+
+    #[test]
+    fn test_layout() {
+        // A box is 8 pixels per character plus padding
+        assert_eq!(terminal("ab").size(), (36, 11, 11));
+        assert_eq!(
+            Node::Sequence(vec![terminal("ab"), terminal("c")]).size(),
+            (36 + 10 + 28, 11, 11)
+        );
+        // The bypass of an optional item runs on the main line
+        let optional = Node::optional(terminal("ab"));
+        assert_eq!(optional.size(), (36 + 40, 0, 21 + 11));
+        // The line back of a loop runs below the item and its separator
+        let list = Node::one_or_more(terminal("item"), terminal(","));
+        assert_eq!(list.size(), (52 + 20, 11, 11 + 10 + 22));
+        // Branches too close for their curves are spread out
+        let skips = Node::Choice(vec![Node::Skip, Node::Skip, Node::Skip]);
+        assert_eq!(
+            branch_offsets(match &skips {
+                Node::Choice(b) => b,
+                _ => unreachable!(),
+            }),
+            [0, 20, 40]
+        );
+    }
+
+    #[test]
+    fn test_rule_diagrams() {
+        let grammar = crate::parse_grammar(
+            r#"
+start stmts;
+terminals { IDENT, NUM }
+
+/// Statements, each ended by a semicolon.
+stmts = (stmt ; ';')* => stmts;
+stmt = IDENT '=' expr => assign
+     | "print" args? => print
+     | _ => empty;
+args = (expr % ',') => args;
+expr = NUM => num | IDENT => var | '(' expr ')' => paren;
+"#,
+        )
+        .unwrap();
+        let stmt = &grammar.rules[1];
+        let svg = rule_svg(&grammar, stmt);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains("<title>stmt</title>"));
+        assert!(svg.contains("<a href=\"expr.svg\">"));
+        assert!(svg.contains("<a href=\"args.svg\">"));
+        assert!(svg.contains(">print</text>"));
+        assert_eq!(svg.matches("<rect class=\"terminal\"").count(), 3);
+
+        let html = index_html(&grammar, "Statements & expressions");
+        assert!(html.contains("<title>Statements &amp; expressions</title>"));
+        for rule in ["stmts", "stmt", "args", "expr"] {
+            assert!(html.contains(&format!("<section id=\"{}\">", rule)));
+        }
+        assert!(html.contains("<a href=\"#stmt\">"));
+        assert!(html.contains("<p>Statements, each ended by a semicolon.</p>"));
+        // Standalone documents carry their own style; the page has one
+        assert_eq!(html.matches("<style>").count(), 1);
+    }
+}
@@ -0,0 +1,567 @@
+//! Printing grammars back as `.gzl` text.
+//!
+//! The layout is canonical: one declaration or terminal per line, and the
+//! alternatives of a rule on separate lines with their `|` aligned under
+//! the `=`. Comments are placed by their source spans: a trailing comment
+//! stays at the end of the line of the item before it, any other comment
+//! goes on its own line before the item after it. A comment inside an
+//! alternative goes at the end of its line.
+
+use std::fmt;
+
+use crate::Associativity;
+use crate::grammar::{Alt, AltPrec, Comment, Grammar, Span, Spanned, Term, TerminalDef};
+
+/// A line of output, with the source spans of the items on it.
+pub(crate) struct Line {
+    pub text: String,
+    pub spans: Vec<Span>,
+    /// Span of the item printed whole on this line; comments inside it are
+    /// kept on the line.
+    pub item: Option<Span>,
+    /// Separate this line from the previous one by a blank line.
+    pub blank_before: bool,
+}
+
+impl Line {
+    pub fn new(text: String, span: Option<Span>) -> Self {
+        Line {
+            text,
+            spans: span.into_iter().collect(),
+            item: None,
+            blank_before: false,
+        }
+    }
+
+    pub fn blank_before(mut self) -> Self {
+        self.blank_before = true;
+        self
+    }
+}
+
+impl Grammar {
+    /// Print the grammar as `.gzl` source, with its comments.
+    ///
+    /// The result parses back to the same grammar, apart from spans:
+    ///
+    /// ```
+    /// let grammar = gazelle::parse_grammar(
+    ///     "start s; terminals { A, B } s = A => a | s B => more; // recursive",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(
+    ///     grammar.to_gzl(),
+    ///     "start s;\n\
+    ///      \n\
+    ///      terminals {\n    A,\n    B\n}\n\
+    ///      \n\
+    ///      s = A => a\n  | s B => more;  // recursive\n"
+    /// );
+    /// ```
+    pub fn to_gzl(&self) -> String {
+        let mut lines: Vec<Line> = self
+            .attrs
+            .iter()
+            .map(|attr| Line::new(inner_attr(attr), None))
+            .collect();
+        if !self.start.is_empty() {
+            lines.push(Line::new(start_decl(self.starts()), None));
+        }
+        if self.expect_rr > 0 {
+            lines.push(Line::new(format!("expect {} rr;", self.expect_rr), None));
+        }
+        if self.expect_sr > 0 {
+            lines.push(Line::new(format!("expect {} sr;", self.expect_sr), None));
+        }
+        if self.split_tokens {
+            lines.push(Line::new("mode split;".to_string(), None));
+        }
+        let terminal_attrs: Vec<_> = self
+            .terminal_attrs
+            .iter()
+            .map(|attr| (attr.clone(), None))
+            .collect();
+        lines.extend(terminals_block(&terminal_attrs, &self.terminals, None));
+        for rule in &self.rules {
+            let head = rule_head(&rule.name, &rule.params);
+            let header = header_lines(&rule.doc, &rule.attrs, &rule.span);
+            let rule_lines = rule_lines(&head, "=", &rule.alts, header, rule.span.clone());
+            lines.extend(start_section(rule_lines));
+        }
+        render(lines, &self.comments, None)
+    }
+}
+
+impl fmt::Display for Grammar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_gzl())
+    }
+}
+
+/// Mark the first of `lines` as starting a new section.
+pub(crate) fn start_section(mut lines: Vec<Line>) -> Vec<Line> {
+    if let Some(first) = lines.first_mut() {
+        first.blank_before = true;
+    }
+    lines
+}
+
+pub(crate) fn start_decl<'a>(names: impl IntoIterator<Item = &'a String>) -> String {
+    let names: Vec<&str> = names.into_iter().map(String::as_str).collect();
+    format!("start {};", names.join(", "))
+}
+
+/// A string in the quotes of the grammar syntax.
+#[cfg(not(feature = "bootstrap"))] // only `import` paths are quoted
+pub(crate) fn quote(text: &str) -> String {
+    format!("{:?}", text)
+}
+
+/// An inner attribute, `#![attr]`.
+pub(crate) fn inner_attr(attr: &str) -> String {
+    format!("#![{}]", attr)
+}
+
+/// The `terminals { ... }` block with its inner attributes, or nothing if
+/// there are no terminals. `span` locates the block in the source.
+pub(crate) fn terminals_block(
+    attrs: &[(String, Option<Span>)],
+    terminals: &[TerminalDef],
+    span: Option<Span>,
+) -> Vec<Line> {
+    if terminals.is_empty() {
+        return Vec::new();
+    }
+    // The braces take the ends of the span, so comments stay on their side
+    let open = span.as_ref().map(|s| s.start..s.start);
+    let close = span.map(|s| s.end - 1..s.end);
+    let mut lines = vec![Line::new("terminals {".to_string(), open).blank_before()];
+    for (attr, span) in attrs {
+        lines.push(Line::new(format!("    {}", inner_attr(attr)), span.clone()));
+    }
+    for (i, t) in terminals.iter().enumerate() {
+        let comma = if i + 1 < terminals.len() { "," } else { "" };
+        lines.extend(doc_lines("    ", &t.doc, &t.span));
+        lines.push(Line::new(
+            format!("    {}{}", terminal(t), comma),
+            t.span.clone(),
+        ));
+    }
+    lines.push(Line::new("}".to_string(), close));
+    lines
+}
+
+fn terminal(t: &TerminalDef) -> String {
+    let mut out = String::new();
+    if t.is_prec {
+        out.push_str("prec ");
+    }
+    out.push_str(&t.name);
+    if t.has_type {
+        out.push_str(": _");
+    }
+    if let Some(pattern) = &t.pattern {
+        out.push_str(" = /");
+        // Escape slashes that would end the pattern early
+        let mut escaped = false;
+        for c in pattern.chars() {
+            if c == '/' && !escaped {
+                out.push('\\');
+            }
+            escaped = c == '\\' && !escaped;
+            out.push(c);
+        }
+        out.push('/');
+    }
+    out
+}
+
+pub(crate) fn rule_head(name: &str, params: &[String]) -> String {
+    if params.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}<{}>", name, params.join(", "))
+    }
+}
+
+/// `/// ...` lines at `indent`. They share the span of the item they
+/// document, so comments are placed before them.
+fn doc_lines(indent: &str, doc: &[String], span: &Option<Span>) -> Vec<Line> {
+    doc.iter()
+        .map(|line| Line::new(format!("{}///{}", indent, line), span.clone()))
+        .collect()
+}
+
+/// The doc comment and then the `#[...]` attributes of a rule.
+pub(crate) fn header_lines(doc: &[String], attrs: &[String], span: &Option<Span>) -> Vec<Line> {
+    let mut lines = doc_lines("", doc, span);
+    lines.extend(
+        attrs
+            .iter()
+            .map(|attr| Line::new(format!("#[{}]", attr), span.clone())),
+    );
+    lines
+}
+
+/// A rule `header head op alts;`, one alternative per line, with each `|`
+/// under the first character of `op`. Documented alternatives have their
+/// doc comments before the `|`, or after `op` for the first one.
+pub(crate) fn rule_lines(
+    head: &str,
+    op: &str,
+    alts: &[Alt],
+    header: Vec<Line>,
+    span: Option<Span>,
+) -> Vec<Line> {
+    let indent = " ".repeat(head.len() + 1);
+    let mut lines = header;
+    for (i, alt) in alts.iter().enumerate() {
+        let text = match (i, alt.doc.split_first()) {
+            (0, None) => format!("{} {} {}", head, op, alt_text(alt)),
+            (0, Some((first, rest))) => {
+                let indent = " ".repeat(head.len() + op.len() + 2);
+                lines.push(Line::new(
+                    format!("{} {} ///{}", head, op, first),
+                    span.clone(),
+                ));
+                lines.extend(doc_lines(&indent, rest, &alt.span));
+                format!("{}{}", indent, alt_text(alt))
+            }
+            _ => {
+                lines.extend(doc_lines(&indent, &alt.doc, &alt.span));
+                format!("{}| {}", indent, alt_text(alt))
+            }
+        };
+        let mut line = Line::new(text, alt.span.clone());
+        line.item = alt.span.clone();
+        if i == 0 {
+            line.spans.splice(0..0, span.clone());
+        }
+        lines.push(line);
+    }
+    if alts.is_empty() {
+        lines.push(Line::new(format!("{} {} _", head, op), span));
+    }
+    if let Some(last) = lines.last_mut() {
+        last.text.push(';');
+    }
+    lines
+}
+
+fn alt_text(alt: &Alt) -> String {
+    let mut parts: Vec<String> = alt
+        .terms
+        .iter()
+        .enumerate()
+        .map(|(i, term)| match alt.labels.get(i) {
+            Some(Some(label)) => format!("{}:{}", label, term_text(term)),
+            _ => term_text(term),
+        })
+        .collect();
+    if parts.is_empty() {
+        parts.push("_".to_string());
+    }
+    if !alt.name.is_empty() {
+        parts.push(format!("=> {}", alt.name));
+    }
+    match &alt.prec {
+        Some(AltPrec::Symbol(symbol)) => parts.push(format!("prec({})", symbol)),
+        Some(AltPrec::Fixed(prec)) => {
+            let assoc = match prec.associativity() {
+                Associativity::Left => "left",
+                Associativity::Right => "right",
+                Associativity::NonAssoc => "nonassoc",
+            };
+            parts.push(format!("prec({}, {})", prec.level(), assoc));
+        }
+        None => {}
+    }
+    parts.join(" ")
+}
+
+/// The terms of an alternative as written, `_` if there are none.
+pub(crate) fn production(terms: &[Spanned<Term>]) -> String {
+    if terms.is_empty() {
+        return "_".to_string();
+    }
+    terms
+        .iter()
+        .map(|t| term_text(t))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A single term as written.
+pub(crate) fn term_text(term: &Term) -> String {
+    match term {
+        Term::Symbol(s) => s.clone(),
+        Term::Apply { name, args } => {
+            let args: Vec<String> = args.iter().map(term_text).collect();
+            format!("{}<{}>", name, args.join(", "))
+        }
+        Term::Optional(s) => format!("{}?", s),
+        Term::ZeroOrMore(s) => format!("{}*", s),
+        Term::OneOrMore(s) => format!("{}+", s),
+        Term::SeparatedBy { symbol, sep } => format!("({} % {})", symbol, sep),
+        Term::SeparatedTrailing { symbol, sep } => format!("({} %% {})", symbol, sep),
+        Term::TerminatedBy { symbol, terminator } => format!("({} ; {})", symbol, terminator),
+        Term::ZeroOrMoreTerminatedBy { symbol, terminator } => {
+            format!("({} ; {})*", symbol, terminator)
+        }
+        Term::Group(alts) => group_text(alts),
+        // `list<T>?` needs no parentheses
+        Term::OptionalGroup(alts) | Term::ZeroOrMoreGroup(alts) | Term::OneOrMoreGroup(alts)
+            if matches!(alts.as_slice(), [seq] if matches!(seq.as_slice(), [t] if matches!(t.node, Term::Apply { .. }))) =>
+        {
+            let modifier = match term {
+                Term::OptionalGroup(_) => '?',
+                Term::ZeroOrMoreGroup(_) => '*',
+                _ => '+',
+            };
+            format!("{}{}", term_text(&alts[0][0]), modifier)
+        }
+        Term::OptionalGroup(alts) => format!("{}?", group_text(alts)),
+        Term::ZeroOrMoreGroup(alts) => format!("{}*", group_text(alts)),
+        Term::OneOrMoreGroup(alts) => format!("{}+", group_text(alts)),
+        Term::Permutation(items) => {
+            let items: Vec<String> = items.iter().map(|t| term_text(t)).collect();
+            format!("{{ {} }}", items.join(" "))
+        }
+        Term::Empty => "_".to_string(),
+    }
+}
+
+fn group_text(alts: &[Vec<Spanned<Term>>]) -> String {
+    let seqs: Vec<String> = alts
+        .iter()
+        .map(|seq| match seq.as_slice() {
+            [] => "_".to_string(),
+            terms => production(terms),
+        })
+        .collect();
+    format!("({})", seqs.join(" | "))
+}
+
+/// Join `lines` into text, placing `comments` by their spans. With the
+/// `source` the spans point into, single blank lines of the source are kept
+/// too.
+pub(crate) fn render(lines: Vec<Line>, comments: &[Comment], source: Option<&str>) -> String {
+    let mut leading: Vec<Vec<&Comment>> = lines.iter().map(|_| Vec::new()).collect();
+    let mut trailing: Vec<Vec<&Comment>> = lines.iter().map(|_| Vec::new()).collect();
+    let mut rest = Vec::new();
+    for comment in comments {
+        let within = lines.iter().rposition(|line| {
+            line.item
+                .as_ref()
+                .is_some_and(|s| s.start <= comment.span.start && comment.span.end <= s.end)
+        });
+        if let Some(i) = within {
+            trailing[i].push(comment);
+            continue;
+        }
+        // A trailing comment belongs to the item it follows, any other
+        // comment to the item after it
+        let before = lines
+            .iter()
+            .enumerate()
+            .flat_map(|(i, line)| line.spans.iter().map(move |s| (i, s)))
+            .filter(|(_, s)| s.end <= comment.span.start)
+            .max_by_key(|(_, s)| s.end);
+        let after = lines
+            .iter()
+            .enumerate()
+            .flat_map(|(i, line)| line.spans.iter().map(move |s| (i, s)))
+            .filter(|(_, s)| s.start >= comment.span.end)
+            .min_by_key(|(_, s)| s.start);
+        match (comment.trailing, before, after) {
+            (true, Some((i, _)), _) => trailing[i].push(comment),
+            (_, _, Some((i, _))) => leading[i].push(comment),
+            _ => rest.push(comment),
+        }
+    }
+
+    let mut writer = Writer {
+        out: String::new(),
+        source,
+        last_end: None,
+        blank: false,
+    };
+    for (i, line) in lines.iter().enumerate() {
+        writer.blank |= line.blank_before;
+        let indent = &line.text[..line.text.len() - line.text.trim_start().len()];
+        for comment in &leading[i] {
+            let text = format!("{}{}", indent, comment.text);
+            writer.line(&text, Some(comment.span.clone()));
+        }
+        let mut text = line.text.clone();
+        let mut span = line
+            .spans
+            .iter()
+            .map(|s| s.start)
+            .min()
+            .zip(line.spans.iter().map(|s| s.end).max());
+        for comment in &trailing[i] {
+            text.push_str("  ");
+            text.push_str(&comment.text);
+            span = span.map(|(start, end)| (start, end.max(comment.span.end)));
+        }
+        writer.line(&text, span.map(|(start, end)| start..end));
+    }
+    for comment in rest {
+        writer.line(&comment.text, Some(comment.span.clone()));
+    }
+    writer.out
+}
+
+/// Output text, remembering where the last line written ended in the
+/// source (if it came from the source).
+struct Writer<'a> {
+    out: String,
+    source: Option<&'a str>,
+    last_end: Option<usize>,
+    /// Write a blank line before the next line.
+    blank: bool,
+}
+
+impl Writer<'_> {
+    fn line(&mut self, text: &str, span: Option<Span>) {
+        if let (Some(source), Some(last), Some(span)) = (self.source, self.last_end, &span) {
+            self.blank |= has_blank_line(source.get(last..span.start).unwrap_or(""));
+        }
+        if self.blank && !self.out.is_empty() {
+            self.out.push('\n');
+        }
+        self.blank = false;
+        self.out.push_str(text);
+        self.out.push('\n');
+        self.last_end = span.map(|span| span.end);
+    }
+}
+
+/// Whether the text between two items contains an empty line.
+fn has_blank_line(gap: &str) -> bool {
+    let pieces: Vec<&str> = gap.split('\n').collect();
+    pieces.len() > 2
+        && pieces[1..pieces.len() - 1]
+            .iter()
+            .any(|p| p.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::grammar::{Alt, AltPrec, Grammar, Rule, Term, TerminalDef};
+    use crate::meta::parse_grammar;
+
+    #[test]
+    fn test_to_gzl() {
+        let src = r#"
+            start expr;
+            expect 1 sr;
+            mode split;
+            terminals { prec OP: _, NUM: _ = /[0-9]+/, LPAREN, RPAREN, COMMA }
+            expr = lhs:expr op:OP rhs:expr => binop
+                 | '-' expr => neg prec(9, right)
+                 | expr OP expr? => partial prec(OP)
+                 | (NUM % COMMA) (LPAREN | _)* (expr expr)+ => seq
+                 | {LPAREN? NUM+ COMMA* RPAREN} => perm
+                 | (NUM%%COMMA) (expr;OP) (NUM ; COMMA)* => lists
+                 | list<NUM>? "end" => end;
+            list<T> = T+ => list;
+        "#;
+        let expected = r#"start expr;
+expect 1 sr;
+mode split;
+
+terminals {
+    prec OP: _,
+    NUM: _ = /[0-9]+/,
+    LPAREN,
+    RPAREN,
+    COMMA
+}
+
+expr = lhs:expr op:OP rhs:expr => binop
+     | '-' expr => neg prec(9, right)
+     | expr OP expr? => partial prec(OP)
+     | (NUM % COMMA) (LPAREN | _)* (expr expr)+ => seq
+     | { LPAREN? NUM+ COMMA* RPAREN } => perm
+     | (NUM %% COMMA) (expr ; OP) (NUM ; COMMA)* => lists
+     | list<NUM>? "end" => end;
+
+list<T> = T+ => list;
+"#;
+        let grammar = parse_grammar(src).unwrap();
+        assert_eq!(grammar.to_gzl(), expected);
+        assert_eq!(grammar.to_string(), expected);
+    }
+
+    #[test]
+    fn test_built_grammar() {
+        let grammar = Grammar {
+            start: "s".into(),
+            extra_starts: Vec::new(),
+            expect_rr: 0,
+            expect_sr: 0,
+            split_tokens: false,
+            terminals: vec![TerminalDef {
+                name: "PATH".into(),
+                has_type: true,
+                is_prec: false,
+                pattern: Some("[a-z]+/[a-z]+".into()),
+                doc: vec![" A path.".into()],
+                span: None,
+            }],
+            rules: vec![Rule {
+                name: "s".into(),
+                params: vec![],
+                alts: vec![Alt {
+                    terms: vec![],
+                    labels: vec![],
+                    name: "empty".into(),
+                    prec: Some(AltPrec::Fixed(crate::Precedence::NonAssoc(3))),
+                    doc: vec![" Nothing.".into()],
+                    span: None,
+                }],
+                doc: vec![],
+                attrs: vec!["derive(Clone)".into()],
+                span: None,
+            }],
+            terminal_attrs: vec!["derive(Hash)".into()],
+            ..Default::default()
+        };
+        let gzl = grammar.to_gzl();
+        assert_eq!(
+            gzl,
+            "start s;\n\nterminals {\n    #![derive(Hash)]\n    /// A path.\n    PATH: _ = /[a-z]+\\/[a-z]+/\n}\n\n#[derive(Clone)]\ns = /// Nothing.\n    _ => empty prec(3, nonassoc);\n"
+        );
+        let parsed = parse_grammar(&gzl).unwrap();
+        assert_eq!(parsed.rules[0].alts[0].terms, [Term::Empty]);
+        assert_eq!(parsed.rules[0].alts[0].doc, [" Nothing."]);
+        assert_eq!(parsed.rules[0].attrs, ["derive(Clone)"]);
+        assert_eq!(parsed.terminal_attrs, ["derive(Hash)"]);
+        assert_eq!(
+            parsed.terminals[0].pattern.as_deref(),
+            Some("[a-z]+\\/[a-z]+")
+        );
+    }
+
+    #[test]
+    fn test_comment_in_alternative() {
+        let src = "start s; terminals { A, B } s = A /* mid */ B => ab | A // mid\n B => b;";
+        let grammar = parse_grammar(src).unwrap();
+        let expected = r#"start s;
+
+terminals {
+    A,
+    B
+}
+
+s = A B => ab  /* mid */
+  | A B => b;  // mid
+"#;
+        assert_eq!(grammar.to_gzl(), expected);
+        // Comments take no part in comparisons
+        assert_eq!(parse_grammar(expected).unwrap(), grammar);
+        assert_ne!(parse_grammar(expected).unwrap().comments, grammar.comments);
+    }
+}
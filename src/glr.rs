@@ -0,0 +1,841 @@
+//! Generalized LR parsing for ambiguous and non-LR(1) grammars.
+//!
+//! [`GlrParser`] runs the same tables as [`Parser`](crate::Parser), but where
+//! the LR(1) construction had a conflict it forks the stack and follows every
+//! alternative. Stacks live in a graph-structured stack (GSS): forks share
+//! their common prefix, and stacks that reach the same state at the same input
+//! position are merged. The result is a shared packed parse forest ([`Forest`])
+//! holding every derivation of the input.
+//!
+//! Build the tables with [`CompiledTable::build_glr`](crate::CompiledTable::build_glr)
+//! so the actions dropped by conflict resolution are kept:
+//!
+//! ```rust
+//! use gazelle::glr::GlrParser;
+//! use gazelle::{CompiledTable, Token, parse_grammar};
+//!
+//! let grammar = parse_grammar(r#"
+//!     start expr;
+//!     terminals { NUM, PLUS }
+//!     expr = expr PLUS expr => add | NUM => num;
+//! "#).unwrap();
+//! let compiled = CompiledTable::build_glr(&grammar).unwrap();
+//! let num = Token::new(compiled.symbol_id("NUM").unwrap());
+//! let plus = Token::new(compiled.symbol_id("PLUS").unwrap());
+//!
+//! let mut parser = GlrParser::new(compiled.table());
+//! for tok in [num, plus, num, plus, num] {
+//!     parser.push(tok).unwrap();
+//! }
+//! let forest = parser.finish().map_err(|(_, e)| e).unwrap();
+//! assert!(forest.is_ambiguous());
+//! assert_eq!(forest.trees().len(), 2);
+//! ```
+//!
+//! `prec` terminals are still resolved by their runtime [`Precedence`], so
+//! operator grammars only fork where the grammar itself is ambiguous.
+//! Grammars with cyclic derivations (`a =>+ a`) are not supported.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::grammar::SymbolId;
+use crate::runtime::{
+    Cst, ParseError, ParseTable, ParserOp, Precedence, PrecedenceGroups, RulePrec, Token,
+    prec_reduces,
+};
+
+/// A node in a [`Forest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ForestNode {
+    /// A terminal leaf.
+    Leaf {
+        /// The terminal's symbol ID.
+        symbol: SymbolId,
+        /// Token index (number of tokens pushed before it).
+        token_index: usize,
+    },
+    /// A non-terminal spanning the tokens `start..end`, with one packed
+    /// alternative per distinct derivation.
+    Symbol {
+        /// The non-terminal's symbol ID.
+        symbol: SymbolId,
+        /// First token index covered.
+        start: usize,
+        /// One past the last token index covered.
+        end: usize,
+        /// The derivations of this symbol over the span.
+        packed: Vec<Packed>,
+    },
+}
+
+/// One derivation of a [`ForestNode::Symbol`]: the rule used and its children.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Packed {
+    /// The rule index that produced this derivation.
+    pub rule: usize,
+    /// Child node IDs, one per RHS symbol.
+    pub children: Vec<usize>,
+}
+
+/// A shared packed parse forest built by [`GlrParser`].
+///
+/// Nodes are addressed by index. Sub-derivations shared between parses are
+/// stored once, so the forest stays polynomial in the input length even when
+/// the number of trees is exponential.
+#[derive(Debug, Clone)]
+pub struct Forest {
+    nodes: Vec<ForestNode>,
+    root: usize,
+}
+
+impl Forest {
+    /// The node ID of the start symbol spanning the whole input.
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// Get a node by ID.
+    pub fn node(&self, id: usize) -> &ForestNode {
+        &self.nodes[id]
+    }
+
+    /// All nodes, indexed by ID. Includes nodes that are not reachable from the root.
+    pub fn nodes(&self) -> &[ForestNode] {
+        &self.nodes
+    }
+
+    /// Returns true if any node reachable from the root has more than one derivation.
+    pub fn is_ambiguous(&self) -> bool {
+        let mut seen = vec![false; self.nodes.len()];
+        let mut work = vec![self.root];
+        while let Some(id) = work.pop() {
+            if std::mem::replace(&mut seen[id], true) {
+                continue;
+            }
+            if let ForestNode::Symbol { packed, .. } = &self.nodes[id] {
+                if packed.len() > 1 {
+                    return true;
+                }
+                work.extend(packed.iter().flat_map(|p| p.children.iter().copied()));
+            }
+        }
+        false
+    }
+
+    /// Expand the forest into every parse tree it contains.
+    ///
+    /// The number of trees can grow exponentially with the input; use
+    /// [`is_ambiguous`](Self::is_ambiguous) or walk the nodes directly for
+    /// highly ambiguous input.
+    pub fn trees(&self) -> Vec<Cst> {
+        self.expand(self.root)
+    }
+
+    fn expand(&self, id: usize) -> Vec<Cst> {
+        match &self.nodes[id] {
+            &ForestNode::Leaf {
+                symbol,
+                token_index,
+            } => vec![Cst::Leaf {
+                symbol,
+                token_index,
+            }],
+            ForestNode::Symbol { packed, .. } => {
+                let mut trees = Vec::new();
+                for p in packed {
+                    // Cartesian product of the children's expansions.
+                    let mut partial: Vec<Vec<Cst>> = vec![Vec::new()];
+                    for &child in &p.children {
+                        let options = self.expand(child);
+                        partial = partial
+                            .into_iter()
+                            .flat_map(|prefix| {
+                                options.iter().map(move |opt| {
+                                    let mut next = prefix.clone();
+                                    next.push(opt.clone());
+                                    next
+                                })
+                            })
+                            .collect();
+                    }
+                    trees.extend(partial.into_iter().map(|children| Cst::Node {
+                        rule: p.rule,
+                        children,
+                    }));
+                }
+                trees
+            }
+        }
+    }
+}
+
+/// A vertex of the graph-structured stack.
+#[derive(Debug, Clone)]
+struct Vertex {
+    state: usize,
+    prec: Option<Precedence>,
+    /// Number of tokens shifted when this vertex was created.
+    position: usize,
+    /// Edges towards the bottom of the stack: (target vertex, forest node).
+    links: Vec<(usize, usize)>,
+}
+
+/// A GLR parser that builds a [`Forest`] of all parses.
+///
+/// Mirrors the `push`/`finish` pattern of [`CstParser`](crate::CstParser).
+#[derive(Clone)]
+pub struct GlrParser<'a> {
+    table: ParseTable<'a>,
+    vertices: Vec<Vertex>,
+    /// Vertices at the current input position (the tops of all live stacks).
+    frontier: Vec<usize>,
+    nodes: Vec<ForestNode>,
+    /// Symbol nodes by (symbol, start, end), so equal spans share a node.
+    symbol_nodes: HashMap<(SymbolId, usize, usize), usize>,
+    token_count: usize,
+    accepted: Option<usize>,
+    /// The registry that orders precedence groups.
+    groups: Option<Arc<PrecedenceGroups>>,
+}
+
+impl<'a> GlrParser<'a> {
+    /// Create a new GLR parser with the given parse table.
+    pub fn new(table: ParseTable<'a>) -> Self {
+        GlrParser {
+            table,
+            vertices: vec![Vertex {
+                state: 0,
+                prec: None,
+                position: 0,
+                links: Vec::new(),
+            }],
+            frontier: vec![0],
+            nodes: Vec::new(),
+            symbol_nodes: HashMap::new(),
+            token_count: 0,
+            accepted: None,
+            groups: None,
+        }
+    }
+
+    /// Compare precedence groups by `groups` (see
+    /// [`Parser::set_precedence_groups`](crate::Parser::set_precedence_groups)).
+    pub fn set_precedence_groups(&mut self, groups: Arc<PrecedenceGroups>) {
+        self.groups = Some(groups);
+    }
+
+    /// Create a GLR parser for one entry point of a grammar with several
+    /// start symbols (see [`Parser::for_entry`](crate::Parser::for_entry)).
+    pub fn for_entry(table: ParseTable<'a>, entry: SymbolId) -> Self {
+        let mut parser = Self::new(table);
+        let ParserOp::Shift(state) = table.action(0, entry) else {
+            panic!("symbol {} is not an entry point marker", entry.0);
+        };
+        // The marker gets a leaf so the accept rule's path is complete; it
+        // never appears under the root.
+        parser.nodes.push(ForestNode::Leaf {
+            symbol: entry,
+            token_index: 0,
+        });
+        parser.vertices.push(Vertex {
+            state,
+            prec: None,
+            position: 0,
+            links: vec![(0, 0)],
+        });
+        parser.frontier = vec![1];
+        parser
+    }
+
+    /// Get the count of tokens shifted so far.
+    pub fn token_count(&self) -> usize {
+        self.token_count
+    }
+
+    /// Number of live stacks (distinct stack tops) at the current position.
+    pub fn num_stacks(&self) -> usize {
+        self.frontier.len()
+    }
+
+    /// Push a token, performing all reductions on every live stack first.
+    ///
+    /// Returns an error if no stack can shift the token. The reductions made
+    /// for it are rolled back, so the parser is unchanged and a different
+    /// token may be pushed.
+    pub fn push(&mut self, token: Token) -> Result<(), ParseError> {
+        let checkpoint = self.checkpoint();
+        self.reduce_all(Some(token));
+
+        let leaf = self.nodes.len();
+        let mut next: Vec<usize> = Vec::new();
+        for &v in &self.frontier {
+            let vertex = &self.vertices[v];
+            let target = match self.table.action(vertex.state, token.terminal) {
+                ParserOp::Shift(s) => s,
+                ParserOp::ShiftOrReduce {
+                    shift_state,
+                    reduce_rule,
+                } if self.reduce_precs(v, reduce_rule).into_iter().any(|prec| {
+                    prec_reduces(prec, token.prec, self.groups.as_deref()) == Ok(false)
+                }) =>
+                {
+                    shift_state
+                }
+                _ => continue,
+            };
+            let prec = token.prec.or(vertex.prec);
+            match next
+                .iter()
+                .find(|&&w| self.vertices[w].state == target && self.vertices[w].prec == prec)
+            {
+                Some(&w) => self.vertices[w].links.push((v, leaf)),
+                None => {
+                    next.push(self.vertices.len());
+                    self.vertices.push(Vertex {
+                        state: target,
+                        prec,
+                        position: self.token_count + 1,
+                        links: vec![(v, leaf)],
+                    });
+                }
+            }
+        }
+
+        if next.is_empty() {
+            self.rollback(checkpoint);
+            return Err(ParseError::new(token.terminal));
+        }
+        self.nodes.push(ForestNode::Leaf {
+            symbol: token.terminal,
+            token_index: self.token_count,
+        });
+        self.frontier = next;
+        self.token_count += 1;
+        Ok(())
+    }
+
+    /// Finish parsing and return the forest of all parses.
+    #[allow(clippy::result_large_err)]
+    pub fn finish(mut self) -> Result<Forest, (Self, ParseError)> {
+        self.reduce_all(None);
+        match self.accepted {
+            Some(root) => Ok(Forest {
+                nodes: self.nodes,
+                root,
+            }),
+            None => Err((self, ParseError::new(SymbolId::EOF))),
+        }
+    }
+
+    /// The precedences a reduction at vertex `v` competes with, as in
+    /// [`Parser`](crate::Parser). A `prec(SYM)` position can differ between
+    /// the paths below `v`, so it yields one precedence per distinct value.
+    fn reduce_precs(&self, v: usize, rule: usize) -> Vec<Option<Precedence>> {
+        match self.table.rule_prec(rule) {
+            None => vec![self.vertices[v].prec],
+            Some(RulePrec::Fixed(prec)) => vec![Some(prec)],
+            Some(RulePrec::Position(pos)) => {
+                let len = self.table.rule_info(rule).1;
+                let mut precs = Vec::new();
+                for path in self.paths(v, len, None) {
+                    let prec = self.path_prec(&path, len, pos);
+                    if !precs.contains(&prec) {
+                        precs.push(prec);
+                    }
+                }
+                precs
+            }
+        }
+    }
+
+    /// The precedence of the symbol at `pos` of a rule of length `len`,
+    /// read from the vertices of one reduction path.
+    fn path_prec(&self, path: &Path, len: usize, pos: u8) -> Option<Precedence> {
+        self.vertices[path.vertices[len - 1 - pos as usize]].prec
+    }
+
+    /// Rules to reduce at a vertex for the given lookahead. The flag is set
+    /// when each path must be checked against the lookahead's precedence.
+    fn reductions(&self, v: usize, lookahead: Option<Token>) -> Vec<(usize, bool)> {
+        let terminal = lookahead.map(|t| t.terminal).unwrap_or(SymbolId::EOF);
+        let vertex = &self.vertices[v];
+        let mut rules = Vec::new();
+        match self.table.action(vertex.state, terminal) {
+            ParserOp::Reduce(rule) => rules.push((rule, false)),
+            ParserOp::ShiftOrReduce { reduce_rule, .. }
+                if self.reduce_precs(v, reduce_rule).into_iter().any(|prec| {
+                    prec_reduces(prec, lookahead.and_then(|t| t.prec), self.groups.as_deref())
+                        == Ok(true)
+                }) =>
+            {
+                let per_path = matches!(
+                    self.table.rule_prec(reduce_rule),
+                    Some(RulePrec::Position(_))
+                );
+                rules.push((reduce_rule, per_path))
+            }
+            _ => {}
+        }
+        rules.extend(
+            self.table
+                .conflict_reduces(vertex.state, terminal)
+                .map(|rule| (rule, false)),
+        );
+        rules
+    }
+
+    /// Perform every reduction reachable from the frontier, growing the
+    /// frontier as new stack tops appear.
+    fn reduce_all(&mut self, lookahead: Option<Token>) {
+        // Work items: (vertex, rule, check the path's precedence, only paths
+        // through this edge).
+        let mut work: Vec<(usize, usize, bool, Option<Edge>)> = Vec::new();
+        for &v in &self.frontier {
+            for (rule, per_path) in self.reductions(v, lookahead) {
+                work.push((v, rule, per_path, None));
+            }
+        }
+
+        while let Some((v, rule, per_path, through)) = work.pop() {
+            let (lhs, len) = self.table.rule_info(rule);
+            for path in self.paths(v, len, through) {
+                if per_path
+                    && let Some(RulePrec::Position(pos)) = self.table.rule_prec(rule)
+                    && prec_reduces(
+                        self.path_prec(&path, len, pos),
+                        lookahead.and_then(|t| t.prec),
+                        self.groups.as_deref(),
+                    ) != Ok(true)
+                {
+                    continue;
+                }
+                let Path {
+                    anchor, children, ..
+                } = path;
+                if self.table.is_accept(rule) {
+                    if lookahead.is_none() {
+                        self.accepted = children.last().copied();
+                    }
+                    continue;
+                }
+                let Some(target) = self.table.goto(self.vertices[anchor].state, lhs) else {
+                    continue;
+                };
+                let prec = match len {
+                    0 => None,
+                    1 => self.vertices[v].prec,
+                    _ => self.vertices[anchor].prec,
+                };
+                let node = self.symbol_node(lhs, self.vertices[anchor].position);
+                if let ForestNode::Symbol { packed, .. } = &mut self.nodes[node]
+                    && !packed
+                        .iter()
+                        .any(|p| p.rule == rule && p.children == children)
+                {
+                    packed.push(Packed { rule, children });
+                }
+
+                let existing =
+                    self.frontier.iter().copied().find(|&w| {
+                        self.vertices[w].state == target && self.vertices[w].prec == prec
+                    });
+                match existing {
+                    Some(w) => {
+                        if self.vertices[w].links.contains(&(anchor, node)) {
+                            continue;
+                        }
+                        // A new edge into an existing stack top. Redo the
+                        // non-empty reductions through that edge, both at `w`
+                        // and at the tops stacked on `w` by empty reductions
+                        // (Farshi's correction for right-nullable rules).
+                        self.vertices[w].links.push((anchor, node));
+                        let edge = (w, self.vertices[w].links.len() - 1);
+                        for &x in &self.frontier {
+                            for (rule, per_path) in self.reductions(x, lookahead) {
+                                if self.table.rule_info(rule).1 > 0 {
+                                    work.push((x, rule, per_path, Some(edge)));
+                                }
+                            }
+                        }
+                    }
+                    None => {
+                        let w = self.vertices.len();
+                        self.vertices.push(Vertex {
+                            state: target,
+                            prec,
+                            position: self.token_count,
+                            links: vec![(anchor, node)],
+                        });
+                        self.frontier.push(w);
+                        for (rule, per_path) in self.reductions(w, lookahead) {
+                            work.push((w, rule, per_path, None));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Record the parser's size before a push, for [`rollback`](Self::rollback).
+    fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            vertices: self.vertices.len(),
+            nodes: self.nodes.len(),
+            frontier: self
+                .frontier
+                .iter()
+                .map(|&v| (v, self.vertices[v].links.len()))
+                .collect(),
+        }
+    }
+
+    /// Undo the reductions made since `checkpoint`. They only create vertices
+    /// and symbol nodes at the current position, and add edges to the tops
+    /// of the frontier, so truncating those restores the parser.
+    fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.vertices.truncate(checkpoint.vertices);
+        self.nodes.truncate(checkpoint.nodes);
+        self.symbol_nodes
+            .retain(|_, &mut node| node < checkpoint.nodes);
+        self.frontier = checkpoint
+            .frontier
+            .into_iter()
+            .map(|(v, links)| {
+                self.vertices[v].links.truncate(links);
+                v
+            })
+            .collect();
+    }
+
+    /// Get or create the symbol node for `symbol` spanning `start..token_count`.
+    fn symbol_node(&mut self, symbol: SymbolId, start: usize) -> usize {
+        let end = self.token_count;
+        *self
+            .symbol_nodes
+            .entry((symbol, start, end))
+            .or_insert_with(|| {
+                self.nodes.push(ForestNode::Symbol {
+                    symbol,
+                    start,
+                    end,
+                    packed: Vec::new(),
+                });
+                self.nodes.len() - 1
+            })
+    }
+
+    /// All paths of `len` edges down from `v`. If `through` is set, only
+    /// paths that use that edge are returned.
+    fn paths(&self, v: usize, len: usize, through: Option<Edge>) -> Vec<Path> {
+        let mut result = Vec::new();
+        let mut stack = vec![(vec![v], Vec::new(), through.is_none())];
+        while let Some((vertices, nodes, used)) = stack.pop() {
+            let vertex = *vertices.last().unwrap();
+            if nodes.len() == len {
+                if used {
+                    let mut children: Vec<usize> = nodes;
+                    children.reverse();
+                    result.push(Path {
+                        anchor: vertex,
+                        children,
+                        vertices,
+                    });
+                }
+                continue;
+            }
+            for (i, &(target, node)) in self.vertices[vertex].links.iter().enumerate() {
+                let mut vertices = vertices.clone();
+                vertices.push(target);
+                let mut nodes = nodes.clone();
+                nodes.push(node);
+                stack.push((vertices, nodes, used || through == Some((vertex, i))));
+            }
+        }
+        result
+    }
+}
+
+/// An edge of the graph-structured stack: (vertex, link index).
+type Edge = (usize, usize);
+
+/// A reduction path down the graph-structured stack.
+struct Path {
+    /// The vertex the path ends at, below the reduced symbols.
+    anchor: usize,
+    /// Forest nodes of the reduced symbols, in left-to-right order.
+    children: Vec<usize>,
+    /// The vertices along the path, from the top down to `anchor`.
+    vertices: Vec<usize>,
+}
+
+/// The parser's size before a push, so a failed push can be undone.
+struct Checkpoint {
+    vertices: usize,
+    nodes: usize,
+    /// Frontier vertices with their number of links.
+    frontier: Vec<(usize, usize)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::parse_grammar;
+    use crate::runtime::CstParser;
+    use crate::table::CompiledTable;
+
+    fn compile(src: &str) -> CompiledTable {
+        CompiledTable::build_glr(&parse_grammar(src).unwrap()).unwrap()
+    }
+
+    fn tokens(compiled: &CompiledTable, names: &[&str]) -> Vec<Token> {
+        names
+            .iter()
+            .map(|n| Token::new(compiled.symbol_id(n).unwrap()))
+            .collect()
+    }
+
+    fn parse(compiled: &CompiledTable, names: &[&str]) -> Result<Forest, ParseError> {
+        let mut parser = GlrParser::new(compiled.table());
+        for tok in tokens(compiled, names) {
+            parser.push(tok)?;
+        }
+        parser.finish().map_err(|(_, e)| e)
+    }
+
+    #[test]
+    fn test_unambiguous_matches_lr() {
+        let compiled = compile(
+            r#"
+            start expr;
+            terminals { NUM, PLUS, STAR }
+            expr = expr PLUS term => add | term => term;
+            term = term STAR NUM => mul | NUM => num;
+        "#,
+        );
+        assert!(!compiled.has_conflicts());
+        let input = ["NUM", "PLUS", "NUM", "STAR", "NUM"];
+
+        let forest = parse(&compiled, &input).unwrap();
+        assert!(!forest.is_ambiguous());
+
+        let mut cst = CstParser::new(compiled.table());
+        for tok in tokens(&compiled, &input) {
+            cst.push(tok).unwrap();
+        }
+        let tree = cst.finish().map_err(|(_, e)| e).unwrap();
+        assert_eq!(forest.trees(), vec![tree]);
+    }
+
+    #[test]
+    fn test_ambiguous_shift_reduce() {
+        let compiled = compile(
+            r#"
+            start expr;
+            terminals { NUM, PLUS }
+            expr = expr PLUS expr => add | NUM => num;
+        "#,
+        );
+        assert!(compiled.has_conflicts());
+
+        let forest = parse(&compiled, &["NUM", "PLUS", "NUM", "PLUS", "NUM"]).unwrap();
+        assert!(forest.is_ambiguous());
+        assert_eq!(forest.trees().len(), 2);
+
+        // Catalan number of bracketings for four operands.
+        let forest = parse(
+            &compiled,
+            &["NUM", "PLUS", "NUM", "PLUS", "NUM", "PLUS", "NUM"],
+        )
+        .unwrap();
+        assert_eq!(forest.trees().len(), 5);
+        match forest.node(forest.root()) {
+            ForestNode::Symbol { start, end, .. } => assert_eq!((*start, *end), (0, 7)),
+            other => panic!("expected symbol root, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reduce_reduce_needs_two_lookahead() {
+        // LR(2): after P, the reduce depends on the token after X.
+        let compiled = compile(
+            r#"
+            start s;
+            terminals { P, X, Y, Z }
+            s = a X Y => ay | b X Z => bz;
+            a = P => a;
+            b = P => b;
+        "#,
+        );
+        assert!(compiled.has_conflicts());
+
+        let a_rule = (0..compiled.grammar.rules.len())
+            .find(|&r| compiled.rule_name(r) == Some("a"))
+            .unwrap();
+        let b_rule = (0..compiled.grammar.rules.len())
+            .find(|&r| compiled.rule_name(r) == Some("b"))
+            .unwrap();
+
+        for (input, rule) in [(["P", "X", "Y"], a_rule), (["P", "X", "Z"], b_rule)] {
+            let forest = parse(&compiled, &input).unwrap();
+            let trees = forest.trees();
+            assert_eq!(trees.len(), 1);
+            let Cst::Node { children, .. } = &trees[0] else {
+                panic!("expected node");
+            };
+            assert!(matches!(&children[0], Cst::Node { rule: r, .. } if *r == rule));
+        }
+    }
+
+    #[test]
+    fn test_epsilon_and_merge() {
+        let compiled = compile(
+            r#"
+            start s;
+            terminals { A }
+            s = x A => xa | y A => ya;
+            x = _ => x;
+            y = _ => y;
+        "#,
+        );
+        let forest = parse(&compiled, &["A"]).unwrap();
+        assert!(forest.is_ambiguous());
+        assert_eq!(forest.trees().len(), 2);
+    }
+
+    #[test]
+    fn test_right_nullable() {
+        // Reductions ending in empty symbols must be redone when a later
+        // derivation adds an edge below them.
+        for (src, input, count) in [
+            (
+                "s = p y b => s; p = A => a | A A => aa; y = A => a | A A => aa; b = _ => e;",
+                &["A", "A", "A"][..],
+                2,
+            ),
+            (
+                "s = A s b b => r | A => a; b = _ => e;",
+                &["A", "A", "A"],
+                1,
+            ),
+            ("s = s s b => r | A => a; b = _ => e;", &["A", "A", "A"], 2),
+            (
+                "s = s s b => r | A => a; b = _ => e;",
+                &["A", "A", "A", "A"],
+                5,
+            ),
+        ] {
+            let compiled = compile(&format!("start s; terminals {{ A }} {src}"));
+            let forest = parse(&compiled, input).unwrap();
+            assert_eq!(forest.trees().len(), count, "{src} on {input:?}");
+        }
+    }
+
+    #[test]
+    fn test_prec_terminals_do_not_fork() {
+        let compiled = compile(
+            r#"
+            start expr;
+            terminals { NUM, prec OP }
+            expr = expr OP expr => binop | NUM => num;
+        "#,
+        );
+        let num = Token::new(compiled.symbol_id("NUM").unwrap());
+        let op = compiled.symbol_id("OP").unwrap();
+        let mut parser = GlrParser::new(compiled.table());
+        for tok in [
+            num,
+            Token::with_prec(op, Precedence::Left(1)),
+            num,
+            Token::with_prec(op, Precedence::Left(2)),
+            num,
+        ] {
+            parser.push(tok).unwrap();
+            assert_eq!(parser.num_stacks(), 1);
+        }
+        let forest = parser.finish().map_err(|(_, e)| e).unwrap();
+        assert!(!forest.is_ambiguous());
+    }
+
+    #[test]
+    fn test_errors() {
+        let compiled = compile(
+            r#"
+            start expr;
+            terminals { NUM, PLUS }
+            expr = expr PLUS expr => add | NUM => num;
+        "#,
+        );
+        let plus = compiled.symbol_id("PLUS").unwrap();
+        let err = parse(&compiled, &["NUM", "PLUS", "PLUS"]).unwrap_err();
+        assert_eq!(err.terminal(), plus);
+
+        let err = parse(&compiled, &["NUM", "PLUS"]).unwrap_err();
+        assert_eq!(err.terminal(), SymbolId::EOF);
+
+        // A rejected token leaves the parser as it was.
+        let [num, plus] = tokens(&compiled, &["NUM", "PLUS"])[..] else {
+            unreachable!()
+        };
+        let mut parser = GlrParser::new(compiled.table());
+        for tok in [num, plus, num] {
+            parser.push(tok).unwrap();
+        }
+        let before = (
+            parser.vertices.len(),
+            parser.nodes.len(),
+            parser.frontier.clone(),
+        );
+        assert!(parser.push(num).is_err());
+        assert_eq!(
+            (
+                parser.vertices.len(),
+                parser.nodes.len(),
+                parser.frontier.clone()
+            ),
+            before
+        );
+        for tok in [plus, num] {
+            parser.push(tok).unwrap();
+        }
+        let forest = parser.finish().map_err(|(_, e)| e).unwrap();
+        assert_eq!(forest.trees().len(), 2);
+    }
+
+    #[test]
+    fn test_entry_points() {
+        let compiled = compile(
+            r#"
+            start stmts, expr;
+            terminals { NUM, PLUS, SEMI }
+            stmts = stmts expr SEMI => append | _ => empty;
+            expr = expr PLUS NUM => add | NUM => num;
+        "#,
+        );
+        for (entry, input) in [
+            ("stmts", &["NUM", "SEMI", "NUM", "PLUS", "NUM", "SEMI"][..]),
+            ("expr", &["NUM", "PLUS", "NUM"][..]),
+        ] {
+            let marker = compiled.entry(entry).unwrap();
+            let mut glr = GlrParser::for_entry(compiled.table(), marker);
+            let mut cst = CstParser::for_entry(compiled.table(), marker);
+            for tok in tokens(&compiled, input) {
+                glr.push(tok).unwrap();
+                cst.push(tok).unwrap();
+            }
+            let forest = glr.finish().map_err(|(_, e)| e).unwrap();
+            let tree = cst.finish().map_err(|(_, e)| e).unwrap();
+            assert_eq!(forest.trees(), vec![tree]);
+        }
+
+        // A statement is not an expression.
+        let marker = compiled.entry("expr").unwrap();
+        let mut cst = CstParser::for_entry(compiled.table(), marker);
+        let [num, semi] = tokens(&compiled, &["NUM", "SEMI"])[..] else {
+            unreachable!()
+        };
+        cst.push(num).unwrap();
+        assert!(cst.push(semi).is_err());
+        assert_eq!(compiled.entry("term"), None);
+    }
+}
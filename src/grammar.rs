@@ -22,24 +22,189 @@ impl SymbolId {
 // Public AST types for grammar definitions
 // ============================================================================
 
+/// A byte range in grammar source text.
+pub type Span = std::ops::Range<usize>;
+
+/// An error in a grammar definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrammarError {
+    /// What kind of problem this is.
+    pub kind: GrammarErrorKind,
+    /// The error message.
+    pub message: String,
+    /// Source location of the problem, if known. Spans refer to the text
+    /// given to [`parse_grammar`](crate::parse_grammar); items merged from
+    /// imported or base files carry none.
+    pub span: Option<Span>,
+}
+
+/// The kind of a [`GrammarError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GrammarErrorKind {
+    /// The grammar text is malformed.
+    Syntax,
+    /// An imported or base grammar could not be read or merged.
+    Import,
+    /// A symbol or rule is used but never defined.
+    UndefinedSymbol,
+    /// Two alternatives of a rule generate the same enum variant.
+    DuplicateVariant,
+    /// A symbol lacks a type where one is needed, such as a labeled field.
+    MissingType,
+    /// The grammar's conflicts differ from its `expect` declarations.
+    ExpectMismatch,
+    /// Any other invalid grammar.
+    Invalid,
+}
+
+impl GrammarError {
+    /// Create an error without a location.
+    pub fn new(kind: GrammarErrorKind, message: impl Into<String>) -> Self {
+        GrammarError {
+            kind,
+            message: message.into(),
+            span: None,
+        }
+    }
+
+    /// Set the location, unless the error already has one.
+    pub fn at(mut self, span: Option<Span>) -> Self {
+        if self.span.is_none() {
+            self.span = span;
+        }
+        self
+    }
+
+    /// Format the error with the line and column of its span in `source`.
+    pub fn format(&self, source: &str) -> String {
+        format_located(&self.message, self.span.as_ref(), source)
+    }
+}
+
+/// Prefix `message` with the line and column of `span` in `source`, if any.
+pub(crate) fn format_located(message: &str, span: Option<&Span>, source: &str) -> String {
+    let Some(span) = span else {
+        return message.to_string();
+    };
+    let before = &source[..span.start.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let col = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+    format!("{}:{}: {}", line, col, message)
+}
+
+impl std::fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for GrammarError {}
+
+impl From<GrammarError> for String {
+    fn from(e: GrammarError) -> String {
+        e.message
+    }
+}
+
 /// A grammar definition, typically produced by [`parse_grammar`](crate::parse_grammar)
 /// or built programmatically with fields.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Build it with `..Default::default()` for the fields you don't set. Its
+/// `comments` take no part in comparisons.
+#[derive(Debug, Clone, Default)]
 pub struct Grammar {
     /// Name of the start symbol.
     pub start: String,
+    /// Further start symbols (`start a, b;`). Each entry point gets its own
+    /// accept rule, so one table can parse any of them; `start` stays the
+    /// default.
+    pub extra_starts: Vec<String>,
     /// Expected number of reduce/reduce conflicts.
     pub expect_rr: usize,
     /// Expected number of shift/reduce conflicts.
     pub expect_sr: usize,
+    /// Split each shift/reduce-conflicted terminal into a shift-leaning and a
+    /// reduce-leaning variant (`mode split;`), so the lexer resolves the conflict.
+    pub split_tokens: bool,
     /// Terminal definitions.
     pub terminals: Vec<TerminalDef>,
     /// Grammar rules (productions).
     pub rules: Vec<Rule>,
+    /// Attributes for every generated enum (`#![derive(Clone)]` at the top
+    /// of the grammar), each without its `#![` and `]`.
+    pub attrs: Vec<String>,
+    /// Attributes for the generated `Terminal` enum (`#![...]` inside the
+    /// `terminals` block).
+    pub terminal_attrs: Vec<String>,
+    /// Comments of the grammar source, kept so [`Grammar::to_gzl`] can print
+    /// them back. Empty for grammars built in code.
+    pub comments: Vec<Comment>,
 }
 
-/// A terminal definition in the grammar.
+impl PartialEq for Grammar {
+    fn eq(&self, other: &Self) -> bool {
+        let Grammar {
+            start,
+            extra_starts,
+            expect_rr,
+            expect_sr,
+            split_tokens,
+            terminals,
+            rules,
+            attrs,
+            terminal_attrs,
+            comments: _,
+        } = self;
+        (
+            start,
+            extra_starts,
+            expect_rr,
+            expect_sr,
+            split_tokens,
+            terminals,
+            rules,
+            attrs,
+            terminal_attrs,
+        ) == (
+            &other.start,
+            &other.extra_starts,
+            &other.expect_rr,
+            &other.expect_sr,
+            &other.split_tokens,
+            &other.terminals,
+            &other.rules,
+            &other.attrs,
+            &other.terminal_attrs,
+        )
+    }
+}
+
+impl Eq for Grammar {}
+
+impl Grammar {
+    /// All start symbols: `start`, then `extra_starts`.
+    pub fn starts(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.start).chain(&self.extra_starts)
+    }
+}
+
+/// A comment in grammar source text.
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comment {
+    /// The comment as written, with its `//` or `/* */` delimiters.
+    pub text: String,
+    /// Whether the comment follows other code on the same line.
+    pub trailing: bool,
+    /// Source location of the comment.
+    pub span: Span,
+}
+
+/// A terminal definition in the grammar.
+///
+/// Build it with `..Default::default()` for the fields you don't set. Its
+/// `span` takes no part in comparisons.
+#[derive(Debug, Clone, Default)]
 pub struct TerminalDef {
     /// Terminal name (e.g., "NUM", "PLUS").
     pub name: String,
@@ -49,24 +214,198 @@ pub struct TerminalDef {
     pub is_prec: bool,
     /// Optional regex pattern for automatic lexer generation.
     pub pattern: Option<String>,
+    /// Doc comment lines (`/// ...`), each without its `///`.
+    pub doc: Vec<String>,
+    /// Source location of the declaration, if parsed from text.
+    pub span: Option<Span>,
 }
 
 /// A rule (production) in the grammar.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Build it with `..Default::default()` for the fields you don't set. Its
+/// `span` takes no part in comparisons.
+#[derive(Debug, Clone, Default)]
 pub struct Rule {
     /// Non-terminal name (left-hand side).
     pub name: String,
+    /// Parameters of a parameterized rule (`comma_list<T> = ...`), empty for
+    /// ordinary rules. Each use such as `comma_list<expr>` is expanded into
+    /// a plain rule (`comma_list_expr`).
+    pub params: Vec<String>,
     /// Alternatives (right-hand sides).
     pub alts: Vec<Alt>,
+    /// Doc comment lines (`/// ...`), each without its `///`.
+    pub doc: Vec<String>,
+    /// Attributes for the rule's generated enum (`#[derive(Clone)]`), each
+    /// without its `#[` and `]`.
+    pub attrs: Vec<String>,
+    /// Source location of the rule name, if parsed from text.
+    pub span: Option<Span>,
 }
 
 /// An alternative (right-hand side) of a rule.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Build it with `..Default::default()` for the fields you don't set. Its
+/// `span` takes no part in comparisons.
+#[derive(Debug, Clone, Default)]
 pub struct Alt {
-    /// Terms in this alternative.
-    pub terms: Vec<Term>,
+    /// Terms in this alternative, each with its source location.
+    pub terms: Vec<Spanned<Term>>,
+    /// Field labels (`lhs:expr`), one per term; empty if no term is labeled.
+    /// Labeled alternatives generate struct-like enum variants.
+    pub labels: Vec<Option<String>>,
     /// Action name (e.g., `=> binop`).
     pub name: String,
+    /// Precedence override (e.g., `=> neg prec(15, right)`).
+    pub prec: Option<AltPrec>,
+    /// Doc comment lines (`/// ...`), each without its `///`.
+    pub doc: Vec<String>,
+    /// Source location of the alternative, if parsed from text.
+    pub span: Option<Span>,
+}
+
+impl PartialEq for TerminalDef {
+    fn eq(&self, other: &Self) -> bool {
+        let TerminalDef {
+            name,
+            has_type,
+            is_prec,
+            pattern,
+            doc,
+            span: _,
+        } = self;
+        (name, has_type, is_prec, pattern, doc)
+            == (
+                &other.name,
+                &other.has_type,
+                &other.is_prec,
+                &other.pattern,
+                &other.doc,
+            )
+    }
+}
+
+impl Eq for TerminalDef {}
+
+impl PartialEq for Rule {
+    fn eq(&self, other: &Self) -> bool {
+        let Rule {
+            name,
+            params,
+            alts,
+            doc,
+            attrs,
+            span: _,
+        } = self;
+        (name, params, alts, doc, attrs)
+            == (
+                &other.name,
+                &other.params,
+                &other.alts,
+                &other.doc,
+                &other.attrs,
+            )
+    }
+}
+
+impl Eq for Rule {}
+
+impl PartialEq for Alt {
+    fn eq(&self, other: &Self) -> bool {
+        let Alt {
+            terms,
+            labels,
+            name,
+            prec,
+            doc,
+            span: _,
+        } = self;
+        (terms, labels, name, prec, doc)
+            == (
+                &other.terms,
+                &other.labels,
+                &other.name,
+                &other.prec,
+                &other.doc,
+            )
+    }
+}
+
+impl Eq for Alt {}
+
+/// A value with the source location it was parsed from.
+///
+/// The location takes no part in comparisons or hashing, so a grammar parsed
+/// from text equals the same grammar built in code. A plain value converts
+/// with `.into()`, without a location.
+#[derive(Debug, Clone, Default)]
+pub struct Spanned<T> {
+    /// The value itself.
+    pub node: T,
+    /// Source location, if parsed from text.
+    pub span: Option<Span>,
+}
+
+impl<T> Spanned<T> {
+    /// Wrap `node` with its location.
+    pub fn new(node: T, span: Option<Span>) -> Self {
+        Spanned { node, span }
+    }
+}
+
+impl<T> From<T> for Spanned<T> {
+    fn from(node: T) -> Self {
+        Spanned { node, span: None }
+    }
+}
+
+impl<T> std::ops::Deref for Spanned<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.node
+    }
+}
+
+impl<T: PartialEq> PartialEq for Spanned<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.node == other.node
+    }
+}
+
+impl<T: PartialEq> PartialEq<T> for Spanned<T> {
+    fn eq(&self, other: &T) -> bool {
+        self.node == *other
+    }
+}
+
+impl<T: Eq> Eq for Spanned<T> {}
+
+impl<T: std::hash::Hash> std::hash::Hash for Spanned<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.node.hash(state)
+    }
+}
+
+impl<T: PartialOrd> PartialOrd for Spanned<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.node.partial_cmp(&other.node)
+    }
+}
+
+impl<T: Ord> Ord for Spanned<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.node.cmp(&other.node)
+    }
+}
+
+/// Precedence declared on an alternative, used when its reduction competes
+/// with shifting a `prec` terminal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AltPrec {
+    /// `prec(N, left|right|nonassoc)`: a fixed precedence.
+    Fixed(crate::Precedence),
+    /// `prec(SYM)`: the runtime precedence of the RHS symbol `SYM`.
+    Symbol(String),
 }
 
 /// A term in a grammar rule.
@@ -74,6 +413,10 @@ pub struct Alt {
 pub enum Term {
     /// Plain symbol reference.
     Symbol(String),
+    /// `name<arg, ...>` - a parameterized rule applied to arguments, each a
+    /// `Symbol` or another `Apply`. With `?`, `*` or `+` it is the only term
+    /// of an `OptionalGroup`, `ZeroOrMoreGroup` or `OneOrMoreGroup`.
+    Apply { name: String, args: Vec<Term> },
     /// `?` - optional (zero or one).
     Optional(String),
     /// `*` - zero or more.
@@ -82,6 +425,26 @@ pub enum Term {
     OneOrMore(String),
     /// `%` - one or more separated by the given symbol.
     SeparatedBy { symbol: String, sep: String },
+    /// `%%` - one or more separated by the given symbol, which may also
+    /// follow the last.
+    SeparatedTrailing { symbol: String, sep: String },
+    /// `;` - one or more, each followed by the given symbol.
+    TerminatedBy { symbol: String, terminator: String },
+    /// `(a ; b)*` - zero or more, each followed by the given symbol.
+    ZeroOrMoreTerminatedBy { symbol: String, terminator: String },
+    /// `(a b | c)` - a group of alternative sequences. Its value is the one
+    /// typed symbol of the chosen sequence, or a tuple if there are several.
+    Group(Vec<Vec<Spanned<Term>>>),
+    /// `(...)?` - optional group.
+    OptionalGroup(Vec<Vec<Spanned<Term>>>),
+    /// `(...)*` - group repeated zero or more times.
+    ZeroOrMoreGroup(Vec<Vec<Spanned<Term>>>),
+    /// `(...)+` - group repeated one or more times.
+    OneOrMoreGroup(Vec<Vec<Spanned<Term>>>),
+    /// `{ a b? c* d+ }` - symbols in any order, each as often as its
+    /// modifier allows (exactly once without one). Its value is a tuple with
+    /// a slot per item, like a sequence of the same terms.
+    Permutation(Vec<Spanned<Term>>),
     /// `_` - empty production marker.
     Empty,
 }
@@ -0,0 +1,1019 @@
+//! Import of ANTLR 4 grammars (`.g4` files).
+//!
+//! Parser rules become rules named in snake_case. `?`, `*` and `+` on a
+//! symbol map onto term modifiers, `a (',' a)*` onto `(a % ',')`, and any
+//! other group becomes a helper rule named after its rule (`expr_1`).
+//! Alternative labels (`# add`) name the variants; unlabeled alternatives
+//! are named after their terminals.
+//!
+//! Lexer rules become terminals, with a regex pattern where the regex syntax
+//! allows: `fragment` rules are inlined, and non-greedy loops or Unicode
+//! ranges leave the terminal without a pattern. A quoted token in a parser
+//! rule, such as `'+'`, refers to the lexer rule that matches exactly that
+//! text, if there is one.
+//!
+//! Actions, predicates, rule arguments, element labels and lexer modes have
+//! no counterpart and are dropped with a warning. So are lexer rules sent to
+//! `-> skip` or another channel: the lexer loop has to skip their text. A
+//! wildcard or negated set in a parser rule becomes a placeholder terminal
+//! (`UNSUPPORTED`), also with a warning.
+//!
+//! ```
+//! let import = gazelle::import::antlr::parse(r#"
+//! grammar Calc;
+//! expr : expr '+' term # add
+//!      | term          # single
+//!      ;
+//! term : INT | '(' expr ')' ;
+//! PLUS : '+' ;
+//! INT  : [0-9]+ ;
+//! WS   : [ \t\r\n]+ -> skip ;
+//! "#).unwrap();
+//! assert_eq!(import.grammar.to_gzl(), "\
+//! start expr;
+//!
+//! terminals {
+//!     PLUS = /\\+/,
+//!     INT: _ = /[0-9]+/
+//! }
+//!
+//! expr = expr PLUS term => add
+//!      | term => single;
+//!
+//! term = INT => int
+//!      | '(' expr ')' => lparen_rparen;
+//! ");
+//! assert_eq!(
+//!     import.warnings[0].message,
+//!     "Dropped lexer rule 'WS' (-> skip); the lexer loop has to skip its text",
+//! );
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use super::expr::{Expr, Lowering, regex};
+use super::{
+    Import, Names, Warning, identifier, skip_code, skip_comment, snake_case, syntax_error,
+};
+use crate::grammar::{Grammar, GrammarError, GrammarErrorKind, Span, TerminalDef};
+use crate::lr::is_literal;
+
+/// Translate the ANTLR 4 grammar `source` into a [`Grammar`].
+///
+/// Fails only if `source` is malformed or has no parser rules; everything
+/// that is dropped or approximated is reported in [`Import::warnings`].
+pub fn parse(source: &str) -> Result<Import, GrammarError> {
+    let mut parser = Parser {
+        tokens: lex(source)?,
+        pos: 0,
+        end: source.len(),
+        rules: Vec::new(),
+        token_names: Vec::new(),
+        warnings: Vec::new(),
+    };
+    parser.grammar()?;
+    parser.build()
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    /// A quoted literal, unescaped.
+    Literal(String),
+    /// `[...]`: a character set or rule arguments, as written inside.
+    Bracket(String),
+    /// `{...}`: an action, or the body of `options`, `tokens` or `channels`.
+    Braced(String),
+    /// `<...>`: element options.
+    Angle(String),
+    Punct(&'static str),
+}
+
+const PUNCTS: &[&str] = &[
+    "->", "..", "+=", "::", ":", ";", "|", "(", ")", "?", "*", "+", "~", ".", "#", "=", ",", "@",
+];
+
+fn lex(source: &str) -> Result<Vec<(Tok, Span)>, GrammarError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let start = pos;
+        let c = bytes[pos];
+        if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        if c == b'/' && matches!(bytes.get(pos + 1), Some(b'*' | b'/')) {
+            pos = skip_comment(source, pos)?;
+            continue;
+        }
+        let tok = match c {
+            b'\'' => {
+                let mut i = pos + 1;
+                while i < bytes.len() && bytes[i] != b'\'' && bytes[i] != b'\n' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                if bytes.get(i) != Some(&b'\'') {
+                    return Err(syntax_error(
+                        "Unterminated literal",
+                        start..i.min(bytes.len()),
+                    ));
+                }
+                pos = i + 1;
+                Tok::Literal(unescape(&source[start + 1..i]))
+            }
+            b'[' => {
+                let mut i = pos + 1;
+                while i < bytes.len() && bytes[i] != b']' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                if i >= bytes.len() {
+                    return Err(syntax_error("Unterminated [...]", start..start + 1));
+                }
+                pos = i + 1;
+                Tok::Bracket(source[start + 1..i].to_string())
+            }
+            b'{' => {
+                pos = skip_code(source, pos)?;
+                Tok::Braced(source[start + 1..pos - 1].to_string())
+            }
+            b'<' => {
+                let Some(end) = source[pos..].find('>') else {
+                    return Err(syntax_error("Unterminated <...>", start..start + 1));
+                };
+                pos += end + 1;
+                Tok::Angle(source[start + 1..pos - 1].to_string())
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                while pos < bytes.len()
+                    && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_')
+                {
+                    pos += 1;
+                }
+                Tok::Ident(source[start..pos].to_string())
+            }
+            _ => {
+                let Some(punct) = PUNCTS.iter().find(|p| source[pos..].starts_with(**p)) else {
+                    let c = source[pos..].chars().next().unwrap();
+                    return Err(syntax_error(
+                        format!("Unexpected character '{}'", c),
+                        start..start + c.len_utf8(),
+                    ));
+                };
+                pos += punct.len();
+                Tok::Punct(punct)
+            }
+        };
+        tokens.push((tok, start..pos));
+    }
+    Ok(tokens)
+}
+
+/// The text of a literal or set with ANTLR escapes: `\n`, `\uXXXX`,
+/// `\u{X...}` and the like.
+fn unescape(raw: &str) -> String {
+    let mut out = String::new();
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('b') => out.push('\u{8}'),
+            Some('f') => out.push('\u{c}'),
+            Some('u') => {
+                let hex: String = if chars.peek() == Some(&'{') {
+                    chars.next();
+                    chars.by_ref().take_while(|&c| c != '}').collect()
+                } else {
+                    chars.by_ref().take(4).collect()
+                };
+                out.extend(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32));
+            }
+            Some(c) => out.push(c),
+            None => {}
+        }
+    }
+    out
+}
+
+/// The ranges of a lexer set such as `[a-zA-Z_\-]`.
+fn char_set(raw: &str) -> Result<Vec<(char, char)>, String> {
+    let mut chars: Vec<char> = Vec::new();
+    // Escaped characters that would otherwise be a range dash
+    let mut escaped: Vec<bool> = Vec::new();
+    let mut iter = raw.chars().peekable();
+    while let Some(c) = iter.next() {
+        if c != '\\' {
+            chars.push(c);
+            escaped.push(false);
+            continue;
+        }
+        let Some(&next) = iter.peek() else {
+            break;
+        };
+        if next == 'p' || next == 'P' {
+            return Err("a Unicode property class".to_string());
+        }
+        let mut escape = String::from('\\');
+        escape.push(next);
+        iter.next();
+        if next == 'u' {
+            if iter.peek() == Some(&'{') {
+                for c in iter.by_ref() {
+                    escape.push(c);
+                    if c == '}' {
+                        break;
+                    }
+                }
+            } else {
+                escape.extend(iter.by_ref().take(4));
+            }
+        }
+        chars.extend(unescape(&escape).chars());
+        escaped.push(true);
+    }
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if i + 2 < chars.len() && chars[i + 1] == '-' && !escaped[i + 1] {
+            ranges.push((chars[i], chars[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((chars[i], chars[i]));
+            i += 1;
+        }
+    }
+    Ok(ranges)
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+struct RawRule {
+    name: String,
+    span: Span,
+    fragment: bool,
+    /// Alternatives, each with its `# label`.
+    alts: Vec<(Expr, Option<String>)>,
+    /// Lexer commands after `->`, as written.
+    commands: Vec<String>,
+}
+
+impl RawRule {
+    fn is_lexer(&self) -> bool {
+        self.name.starts_with(|c: char| c.is_ascii_uppercase())
+    }
+
+    fn body(&self) -> Expr {
+        Expr::alt(self.alts.iter().map(|(e, _)| e.clone()).collect())
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Tok, Span)>,
+    pos: usize,
+    end: usize,
+    rules: Vec<RawRule>,
+    /// Tokens declared in `tokens { ... }`.
+    token_names: Vec<String>,
+    warnings: Vec<Warning>,
+}
+
+/// The rule being parsed.
+struct Context<'a> {
+    name: &'a str,
+    lexer: bool,
+    commands: Vec<String>,
+    labels_warned: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn span(&self) -> Span {
+        match self.tokens.get(self.pos) {
+            Some((_, span)) => span.clone(),
+            None => self.end..self.end,
+        }
+    }
+
+    fn at(&self, punct: &str) -> bool {
+        matches!(self.peek(), Some(Tok::Punct(p)) if *p == punct)
+    }
+
+    fn at_ident(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Tok::Ident(w)) if w == word)
+    }
+
+    fn bump(&mut self) -> (Tok, Span) {
+        let tok = self.tokens[self.pos].clone();
+        self.pos += 1;
+        tok
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<Span, GrammarError> {
+        if !self.at(punct) {
+            return Err(syntax_error(format!("Expected '{}'", punct), self.span()));
+        }
+        Ok(self.bump().1)
+    }
+
+    fn ident(&mut self) -> Result<String, GrammarError> {
+        match self.peek() {
+            Some(Tok::Ident(name)) => {
+                let name = name.clone();
+                self.pos += 1;
+                Ok(name)
+            }
+            _ => Err(syntax_error("Expected a name", self.span())),
+        }
+    }
+
+    fn braced(&mut self) -> Result<String, GrammarError> {
+        match self.peek() {
+            Some(Tok::Braced(body)) => {
+                let body = body.clone();
+                self.pos += 1;
+                Ok(body)
+            }
+            _ => Err(syntax_error("Expected '{'", self.span())),
+        }
+    }
+
+    fn warn(&mut self, message: impl Into<String>, span: Span) {
+        self.warnings.push(Warning::new(message, Some(span)));
+    }
+
+    /// Skip to just after the next `;`.
+    fn skip_statement(&mut self) -> Result<(), GrammarError> {
+        while !self.at(";") {
+            if self.peek().is_none() {
+                return Err(syntax_error("Expected ';'", self.span()));
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn grammar(&mut self) -> Result<(), GrammarError> {
+        while let Some(tok) = self.peek().cloned() {
+            let span = self.span();
+            match tok {
+                Tok::Ident(word) if word == "lexer" || word == "parser" || word == "grammar" => {
+                    self.skip_statement()?;
+                }
+                Tok::Ident(word) if word == "options" => {
+                    self.pos += 1;
+                    self.braced()?;
+                    self.warn("Ignored the grammar options", span.start..self.span().start);
+                }
+                Tok::Ident(word) if word == "tokens" => {
+                    self.pos += 1;
+                    let body = self.braced()?;
+                    let names = body.split(',').map(str::trim).filter(|n| !n.is_empty());
+                    self.token_names.extend(names.map(String::from));
+                }
+                Tok::Ident(word) if word == "channels" => {
+                    self.pos += 1;
+                    self.braced()?;
+                }
+                Tok::Ident(word) if word == "import" => {
+                    self.skip_statement()?;
+                    self.warn(
+                        "Imported grammars are not read",
+                        span.start..self.tokens[self.pos - 1].1.end,
+                    );
+                }
+                Tok::Ident(word) if word == "mode" => {
+                    self.skip_statement()?;
+                    self.warn(
+                        "Lexer modes are not supported; the rules of all modes are merged",
+                        span.start..self.tokens[self.pos - 1].1.end,
+                    );
+                }
+                Tok::Punct("@") => {
+                    self.pos += 1;
+                    let mut name = self.ident()?;
+                    if self.at("::") {
+                        self.pos += 1;
+                        name = format!("{}::{}", name, self.ident()?);
+                    }
+                    self.braced()?;
+                    self.warn(
+                        format!("Dropped the @{} action", name),
+                        span.start..self.tokens[self.pos - 1].1.end,
+                    );
+                }
+                Tok::Ident(_) => self.rule()?,
+                _ => return Err(syntax_error("Expected a rule", span)),
+            }
+        }
+        Ok(())
+    }
+
+    fn rule(&mut self) -> Result<(), GrammarError> {
+        let fragment = self.at_ident("fragment");
+        if fragment {
+            self.pos += 1;
+        }
+        let span = self.span();
+        let name = self.ident()?;
+        let lexer = name.starts_with(|c: char| c.is_ascii_uppercase());
+        // Arguments, return values and the like, up to the ':'
+        while !self.at(":") {
+            let start = self.span();
+            let what = match self.peek() {
+                Some(Tok::Bracket(_)) => "arguments",
+                Some(Tok::Ident(w)) if w == "returns" => "return values",
+                Some(Tok::Ident(w)) if w == "locals" => "locals",
+                Some(Tok::Ident(w)) if w == "throws" => "throws clause",
+                Some(Tok::Ident(w)) if w == "options" => "options",
+                Some(Tok::Punct("@")) => "actions",
+                _ => return Err(syntax_error("Expected ':' after the rule name", start)),
+            };
+            self.pos += 1;
+            while !self.at(":") && !self.at("@") && !self.at_ident("returns") {
+                if matches!(self.peek(), None | Some(Tok::Punct(_))) {
+                    break;
+                }
+                self.pos += 1;
+            }
+            let end = self.tokens[self.pos - 1].1.end;
+            self.warn(
+                format!("Dropped the {} of rule '{}'", what, name),
+                start.start..end,
+            );
+        }
+        self.pos += 1;
+        let mut cx = Context {
+            name: &name,
+            lexer,
+            commands: Vec::new(),
+            labels_warned: false,
+        };
+        let alts = self.alternatives(&mut cx, true)?;
+        let commands = cx.commands;
+        self.expect(";")?;
+        // Exception handlers
+        while self.at_ident("catch") || self.at_ident("finally") {
+            let start = self.span().start;
+            self.pos += 1;
+            if let Some(Tok::Bracket(_)) = self.peek() {
+                self.pos += 1;
+            }
+            self.braced()?;
+            let end = self.tokens[self.pos - 1].1.end;
+            self.warn(
+                format!("Dropped an exception handler of rule '{}'", name),
+                start..end,
+            );
+        }
+        match self.rules.iter_mut().find(|r| r.name == name) {
+            Some(_) => {
+                return Err(syntax_error(
+                    format!("Rule '{}' is defined twice", name),
+                    span,
+                ));
+            }
+            None => self.rules.push(RawRule {
+                name,
+                span,
+                fragment,
+                alts,
+                commands,
+            }),
+        }
+        Ok(())
+    }
+
+    fn alternatives(
+        &mut self,
+        cx: &mut Context,
+        top: bool,
+    ) -> Result<Vec<(Expr, Option<String>)>, GrammarError> {
+        let mut alts = vec![self.alternative(cx, top)?];
+        while self.at("|") {
+            self.pos += 1;
+            alts.push(self.alternative(cx, top)?);
+        }
+        Ok(alts)
+    }
+
+    fn alternative(
+        &mut self,
+        cx: &mut Context,
+        top: bool,
+    ) -> Result<(Expr, Option<String>), GrammarError> {
+        let mut items = Vec::new();
+        let mut label = None;
+        loop {
+            let span = self.span();
+            match self.peek().cloned() {
+                None | Some(Tok::Punct("|" | ";" | ")")) => break,
+                Some(Tok::Punct("#")) if top => {
+                    self.pos += 1;
+                    label = Some(identifier(&snake_case(&self.ident()?)));
+                }
+                Some(Tok::Punct("->")) => {
+                    self.pos += 1;
+                    loop {
+                        let mut command = self.ident()?;
+                        if self.at("(") {
+                            self.pos += 1;
+                            command = format!("{}({})", command, self.ident()?);
+                            self.expect(")")?;
+                        }
+                        cx.commands.push(command);
+                        if !self.at(",") {
+                            break;
+                        }
+                        self.pos += 1;
+                    }
+                }
+                Some(Tok::Braced(_)) => {
+                    self.pos += 1;
+                    let what = if self.at("?") {
+                        self.pos += 1;
+                        "a semantic predicate"
+                    } else {
+                        "an action"
+                    };
+                    let end = self.tokens[self.pos - 1].1.end;
+                    self.warn(
+                        format!("Dropped {} in rule '{}'", what, cx.name),
+                        span.start..end,
+                    );
+                }
+                Some(Tok::Angle(options)) => {
+                    self.pos += 1;
+                    self.warn(
+                        format!("Ignored the option <{}> in rule '{}'", options, cx.name),
+                        span,
+                    );
+                }
+                Some(_) => items.push(self.element(cx)?),
+            }
+        }
+        Ok((Expr::seq(items), label))
+    }
+
+    fn element(&mut self, cx: &mut Context) -> Result<Expr, GrammarError> {
+        let labeled = matches!(self.peek(), Some(Tok::Ident(_)))
+            && matches!(
+                self.tokens.get(self.pos + 1),
+                Some((Tok::Punct("=" | "+="), _))
+            );
+        if labeled {
+            let span = self.span();
+            self.pos += 2;
+            if !cx.labels_warned {
+                cx.labels_warned = true;
+                self.warn(
+                    format!("Dropped the element labels of rule '{}'", cx.name),
+                    span,
+                );
+            }
+        }
+        let start = self.span().start;
+        let mut atom = self.atom(cx)?;
+        if !cx.lexer && matches!(self.peek(), Some(Tok::Bracket(_))) {
+            let span = self.span();
+            self.pos += 1;
+            self.warn(
+                format!("Dropped rule arguments in rule '{}'", cx.name),
+                span,
+            );
+        }
+        let op = match self.peek() {
+            Some(Tok::Punct(op @ ("?" | "*" | "+"))) => *op,
+            _ => return Ok(atom),
+        };
+        self.pos += 1;
+        atom = match op {
+            "?" => Expr::Optional(Box::new(atom)),
+            "*" => Expr::ZeroOrMore(Box::new(atom)),
+            _ => Expr::OneOrMore(Box::new(atom)),
+        };
+        if self.at("?") {
+            self.pos += 1;
+            if cx.lexer {
+                let end = self.tokens[self.pos - 1].1.end;
+                atom = Expr::Unsupported("a non-greedy loop".to_string(), start..end);
+            }
+        }
+        Ok(atom)
+    }
+
+    fn atom(&mut self, cx: &mut Context) -> Result<Expr, GrammarError> {
+        let (tok, span) = self.bump();
+        Ok(match tok {
+            Tok::Ident(name) => Expr::Ref(name, span),
+            Tok::Literal(text) if self.at("..") => {
+                self.pos += 1;
+                let (end, end_span) = self.bump();
+                let mut first = text.chars();
+                let range = match (&end, first.next(), first.next()) {
+                    (Tok::Literal(last), Some(lo), None) if last.chars().count() == 1 => {
+                        Some((lo, last.chars().next().unwrap()))
+                    }
+                    _ => None,
+                };
+                match range {
+                    Some(range) => Expr::Class {
+                        ranges: vec![range],
+                        negated: false,
+                    },
+                    None => return Err(syntax_error("Expected a character range", end_span)),
+                }
+            }
+            Tok::Literal(text) => Expr::Literal(text),
+            Tok::Bracket(raw) if cx.lexer => match char_set(&raw) {
+                Ok(ranges) => Expr::Class {
+                    ranges,
+                    negated: false,
+                },
+                Err(reason) => Expr::Unsupported(reason, span),
+            },
+            Tok::Punct(".") if cx.lexer => Expr::Any,
+            Tok::Punct(".") => Expr::Unsupported("the wildcard '.'".to_string(), span),
+            Tok::Punct("~") => {
+                let set = self.atom(cx)?;
+                let end = self.tokens[self.pos - 1].1.end;
+                match negated(&set) {
+                    Some(ranges) if cx.lexer => Expr::Class {
+                        ranges,
+                        negated: true,
+                    },
+                    _ => Expr::Unsupported("a negated set".to_string(), span.start..end),
+                }
+            }
+            Tok::Punct("(") => {
+                let alts = self.alternatives(cx, false)?;
+                self.expect(")")?;
+                Expr::alt(alts.into_iter().map(|(e, _)| e).collect())
+            }
+            _ => return Err(syntax_error("Unexpected token in a rule", span)),
+        })
+    }
+
+    // ========================================================================
+    // Translation
+    // ========================================================================
+
+    fn build(mut self) -> Result<Import, GrammarError> {
+        let rules = std::mem::take(&mut self.rules);
+        let (lexer_rules, parser_rules): (Vec<&RawRule>, Vec<&RawRule>) =
+            rules.iter().partition(|r| r.is_lexer());
+        if parser_rules.is_empty() {
+            return Err(GrammarError::new(
+                GrammarErrorKind::Invalid,
+                "The grammar has no parser rules",
+            ));
+        }
+        let bodies: HashMap<&str, Expr> = lexer_rules
+            .iter()
+            .map(|r| (r.name.as_str(), r.body()))
+            .collect();
+        let lookup = |name: &str| bodies.get(name);
+
+        let mut names = Names::default();
+        for rule in &parser_rules {
+            names.fresh(&rule.name, &identifier(&snake_case(&rule.name)));
+        }
+
+        // Lexer rules, with the tokens that match a fixed text
+        let mut terminals = Vec::new();
+        let mut literal_tokens: HashMap<String, String> = HashMap::new();
+        for rule in lexer_rules.iter().filter(|r| !r.fragment) {
+            if let Some(skip) = rule
+                .commands
+                .iter()
+                .find(|c| *c == "skip" || c.starts_with("channel("))
+            {
+                self.warn(
+                    format!(
+                        "Dropped lexer rule '{}' (-> {}); the lexer loop has to skip its text",
+                        rule.name, skip
+                    ),
+                    rule.span.clone(),
+                );
+                continue;
+            }
+            for command in &rule.commands {
+                self.warn(
+                    format!(
+                        "Ignored lexer command '-> {}' of rule '{}'",
+                        command, rule.name
+                    ),
+                    rule.span.clone(),
+                );
+            }
+            let body = &bodies[rule.name.as_str()];
+            let name = names.fresh(&rule.name, &terminal_name(&rule.name));
+            let pattern = match regex(body, &lookup) {
+                Ok(pattern) => Some(pattern),
+                Err((reason, span)) => {
+                    self.warnings.push(Warning::new(
+                        format!(
+                            "Lexer rule '{}' has no regex pattern: {}",
+                            rule.name, reason
+                        ),
+                        span.or(Some(rule.span.clone())),
+                    ));
+                    None
+                }
+            };
+            let fixed = match body {
+                Expr::Literal(text) => {
+                    literal_tokens.entry(text.clone()).or_insert(name.clone());
+                    true
+                }
+                _ => false,
+            };
+            terminals.push(TerminalDef {
+                name,
+                has_type: !fixed,
+                is_prec: false,
+                pattern,
+                doc: Vec::new(),
+                span: None,
+            });
+        }
+        for token in &self.token_names {
+            if !names.contains(token) {
+                let name = names.fresh(token, &terminal_name(token));
+                terminals.push(terminal(name));
+            }
+        }
+
+        // Parser rules, with references resolved to grammar names
+        let mut resolved = Vec::new();
+        for rule in &parser_rules {
+            let mut resolve = |leaf: &Expr| match leaf {
+                Expr::Ref(name, _) if name == "EOF" => Expr::empty(),
+                Expr::Ref(name, span) => {
+                    if !names.contains(name) {
+                        let terminal_name = names.fresh(name, &terminal_name(name));
+                        self.warnings.push(Warning::new(
+                            format!(
+                                "'{}' is not defined in this grammar; declared as terminal {}",
+                                name, terminal_name
+                            ),
+                            Some(span.clone()),
+                        ));
+                        terminals.push(terminal(terminal_name));
+                    }
+                    Expr::Ref(names.lookup(name).unwrap().to_string(), span.clone())
+                }
+                Expr::Literal(text) => match literal_tokens.get(text) {
+                    Some(token) => Expr::Ref(token.clone(), 0..0),
+                    None => leaf.clone(),
+                },
+                _ => leaf.clone(),
+            };
+            let alts: Vec<(Expr, Option<String>)> = rule
+                .alts
+                .iter()
+                .map(|(e, label)| (e.map_leaves(&mut resolve), label.clone()))
+                .collect();
+            if alts.iter().any(|(e, _)| binary_recursion(e, &rule.name)) {
+                self.warn(
+                    format!(
+                        "Rule '{}' is left-recursive with binary alternatives, whose \
+                         precedence ANTLR takes from their order; that order is not carried \
+                         over, so use prec terminals or expect conflicts",
+                        rule.name
+                    ),
+                    rule.span.clone(),
+                );
+            }
+            resolved.push((
+                names.lookup(&rule.name).unwrap().to_string(),
+                rule.span.clone(),
+                alts,
+            ));
+        }
+
+        let terminal_names: HashSet<String> = terminals.iter().map(|t| t.name.clone()).collect();
+        let is_terminal = |s: &str| is_literal(s) || terminal_names.contains(s);
+        let mut lowering = Lowering {
+            names: &mut names,
+            is_terminal: &is_terminal,
+            rules: Vec::new(),
+            placeholders: Vec::new(),
+            warnings: Vec::new(),
+        };
+        for (name, span, alts) in &resolved {
+            lowering.rule(name, span, alts);
+        }
+        let rules = lowering.rules;
+        terminals.extend(lowering.placeholders.into_iter().map(terminal));
+        let mut warnings = self.warnings;
+        warnings.extend(lowering.warnings);
+        warnings.sort_by_key(|w| w.span.as_ref().map_or(usize::MAX, |s| s.start));
+        Ok(Import {
+            grammar: Grammar {
+                start: resolved[0].0.clone(),
+                extra_starts: Vec::new(),
+                expect_rr: 0,
+                expect_sr: 0,
+                split_tokens: false,
+                terminals,
+                rules,
+                attrs: Vec::new(),
+                terminal_attrs: Vec::new(),
+                comments: Vec::new(),
+            },
+            warnings,
+        })
+    }
+}
+
+/// The ranges that `~set` excludes, if `set` is a set of characters.
+fn negated(set: &Expr) -> Option<Vec<(char, char)>> {
+    match set {
+        Expr::Class {
+            ranges,
+            negated: false,
+        } => Some(ranges.clone()),
+        Expr::Literal(text) if text.chars().count() == 1 => {
+            let c = text.chars().next().unwrap();
+            Some(vec![(c, c)])
+        }
+        Expr::Alt(alts) => {
+            let mut ranges = Vec::new();
+            for alt in alts {
+                ranges.extend(negated(alt)?);
+            }
+            Some(ranges)
+        }
+        _ => None,
+    }
+}
+
+/// Whether an alternative of `rule` both starts and ends with `rule`, as
+/// binary operators in ANTLR's left-recursive rules do.
+fn binary_recursion(alt: &Expr, rule: &str) -> bool {
+    let Expr::Seq(items) = alt else {
+        return false;
+    };
+    let is_rule = |e: Option<&Expr>| matches!(e, Some(Expr::Ref(name, _)) if name == rule);
+    items.len() >= 3 && is_rule(items.first()) && is_rule(items.last())
+}
+
+/// A terminal name in gazelle's style: `Identifier` → `IDENTIFIER`.
+fn terminal_name(name: &str) -> String {
+    identifier(&snake_case(name).to_uppercase())
+}
+
+fn terminal(name: String) -> TerminalDef {
+    TerminalDef {
+        name,
+        has_type: false,
+        is_prec: false,
+        pattern: None,
+        doc: Vec::new(),
+        span: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"
+grammar JSON;
+
+@header { package json; }
+
+json : value EOF ;
+
+obj : '{' pair (',' pair)* '}'  # members
+    | '{' '}'                   # empty
+    ;
+
+pair : key=STRING ':' value ;
+
+arr : '[' (value (',' value)*)? ']' ;
+
+value : STRING | NUMBER | obj | arr | 'true' | 'false' | 'null' ;
+
+STRING : '"' (ESC | ~["\\])* '"' ;
+fragment ESC : '\\' (["\\/bfnrt] | UNICODE) ;
+fragment UNICODE : 'u' HEX HEX HEX HEX ;
+fragment HEX : [0-9a-fA-F] ;
+NUMBER : '-'? INT ('.' [0-9]+)? ;
+fragment INT : '0' | [1-9] [0-9]* ;
+LINE_COMMENT : '//' .*? '\n' ;
+WS : [ \t\n\r]+ -> skip ;
+"#;
+
+    #[test]
+    fn test_json() {
+        let import = parse(JSON).unwrap();
+        assert_eq!(
+            import.grammar.to_gzl(),
+            r#"start json;
+
+terminals {
+    STRING: _ = /"(\\(["\\\/bfnrt]|u[0-9a-fA-F][0-9a-fA-F][0-9a-fA-F][0-9a-fA-F])|[^"\\])*"/,
+    NUMBER: _ = /\-?(0|[1-9][0-9]*)(\.[0-9]+)?/,
+    LINE_COMMENT: _
+}
+
+json = value => value;
+
+obj = '{' (pair % ',') '}' => members
+    | '{' '}' => empty;
+
+pair = STRING ':' value => string_colon;
+
+arr = '[' arr_1? ']' => lbracket_rbracket;
+
+arr_1 = (value % ',') => value;
+
+value = STRING => string
+      | NUMBER => number
+      | obj => obj
+      | arr => arr
+      | "true" => true
+      | "false" => false
+      | "null" => null;
+"#
+        );
+        let messages: Vec<String> = import.warnings.iter().map(|w| w.format(JSON)).collect();
+        assert_eq!(
+            messages,
+            [
+                "4:1: Dropped the @header action",
+                "12:8: Dropped the element labels of rule 'pair'",
+                "24:21: Lexer rule 'LINE_COMMENT' has no regex pattern: a non-greedy loop",
+                "25:1: Dropped lexer rule 'WS' (-> skip); the lexer loop has to skip its text",
+            ]
+        );
+        crate::table::CompiledTable::build(&import.grammar).unwrap();
+    }
+
+    #[test]
+    fn test_tokens_and_recursion() {
+        let source = "parser grammar P;\noptions { tokenVocab = L; }\ntokens { A, B }\n\
+                      e : e '*' e | e '+' e | A | C ;\n";
+        let import = parse(source).unwrap();
+        let terminals: Vec<_> = import.grammar.terminals.iter().map(|t| &t.name).collect();
+        assert_eq!(terminals, ["A", "B", "C"]);
+        let messages: Vec<_> = import.warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(messages[0], "Ignored the grammar options");
+        assert!(messages[1].starts_with("Rule 'e' is left-recursive"));
+        assert_eq!(
+            messages[2],
+            "'C' is not defined in this grammar; declared as terminal C"
+        );
+
+        assert_eq!(
+            parse("lexer grammar L;\nA : 'a' ;\n").unwrap_err().message,
+            "The grammar has no parser rules"
+        );
+        let err = parse("grammar G;\ns : A\n").unwrap_err();
+        assert_eq!(err.format("grammar G;\ns : A\n"), "3:1: Expected ';'");
+    }
+
+    #[test]
+    fn test_unsupported_elements() {
+        let source = "grammar G;\ns : A . | ~A | (~A)? ;\nA : 'a' ;\n";
+        let import = parse(source).unwrap();
+        let s = &import.grammar.rules[0];
+        let alts: Vec<_> = s.alts.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(alts, ["a_unsupported", "unsupported_2", "unsupported_3"]);
+        assert!(
+            import
+                .grammar
+                .terminals
+                .iter()
+                .any(|t| t.name == "UNSUPPORTED_3" && t.pattern.is_none())
+        );
+        let messages: Vec<String> = import.warnings.iter().map(|w| w.format(source)).collect();
+        assert_eq!(
+            messages,
+            [
+                "2:7: Replaced the wildcard '.' in rule 's' with the placeholder terminal UNSUPPORTED",
+                "2:11: Replaced a negated set in rule 's' with the placeholder terminal UNSUPPORTED_2",
+                "2:17: Replaced a negated set in rule 's' with the placeholder terminal UNSUPPORTED_3",
+            ]
+        );
+        crate::table::CompiledTable::build(&import.grammar).unwrap();
+    }
+}
@@ -0,0 +1,716 @@
+//! Import of grammars in the W3C EBNF notation of the XML specification
+//! (`Name ::= expression`).
+//!
+//! W3C grammars are usually written down to the character, so the rules are
+//! split in two. A rule whose body matches characters (classes such as
+//! `[a-z]` or code points such as `#x20`) and refers only to other such rules
+//! is lexical. Lexical rules referenced from the other rules become
+//! terminals with a regex pattern where the regex syntax allows; the rest are
+//! inlined into those patterns. The first rule is never lexical.
+//!
+//! The other rules become rules named in snake_case. `?`, `*` and `+` on a
+//! symbol map onto term modifiers, `a (',' a)*` onto `(a % ',')`, and any
+//! other group becomes a helper rule named after its rule (`list_1`).
+//! Character classes left in them become `CHAR` terminals, or placeholder
+//! terminals (`UNSUPPORTED`) where the regex syntax can't express them.
+//!
+//! Exceptions (`A - B`) keep `A`, and well-formedness and validity
+//! constraints are dropped; both are reported as warnings.
+//!
+//! ```
+//! let import = gazelle::import::ebnf::parse(r#"
+//! list   ::= '(' (item (',' item)*)? ')'
+//! item   ::= Number | list
+//! Number ::= [0-9]+ ('.' [0-9]+)?
+//! "#).unwrap();
+//! assert_eq!(import.grammar.to_gzl(), "\
+//! start list;
+//!
+//! terminals {
+//!     NUMBER: _ = /[0-9]+(\\.[0-9]+)?/
+//! }
+//!
+//! list = '(' list_1? ')' => lparen_rparen;
+//!
+//! list_1 = (item % ',') => item;
+//!
+//! item = NUMBER => number
+//!      | list => list;
+//! ");
+//! assert!(import.warnings.is_empty());
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use super::expr::{Expr, Lowering, regex};
+use super::{Import, Names, Warning, identifier, skip_comment, snake_case, syntax_error};
+use crate::grammar::{Grammar, GrammarError, GrammarErrorKind, Span, TerminalDef};
+use crate::lr::is_literal;
+
+/// Translate the W3C EBNF grammar `source` into a [`Grammar`].
+///
+/// Fails only if `source` is malformed or has no rules; everything that is
+/// dropped or approximated is reported in [`Import::warnings`].
+pub fn parse(source: &str) -> Result<Import, GrammarError> {
+    let mut parser = Parser {
+        tokens: lex(source)?,
+        pos: 0,
+        end: source.len(),
+        rules: Vec::new(),
+        warnings: Vec::new(),
+    };
+    while parser.pos < parser.tokens.len() {
+        parser.rule()?;
+    }
+    parser.build()
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    Literal(String),
+    /// `#xN`: a code point.
+    Char(char),
+    /// `[...]`: a character class, a rule number or a constraint, as written
+    /// inside.
+    Bracket(String),
+    Punct(&'static str),
+}
+
+const PUNCTS: &[&str] = &["::=", "|", "(", ")", "?", "*", "+", "-"];
+
+fn lex(source: &str) -> Result<Vec<(Tok, Span)>, GrammarError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let start = pos;
+        let c = bytes[pos];
+        if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        if source[pos..].starts_with("/*") {
+            pos = skip_comment(source, pos)?;
+            continue;
+        }
+        let tok = match c {
+            b'\'' | b'"' => {
+                let Some(end) = source[pos + 1..].find(c as char) else {
+                    return Err(syntax_error("Unterminated literal", start..start + 1));
+                };
+                pos += end + 2;
+                Tok::Literal(source[start + 1..pos - 1].to_string())
+            }
+            b'[' => {
+                let Some(end) = source[pos..].find(']') else {
+                    return Err(syntax_error("Unterminated [...]", start..start + 1));
+                };
+                // A class may contain ']' only as its first character
+                let end = if end == 1 || (end == 2 && bytes[pos + 1] == b'^') {
+                    source[pos + end + 1..]
+                        .find(']')
+                        .map_or(end, |more| end + 1 + more)
+                } else {
+                    end
+                };
+                pos += end + 1;
+                Tok::Bracket(source[start + 1..pos - 1].to_string())
+            }
+            b'#' => {
+                let (c, len) = code_point(&source[pos..])
+                    .ok_or_else(|| syntax_error("Expected a code point #xN", start..start + 1))?;
+                pos += len;
+                Tok::Char(c)
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                while pos < bytes.len()
+                    && (bytes[pos].is_ascii_alphanumeric() || matches!(bytes[pos], b'_' | b'.'))
+                {
+                    pos += 1;
+                }
+                Tok::Ident(source[start..pos].to_string())
+            }
+            _ => {
+                let Some(punct) = PUNCTS.iter().find(|p| source[pos..].starts_with(**p)) else {
+                    let c = source[pos..].chars().next().unwrap();
+                    return Err(syntax_error(
+                        format!("Unexpected character '{}'", c),
+                        start..start + c.len_utf8(),
+                    ));
+                };
+                pos += punct.len();
+                Tok::Punct(punct)
+            }
+        };
+        tokens.push((tok, start..pos));
+    }
+    Ok(tokens)
+}
+
+/// The code point `#xN` at the start of `text`, and its length.
+fn code_point(text: &str) -> Option<(char, usize)> {
+    let digits = text.strip_prefix("#x")?;
+    let len = digits
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(digits.len());
+    let c = char::from_u32(u32::from_str_radix(&digits[..len], 16).ok()?)?;
+    Some((c, len + 2))
+}
+
+/// The ranges of a class such as `[a-zA-Z]` or `[#x20#x9#xD#xA]`, with
+/// whether it is negated.
+fn char_class(raw: &str) -> Option<(Vec<(char, char)>, bool)> {
+    let (raw, negated) = match raw.strip_prefix('^') {
+        Some(rest) => (rest, true),
+        None => (raw, false),
+    };
+    let mut chars = Vec::new();
+    let mut rest = raw;
+    while !rest.is_empty() {
+        if let Some((c, len)) = code_point(rest) {
+            chars.push((c, false));
+            rest = &rest[len..];
+        } else {
+            let c = rest.chars().next().unwrap();
+            chars.push((c, c == '-'));
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if i + 2 < chars.len() && chars[i + 1].1 {
+            ranges.push((chars[i].0, chars[i + 2].0));
+            i += 3;
+        } else {
+            ranges.push((chars[i].0, chars[i].0));
+            i += 1;
+        }
+    }
+    (!ranges.is_empty()).then_some((ranges, negated))
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+struct RawRule {
+    name: String,
+    span: Span,
+    body: Expr,
+    /// Whether the body matches characters directly.
+    chars: bool,
+}
+
+struct Parser {
+    tokens: Vec<(Tok, Span)>,
+    pos: usize,
+    end: usize,
+    rules: Vec<RawRule>,
+    warnings: Vec<Warning>,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn span(&self) -> Span {
+        match self.tokens.get(self.pos) {
+            Some((_, span)) => span.clone(),
+            None => self.end..self.end,
+        }
+    }
+
+    fn at(&self, punct: &str) -> bool {
+        matches!(self.peek(), Some(Tok::Punct(p)) if *p == punct)
+    }
+
+    fn at_punct(&self, offset: usize, punct: &str) -> bool {
+        matches!(self.tokens.get(self.pos + offset), Some((Tok::Punct(p), _)) if *p == punct)
+    }
+
+    fn warn(&mut self, message: impl Into<String>, span: Span) {
+        self.warnings.push(Warning::new(message, Some(span)));
+    }
+
+    /// Whether a rule starts here: `Name ::=`, or `[n] Name ::=`.
+    fn at_rule_start(&self) -> bool {
+        match self.peek() {
+            Some(Tok::Ident(_)) => self.at_punct(1, "::="),
+            Some(Tok::Bracket(n)) if is_number(n) => {
+                matches!(self.tokens.get(self.pos + 1), Some((Tok::Ident(_), _)))
+                    && self.at_punct(2, "::=")
+            }
+            _ => false,
+        }
+    }
+
+    fn rule(&mut self) -> Result<(), GrammarError> {
+        if matches!(self.peek(), Some(Tok::Bracket(n)) if is_number(n)) {
+            self.pos += 1;
+        }
+        let span = self.span();
+        let name = match self.peek() {
+            Some(Tok::Ident(name)) if self.at_punct(1, "::=") => name.clone(),
+            _ => return Err(syntax_error("Expected a rule 'Name ::= ...'", span)),
+        };
+        self.pos += 2;
+        let mut chars = false;
+        let body = self.choice(&name, &mut chars)?;
+        if !self.at_rule_start() && self.pos < self.tokens.len() {
+            return Err(syntax_error("Unexpected token in a rule", self.span()));
+        }
+        if self.rules.iter().any(|r| r.name == name) {
+            return Err(syntax_error(
+                format!("Rule '{}' is defined twice", name),
+                span,
+            ));
+        }
+        self.rules.push(RawRule {
+            name,
+            span,
+            body,
+            chars,
+        });
+        Ok(())
+    }
+
+    fn choice(&mut self, rule: &str, chars: &mut bool) -> Result<Expr, GrammarError> {
+        let mut alts = vec![self.sequence(rule, chars)?];
+        while self.at("|") {
+            self.pos += 1;
+            alts.push(self.sequence(rule, chars)?);
+        }
+        Ok(Expr::alt(alts))
+    }
+
+    fn sequence(&mut self, rule: &str, chars: &mut bool) -> Result<Expr, GrammarError> {
+        let mut items = Vec::new();
+        loop {
+            if self.at_rule_start() {
+                break;
+            }
+            let span = self.span();
+            match self.peek() {
+                None | Some(Tok::Punct("|" | ")")) => break,
+                Some(Tok::Bracket(raw)) if is_constraint(raw) => {
+                    let constraint = raw.trim().to_string();
+                    self.pos += 1;
+                    self.warn(
+                        format!("Dropped the constraint [{}] of rule '{}'", constraint, rule),
+                        span,
+                    );
+                }
+                _ => {
+                    let item = self.exception(rule, chars)?;
+                    items.push(item);
+                }
+            }
+        }
+        Ok(Expr::seq(items))
+    }
+
+    /// `A - B`, which matches `A` only.
+    fn exception(&mut self, rule: &str, chars: &mut bool) -> Result<Expr, GrammarError> {
+        let item = self.item(rule, chars)?;
+        if self.at("-") {
+            let start = self.span().start;
+            self.pos += 1;
+            self.item(rule, &mut false)?;
+            let end = self.tokens[self.pos - 1].1.end;
+            self.warn(
+                format!(
+                    "Dropped an exception from rule '{}'; it matches more than it should",
+                    rule
+                ),
+                start..end,
+            );
+        }
+        Ok(item)
+    }
+
+    fn item(&mut self, rule: &str, chars: &mut bool) -> Result<Expr, GrammarError> {
+        let mut item = self.primary(rule, chars)?;
+        loop {
+            item = match self.peek() {
+                Some(Tok::Punct("?")) => Expr::Optional(Box::new(item)),
+                Some(Tok::Punct("*")) => Expr::ZeroOrMore(Box::new(item)),
+                Some(Tok::Punct("+")) => Expr::OneOrMore(Box::new(item)),
+                _ => return Ok(item),
+            };
+            self.pos += 1;
+        }
+    }
+
+    fn primary(&mut self, rule: &str, chars: &mut bool) -> Result<Expr, GrammarError> {
+        let span = self.span();
+        let Some(tok) = self.peek().cloned() else {
+            return Err(syntax_error("Expected an expression", span));
+        };
+        self.pos += 1;
+        Ok(match tok {
+            Tok::Ident(name) => Expr::Ref(name, span),
+            Tok::Literal(text) => Expr::Literal(text),
+            Tok::Char(c) => {
+                *chars = true;
+                Expr::Literal(c.to_string())
+            }
+            Tok::Bracket(raw) => {
+                let Some((ranges, negated)) = char_class(&raw) else {
+                    return Err(syntax_error("Expected a character class", span));
+                };
+                *chars = true;
+                Expr::Class { ranges, negated }
+            }
+            Tok::Punct("(") => {
+                let inner = self.choice(rule, chars)?;
+                if !self.at(")") {
+                    return Err(syntax_error("Expected ')'", self.span()));
+                }
+                self.pos += 1;
+                inner
+            }
+            _ => return Err(syntax_error("Expected an expression", span)),
+        })
+    }
+
+    // ========================================================================
+    // Translation
+    // ========================================================================
+
+    fn build(mut self) -> Result<Import, GrammarError> {
+        let rules = std::mem::take(&mut self.rules);
+        if rules.is_empty() {
+            return Err(GrammarError::new(
+                GrammarErrorKind::Invalid,
+                "The grammar has no rules",
+            ));
+        }
+        let lexical = lexical_rules(&rules);
+        let bodies: HashMap<&str, &Expr> = rules
+            .iter()
+            .filter(|r| lexical.contains(r.name.as_str()))
+            .map(|r| (r.name.as_str(), &r.body))
+            .collect();
+        let lookup = |name: &str| bodies.get(name).copied();
+        let (lexical_rules, grammar_rules): (Vec<&RawRule>, Vec<&RawRule>) = rules
+            .iter()
+            .partition(|r| lexical.contains(r.name.as_str()));
+
+        let mut names = Names::default();
+        for rule in &grammar_rules {
+            names.fresh(&rule.name, &identifier(&snake_case(&rule.name)));
+        }
+
+        // Lexical rules that the grammar rules refer to
+        let mut referenced = HashSet::new();
+        for rule in &grammar_rules {
+            rule.body.visit_leaves(&mut |leaf| {
+                if let Expr::Ref(name, _) = leaf {
+                    referenced.insert(name.as_str());
+                }
+            });
+        }
+        let mut terminals = Vec::new();
+        for rule in lexical_rules
+            .iter()
+            .filter(|r| referenced.contains(r.name.as_str()))
+        {
+            let name = names.fresh(&rule.name, &terminal_name(&rule.name));
+            let pattern = match regex(&rule.body, &lookup) {
+                Ok(pattern) => Some(pattern),
+                Err((reason, span)) => {
+                    self.warnings.push(Warning::new(
+                        format!(
+                            "Lexical rule '{}' has no regex pattern: {}",
+                            rule.name, reason
+                        ),
+                        span.or(Some(rule.span.clone())),
+                    ));
+                    None
+                }
+            };
+            terminals.push(TerminalDef {
+                name,
+                has_type: !matches!(rule.body, Expr::Literal(_)),
+                is_prec: false,
+                pattern,
+                doc: Vec::new(),
+                span: None,
+            });
+        }
+
+        // Grammar rules, with references resolved to grammar names and
+        // character classes to terminals
+        let mut classes: HashMap<String, String> = HashMap::new();
+        let mut resolved = Vec::new();
+        for rule in &grammar_rules {
+            let mut resolve = |leaf: &Expr| match leaf {
+                Expr::Ref(name, span) => {
+                    if !names.contains(name) {
+                        let terminal_name = names.fresh(name, &terminal_name(name));
+                        self.warnings.push(Warning::new(
+                            format!(
+                                "'{}' is not defined in this grammar; declared as terminal {}",
+                                name, terminal_name
+                            ),
+                            Some(span.clone()),
+                        ));
+                        terminals.push(terminal(terminal_name, None));
+                    }
+                    Expr::Ref(names.lookup(name).unwrap().to_string(), span.clone())
+                }
+                Expr::Class { .. } => match regex(leaf, &|_| None) {
+                    Ok(pattern) => {
+                        let name = classes.entry(pattern.clone()).or_insert_with(|| {
+                            let name = names.fresh(&format!("#{}", pattern), "CHAR");
+                            terminals.push(terminal(name.clone(), Some(pattern)));
+                            name
+                        });
+                        Expr::Ref(name.clone(), rule.span.clone())
+                    }
+                    Err((reason, _)) => Expr::Unsupported(reason, rule.span.clone()),
+                },
+                _ => leaf.clone(),
+            };
+            let body = rule.body.map_leaves(&mut resolve);
+            let alts: Vec<(Expr, Option<String>)> = match body {
+                Expr::Alt(alts) => alts.into_iter().map(|a| (a, None)).collect(),
+                body => vec![(body, None)],
+            };
+            resolved.push((
+                names.lookup(&rule.name).unwrap().to_string(),
+                rule.span.clone(),
+                alts,
+            ));
+        }
+
+        let terminal_names: HashSet<String> = terminals.iter().map(|t| t.name.clone()).collect();
+        let is_terminal = |s: &str| is_literal(s) || terminal_names.contains(s);
+        let mut lowering = Lowering {
+            names: &mut names,
+            is_terminal: &is_terminal,
+            rules: Vec::new(),
+            placeholders: Vec::new(),
+            warnings: Vec::new(),
+        };
+        for (name, span, alts) in &resolved {
+            lowering.rule(name, span, alts);
+        }
+        let rules = lowering.rules;
+        terminals.extend(
+            lowering
+                .placeholders
+                .into_iter()
+                .map(|name| terminal(name, None)),
+        );
+        let mut warnings = self.warnings;
+        warnings.extend(lowering.warnings);
+        warnings.sort_by_key(|w| w.span.as_ref().map_or(usize::MAX, |s| s.start));
+        Ok(Import {
+            grammar: Grammar {
+                start: resolved[0].0.clone(),
+                extra_starts: Vec::new(),
+                expect_rr: 0,
+                expect_sr: 0,
+                split_tokens: false,
+                terminals,
+                rules,
+                attrs: Vec::new(),
+                terminal_attrs: Vec::new(),
+                comments: Vec::new(),
+            },
+            warnings,
+        })
+    }
+}
+
+/// The names of the lexical rules: those that match characters directly or
+/// through other lexical rules, refer to nothing else, are not recursive and
+/// don't match the empty string. The first rule is never lexical.
+fn lexical_rules(rules: &[RawRule]) -> HashSet<&str> {
+    let refs: HashMap<&str, Vec<&str>> = rules
+        .iter()
+        .map(|r| {
+            let mut refs = Vec::new();
+            r.body.visit_leaves(&mut |leaf| {
+                if let Expr::Ref(name, _) = leaf {
+                    refs.push(name.as_str());
+                }
+            });
+            (r.name.as_str(), refs)
+        })
+        .collect();
+    let mut lexical: HashSet<&str> = HashSet::new();
+    loop {
+        let mut changed = false;
+        for rule in &rules[1..] {
+            let name = rule.name.as_str();
+            let rule_refs = &refs[name];
+            if lexical.contains(name) || !rule_refs.iter().all(|r| lexical.contains(r)) {
+                continue;
+            }
+            if (rule.chars || !rule_refs.is_empty()) && !nullable(&rule.body) {
+                lexical.insert(name);
+                changed = true;
+            }
+        }
+        // A rule joins only after everything it refers to, so none of them
+        // can be recursive
+        if !changed {
+            return lexical;
+        }
+    }
+}
+
+/// Whether `expr` matches the empty string, given that the rules it refers
+/// to don't.
+fn nullable(expr: &Expr) -> bool {
+    match expr {
+        Expr::Literal(text) => text.is_empty(),
+        Expr::Seq(items) => items.iter().all(nullable),
+        Expr::Alt(alts) => alts.iter().any(nullable),
+        Expr::Optional(_) | Expr::ZeroOrMore(_) => true,
+        Expr::OneOrMore(e) => nullable(e),
+        _ => false,
+    }
+}
+
+fn is_number(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_digit())
+}
+
+/// `[ wfc: ... ]` or `[ vc: ... ]`.
+fn is_constraint(text: &str) -> bool {
+    let text = text.trim_start();
+    text.starts_with("wfc:") || text.starts_with("vc:")
+}
+
+/// A terminal name in gazelle's style: `NameChar` → `NAME_CHAR`.
+fn terminal_name(name: &str) -> String {
+    identifier(&snake_case(name).to_uppercase())
+}
+
+fn terminal(name: String, pattern: Option<String>) -> TerminalDef {
+    TerminalDef {
+        name,
+        has_type: pattern.is_some(),
+        is_prec: false,
+        pattern,
+        doc: Vec::new(),
+        span: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Adapted from the XML 1.0 specification
+    const XML: &str = r#"
+[1]  document ::= prolog element Misc*
+[22] prolog   ::= XMLDecl? Misc*
+[23] XMLDecl  ::= '<?xml' S? '?>'
+[27] Misc     ::= Comment | S
+[15] Comment  ::= '<!--' ((Char - '-') | ('-' (Char - '-')))* '-->'
+[39] element  ::= '<' Name '/>'
+                | '<' Name '>' content '</' Name '>'  [ wfc: Element Type Match ]
+[43] content  ::= CharData? (element CharData?)*
+[14] CharData ::= [^<&]*
+[5]  Name     ::= [a-zA-Z_:] [a-zA-Z0-9_:.-]*
+[2]  Char     ::= #x9 | #xA | #xD | [#x20-#x7E]
+[3]  S        ::= (#x20 | #x9 | #xD | #xA)+
+"#;
+
+    #[test]
+    fn test_xml() {
+        let import = parse(XML).unwrap();
+        assert_eq!(
+            import.grammar.to_gzl(),
+            r#"start document;
+
+terminals {
+    XML_DECL: _ = /<\?xml(( |\t|\r|\n)+)?\?>/,
+    MISC: _ = /<!\-\-(\t|\n|\r|[ -~]|\-(\t|\n|\r|[ -~]))*\-\->|( |\t|\r|\n)+/,
+    NAME: _ = /[a-zA-Z_:][a-zA-Z0-9_:.\-]*/,
+    CHAR: _ = /[^<&]/
+}
+
+document = prolog element MISC* => misc;
+
+prolog = XML_DECL? MISC* => xml_decl_misc;
+
+element = '<' NAME "/>" => lt_name_slash_gt
+        | '<' NAME '>' content "</" NAME '>' => lt_name_gt_lt_slash_name_gt;
+
+content = char_data? content_1* => char_data_content_1;
+
+content_1 = element char_data? => element_char_data;
+
+char_data = CHAR* => char;
+"#
+        );
+        let messages: Vec<String> = import.warnings.iter().map(|w| w.format(XML)).collect();
+        assert_eq!(
+            messages,
+            [
+                "6:33: Dropped an exception from rule 'Comment'; it matches more than it should",
+                "6:53: Dropped an exception from rule 'Comment'; it matches more than it should",
+                "8:55: Dropped the constraint [wfc: Element Type Match] of rule 'element'",
+            ]
+        );
+    }
+
+    #[cfg(feature = "codegen")]
+    #[test]
+    fn test_lists_generate_code() {
+        let import = parse(
+            r#"
+value  ::= object | array | String | Number
+object ::= '{' (member (',' member)*)? '}'
+member ::= String ':' value
+array  ::= '[' (value (',' value)*)? ']'
+String ::= '"' [^"]* '"'
+Number ::= [0-9]+
+"#,
+        )
+        .unwrap();
+        let gzl = import.grammar.to_gzl();
+        assert!(gzl.contains("(member % ',')"), "{}", gzl);
+        assert!(gzl.contains("(value % ',')"), "{}", gzl);
+        let ctx = crate::codegen::CodegenContext::from_grammar(&import.grammar, "json", "", false)
+            .unwrap();
+        crate::codegen::generate_tokens(&ctx).unwrap();
+    }
+
+    #[test]
+    fn test_classes_and_errors() {
+        let import = parse("word ::= [a-z] [a-z0-9]* | [a-z] 'x'\n").unwrap();
+        let terminals: Vec<_> = import
+            .grammar
+            .terminals
+            .iter()
+            .map(|t| (t.name.as_str(), t.pattern.as_deref()))
+            .collect();
+        assert_eq!(
+            terminals,
+            [("CHAR", Some("[a-z]")), ("CHAR_2", Some("[a-z0-9]"))]
+        );
+
+        assert_eq!(
+            parse("/* nothing */").unwrap_err().message,
+            "The grammar has no rules"
+        );
+        let source = "a ::= 'x'\na ::= 'y'\n";
+        assert_eq!(
+            parse(source).unwrap_err().format(source),
+            "2:1: Rule 'a' is defined twice"
+        );
+    }
+}
@@ -0,0 +1,535 @@
+//! Rule bodies as EBNF writes them, shared by the EBNF and ANTLR importers:
+//! lowering to gazelle rules, and lexical rules to regex patterns.
+
+use std::collections::HashSet;
+
+use super::{Names, Warning, variant_name};
+use crate::grammar::{Alt, Rule, Span, Spanned, Term};
+use crate::lr::literal_name;
+
+/// An EBNF expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Expr {
+    /// A rule or token, by name.
+    Ref(String, Span),
+    /// Literal text.
+    Literal(String),
+    /// A character class: inclusive ranges, or every character outside them.
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    /// Any character.
+    Any,
+    Seq(Vec<Expr>),
+    Alt(Vec<Expr>),
+    Optional(Box<Expr>),
+    ZeroOrMore(Box<Expr>),
+    OneOrMore(Box<Expr>),
+    /// A construct with no translation, and why.
+    Unsupported(String, Span),
+}
+
+impl Expr {
+    /// The empty sequence.
+    pub fn empty() -> Expr {
+        Expr::Seq(Vec::new())
+    }
+
+    /// A sequence of `items`, or the item itself if there is one.
+    pub fn seq(mut items: Vec<Expr>) -> Expr {
+        if items.len() == 1 {
+            items.pop().unwrap()
+        } else {
+            Expr::Seq(items)
+        }
+    }
+
+    /// A choice of `alts`, or the alternative itself if there is one.
+    pub fn alt(mut alts: Vec<Expr>) -> Expr {
+        if alts.len() == 1 {
+            alts.pop().unwrap()
+        } else {
+            Expr::Alt(alts)
+        }
+    }
+
+    /// The expression with each leaf (anything but a sequence, choice or
+    /// repetition) replaced by `f`.
+    pub fn map_leaves(&self, f: &mut impl FnMut(&Expr) -> Expr) -> Expr {
+        let boxed = |e: &Expr, f: &mut _| Box::new(e.map_leaves(f));
+        match self {
+            Expr::Seq(items) => Expr::Seq(items.iter().map(|e| e.map_leaves(f)).collect()),
+            Expr::Alt(alts) => Expr::Alt(alts.iter().map(|e| e.map_leaves(f)).collect()),
+            Expr::Optional(e) => Expr::Optional(boxed(e, f)),
+            Expr::ZeroOrMore(e) => Expr::ZeroOrMore(boxed(e, f)),
+            Expr::OneOrMore(e) => Expr::OneOrMore(boxed(e, f)),
+            leaf => f(leaf),
+        }
+    }
+
+    /// Call `f` on each leaf.
+    pub fn visit_leaves<'a>(&'a self, f: &mut impl FnMut(&'a Expr)) {
+        match self {
+            Expr::Seq(items) | Expr::Alt(items) => {
+                items.iter().for_each(|e| e.visit_leaves(f));
+            }
+            Expr::Optional(e) | Expr::ZeroOrMore(e) | Expr::OneOrMore(e) => e.visit_leaves(f),
+            leaf => f(leaf),
+        }
+    }
+
+    /// Whether the expression matches only the empty string.
+    fn is_empty(&self) -> bool {
+        match self {
+            Expr::Seq(items) => items.iter().all(Expr::is_empty),
+            Expr::Literal(text) => text.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+// ============================================================================
+// Lowering to rules
+// ============================================================================
+
+/// Build gazelle rules from rule bodies whose references already carry
+/// their grammar names. `?`, `*` and `+` on a symbol become term modifiers,
+/// `a (sep a)*` becomes `(a % sep)`, and any other group gets a helper rule
+/// named after the rule it appears in. A construct with no translation
+/// becomes a placeholder terminal, so its alternative still needs input.
+pub(super) struct Lowering<'a> {
+    pub names: &'a mut Names,
+    /// Whether a grammar name is a terminal, for variant names.
+    pub is_terminal: &'a dyn Fn(&str) -> bool,
+    pub rules: Vec<Rule>,
+    /// Placeholder terminals, for the importer to declare.
+    pub placeholders: Vec<String>,
+    pub warnings: Vec<Warning>,
+}
+
+impl Lowering<'_> {
+    /// Add rule `name` with the alternatives `alts`, each with an optional
+    /// variant name. `span` is the rule's source, for warnings about parts
+    /// with no span of their own.
+    pub fn rule(&mut self, name: &str, span: &Span, alts: &[(Expr, Option<String>)]) {
+        let index = self.rules.len();
+        self.rules.push(Rule {
+            name: name.to_string(),
+            params: Vec::new(),
+            alts: Vec::new(),
+            doc: Vec::new(),
+            attrs: Vec::new(),
+            span: None,
+        });
+        let mut lowered = Vec::new();
+        for (expr, label) in alts {
+            let mut terms = Vec::new();
+            self.sequence(name, span, expr, &mut terms);
+            if terms.is_empty() {
+                terms.push(Term::Empty);
+            }
+            lowered.push((terms, label));
+        }
+        let placeholders = &self.placeholders;
+        let is_terminal = |s: &str| (self.is_terminal)(s) || placeholders.iter().any(|p| p == s);
+        let mut used = HashSet::new();
+        // Labels claim their names before generated names are chosen
+        for (_, label) in &lowered {
+            if let Some(label) = label {
+                used.insert(label.clone());
+            }
+        }
+        self.rules[index].alts = lowered
+            .into_iter()
+            .map(|(terms, label)| Alt {
+                name: match label {
+                    Some(label) => label.clone(),
+                    None => variant_name(&terms, is_terminal, &mut used),
+                },
+                terms: terms.into_iter().map(Spanned::from).collect(),
+                labels: Vec::new(),
+                prec: None,
+                doc: Vec::new(),
+                span: None,
+            })
+            .collect();
+    }
+
+    fn sequence(&mut self, rule: &str, span: &Span, expr: &Expr, terms: &mut Vec<Term>) {
+        let items = match expr {
+            Expr::Seq(items) => items.as_slice(),
+            _ => std::slice::from_ref(expr),
+        };
+        let mut i = 0;
+        while i < items.len() {
+            if let Some(term) = separated(&items[i..]) {
+                terms.push(term);
+                i += 2;
+                continue;
+            }
+            let term = match &items[i] {
+                Expr::Seq(_) => {
+                    self.sequence(rule, span, &items[i], terms);
+                    None
+                }
+                Expr::Optional(e) => Some(Term::Optional(self.symbol(rule, span, e))),
+                Expr::ZeroOrMore(e) => Some(Term::ZeroOrMore(self.symbol(rule, span, e))),
+                Expr::OneOrMore(e) => Some(Term::OneOrMore(self.symbol(rule, span, e))),
+                e => Some(Term::Symbol(self.symbol(rule, span, e))),
+            };
+            terms.extend(term);
+            i += 1;
+        }
+    }
+
+    /// A symbol for `expr`: its name if it is a plain symbol, a placeholder
+    /// terminal if it has no translation, otherwise a new helper rule.
+    fn symbol(&mut self, rule: &str, span: &Span, expr: &Expr) -> String {
+        if let Some(name) = leaf_symbol(expr) {
+            return name;
+        }
+        match expr {
+            Expr::Seq(items) if items.len() == 1 => return self.symbol(rule, span, &items[0]),
+            Expr::Alt(alts) if alts.len() == 1 => return self.symbol(rule, span, &alts[0]),
+            Expr::Unsupported(reason, at) => return self.placeholder(rule, reason, at),
+            Expr::Class { .. } | Expr::Any => {
+                return self.placeholder(rule, "a character pattern", span);
+            }
+            _ => {}
+        }
+        let mut n = 1;
+        while self.names.contains(&format!("{}#{}", rule, n)) {
+            n += 1;
+        }
+        let helper = self
+            .names
+            .fresh(&format!("{}#{}", rule, n), &format!("{}_{}", rule, n));
+        let alts: Vec<(Expr, Option<String>)> = match expr {
+            Expr::Alt(alts) => alts.iter().map(|a| (a.clone(), None)).collect(),
+            _ => vec![(expr.clone(), None)],
+        };
+        self.rule(&helper, span, &alts);
+        helper
+    }
+
+    /// A new placeholder terminal standing in for `what`, which is at `span`.
+    fn placeholder(&mut self, rule: &str, what: &str, span: &Span) -> String {
+        let key = format!("#unsupported#{}", self.placeholders.len());
+        let name = self.names.fresh(&key, "UNSUPPORTED");
+        self.warnings.push(Warning::new(
+            format!(
+                "Replaced {} in rule '{}' with the placeholder terminal {}",
+                what, rule, name
+            ),
+            Some(span.clone()),
+        ));
+        self.placeholders.push(name.clone());
+        name
+    }
+}
+
+/// The symbol of a reference or literal.
+fn leaf_symbol(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ref(name, _) => Some(name.clone()),
+        Expr::Literal(text) => Some(literal_name(text)),
+        _ => None,
+    }
+}
+
+/// `a (sep a)*` at the start of `items`, as `(a % sep)`.
+fn separated(items: &[Expr]) -> Option<Term> {
+    let [first, Expr::ZeroOrMore(rest), ..] = items else {
+        return None;
+    };
+    let Expr::Seq(pair) = rest.as_ref() else {
+        return None;
+    };
+    let [sep, again] = pair.as_slice() else {
+        return None;
+    };
+    let symbol = leaf_symbol(first)?;
+    (leaf_symbol(again)? == symbol).then_some(())?;
+    Some(Term::SeparatedBy {
+        symbol,
+        sep: leaf_symbol(sep)?,
+    })
+}
+
+// ============================================================================
+// Regex patterns
+// ============================================================================
+
+/// Why an expression has no regex: a reason, and where.
+pub(super) type NoRegex = (String, Option<Span>);
+
+/// How tightly a regex binds, to decide where it needs parentheses.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    Alt,
+    Seq,
+    Atom,
+}
+
+/// The regex pattern for a lexical expression. References are inlined with
+/// `lookup`; they may not be recursive.
+pub(super) fn regex<'a>(
+    expr: &'a Expr,
+    lookup: &dyn Fn(&str) -> Option<&'a Expr>,
+) -> Result<String, NoRegex> {
+    if expr.is_empty() {
+        return Err(("it matches only the empty string".to_string(), None));
+    }
+    Ok(regex_rec(expr, lookup, &mut Vec::new())?.0)
+}
+
+fn regex_rec<'a>(
+    expr: &'a Expr,
+    lookup: &dyn Fn(&str) -> Option<&'a Expr>,
+    stack: &mut Vec<&'a str>,
+) -> Result<(String, Level), NoRegex> {
+    Ok(match expr {
+        Expr::Literal(text) => {
+            let escaped: String = text.chars().map(escape).collect();
+            // A non-ASCII character is several bytes to the regex
+            let atom = text.chars().count() == 1 && text.is_ascii();
+            (escaped, if atom { Level::Atom } else { Level::Seq })
+        }
+        Expr::Class { ranges, negated } => class(ranges, *negated)?,
+        Expr::Any => (".".to_string(), Level::Atom),
+        Expr::Seq(items) => {
+            let mut out = String::new();
+            for item in items {
+                out.push_str(&wrap(regex_rec(item, lookup, stack)?, Level::Seq));
+            }
+            (out, Level::Seq)
+        }
+        Expr::Alt(alts) => {
+            // An empty alternative makes the rest optional
+            let (empty, alts): (Vec<&Expr>, Vec<&Expr>) = alts.iter().partition(|a| a.is_empty());
+            let mut parts = Vec::new();
+            for alt in alts {
+                parts.push(wrap(regex_rec(alt, lookup, stack)?, Level::Alt));
+            }
+            let choice = (parts.join("|"), Level::Alt);
+            if empty.is_empty() {
+                choice
+            } else {
+                (format!("{}?", wrap(choice, Level::Atom)), Level::Seq)
+            }
+        }
+        Expr::Optional(e) => repeat(regex_rec(e, lookup, stack)?, '?'),
+        Expr::ZeroOrMore(e) => repeat(regex_rec(e, lookup, stack)?, '*'),
+        Expr::OneOrMore(e) => repeat(regex_rec(e, lookup, stack)?, '+'),
+        Expr::Ref(name, span) => {
+            if stack.contains(&name.as_str()) {
+                return Err((format!("'{}' is recursive", name), Some(span.clone())));
+            }
+            let Some(body) = lookup(name) else {
+                return Err((
+                    format!("'{}' is not a lexical rule", name),
+                    Some(span.clone()),
+                ));
+            };
+            stack.push(name);
+            let result = regex_rec(body, lookup, stack)?;
+            stack.pop();
+            result
+        }
+        Expr::Unsupported(reason, span) => return Err((reason.clone(), Some(span.clone()))),
+    })
+}
+
+fn wrap((regex, level): (String, Level), min: Level) -> String {
+    if level >= min {
+        regex
+    } else {
+        format!("({})", regex)
+    }
+}
+
+fn repeat(inner: (String, Level), op: char) -> (String, Level) {
+    // A repetition is wrapped again if repeated, so `*` never follows `+`
+    (format!("{}{}", wrap(inner, Level::Atom), op), Level::Seq)
+}
+
+/// A character class. The regex matches bytes, so ranges must be ASCII; a
+/// few single non-ASCII characters become a choice instead.
+fn class(ranges: &[(char, char)], negated: bool) -> Result<(String, Level), NoRegex> {
+    if ranges.iter().all(|(lo, hi)| lo.is_ascii() && hi.is_ascii()) {
+        let mut out = String::from(if negated { "[^" } else { "[" });
+        for &(lo, hi) in ranges {
+            out.push_str(&escape_in_class(lo));
+            if hi != lo {
+                out.push('-');
+                out.push_str(&escape_in_class(hi));
+            }
+        }
+        out.push(']');
+        return Ok((out, Level::Atom));
+    }
+    if negated || ranges.iter().any(|(lo, hi)| lo != hi) {
+        return Err(("a character class with non-ASCII ranges".to_string(), None));
+    }
+    let alts: Vec<String> = ranges.iter().map(|&(c, _)| escape(c)).collect();
+    Ok((alts.join("|"), Level::Alt))
+}
+
+/// A character outside a class, escaped.
+fn escape(c: char) -> String {
+    match c {
+        '\\' | '|' | '(' | ')' | '[' | ']' | '*' | '+' | '?' | '.' | '^' | '$' | '{' | '}'
+        | '-' => format!("\\{}", c),
+        _ => escape_control(c),
+    }
+}
+
+/// A character in a class, escaped.
+fn escape_in_class(c: char) -> String {
+    match c {
+        '\\' | ']' | '[' | '^' | '-' => format!("\\{}", c),
+        _ => escape_control(c),
+    }
+}
+
+fn escape_control(c: char) -> String {
+    match c {
+        '\n' => "\\n".to_string(),
+        '\t' => "\\t".to_string(),
+        '\r' => "\\r".to_string(),
+        c if c.is_ascii_control() => format!("\\x{:02x}", c as u32),
+        c => c.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lit(text: &str) -> Expr {
+        Expr::Literal(text.to_string())
+    }
+
+    fn class(ranges: &[(char, char)]) -> Expr {
+        Expr::Class {
+            ranges: ranges.to_vec(),
+            negated: false,
+        }
+    }
+
+    #[test]
+    fn test_regex() {
+        let digits = Expr::OneOrMore(Box::new(class(&[('0', '9')])));
+        let fragments = [("DIGITS", digits.clone())];
+        let lookup = |name: &str| fragments.iter().find(|(n, _)| *n == name).map(|(_, e)| e);
+        let number = Expr::Seq(vec![
+            Expr::Ref("DIGITS".to_string(), 0..0),
+            Expr::Optional(Box::new(Expr::Seq(vec![
+                lit("."),
+                Expr::Ref("DIGITS".to_string(), 0..0),
+            ]))),
+        ]);
+        assert_eq!(regex(&number, &lookup).unwrap(), r"[0-9]+(\.[0-9]+)?");
+        let word = Expr::Alt(vec![lit("if"), lit("else"), Expr::empty()]);
+        assert_eq!(regex(&word, &lookup).unwrap(), "(if|else)?");
+        let string = Expr::Seq(vec![
+            lit("\""),
+            Expr::ZeroOrMore(Box::new(Expr::Class {
+                ranges: vec![('"', '"'), ('\\', '\\'), ('\n', '\n')],
+                negated: true,
+            })),
+            lit("\""),
+        ]);
+        assert_eq!(regex(&string, &lookup).unwrap(), r#""[^"\\\n]*""#);
+        let wide = class(&[('\u{80}', '\u{ffff}')]);
+        assert!(regex(&wide, &lookup).is_err());
+        let cyclic = Expr::Ref("LOOP".to_string(), 0..0);
+        let lookup = |_: &str| Some(&cyclic);
+        assert_eq!(
+            regex(&cyclic, &lookup).unwrap_err().0,
+            "'LOOP' is recursive"
+        );
+    }
+
+    #[test]
+    fn test_lowering() {
+        let sym = |name: &str| Expr::Ref(name.to_string(), 0..0);
+        let mut names = Names::default();
+        let is_terminal = |s: &str| s.chars().all(|c| !c.is_ascii_lowercase());
+        let mut lowering = Lowering {
+            names: &mut names,
+            is_terminal: &is_terminal,
+            rules: Vec::new(),
+            placeholders: Vec::new(),
+            warnings: Vec::new(),
+        };
+        // args = expr (',' expr)* | '(' (A | B C)+ ')'? ;
+        let list = Expr::Seq(vec![
+            sym("expr"),
+            Expr::ZeroOrMore(Box::new(Expr::Seq(vec![lit(","), sym("expr")]))),
+        ]);
+        let group = Expr::Seq(vec![
+            lit("("),
+            Expr::OneOrMore(Box::new(Expr::Alt(vec![
+                sym("A"),
+                Expr::Seq(vec![sym("B"), sym("C")]),
+            ]))),
+            Expr::Optional(Box::new(lit(")"))),
+        ]);
+        lowering.rule(
+            "args",
+            &(0..0),
+            &[(list, None), (group, Some("paren".to_string()))],
+        );
+        let gzl: Vec<String> = lowering
+            .rules
+            .iter()
+            .map(|r| crate::format::rule_lines(&r.name, "=", &r.alts, Vec::new(), None))
+            .flat_map(|lines| lines.into_iter().map(|l| l.text))
+            .collect();
+        assert_eq!(
+            gzl,
+            [
+                "args = (expr % ',') => expr",
+                "     | '(' args_1+ ')'? => paren;",
+                "args_1 = A => a",
+                "       | B C => b_c;",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lowering_unsupported() {
+        let mut names = Names::default();
+        let is_terminal = |s: &str| s.chars().all(|c| !c.is_ascii_lowercase());
+        let mut lowering = Lowering {
+            names: &mut names,
+            is_terminal: &is_terminal,
+            rules: Vec::new(),
+            placeholders: Vec::new(),
+            warnings: Vec::new(),
+        };
+        // An alternative of only untranslatable parts still needs input
+        let unsupported = Expr::Unsupported("a predicate".to_string(), 4..9);
+        let group = Expr::Optional(Box::new(Expr::Seq(vec![lit("x"), Expr::Any])));
+        lowering.rule("r", &(0..20), &[(unsupported, None), (group, None)]);
+        let gzl: Vec<String> = lowering
+            .rules
+            .iter()
+            .map(|r| crate::format::rule_lines(&r.name, "=", &r.alts, Vec::new(), None))
+            .flat_map(|lines| lines.into_iter().map(|l| l.text))
+            .collect();
+        assert_eq!(
+            gzl,
+            [
+                "r = UNSUPPORTED => unsupported",
+                "  | r_1? => r_1;",
+                "r_1 = 'x' UNSUPPORTED_2 => unsupported_2;",
+            ]
+        );
+        assert_eq!(lowering.placeholders, ["UNSUPPORTED", "UNSUPPORTED_2"]);
+        let spans: Vec<_> = lowering.warnings.iter().map(|w| w.span.clone()).collect();
+        assert_eq!(spans, [Some(4..9), Some(0..20)]);
+    }
+}
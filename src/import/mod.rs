@@ -0,0 +1,321 @@
+//! Importers that translate grammars written for other tools into a
+//! [`Grammar`].
+//!
+//! An import keeps the structure of the source grammar and drops what has no
+//! counterpart in gazelle, such as embedded action code. Each dropped or
+//! approximated construct is reported as a [`Warning`], so the result can be
+//! printed with [`Grammar::to_gzl`] and finished by hand.
+
+pub mod antlr;
+pub mod ebnf;
+mod expr;
+pub mod yacc;
+
+use std::collections::{HashMap, HashSet};
+
+use crate::grammar::{Grammar, GrammarError, GrammarErrorKind, Span, Term};
+use crate::lr::{is_literal, to_camel_case};
+
+/// A grammar translated from another format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Import {
+    /// The translated grammar.
+    pub grammar: Grammar,
+    /// What was dropped or approximated, in source order.
+    pub warnings: Vec<Warning>,
+}
+
+/// Something an importer could not translate faithfully.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// The warning message.
+    pub message: String,
+    /// Location in the imported source, if known.
+    pub span: Option<Span>,
+}
+
+impl Warning {
+    pub(crate) fn new(message: impl Into<String>, span: Option<Span>) -> Self {
+        Warning {
+            message: message.into(),
+            span,
+        }
+    }
+
+    /// Format the warning with the line and column of its span in `source`.
+    pub fn format(&self, source: &str) -> String {
+        crate::grammar::format_located(&self.message, self.span.as_ref(), source)
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Words the grammar syntax reserves, which cannot name symbols.
+const KEYWORDS: &[&str] = &["start", "terminals", "prec", "expect", "mode"];
+
+/// A valid symbol name for `name`: characters other than ASCII letters,
+/// digits and `_` become `_`, and names that are reserved, start with a digit
+/// or with `__` (reserved for generated rules) get adjusted.
+pub(crate) fn identifier(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    while out.starts_with("__") {
+        out.remove(0);
+    }
+    if out == "_" {
+        out = "underscore".to_string();
+    }
+    if KEYWORDS.contains(&out.as_str()) {
+        out.push('_');
+    }
+    out
+}
+
+/// snake_case for a symbol: `exprList` → "expr_list", `XMLDecl` →
+/// "xml_decl", `LPAREN` → "lparen", `'+'` → "plus".
+pub(crate) fn snake_case(name: &str) -> String {
+    let literal = is_literal(name);
+    let name: Vec<char> = if literal {
+        to_camel_case(name).chars().collect()
+    } else {
+        name.chars().collect()
+    };
+    let mut out = String::new();
+    for (i, &c) in name.iter().enumerate() {
+        let prev = i.checked_sub(1).map(|i| name[i]);
+        let next = name.get(i + 1);
+        // The last capital of an acronym starts the next word, except in the
+        // spelled-out names of literals (`LParen`)
+        let boundary = c.is_ascii_uppercase()
+            && prev.is_some_and(|p| {
+                p.is_ascii_lowercase()
+                    || p.is_ascii_digit()
+                    || (!literal
+                        && p.is_ascii_uppercase()
+                        && next.is_some_and(|n| n.is_ascii_lowercase()))
+            });
+        if boundary {
+            out.push('_');
+        }
+        out.push(c.to_ascii_lowercase());
+    }
+    out.trim_matches('_').to_string()
+}
+
+/// A variant name for an alternative: its terminals in snake_case, or its
+/// rules if it has no terminals, or `empty`. Made unique among `used`.
+pub(crate) fn variant_name(
+    terms: &[Term],
+    is_terminal: impl Fn(&str) -> bool,
+    used: &mut HashSet<String>,
+) -> String {
+    let mut terminals = Vec::new();
+    let mut rules = Vec::new();
+    for term in terms {
+        let symbol = match term {
+            Term::Symbol(s) | Term::Optional(s) | Term::ZeroOrMore(s) | Term::OneOrMore(s) => s,
+            Term::SeparatedBy { symbol, .. }
+            | Term::SeparatedTrailing { symbol, .. }
+            | Term::TerminatedBy { symbol, .. }
+            | Term::ZeroOrMoreTerminatedBy { symbol, .. } => symbol,
+            _ => continue,
+        };
+        let part = snake_case(symbol);
+        if part.is_empty() {
+            continue;
+        }
+        if is_terminal(symbol) {
+            terminals.push(part);
+        } else {
+            rules.push(part);
+        }
+    }
+    let parts = if terminals.is_empty() {
+        rules
+    } else {
+        terminals
+    };
+    let base = if parts.is_empty() {
+        "empty".to_string()
+    } else {
+        parts.join("_")
+    };
+    let base = identifier(&base);
+    let mut name = base.clone();
+    let mut n = 2;
+    while !used.insert(name.clone()) {
+        name = format!("{}_{}", base, n);
+        n += 1;
+    }
+    name
+}
+
+/// Symbol names of a translated grammar, kept distinct.
+#[derive(Default)]
+pub(crate) struct Names {
+    names: HashMap<String, String>,
+    taken: HashSet<String>,
+}
+
+impl Names {
+    pub(crate) fn contains(&self, symbol: &str) -> bool {
+        self.names.contains_key(symbol)
+    }
+
+    /// The name given to `symbol`, if any.
+    pub(crate) fn lookup(&self, symbol: &str) -> Option<&str> {
+        self.names.get(symbol).map(String::as_str)
+    }
+
+    /// The name for `symbol`, reporting a rename the first time.
+    pub(crate) fn get(
+        &mut self,
+        symbol: &str,
+        warnings: &mut Vec<Warning>,
+        span: Option<Span>,
+    ) -> String {
+        if let Some(name) = self.names.get(symbol) {
+            return name.clone();
+        }
+        let name = self.fresh(symbol, &identifier(symbol));
+        if name != symbol {
+            warnings.push(Warning::new(
+                format!("Renamed '{}' to '{}'", symbol, name),
+                span,
+            ));
+        }
+        name
+    }
+
+    /// Name `symbol` after `base`, made unique.
+    pub(crate) fn fresh(&mut self, symbol: &str, base: &str) -> String {
+        let base = if base.is_empty() { "TOKEN" } else { base };
+        let mut name = base.to_string();
+        let mut n = 2;
+        while !self.taken.insert(name.clone()) {
+            name = format!("{}_{}", base, n);
+            n += 1;
+        }
+        self.names.insert(symbol.to_string(), name.clone());
+        name
+    }
+}
+
+// ============================================================================
+// Source scanning
+// ============================================================================
+
+pub(crate) fn syntax_error(message: impl Into<String>, span: Span) -> GrammarError {
+    GrammarError::new(GrammarErrorKind::Syntax, message).at(Some(span))
+}
+
+/// Skip the comment at `pos`, returning the offset after it.
+pub(crate) fn skip_comment(source: &str, pos: usize) -> Result<usize, GrammarError> {
+    if source[pos..].starts_with("//") {
+        return Ok(source[pos..]
+            .find('\n')
+            .map_or(source.len(), |end| pos + end));
+    }
+    match source[pos + 2..].find("*/") {
+        Some(end) => Ok(pos + 2 + end + 2),
+        None => Err(syntax_error("Unterminated comment", pos..pos + 2)),
+    }
+}
+
+/// Skip the character or string literal at `pos`, returning the offset after it.
+pub(crate) fn skip_quoted(source: &str, pos: usize) -> Result<usize, GrammarError> {
+    let bytes = source.as_bytes();
+    let quote = bytes[pos];
+    let mut i = pos + 1;
+    while i < bytes.len() && bytes[i] != b'\n' {
+        match bytes[i] {
+            b'\\' => i += 2,
+            c if c == quote => return Ok(i + 1),
+            _ => i += 1,
+        }
+    }
+    Err(syntax_error(
+        "Unterminated literal",
+        pos..i.min(bytes.len()),
+    ))
+}
+
+/// Skip the braced code starting at or after `pos`, returning the offset after
+/// its closing brace. Braces in strings, characters and comments don't count.
+pub(crate) fn skip_code(source: &str, pos: usize) -> Result<usize, GrammarError> {
+    let bytes = source.as_bytes();
+    let Some(open) = source[pos..].find('{') else {
+        return Err(syntax_error("Expected '{'", pos..pos));
+    };
+    let mut i = pos + open;
+    let mut depth = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(i + 1);
+                }
+            }
+            b'\'' | b'"' => {
+                // A quote in code that doesn't close on its line (`$<'` or an
+                // apostrophe in a macro) is taken as a plain character
+                i = skip_quoted(source, i).unwrap_or(i + 1);
+                continue;
+            }
+            b'/' if matches!(bytes.get(i + 1), Some(b'*' | b'/')) => {
+                i = skip_comment(source, i)?;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    Err(syntax_error("Unterminated code block", pos..pos + open + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(identifier("expr.list"), "expr_list");
+        assert_eq!(identifier("start"), "start_");
+        assert_eq!(identifier("__x"), "_x");
+        assert_eq!(snake_case("exprList"), "expr_list");
+        assert_eq!(snake_case("XMLDecl"), "xml_decl");
+        assert_eq!(snake_case("LPAREN"), "lparen");
+        assert_eq!(snake_case("'+'"), "plus");
+        assert_eq!(snake_case("\"while\""), "while");
+
+        let mut used = HashSet::new();
+        let terms = |names: &[&str]| -> Vec<Term> {
+            names.iter().map(|n| Term::Symbol(n.to_string())).collect()
+        };
+        let is_terminal = |s: &str| s.chars().all(|c| !c.is_ascii_lowercase());
+        assert_eq!(
+            variant_name(&terms(&["expr", "'+'", "expr"]), is_terminal, &mut used),
+            "plus"
+        );
+        assert_eq!(
+            variant_name(&terms(&["expr", "PLUS", "term"]), is_terminal, &mut used),
+            "plus_2"
+        );
+        assert_eq!(
+            variant_name(&terms(&["stmt", "stmts"]), is_terminal, &mut used),
+            "stmt_stmts"
+        );
+        assert_eq!(variant_name(&[], is_terminal, &mut used), "empty");
+    }
+}
@@ -0,0 +1,967 @@
+//! Import of Bison and yacc grammars (`.y` files).
+//!
+//! Token declarations, rules, `%start` and `%expect` carry over. Tokens with
+//! `%left`, `%right`, `%nonassoc` or `%precedence` become `prec` terminals,
+//! documented with the [`Precedence`] to push for them; each declaration line
+//! is one level, and later lines bind tighter, as in Bison. A precedence
+//! declared on a character literal such as `'+'` moves to a named terminal
+//! (`PLUS`), since literals cannot be `prec`. `%prec` on a rule becomes a fixed
+//! `prec(N, assoc)`.
+//!
+//! C code has no counterpart: actions, the prologue, the epilogue, `%union`
+//! and `%code` are dropped and reported, and so are parser settings such as
+//! `%define`. Alternatives get variant names from
+//! their terminals, such as `=> plus` for `expr '+' expr`.
+//!
+//! ```
+//! let import = gazelle::import::yacc::parse(r#"
+//! %token NUM
+//! %left '+'
+//! %%
+//! expr: expr '+' expr { $$ = $1 + $3; }
+//!     | NUM
+//!     ;
+//! "#).unwrap();
+//! assert_eq!(import.warnings.len(), 1);
+//! assert_eq!(import.grammar.to_gzl(), "\
+//! start expr;
+//!
+//! terminals {
+//!     NUM,
+//!     /// `%left` level 1: push with `Precedence::Left(1)`.
+//!     prec PLUS
+//! }
+//!
+//! expr = expr PLUS expr => plus
+//!      | NUM => num;
+//! ");
+//! ```
+
+use std::collections::{HashMap, HashSet};
+
+use super::{
+    Import, Names, Warning, skip_code, skip_comment, skip_quoted, snake_case, syntax_error,
+    variant_name,
+};
+use crate::Precedence;
+use crate::grammar::{Alt, AltPrec, Grammar, GrammarError, Rule, Span, Spanned, Term, TerminalDef};
+use crate::lr::{is_literal, literal_name, literal_text, to_camel_case};
+
+/// Translate the Bison grammar `source` into a [`Grammar`].
+///
+/// Fails only if `source` is not a well-formed grammar file; everything that
+/// is dropped or approximated is reported in [`Import::warnings`].
+pub fn parse(source: &str) -> Result<Import, GrammarError> {
+    let (tokens, epilogue) = lex(source)?;
+    let mut parser = Parser {
+        source,
+        tokens,
+        pos: 0,
+        decls: Decls::default(),
+        rules: Vec::new(),
+        warnings: Vec::new(),
+    };
+    parser.declarations()?;
+    parser.rules()?;
+    if let Some(span) = epilogue {
+        parser.warn("Dropped the epilogue code after the second %%", span);
+    }
+    parser.build()
+}
+
+// ============================================================================
+// Lexer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Ident(String),
+    /// A character or string literal, as written.
+    Literal(String),
+    /// `<type>`
+    Tag,
+    Num(usize),
+    /// Braced code, `{ ... }`.
+    Code,
+    /// `%name`, without the `%`.
+    Directive(String),
+    /// `%{ ... %}`
+    Prologue,
+    /// `%%`
+    Separator,
+    Colon,
+    Pipe,
+    Semi,
+    /// `[name]`, naming a symbol for use in actions.
+    Named,
+}
+
+/// Tokens with their spans.
+type Tokens = Vec<(Tok, Span)>;
+
+/// Split `source` into tokens up to the second `%%`, and return the span of
+/// the code after it, if there is any.
+fn lex(source: &str) -> Result<(Tokens, Option<Span>), GrammarError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut separators = 0;
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let start = pos;
+        let c = bytes[pos];
+        let next = bytes.get(pos + 1).copied();
+        if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        if c == b'/' && matches!(next, Some(b'*' | b'/')) {
+            pos = skip_comment(source, pos)?;
+            continue;
+        }
+        let tok = match c {
+            b'%' if next == Some(b'%') => {
+                pos += 2;
+                separators += 1;
+                if separators == 2 {
+                    tokens.push((Tok::Separator, start..pos));
+                    let rest = source[pos..].trim_start();
+                    let start = source.len() - rest.len();
+                    let epilogue = (!rest.is_empty()).then_some(start..source.len());
+                    return Ok((tokens, epilogue));
+                }
+                Tok::Separator
+            }
+            b'%' if next == Some(b'{') => {
+                let Some(end) = source[pos..].find("%}") else {
+                    return Err(syntax_error("Unterminated %{ block", start..pos + 2));
+                };
+                pos += end + 2;
+                Tok::Prologue
+            }
+            b'%' if next == Some(b'?') => {
+                pos = skip_code(source, pos + 2)?;
+                Tok::Code
+            }
+            b'%' => {
+                pos += 1;
+                while pos < bytes.len()
+                    && (bytes[pos].is_ascii_alphanumeric() || matches!(bytes[pos], b'_' | b'-'))
+                {
+                    pos += 1;
+                }
+                if pos == start + 1 {
+                    return Err(syntax_error("Expected a directive after '%'", start..pos));
+                }
+                Tok::Directive(source[start + 1..pos].to_string())
+            }
+            b'{' => {
+                pos = skip_code(source, pos)?;
+                Tok::Code
+            }
+            b'<' => {
+                let mut depth = 0;
+                while pos < bytes.len() {
+                    match bytes[pos] {
+                        b'<' => depth += 1,
+                        b'>' => depth -= 1,
+                        _ => {}
+                    }
+                    pos += 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                if depth != 0 {
+                    return Err(syntax_error("Unterminated <type> tag", start..pos));
+                }
+                Tok::Tag
+            }
+            b'\'' | b'"' => {
+                pos = skip_quoted(source, pos)?;
+                Tok::Literal(source[start..pos].to_string())
+            }
+            b'[' => {
+                let Some(end) = source[pos..].find(']') else {
+                    return Err(syntax_error("Unterminated [name]", start..pos + 1));
+                };
+                pos += end + 1;
+                Tok::Named
+            }
+            b':' => {
+                pos += 1;
+                Tok::Colon
+            }
+            b'|' => {
+                pos += 1;
+                Tok::Pipe
+            }
+            b';' => {
+                pos += 1;
+                Tok::Semi
+            }
+            b'0'..=b'9' => {
+                while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                    pos += 1;
+                }
+                let n = source[start..pos]
+                    .parse()
+                    .map_err(|_| syntax_error("Number too large", start..pos))?;
+                Tok::Num(n)
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' || c == b'.' => {
+                while pos < bytes.len()
+                    && (bytes[pos].is_ascii_alphanumeric()
+                        || matches!(bytes[pos], b'_' | b'.' | b'-'))
+                {
+                    pos += 1;
+                }
+                Tok::Ident(source[start..pos].to_string())
+            }
+            _ => {
+                let c = source[pos..].chars().next().unwrap();
+                return Err(syntax_error(
+                    format!("Unexpected character '{}'", c),
+                    start..pos + c.len_utf8(),
+                ));
+            }
+        };
+        tokens.push((tok, start..pos));
+    }
+    Ok((tokens, None))
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+/// The declarations section.
+#[derive(Default)]
+struct Decls {
+    /// Declared tokens, in order, with whether they have a `<type>`.
+    tokens: Vec<(String, bool)>,
+    /// String aliases of tokens (`%token PLUS "+"`), as written.
+    aliases: HashMap<String, String>,
+    /// Precedence of symbols as written, with the declaring directive.
+    precs: Vec<(String, Precedence, String)>,
+    start: Option<String>,
+    expect_sr: usize,
+    expect_rr: usize,
+    /// Whether any declaration has a `<type>` tag.
+    tagged: bool,
+}
+
+impl Decls {
+    fn declare(&mut self, name: &str, typed: bool) {
+        match self.tokens.iter_mut().find(|(n, _)| n == name) {
+            Some((_, t)) => *t |= typed,
+            None => self.tokens.push((name.to_string(), typed)),
+        }
+    }
+}
+
+/// A symbol or action in an alternative.
+enum Item {
+    /// A symbol as written.
+    Symbol(String, Span),
+    /// Action code; only `$N` references in it matter.
+    Action(Span),
+}
+
+struct RawAlt {
+    items: Vec<Item>,
+    /// `%prec SYM`
+    prec: Option<(String, Span)>,
+    span: Span,
+}
+
+struct RawRule {
+    name: String,
+    span: Span,
+    alts: Vec<RawAlt>,
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Tokens,
+    pos: usize,
+    decls: Decls,
+    rules: Vec<RawRule>,
+    warnings: Vec<Warning>,
+}
+
+/// Directives whose C code is dropped with a warning.
+const CODE_DIRECTIVES: &[&str] = &["union", "code", "destructor", "printer", "initial-action"];
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Tok> {
+        self.tokens.get(self.pos).map(|(t, _)| t)
+    }
+
+    fn span(&self) -> Span {
+        match self.tokens.get(self.pos) {
+            Some((_, span)) => span.clone(),
+            None => self.source.len()..self.source.len(),
+        }
+    }
+
+    fn bump(&mut self) -> (Tok, Span) {
+        let tok = self.tokens[self.pos].clone();
+        self.pos += 1;
+        tok
+    }
+
+    fn warn(&mut self, message: impl Into<String>, span: Span) {
+        self.warnings.push(Warning::new(message, Some(span)));
+    }
+
+    /// The arguments of a declaration: tokens up to the next directive.
+    fn args(&mut self) -> Tokens {
+        let start = self.pos;
+        while let Some(tok) = self.peek() {
+            if matches!(tok, Tok::Directive(_) | Tok::Prologue | Tok::Separator) {
+                break;
+            }
+            self.pos += 1;
+        }
+        self.tokens[start..self.pos].to_vec()
+    }
+
+    fn declarations(&mut self) -> Result<(), GrammarError> {
+        let mut level = 0u8;
+        loop {
+            let span = self.span();
+            let Some(tok) = self.peek().cloned() else {
+                return Err(syntax_error("Expected %% before the grammar rules", span));
+            };
+            self.pos += 1;
+            let directive = match tok {
+                Tok::Separator => return Ok(()),
+                Tok::Prologue => {
+                    self.warn("Dropped the %{ ... %} prologue", span);
+                    continue;
+                }
+                Tok::Directive(d) => d,
+                _ => return Err(syntax_error("Expected a declaration", span)),
+            };
+            let args = self.args();
+            match directive.as_str() {
+                "token" => {
+                    let mut typed = false;
+                    let mut last = None;
+                    for (tok, _) in args {
+                        match tok {
+                            Tok::Tag => {
+                                typed = true;
+                                self.decls.tagged = true;
+                            }
+                            Tok::Ident(name) => {
+                                self.decls.declare(&name, typed);
+                                last = Some(name);
+                            }
+                            Tok::Literal(alias) if alias.starts_with('"') => {
+                                if let Some(name) = last.take() {
+                                    self.decls.aliases.insert(alias, name);
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                "left" | "right" | "nonassoc" | "precedence" => {
+                    level = level
+                        .checked_add(1)
+                        .ok_or_else(|| syntax_error("Too many precedence levels", span.clone()))?;
+                    let prec = match directive.as_str() {
+                        "left" => Precedence::Left(level),
+                        "right" => Precedence::Right(level),
+                        _ => Precedence::NonAssoc(level),
+                    };
+                    let mut typed = false;
+                    for (tok, _) in args {
+                        let symbol = match tok {
+                            Tok::Tag => {
+                                typed = true;
+                                self.decls.tagged = true;
+                                continue;
+                            }
+                            Tok::Ident(name) => {
+                                self.decls.declare(&name, typed);
+                                name
+                            }
+                            Tok::Literal(lit) => lit,
+                            _ => continue,
+                        };
+                        self.decls.precs.push((symbol, prec, directive.clone()));
+                    }
+                }
+                "start" => match args.first() {
+                    Some((Tok::Ident(name), _)) => self.decls.start = Some(name.clone()),
+                    _ => return Err(syntax_error("Expected a symbol after %start", span)),
+                },
+                "expect" | "expect-rr" => {
+                    let Some((Tok::Num(n), _)) = args.first() else {
+                        return Err(syntax_error(
+                            format!("Expected a number after %{}", directive),
+                            span,
+                        ));
+                    };
+                    if directive == "expect" {
+                        self.decls.expect_sr = *n;
+                    } else {
+                        self.decls.expect_rr = *n;
+                    }
+                }
+                "type" | "nterm" => {
+                    if args.iter().any(|(t, _)| *t == Tok::Tag) {
+                        self.decls.tagged = true;
+                    }
+                }
+                d if CODE_DIRECTIVES.contains(&d) => {
+                    let end = args.last().map_or(span.end, |(_, s)| s.end);
+                    self.warn(format!("Dropped the %{} declaration", d), span.start..end);
+                }
+                // Parser settings (%define, %defines, %pure-parser, ...)
+                d => {
+                    let end = args.last().map_or(span.end, |(_, s)| s.end);
+                    self.warn(format!("Ignored the %{} setting", d), span.start..end);
+                }
+            }
+        }
+    }
+
+    /// Whether the next tokens start a rule: `name [label] :`.
+    fn at_rule_start(&self) -> bool {
+        let kinds = |i: usize| self.tokens.get(self.pos + i).map(|(t, _)| t);
+        matches!(
+            (kinds(0), kinds(1), kinds(2)),
+            (Some(Tok::Ident(_)), Some(Tok::Colon), _)
+                | (Some(Tok::Ident(_)), Some(Tok::Named), Some(Tok::Colon))
+        )
+    }
+
+    fn rules(&mut self) -> Result<(), GrammarError> {
+        while self.peek().is_some() {
+            if *self.peek().unwrap() == Tok::Separator {
+                self.pos += 1;
+                break;
+            }
+            if !self.at_rule_start() {
+                return Err(syntax_error("Expected a rule: name ':' ...", self.span()));
+            }
+            let (Tok::Ident(name), span) = self.bump() else {
+                unreachable!()
+            };
+            while self.bump().0 != Tok::Colon {}
+            let mut alts = vec![self.alternative(&name)?];
+            while self.peek() == Some(&Tok::Pipe) {
+                self.pos += 1;
+                alts.push(self.alternative(&name)?);
+            }
+            if self.peek() == Some(&Tok::Semi) {
+                self.pos += 1;
+            }
+            // A rule may be continued in another block with the same name
+            match self.rules.iter_mut().find(|r| r.name == name) {
+                Some(rule) => rule.alts.extend(alts),
+                None => self.rules.push(RawRule { name, span, alts }),
+            }
+        }
+        Ok(())
+    }
+
+    fn alternative(&mut self, rule: &str) -> Result<RawAlt, GrammarError> {
+        let start = self.span().start;
+        let mut items = Vec::new();
+        let mut prec = None;
+        let mut end = start;
+        loop {
+            if self.at_rule_start() {
+                break;
+            }
+            let span = self.span();
+            match self.peek().cloned() {
+                None | Some(Tok::Pipe | Tok::Semi | Tok::Separator) => break,
+                Some(Tok::Ident(name)) => items.push(Item::Symbol(name, span.clone())),
+                Some(Tok::Literal(lit)) => items.push(Item::Symbol(lit, span.clone())),
+                Some(Tok::Code) => items.push(Item::Action(span.clone())),
+                Some(Tok::Named) => {}
+                Some(Tok::Directive(d)) => match d.as_str() {
+                    "empty" => {}
+                    "prec" => {
+                        self.pos += 1;
+                        let span = self.span();
+                        match self.peek().cloned() {
+                            Some(Tok::Ident(s) | Tok::Literal(s)) => prec = Some((s, span)),
+                            _ => return Err(syntax_error("Expected a symbol after %prec", span)),
+                        }
+                    }
+                    "dprec" | "merge" | "expect" | "expect-rr" => {
+                        self.pos += 1;
+                        if matches!(self.peek(), Some(Tok::Num(_) | Tok::Tag)) {
+                            self.pos += 1;
+                        }
+                        let end = self.tokens[self.pos - 1].1.end;
+                        self.warn(
+                            format!("Ignored %{} in rule '{}'", d, rule),
+                            span.start..end,
+                        );
+                        continue;
+                    }
+                    _ => {
+                        return Err(syntax_error(format!("Unexpected %{} in a rule", d), span));
+                    }
+                },
+                Some(_) => return Err(syntax_error("Unexpected token in a rule", span)),
+            }
+            self.pos += 1;
+            end = self.tokens[self.pos - 1].1.end;
+        }
+        Ok(RawAlt {
+            items,
+            prec,
+            span: start..end,
+        })
+    }
+
+    // ========================================================================
+    // Translation
+    // ========================================================================
+
+    fn build(mut self) -> Result<Import, GrammarError> {
+        if self.rules.is_empty() {
+            return Err(syntax_error("The grammar has no rules", self.span()));
+        }
+        let decls = std::mem::take(&mut self.decls);
+        let raw_rules = std::mem::take(&mut self.rules);
+        let rule_names: HashSet<String> = raw_rules.iter().map(|r| r.name.clone()).collect();
+        let canonical = |symbol: &str| -> String {
+            decls
+                .aliases
+                .get(symbol)
+                .cloned()
+                .unwrap_or_else(|| symbol.to_string())
+        };
+        let precs: HashMap<String, (Precedence, &str)> = decls
+            .precs
+            .iter()
+            .map(|(s, p, d)| (canonical(s), (*p, d.as_str())))
+            .collect();
+
+        // Which tokens the rules use, and which have their value read by `$N`
+        let mut used: HashSet<String> = HashSet::new();
+        let mut valued: HashSet<String> = HashSet::new();
+        for alt in raw_rules.iter().flat_map(|r| &r.alts) {
+            for item in &alt.items {
+                match item {
+                    Item::Symbol(s, _) => {
+                        used.insert(canonical(s));
+                    }
+                    Item::Action(span) => {
+                        for n in value_refs(&self.source[span.clone()]) {
+                            if let Some(Item::Symbol(s, _)) = alt.items.get(n.wrapping_sub(1)) {
+                                valued.insert(canonical(s));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut names = Names::default();
+        for rule in &raw_rules {
+            names.get(&rule.name, &mut self.warnings, Some(rule.span.clone()));
+        }
+
+        // Declared terminals, except precedence-only pseudo-tokens like UMINUS
+        let mut terminals = Vec::new();
+        for (token, tagged) in &decls.tokens {
+            if rule_names.contains(token) {
+                continue;
+            }
+            let prec = precs.get(token.as_str());
+            if prec.is_some() && !used.contains(token) {
+                continue;
+            }
+            let name = names.get(token, &mut self.warnings, None);
+            let has_type = *tagged || (!decls.tagged && valued.contains(token));
+            terminals.push(terminal(name, has_type, prec));
+        }
+        let mut declared: HashSet<String> = decls.tokens.iter().map(|(t, _)| t.clone()).collect();
+
+        let mut rules = Vec::new();
+        let mut term_names = HashSet::new();
+        for raw in &raw_rules {
+            let mut alts = Vec::new();
+            for alt in &raw.alts {
+                let mut terms = Vec::new();
+                let mut actions = alt
+                    .items
+                    .iter()
+                    .filter_map(|item| match item {
+                        Item::Action(span) => Some(span.clone()),
+                        _ => None,
+                    })
+                    .peekable();
+                while let Some(span) = actions.next() {
+                    let mid_rule = actions.peek().is_some()
+                        || !matches!(alt.items.last(), Some(Item::Action(_)));
+                    let message = if mid_rule {
+                        format!("Dropped a mid-rule action in rule '{}'", raw.name)
+                    } else {
+                        format!("Dropped the action code in rule '{}'", raw.name)
+                    };
+                    self.warnings.push(Warning::new(message, Some(span)));
+                }
+                if alt
+                    .items
+                    .iter()
+                    .any(|i| matches!(i, Item::Symbol(s, _) if s == "error"))
+                {
+                    self.warn(
+                        format!(
+                            "Dropped an alternative of rule '{}' that uses the 'error' token; \
+                             gazelle parsers recover from errors without grammar rules",
+                            raw.name
+                        ),
+                        alt.span.clone(),
+                    );
+                    continue;
+                }
+                for item in &alt.items {
+                    let Item::Symbol(written, span) = item else {
+                        continue;
+                    };
+                    let symbol = canonical(written);
+                    let name = if is_literal(&symbol) {
+                        match precs.get(&symbol) {
+                            Some(prec) => {
+                                if !names.contains(&symbol) {
+                                    let base = snake_case(&literal_name(&literal_text(&symbol)))
+                                        .to_uppercase();
+                                    let name = names.fresh(&symbol, &base);
+                                    terminals.push(terminal(name, false, Some(prec)));
+                                }
+                                names.get(&symbol, &mut self.warnings, None)
+                            }
+                            None => literal_name(&literal_text(&symbol)),
+                        }
+                    } else {
+                        if !rule_names.contains(&symbol) && declared.insert(symbol.clone()) {
+                            self.warnings.push(Warning::new(
+                                format!(
+                                    "'{}' is neither declared with %token nor defined by a rule; \
+                                     treating it as a terminal",
+                                    symbol
+                                ),
+                                Some(span.clone()),
+                            ));
+                            let name = names.get(&symbol, &mut self.warnings, None);
+                            let has_type = !decls.tagged && valued.contains(&symbol);
+                            terminals.push(terminal(name, has_type, None));
+                        }
+                        names.get(&symbol, &mut self.warnings, None)
+                    };
+                    if !rule_names.contains(&symbol) {
+                        term_names.insert(name.clone());
+                    }
+                    terms.push(Term::Symbol(name));
+                }
+                let prec = match &alt.prec {
+                    Some((symbol, span)) => match precs.get(&canonical(symbol)) {
+                        Some((prec, _)) => Some(AltPrec::Fixed(*prec)),
+                        None => {
+                            self.warn(
+                                format!("'{}' in %prec has no declared precedence", symbol),
+                                span.clone(),
+                            );
+                            None
+                        }
+                    },
+                    None => None,
+                };
+                if terms.is_empty() {
+                    terms.push(Term::Empty);
+                }
+                alts.push((terms, prec));
+            }
+            let mut variants = HashSet::new();
+            let alts = alts
+                .into_iter()
+                .map(|(terms, prec)| Alt {
+                    name: variant_name(
+                        &terms,
+                        |s| is_literal(s) || term_names.contains(s),
+                        &mut variants,
+                    ),
+                    terms: terms.into_iter().map(Spanned::from).collect(),
+                    labels: Vec::new(),
+                    prec,
+                    doc: Vec::new(),
+                    span: None,
+                })
+                .collect();
+            rules.push(Rule {
+                name: names.get(&raw.name, &mut self.warnings, None),
+                params: Vec::new(),
+                alts,
+                doc: Vec::new(),
+                attrs: Vec::new(),
+                span: None,
+            });
+        }
+
+        // A literal such as '-' can't share its type name with a terminal
+        // such as MINUS, so it gets a named terminal of its own
+        let camel: HashSet<String> = terminals.iter().map(|t| to_camel_case(&t.name)).collect();
+        let mut renamed: HashMap<String, String> = HashMap::new();
+        let terms = rules
+            .iter_mut()
+            .flat_map(|r| &mut r.alts)
+            .flat_map(|a| &mut a.terms);
+        for term in terms {
+            let Term::Symbol(symbol) = &mut term.node else {
+                continue;
+            };
+            if !is_literal(symbol) || !camel.contains(&to_camel_case(symbol)) {
+                continue;
+            }
+            if !renamed.contains_key(symbol) {
+                let name = names.fresh(symbol, &snake_case(symbol).to_uppercase());
+                self.warnings.push(Warning::new(
+                    format!(
+                        "Literal {} would share its name with another terminal; \
+                         it becomes terminal {}",
+                        symbol, name
+                    ),
+                    None,
+                ));
+                terminals.push(terminal(name.clone(), false, None));
+                renamed.insert(symbol.clone(), name);
+            }
+            *symbol = renamed[symbol.as_str()].clone();
+        }
+
+        let start = decls.start.as_deref().unwrap_or(&raw_rules[0].name);
+        let start = names.get(start, &mut self.warnings, None);
+        let mut warnings = self.warnings;
+        warnings.sort_by_key(|w| w.span.as_ref().map_or(usize::MAX, |s| s.start));
+        Ok(Import {
+            grammar: Grammar {
+                start: start.to_string(),
+                extra_starts: Vec::new(),
+                expect_rr: decls.expect_rr,
+                expect_sr: decls.expect_sr,
+                split_tokens: false,
+                terminals,
+                rules,
+                attrs: Vec::new(),
+                terminal_attrs: Vec::new(),
+                comments: Vec::new(),
+            },
+            warnings,
+        })
+    }
+}
+
+/// The positions `N` of `$N` and `$<type>N` references in action code.
+fn value_refs(code: &str) -> Vec<usize> {
+    let mut refs = Vec::new();
+    let mut rest = code;
+    while let Some(i) = rest.find('$') {
+        rest = &rest[i + 1..];
+        let after_tag = match rest.strip_prefix('<') {
+            Some(tagged) => tagged.find('>').map_or(tagged, |end| &tagged[end + 1..]),
+            None => rest,
+        };
+        let digits: String = after_tag
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        if let Ok(n) = digits.parse() {
+            refs.push(n);
+        }
+    }
+    refs
+}
+
+fn terminal(name: String, has_type: bool, prec: Option<&(Precedence, &str)>) -> TerminalDef {
+    let doc = prec
+        .map(|(prec, directive)| {
+            vec![format!(
+                " `%{}` level {}: push with `Precedence::{:?}`.",
+                directive,
+                prec.level(),
+                prec
+            )]
+        })
+        .unwrap_or_default();
+    TerminalDef {
+        name,
+        has_type,
+        is_prec: prec.is_some(),
+        pattern: None,
+        doc,
+        span: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALC: &str = r#"
+%{
+#include <stdio.h>
+%}
+%union { int num; }
+%token <num> NUM
+%token PLUS "+" MINUS "-"
+%left "+" MINUS
+%left '*' '/'
+%precedence UMINUS
+%type <num> expr
+%start input
+%expect 0
+%%
+input: %empty
+     | input line
+     ;
+line: '\n' | expr '\n' { printf("%d\n", $1); } ;
+expr: NUM
+    | expr "+" expr { $$ = $1 + $3; }
+    | expr MINUS expr { $$ = $1 - $3; }
+    | expr '*' expr { $$ = $1 * $3; }
+    | expr '/' expr { $$ = $1 / $3; }
+    | '-' expr %prec UMINUS { $$ = -$2; }
+    | '(' expr ')' { $$ = $2; }
+    | error
+    ;
+%%
+int main(void) { return yyparse(); }
+"#;
+
+    #[test]
+    fn test_calc() {
+        let import = parse(CALC).unwrap();
+        assert_eq!(
+            import.grammar.to_gzl(),
+            r#"start input;
+
+terminals {
+    NUM: _,
+    /// `%left` level 1: push with `Precedence::Left(1)`.
+    prec PLUS,
+    /// `%left` level 1: push with `Precedence::Left(1)`.
+    prec MINUS,
+    /// `%left` level 2: push with `Precedence::Left(2)`.
+    prec STAR,
+    /// `%left` level 2: push with `Precedence::Left(2)`.
+    prec SLASH,
+    MINUS_2
+}
+
+input = _ => empty
+      | input line => input_line;
+
+line = '\n' => newline
+     | expr '\n' => newline_2;
+
+expr = NUM => num
+     | expr PLUS expr => plus
+     | expr MINUS expr => minus
+     | expr STAR expr => star
+     | expr SLASH expr => slash
+     | MINUS_2 expr => minus_2 prec(3, nonassoc)
+     | '(' expr ')' => lparen_rparen;
+"#
+        );
+        let messages: Vec<String> = import.warnings.iter().map(|w| w.format(CALC)).collect();
+        assert_eq!(
+            messages,
+            [
+                "2:1: Dropped the %{ ... %} prologue",
+                "5:1: Dropped the %union declaration",
+                "18:24: Dropped the action code in rule 'line'",
+                "20:21: Dropped the action code in rule 'expr'",
+                "21:23: Dropped the action code in rule 'expr'",
+                "22:21: Dropped the action code in rule 'expr'",
+                "23:21: Dropped the action code in rule 'expr'",
+                "24:29: Dropped the action code in rule 'expr'",
+                "25:20: Dropped the action code in rule 'expr'",
+                "26:7: Dropped an alternative of rule 'expr' that uses the 'error' token; \
+                 gazelle parsers recover from errors without grammar rules",
+                "29:1: Dropped the epilogue code after the second %%",
+                "Literal '-' would share its name with another terminal; \
+                 it becomes terminal MINUS_2",
+            ]
+        );
+        crate::table::CompiledTable::build(&import.grammar).unwrap();
+    }
+
+    #[test]
+    fn test_untyped_values() {
+        // Without <type> tags, a token is typed if an action reads its value
+        let import = parse(
+            "%token ID SEMI\n%%\nstmts: stmts stmt | stmt;\nstmt: ID SEMI { use($1); }\n\
+             | mid { a(); } ID { b(); };\n",
+        )
+        .unwrap();
+        let terminals: Vec<_> = import
+            .grammar
+            .terminals
+            .iter()
+            .map(|t| (t.name.as_str(), t.has_type))
+            .collect();
+        assert_eq!(terminals, [("ID", true), ("SEMI", false), ("mid", false)]);
+        let messages: Vec<_> = import.warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Dropped the action code in rule 'stmt'",
+                "'mid' is neither declared with %token nor defined by a rule; \
+                 treating it as a terminal",
+                "Dropped a mid-rule action in rule 'stmt'",
+                "Dropped the action code in rule 'stmt'",
+            ]
+        );
+        let stmts = &import.grammar.rules[0];
+        let names: Vec<_> = stmts.alts.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["stmts_stmt", "stmt"]);
+    }
+
+    #[test]
+    fn test_names_and_errors() {
+        let import = parse("%%\nstart: a.b ;\na.b: 'x' | ;\n").unwrap();
+        assert_eq!(
+            import.grammar.to_gzl(),
+            "start start_;\n\nstart_ = a_b => a_b;\n\na_b = 'x' => x\n    | _ => empty;\n"
+        );
+        let err = parse("%token A\nexpr: A").unwrap_err();
+        assert_eq!(
+            err.format("%token A\nexpr: A"),
+            "2:8: Expected %% before the grammar rules"
+        );
+        assert!(parse("%%\nexpr: { unclosed").is_err());
+    }
+
+    #[test]
+    fn test_settings_and_escapes() {
+        let src = "%define api.pure full\n%token NUM\n%verbose\n%%\n\
+                   s: '\\n' s | '\\t' | '\\r' | '\\0' | NUM ;\n";
+        let import = parse(src).unwrap();
+        let messages: Vec<String> = import.warnings.iter().map(|w| w.format(src)).collect();
+        assert_eq!(
+            messages,
+            [
+                "1:1: Ignored the %define setting",
+                "3:1: Ignored the %verbose setting"
+            ]
+        );
+        let names: Vec<_> = import.grammar.rules[0]
+            .alts
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect();
+        assert_eq!(names, ["newline", "tab", "return", "nul", "num"]);
+    }
+}
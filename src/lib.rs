@@ -60,9 +60,16 @@
 //!   full CST, to a custom type for an AST, or to [`Ignore`] to discard.
 //! - **Library API**: build [`CompiledTable`]s programmatically for dynamic
 //!   grammars, analyzers, or conflict debuggers.
+//! - **GLR fallback**: [`GlrParser`] explores every side of a conflict and
+//!   returns a shared packed parse [`Forest`] for ambiguous grammars.
 
 pub mod automaton;
+pub mod diagram;
+mod format;
+pub mod glr;
 pub mod grammar;
+pub mod import;
+pub mod lint;
 mod lr;
 pub mod table;
 
@@ -77,22 +84,31 @@ pub mod runtime;
 #[doc(hidden)]
 #[cfg(feature = "codegen")]
 pub mod codegen;
+#[cfg(feature = "codegen")]
+mod rewrite;
 
 // Core grammar types (AST)
-pub use grammar::{Alt, Grammar, Rule, SymbolId, Term, TerminalDef};
+pub use grammar::{
+    Alt, AltPrec, Comment, Grammar, GrammarError, GrammarErrorKind, Rule, Span, Spanned, SymbolId,
+    Term, TerminalDef,
+};
 
 // Parse table types
 pub use table::{CompiledTable, Conflict, ErrorInfo};
 
 // Runtime parser types
 pub use runtime::{
-    Action, AstNode, Cst, CstParser, ErrorContext, FromAstNode, Ignore, ParseError, ParseTable,
-    Parser, Precedence, RecoveryInfo, Repair, Token,
+    Action, Associativity, AstNode, Cst, CstParser, ErrorContext, FromAstNode, Ignore, ParseError,
+    ParseTable, Parser, PrecGroup, Precedence, PrecedenceGroups, RecoveryInfo, Repair, RulePrec,
+    Token,
 };
 
+// GLR parser types
+pub use glr::{Forest, GlrParser};
+
 // Lexer DFA
 pub use lexer::LexerDfa;
 
 // Meta-grammar parser
 #[cfg(not(feature = "bootstrap"))]
-pub use meta::parse_grammar;
+pub use meta::{format_grammar, parse_grammar, parse_grammar_with};
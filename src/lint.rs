@@ -0,0 +1,462 @@
+//! Grammar lints: problems that do not stop a grammar from building but
+//! usually point at a mistake.
+//!
+//! ```
+//! use gazelle::lint::{lint, WarningKind};
+//!
+//! let grammar = gazelle::parse_grammar(r#"
+//!     start expr;
+//!     terminals { NUM, MINUS }
+//!     expr = NUM => num;
+//!     neg = MINUS expr => neg;
+//! "#).unwrap();
+//! let warnings = lint(&grammar).unwrap();
+//! assert_eq!(warnings[0].kind, WarningKind::Unreachable);
+//! assert_eq!(warnings[0].symbol, "neg");
+//! ```
+//!
+//! A lint is turned off by name with an `allow` attribute: `#![allow(unreachable)]`
+//! at the top of the grammar for all symbols, `#[allow(unreachable)]` on a
+//! rule for that rule, or `#![allow(unused_terminal)]` inside the `terminals`
+//! block for its terminals. Generated code never sees these names.
+
+use std::collections::BTreeSet;
+
+use crate::grammar::{Grammar, GrammarError, Span, SymbolId};
+use crate::lr::{FirstSets, GrammarInternal, TerminalSet, to_grammar_internal};
+
+/// A problem found by [`lint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// What kind of problem this is.
+    pub kind: WarningKind,
+    /// The terminal or rule the warning is about.
+    pub symbol: String,
+    /// The warning message.
+    pub message: String,
+    /// Source location of the symbol's declaration, if known.
+    pub span: Option<Span>,
+}
+
+/// The kind of a [`Warning`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WarningKind {
+    /// A rule that can never derive a string of terminals.
+    Unproductive,
+    /// A rule that no start symbol can reach.
+    Unreachable,
+    /// A declared terminal that no rule uses.
+    UnusedTerminal,
+    /// A `prec` terminal used only where its precedence is never consulted.
+    UselessPrec,
+}
+
+/// All lints, in the order [`lint`] checks them.
+const LINTS: [WarningKind; 4] = [
+    WarningKind::UnusedTerminal,
+    WarningKind::Unproductive,
+    WarningKind::Unreachable,
+    WarningKind::UselessPrec,
+];
+
+impl WarningKind {
+    /// The lint's name, as used in `allow` attributes.
+    pub fn name(self) -> &'static str {
+        match self {
+            WarningKind::Unproductive => "unproductive",
+            WarningKind::Unreachable => "unreachable",
+            WarningKind::UnusedTerminal => "unused_terminal",
+            WarningKind::UselessPrec => "useless_prec",
+        }
+    }
+}
+
+/// Split an attribute such as `allow(unreachable, dead_code)` into the lints
+/// it allows and the attribute that remains for the generated code, if any.
+pub(crate) fn split_allow(attr: &str) -> (Vec<WarningKind>, Option<String>) {
+    let compact: String = attr.chars().filter(|c| !c.is_whitespace()).collect();
+    let Some(names) = compact
+        .strip_prefix("allow(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    else {
+        return (Vec::new(), Some(attr.to_string()));
+    };
+    let mut kinds = Vec::new();
+    let mut rest = Vec::new();
+    for name in names.split(',').filter(|name| !name.is_empty()) {
+        match LINTS.into_iter().find(|kind| kind.name() == name) {
+            Some(kind) => kinds.push(kind),
+            None => rest.push(name),
+        }
+    }
+    if kinds.is_empty() {
+        (kinds, Some(attr.to_string()))
+    } else if rest.is_empty() {
+        (kinds, None)
+    } else {
+        (kinds, Some(format!("allow({})", rest.join(", "))))
+    }
+}
+
+impl Warning {
+    /// Format the warning with the line and column of its span in `source`.
+    pub fn format(&self, source: &str) -> String {
+        crate::grammar::format_located(&self.message, self.span.as_ref(), source)
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Check a grammar for unproductive and unreachable rules, unused terminals
+/// and `prec` terminals whose precedence can never matter, except the lints
+/// the grammar allows.
+///
+/// Fails only if the grammar itself is invalid.
+pub fn lint(grammar: &Grammar) -> Result<Vec<Warning>, GrammarError> {
+    let internal = to_grammar_internal(grammar)?;
+    let mut warnings = check(&internal);
+    warnings.retain_mut(|warning| {
+        let terminal = grammar.terminals.iter().find(|t| t.name == warning.symbol);
+        let rule = grammar.rules.iter().find(|r| r.name == warning.symbol);
+        let attrs = match (terminal, rule) {
+            (Some(_), _) => &grammar.terminal_attrs[..],
+            (None, Some(rule)) => &rule.attrs[..],
+            (None, None) => &[],
+        };
+        warning.span = match terminal {
+            Some(t) => t.span.clone(),
+            None => rule.and_then(|r| r.span.clone()),
+        };
+        !grammar
+            .attrs
+            .iter()
+            .chain(attrs)
+            .any(|attr| split_allow(attr).0.contains(&warning.kind))
+    });
+    Ok(warnings)
+}
+
+/// Lint the internal grammar. Synthetic rules (`__name`) are not reported:
+/// their problems show up in the rules that use them.
+pub(crate) fn check(grammar: &GrammarInternal) -> Vec<Warning> {
+    let symbols = &grammar.symbols;
+    let name = |id: SymbolId| symbols.name(id).to_string();
+    let is_user_rule = |id: SymbolId| !symbols.name(id).starts_with("__");
+    let mut warnings = Vec::new();
+    let mut warn = |kind, id: SymbolId, message: String| {
+        warnings.push(Warning {
+            kind,
+            symbol: name(id),
+            message,
+            span: None,
+        })
+    };
+
+    let used: BTreeSet<SymbolId> = grammar
+        .rules
+        .iter()
+        .flat_map(|r| r.rhs.iter().map(|s| s.id()))
+        .collect();
+    let declared: Vec<SymbolId> = symbols
+        .terminal_ids()
+        .skip(1)
+        .filter(|&id| !grammar.is_entry_marker(id) && grammar.twin_of(id).is_none())
+        .collect();
+    for &id in &declared {
+        if !used.contains(&id) {
+            warn(
+                WarningKind::UnusedTerminal,
+                id,
+                format!("Terminal '{}' is never used", name(id)),
+            );
+        }
+    }
+
+    let productive = productive(grammar);
+    for id in symbols.non_terminal_ids().filter(|&id| is_user_rule(id)) {
+        if !productive.contains(&id) {
+            warn(
+                WarningKind::Unproductive,
+                id,
+                format!("Rule '{}' can never derive a string of terminals", name(id)),
+            );
+        }
+    }
+
+    let reachable = reachable(grammar);
+    for id in symbols.non_terminal_ids().filter(|&id| is_user_rule(id)) {
+        if !reachable.contains(&id) {
+            warn(
+                WarningKind::Unreachable,
+                id,
+                format!("Rule '{}' is unreachable from the start symbol", name(id)),
+            );
+        }
+    }
+
+    let prec_terminals: Vec<SymbolId> = declared
+        .into_iter()
+        .filter(|&id| symbols.is_prec_terminal(id) && used.contains(&id))
+        .collect();
+    if !prec_terminals.is_empty() {
+        let follow = follow_sets(grammar);
+        for &id in &prec_terminals {
+            if !prec_may_matter(grammar, &follow, &prec_terminals, id) {
+                warn(
+                    WarningKind::UselessPrec,
+                    id,
+                    format!(
+                        "Precedence of '{}' can never matter: no shift/reduce conflict can involve it",
+                        name(id)
+                    ),
+                );
+            }
+        }
+    }
+
+    warnings
+}
+
+/// Non-terminals that derive at least one string of terminals.
+fn productive(grammar: &GrammarInternal) -> BTreeSet<SymbolId> {
+    let mut productive = BTreeSet::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for rule in &grammar.rules {
+            let lhs = rule.lhs.id();
+            if !productive.contains(&lhs)
+                && rule
+                    .rhs
+                    .iter()
+                    .all(|s| grammar.symbols.is_terminal(s.id()) || productive.contains(&s.id()))
+            {
+                productive.insert(lhs);
+                changed = true;
+            }
+        }
+    }
+    productive
+}
+
+/// Non-terminals reachable from the augmented start rule.
+fn reachable(grammar: &GrammarInternal) -> BTreeSet<SymbolId> {
+    let start = grammar.rules[0].lhs.id();
+    let mut reachable = BTreeSet::from([start]);
+    let mut pending = vec![start];
+    while let Some(lhs) = pending.pop() {
+        for rule in grammar.rules.iter().filter(|r| r.lhs.id() == lhs) {
+            for sym in &rule.rhs {
+                if !grammar.symbols.is_terminal(sym.id()) && reachable.insert(sym.id()) {
+                    pending.push(sym.id());
+                }
+            }
+        }
+    }
+    reachable
+}
+
+/// FOLLOW sets of all symbols, indexed by SymbolId.
+fn follow_sets(grammar: &GrammarInternal) -> Vec<TerminalSet> {
+    let symbols = &grammar.symbols;
+    let first = FirstSets::compute(grammar);
+    let mut follow: Vec<TerminalSet> = (0..symbols.num_symbols())
+        .map(|_| TerminalSet::new(symbols.num_terminals()))
+        .collect();
+    // EOF follows the augmented start symbol
+    follow[grammar.rules[0].lhs.id().0 as usize].insert(SymbolId(0));
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for rule in &grammar.rules {
+            let rhs: Vec<SymbolId> = rule.rhs.iter().map(|s| s.id()).collect();
+            for (i, &sym) in rhs.iter().enumerate() {
+                if symbols.is_terminal(sym) {
+                    continue;
+                }
+                let rest = first.of_sequence(&rhs[i + 1..], symbols);
+                let mut add: Vec<SymbolId> = rest.iter().collect();
+                if rest.has_epsilon {
+                    add.extend(follow[rule.lhs.id().0 as usize].iter());
+                }
+                for id in add {
+                    changed |= follow[sym.0 as usize].insert(id);
+                }
+            }
+        }
+    }
+    follow
+}
+
+/// Whether the precedence of `prec` may be consulted. Precedence resolves a
+/// shift/reduce conflict between reducing with a `prec` lookahead and
+/// shifting it, comparing the lookahead with the precedence on the stack.
+/// So `prec` matters only if it can follow a non-terminal (as lookahead),
+/// or if a non-terminal deriving it can be followed by a `prec` terminal
+/// (as stack precedence).
+fn prec_may_matter(
+    grammar: &GrammarInternal,
+    follow: &[TerminalSet],
+    prec_terminals: &[SymbolId],
+    prec: SymbolId,
+) -> bool {
+    let symbols = &grammar.symbols;
+    if symbols
+        .non_terminal_ids()
+        .any(|id| follow[id.0 as usize].contains(prec))
+    {
+        return true;
+    }
+
+    // Non-terminals with `prec` somewhere in a derivation
+    let mut deriving = BTreeSet::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for rule in &grammar.rules {
+            if !deriving.contains(&rule.lhs.id())
+                && rule
+                    .rhs
+                    .iter()
+                    .any(|s| s.id() == prec || deriving.contains(&s.id()))
+            {
+                deriving.insert(rule.lhs.id());
+                changed = true;
+            }
+        }
+    }
+    deriving.iter().any(|id| {
+        prec_terminals
+            .iter()
+            .any(|&t| follow[id.0 as usize].contains(t))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meta::parse_grammar;
+
+    fn warnings(src: &str) -> Vec<(WarningKind, String)> {
+        lint(&parse_grammar(src).unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|w| (w.kind, w.symbol))
+            .collect()
+    }
+
+    #[test]
+    fn test_clean_grammar() {
+        let src = r#"
+            start expr;
+            terminals { NUM, prec OP, LPAREN, RPAREN }
+            expr = expr OP expr => binop | atom => atom;
+            atom = NUM => num | LPAREN expr? RPAREN => paren;
+        "#;
+        assert_eq!(warnings(src), []);
+    }
+
+    #[test]
+    fn test_unproductive_and_unreachable() {
+        let src = r#"
+            start s;
+            terminals { A, B, C }
+            s = A => a | loop B => loop;
+            loop = loop A => more;
+            orphan = C => c;
+        "#;
+        assert_eq!(
+            warnings(src),
+            [
+                (WarningKind::Unproductive, "loop".to_string()),
+                (WarningKind::Unreachable, "orphan".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_terminal() {
+        let src = "start s;\nterminals { A, UNUSED }\ns = A => a;";
+        let grammar = parse_grammar(src).unwrap();
+        let warnings = lint(&grammar).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, WarningKind::UnusedTerminal);
+        assert_eq!(
+            warnings[0].format(src),
+            "2:16: Terminal 'UNUSED' is never used"
+        );
+    }
+
+    #[test]
+    fn test_useless_prec() {
+        // OP only ever starts a statement; nothing it could conflict with
+        let src = r#"
+            start s;
+            terminals { prec OP, NUM }
+            s = OP NUM => s;
+        "#;
+        assert_eq!(
+            warnings(src),
+            [(WarningKind::UselessPrec, "OP".to_string())]
+        );
+
+        // As a prefix operator, its precedence is on the stack when a
+        // binary operator follows
+        let src = r#"
+            start expr;
+            terminals { prec OP, prec NEG, NUM }
+            expr = expr OP expr => binop | NEG expr => neg | NUM => num;
+        "#;
+        assert_eq!(warnings(src), []);
+    }
+
+    #[test]
+    fn test_allow() {
+        let src = r#"
+            #![allow(unreachable)]
+            start s;
+            terminals { A, UNUSED }
+            s = A => a;
+            orphan = A => a;
+        "#;
+        assert_eq!(
+            warnings(src),
+            [(WarningKind::UnusedTerminal, "UNUSED".to_string())]
+        );
+
+        let src = r#"
+            start s;
+            terminals { #![allow(unused_terminal)] A, UNUSED }
+            s = A => a;
+            #[allow(unreachable)]
+            orphan = A => a;
+            other = A => a;
+        "#;
+        assert_eq!(
+            warnings(src),
+            [(WarningKind::Unreachable, "other".to_string())]
+        );
+
+        assert_eq!(
+            split_allow("allow(unreachable)"),
+            (vec![WarningKind::Unreachable], None)
+        );
+        assert_eq!(
+            split_allow("allow (dead_code , useless_prec)"),
+            (
+                vec![WarningKind::UselessPrec],
+                Some("allow(dead_code)".into())
+            )
+        );
+        assert_eq!(
+            split_allow("derive(Clone)"),
+            (vec![], Some("derive(Clone)".into()))
+        );
+    }
+}
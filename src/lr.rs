@@ -2,8 +2,12 @@ use crate::grammar::SymbolId;
 use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// Convert snake_case or SCREAMING_SNAKE name to CamelCase type name.
-/// e.g., "grammar_def" → "GrammarDef", "NAME" → "Name", "COMP_OP" → "CompOp"
+/// e.g., "grammar_def" → "GrammarDef", "NAME" → "Name", "COMP_OP" → "CompOp".
+/// Literal terminals are named after their text: `'+'` → "Plus", `"while"` → "While".
 pub(crate) fn to_camel_case(name: &str) -> String {
+    if is_literal(name) {
+        return literal_camel_case(&literal_text(name));
+    }
     name.split('_')
         .map(|seg| {
             let mut chars = seg.chars();
@@ -15,6 +19,116 @@ pub(crate) fn to_camel_case(name: &str) -> String {
         .collect()
 }
 
+/// Whether a symbol name is a quoted literal terminal (`'+'`, `"while"`).
+pub(crate) fn is_literal(name: &str) -> bool {
+    name.starts_with('\'') || name.starts_with('"')
+}
+
+/// The symbol name of a literal terminal matching `text`, quoted and escaped
+/// the way Rust writes a char (one character) or string literal.
+pub(crate) fn literal_name(text: &str) -> String {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => format!("{:?}", c),
+        _ => format!("{:?}", text),
+    }
+}
+
+/// The text matched by a literal terminal, undoing [`literal_name`].
+pub(crate) fn literal_text(name: &str) -> String {
+    let mut text = String::new();
+    let mut chars = name[1..name.len() - 1].chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => text.push('\n'),
+            Some('r') => text.push('\r'),
+            Some('t') => text.push('\t'),
+            Some('0') => text.push('\0'),
+            Some('u') => {
+                let hex: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                text.extend(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32));
+            }
+            Some(c) => text.push(c),
+            None => {}
+        }
+    }
+    text
+}
+
+/// CamelCase name for a literal: words stay words, punctuation and common
+/// control characters are spelled out, e.g. "+=" → "PlusEq",
+/// "else if" → "ElseSpaceIf", "\n" → "Newline".
+fn literal_camel_case(text: &str) -> String {
+    let mut name = String::new();
+    let mut word = String::new();
+    let flush = |word: &mut String, name: &mut String| {
+        if !word.is_empty() {
+            name.push_str(&to_camel_case(word));
+            word.clear();
+        }
+    };
+    for c in text.chars() {
+        if c.is_ascii_alphanumeric() {
+            word.push(c);
+            continue;
+        }
+        flush(&mut word, &mut name);
+        let spelled = match c {
+            '+' => "Plus",
+            '-' => "Minus",
+            '*' => "Star",
+            '/' => "Slash",
+            '%' => "Percent",
+            '=' => "Eq",
+            '<' => "Lt",
+            '>' => "Gt",
+            '!' => "Bang",
+            '&' => "Amp",
+            '|' => "Pipe",
+            '^' => "Caret",
+            '~' => "Tilde",
+            '?' => "Question",
+            ':' => "Colon",
+            ';' => "Semi",
+            ',' => "Comma",
+            '.' => "Dot",
+            '(' => "Lparen",
+            ')' => "Rparen",
+            '[' => "Lbracket",
+            ']' => "Rbracket",
+            '{' => "Lbrace",
+            '}' => "Rbrace",
+            '@' => "At",
+            '#' => "Hash",
+            '$' => "Dollar",
+            '\\' => "Backslash",
+            '\'' => "Quote",
+            '"' => "Dquote",
+            '`' => "Backtick",
+            '_' => "Underscore",
+            ' ' => "Space",
+            '\n' => "Newline",
+            '\t' => "Tab",
+            '\r' => "Return",
+            '\0' => "Nul",
+            _ => {
+                name.push_str(&format!("U{:X}", c as u32));
+                continue;
+            }
+        };
+        name.push_str(spelled);
+    }
+    flush(&mut word, &mut name);
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, "Lit");
+    }
+    name
+}
+
 // ============================================================================
 // Internal grammar representation
 // ============================================================================
@@ -197,8 +311,16 @@ impl Default for SymbolTable {
 /// The action to perform when a rule alternative is reduced.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum AltAction {
-    /// User-defined action name (e.g., `=> binop`).
-    Named(String),
+    /// User-defined action name (e.g., `=> binop`), with a field label per
+    /// RHS symbol if the alternative is labeled (`lhs:expr`).
+    Named {
+        name: String,
+        labels: Vec<Option<String>>,
+        /// Doc comment lines of the alternative.
+        doc: Vec<String>,
+        /// The alternative as written (`expr OP expr`), for documentation.
+        production: String,
+    },
     /// Synthetic: wrap value in `Some` (from `?` modifier).
     OptSome,
     /// Synthetic: produce `None` (from `?` modifier).
@@ -209,6 +331,25 @@ pub(crate) enum AltAction {
     VecSingle,
     /// Synthetic: append last element to `Vec` (from `+`, `*`, `%` modifiers).
     VecAppend,
+    /// Synthetic: the `Vec` unchanged, dropping a trailing separator or
+    /// terminator (from `%%` and `;` modifiers).
+    VecKeep,
+    /// Synthetic: the typed values of a group alternative; one value passes
+    /// through, several form a tuple.
+    Tuple,
+    /// Synthetic: start a permutation's value from its first item. The slots
+    /// start empty, `true` marking a `Vec` and `false` an `Option`, and the
+    /// item goes in its slot as for `PermAdd`.
+    PermFirst(Vec<bool>, Option<(usize, bool)>),
+    /// Synthetic: put the item just read into its permutation slot, with
+    /// whether that slot is a `Vec`. Untyped items required once have no slot.
+    PermAdd(Option<(usize, bool)>),
+    /// Synthetic: the finished permutation value; `true` marks the slots of
+    /// items required once, which are unwrapped from their `Option`.
+    PermFinish(Vec<bool>),
+    /// Synthetic: the value of a permutation without items, its slots empty
+    /// as for `PermFirst`.
+    PermEmpty(Vec<bool>),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -217,6 +358,32 @@ pub(crate) struct Rule {
     pub rhs: Vec<Symbol>,
     /// Action to perform when this rule is reduced.
     pub action: AltAction,
+    /// Declared precedence (`prec(...)`), overriding the stack's at runtime.
+    pub prec: Option<RulePrec>,
+}
+
+/// How the value of a rewritten rule is rebuilt from its RHS values.
+///
+/// Leaves consume the rule's RHS values left to right; each `Build` reduces a
+/// rule of the grammar as written, so the user's nodes are reassembled even
+/// though the parser never reduced those rules itself.
+#[cfg(feature = "codegen")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Recipe {
+    /// The next RHS value, unchanged.
+    Value,
+    /// Reduce a source rule (index into [`Rewrite::source_rules`]) from the given parts.
+    Build(usize, Vec<Recipe>),
+}
+
+/// Record of the rewrites applied to remove spurious conflicts.
+#[cfg(feature = "codegen")]
+#[derive(Debug, Clone)]
+pub(crate) struct Rewrite {
+    /// The rules as the user wrote them (after desugaring).
+    pub source_rules: Vec<Rule>,
+    /// For each rule in [`GrammarInternal::rules`], how to rebuild its nodes.
+    pub recipes: Vec<Recipe>,
 }
 
 #[derive(Debug, Clone)]
@@ -225,6 +392,26 @@ pub(crate) struct GrammarInternal {
     pub symbols: SymbolTable,
     /// Type for each symbol (terminal payload or non-terminal result). None = unit type.
     pub types: BTreeMap<SymbolId, Option<String>>,
+    /// Doc comment lines of documented terminals and rules.
+    #[cfg(feature = "codegen")]
+    pub docs: BTreeMap<SymbolId, Vec<String>>,
+    /// Attributes of each rule's enum: the grammar's, then the rule's own.
+    #[cfg(feature = "codegen")]
+    pub attrs: BTreeMap<SymbolId, Vec<String>>,
+    /// Attributes of the `Terminal` enum: the grammar's, then the terminals
+    /// block's.
+    #[cfg(feature = "codegen")]
+    pub terminal_attrs: Vec<String>,
+    /// Rules marked `#[inline]`, which conflict rewrites may inline.
+    #[cfg(feature = "codegen")]
+    pub inline: Vec<Symbol>,
+    /// Set if `rules` were rewritten to be LR(1) (see [`crate::rewrite`]).
+    #[cfg(feature = "codegen")]
+    pub rewrite: Option<Rewrite>,
+    /// Split terminals as (shift-leaning, reduce-leaning) pairs, for grammars
+    /// with `mode split;`. The reduce-leaning twin never appears in a rule;
+    /// it acts like its original except where that would shift/reduce conflict.
+    pub twins: Vec<(SymbolId, SymbolId)>,
 }
 
 impl GrammarInternal {
@@ -235,21 +422,384 @@ impl GrammarInternal {
             .enumerate()
             .filter(move |(_, rule)| rule.lhs == symbol)
     }
+
+    /// The shift-leaning terminal a reduce-leaning twin was split from.
+    pub fn twin_of(&self, id: SymbolId) -> Option<SymbolId> {
+        self.twins
+            .iter()
+            .find(|&&(_, twin)| twin == id)
+            .map(|&(original, _)| original)
+    }
+
+    /// Number of accept rules (`__start -> ...`), one per start symbol.
+    /// They come first, so rule `i` is an accept rule iff `i < num_entries()`.
+    pub fn num_entries(&self) -> usize {
+        let start = self.rules[0].lhs;
+        self.rules.iter().take_while(|r| r.lhs == start).count()
+    }
+
+    /// Entry points as (marker terminal, start symbol). A grammar with
+    /// several start symbols prefixes each with a marker terminal that the
+    /// parser shifts implicitly; with a single start symbol there are none.
+    pub fn entries(&self) -> impl Iterator<Item = (SymbolId, Symbol)> + '_ {
+        self.rules[..self.num_entries()]
+            .iter()
+            .filter(|r| r.rhs.len() == 2)
+            .map(|r| (r.rhs[0].id(), r.rhs[1]))
+    }
+
+    /// True if `id` is an entry marker terminal (see [`Self::entries`]).
+    pub fn is_entry_marker(&self, id: SymbolId) -> bool {
+        self.entries().any(|(marker, _)| marker == id)
+    }
+
+    /// The start symbols, in declaration order.
+    #[cfg(feature = "codegen")]
+    pub fn start_symbols(&self) -> impl Iterator<Item = Symbol> + '_ {
+        self.rules[..self.num_entries()]
+            .iter()
+            .map(|r| *r.rhs.last().unwrap())
+    }
+
+    /// The rules as the user wrote them, before any conflict rewrites.
+    #[cfg(feature = "codegen")]
+    pub fn source_rules(&self) -> &[Rule] {
+        match &self.rewrite {
+            Some(rewrite) => &rewrite.source_rules,
+            None => &self.rules,
+        }
+    }
+
+    /// How to rebuild the source nodes when reducing `rules[rule]`.
+    #[cfg(feature = "codegen")]
+    pub fn recipe(&self, rule: usize) -> Recipe {
+        match &self.rewrite {
+            Some(rewrite) => rewrite.recipes[rule].clone(),
+            None => Recipe::Build(rule, vec![Recipe::Value; self.rules[rule].rhs.len()]),
+        }
+    }
 }
 
 // ============================================================================
 // Grammar conversion (AST -> Internal)
 // ============================================================================
 
-use crate::grammar::{Grammar, Term};
+use crate::grammar::{
+    Alt, AltPrec, Grammar, GrammarError, GrammarErrorKind, Rule as GrammarRule, Span, Spanned, Term,
+};
+use crate::runtime::RulePrec;
+
+/// Prefix of the marker terminal of each entry point, for grammars with
+/// several start symbols.
+pub(crate) const ENTRY_MARKER_PREFIX: &str = "__entry_";
+
+/// Suffix of the reduce-leaning twin of a split terminal. It makes the name
+/// an invalid identifier, so it never collides with a user terminal.
+pub(crate) const REDUCE_TWIN_SUFFIX: &str = "(reduce)";
+
+/// Rust variant name suffix of a reduce-leaning twin. Camel-cased names never
+/// contain `_`, so it cannot collide with a user terminal's variant either.
+#[cfg(feature = "codegen")]
+pub(crate) const REDUCE_TWIN_VARIANT_SUFFIX: &str = "_Reduce";
 
 /// Convert Grammar AST to internal representation.
 ///
 /// Desugars modifier symbols (?, *, +, %) into synthetic helper rules
 /// with proper [`AltAction`]s, then builds the augmented grammar.
-pub(crate) fn to_grammar_internal(grammar: &Grammar) -> Result<GrammarInternal, String> {
+///
+/// With [`Grammar::split_tokens`], every terminal that has a shift/reduce
+/// conflict also gets a reduce-leaning twin (see [`GrammarInternal::twins`]).
+pub(crate) fn to_grammar_internal(grammar: &Grammar) -> Result<GrammarInternal, GrammarError> {
+    let grammar = &expand_parameterized(grammar)?;
+    let internal = desugar(grammar, &[])?;
+    if !grammar.split_tokens {
+        return Ok(internal);
+    }
+
+    let mut conflicted = Vec::new();
+    for conflict in build_minimal_automaton(&internal).conflicts {
+        if let crate::table::Conflict::ShiftReduce { terminal, .. } = conflict
+            && !internal.symbols.is_prec_terminal(terminal)
+            && !conflicted.contains(&terminal)
+        {
+            conflicted.push(terminal);
+        }
+    }
+    if conflicted.is_empty() {
+        return Ok(internal);
+    }
+    conflicted.sort();
+    let names: Vec<&str> = conflicted
+        .iter()
+        .map(|&id| internal.symbols.name(id))
+        .collect();
+    desugar(grammar, &names)
+}
+
+/// Upper bound on the number of rules instantiated from parameterized rules,
+/// to reject expansions that never terminate (`f<T> = f<g<T>> ...`).
+const MAX_INSTANCES: usize = 10_000;
+
+/// Expand parameterized rules monomorphically: each application such as
+/// `comma_list<expr>` becomes a plain rule named after the rule and its
+/// arguments (`comma_list_expr`), with the parameters substituted.
+pub(crate) fn expand_parameterized(grammar: &Grammar) -> Result<Grammar, GrammarError> {
+    let mut expander = Expander {
+        templates: grammar
+            .rules
+            .iter()
+            .filter(|r| !r.params.is_empty())
+            .map(|r| (r.name.as_str(), r))
+            .collect(),
+        rule_names: grammar.rules.iter().map(|r| r.name.as_str()).collect(),
+        instances: HashMap::new(),
+        pending: Vec::new(),
+    };
+
+    let mut rules = Vec::new();
+    for rule in grammar.rules.iter().filter(|r| r.params.is_empty()) {
+        rules.push(expander.expand_rule(rule, rule.name.clone(), &HashMap::new())?);
+    }
+    let mut next = 0;
+    while let Some((name, template, args)) = expander.pending.get(next).cloned() {
+        next += 1;
+        if next > MAX_INSTANCES {
+            return Err(GrammarError::new(
+                GrammarErrorKind::Invalid,
+                format!(
+                    "Expansion of parameterized rule '{}' does not terminate",
+                    template.name
+                ),
+            )
+            .at(template.span.clone()));
+        }
+        let subst = template
+            .params
+            .iter()
+            .map(String::as_str)
+            .zip(args)
+            .collect();
+        rules.push(expander.expand_rule(template, name, &subst)?);
+    }
+
+    Ok(Grammar {
+        rules,
+        ..grammar.clone()
+    })
+}
+
+struct Expander<'a> {
+    /// Parameterized rules by name.
+    templates: HashMap<&'a str, &'a GrammarRule>,
+    /// Names of all rules written in the grammar.
+    rule_names: BTreeSet<&'a str>,
+    /// Instantiated rule name -> the application it came from.
+    instances: HashMap<String, String>,
+    /// Instantiations in order of first use: (rule name, template, arguments).
+    pending: Vec<(String, &'a GrammarRule, Vec<String>)>,
+}
+
+impl<'a> Expander<'a> {
+    /// Copy `rule` under `name`, substituting parameters and replacing
+    /// applications with the names of their instances.
+    fn expand_rule(
+        &mut self,
+        rule: &GrammarRule,
+        name: String,
+        subst: &HashMap<&str, String>,
+    ) -> Result<GrammarRule, GrammarError> {
+        let mut alts = Vec::with_capacity(rule.alts.len());
+        for alt in &rule.alts {
+            let terms = self
+                .terms(&alt.terms, subst)
+                .map_err(|e| in_rule(e, &rule.name))?;
+            let prec = match &alt.prec {
+                Some(AltPrec::Symbol(s)) => Some(AltPrec::Symbol(
+                    self.symbol(s, subst)
+                        .map_err(|e| in_rule(e.at(alt.span.clone()), &rule.name))?,
+                )),
+                prec => prec.clone(),
+            };
+            alts.push(crate::grammar::Alt {
+                terms,
+                prec,
+                ..alt.clone()
+            });
+        }
+        Ok(GrammarRule {
+            name,
+            params: Vec::new(),
+            alts,
+            doc: rule.doc.clone(),
+            attrs: rule.attrs.clone(),
+            span: rule.span.clone(),
+        })
+    }
+
+    fn terms(
+        &mut self,
+        terms: &[Spanned<Term>],
+        subst: &HashMap<&str, String>,
+    ) -> Result<Vec<Spanned<Term>>, GrammarError> {
+        terms
+            .iter()
+            .map(|term| {
+                let node = self
+                    .term(term, subst)
+                    .map_err(|e| e.at(term.span.clone()))?;
+                Ok(Spanned::new(node, term.span.clone()))
+            })
+            .collect()
+    }
+
+    fn term(&mut self, term: &Term, subst: &HashMap<&str, String>) -> Result<Term, GrammarError> {
+        Ok(match term {
+            Term::Symbol(s) => Term::Symbol(self.symbol(s, subst)?),
+            Term::Apply { name, args } => Term::Symbol(self.apply(name, args, subst)?),
+            // `list<T>?` is a group of one application, like a symbol's `?`
+            Term::OptionalGroup(alts)
+            | Term::ZeroOrMoreGroup(alts)
+            | Term::OneOrMoreGroup(alts)
+                if matches!(alts.as_slice(), [seq] if matches!(seq.as_slice(), [t] if matches!(t.node, Term::Apply { .. }))) =>
+            {
+                let Term::Symbol(s) = self.term(&alts[0][0], subst)? else {
+                    unreachable!()
+                };
+                match term {
+                    Term::OptionalGroup(_) => Term::Optional(s),
+                    Term::ZeroOrMoreGroup(_) => Term::ZeroOrMore(s),
+                    _ => Term::OneOrMore(s),
+                }
+            }
+            Term::Optional(s) => Term::Optional(self.symbol(s, subst)?),
+            Term::ZeroOrMore(s) => Term::ZeroOrMore(self.symbol(s, subst)?),
+            Term::OneOrMore(s) => Term::OneOrMore(self.symbol(s, subst)?),
+            Term::SeparatedBy { symbol, sep } => Term::SeparatedBy {
+                symbol: self.symbol(symbol, subst)?,
+                sep: self.symbol(sep, subst)?,
+            },
+            Term::SeparatedTrailing { symbol, sep } => Term::SeparatedTrailing {
+                symbol: self.symbol(symbol, subst)?,
+                sep: self.symbol(sep, subst)?,
+            },
+            Term::TerminatedBy { symbol, terminator } => Term::TerminatedBy {
+                symbol: self.symbol(symbol, subst)?,
+                terminator: self.symbol(terminator, subst)?,
+            },
+            Term::ZeroOrMoreTerminatedBy { symbol, terminator } => Term::ZeroOrMoreTerminatedBy {
+                symbol: self.symbol(symbol, subst)?,
+                terminator: self.symbol(terminator, subst)?,
+            },
+            Term::Group(alts) => Term::Group(self.groups(alts, subst)?),
+            Term::OptionalGroup(alts) => Term::OptionalGroup(self.groups(alts, subst)?),
+            Term::ZeroOrMoreGroup(alts) => Term::ZeroOrMoreGroup(self.groups(alts, subst)?),
+            Term::OneOrMoreGroup(alts) => Term::OneOrMoreGroup(self.groups(alts, subst)?),
+            Term::Permutation(items) => Term::Permutation(self.terms(items, subst)?),
+            Term::Empty => Term::Empty,
+        })
+    }
+
+    fn groups(
+        &mut self,
+        alts: &[Vec<Spanned<Term>>],
+        subst: &HashMap<&str, String>,
+    ) -> Result<Vec<Vec<Spanned<Term>>>, GrammarError> {
+        alts.iter().map(|seq| self.terms(seq, subst)).collect()
+    }
+
+    /// Resolve a symbol reference: a parameter becomes its argument.
+    fn symbol(
+        &mut self,
+        name: &str,
+        subst: &HashMap<&str, String>,
+    ) -> Result<String, GrammarError> {
+        if is_literal(name) {
+            return Ok(name.to_string());
+        }
+        if let Some(arg) = subst.get(name) {
+            return Ok(arg.clone());
+        }
+        if self.templates.contains_key(name) {
+            return Err(GrammarError::new(
+                GrammarErrorKind::Invalid,
+                format!("Parameterized rule '{}' used without arguments", name),
+            ));
+        }
+        Ok(name.to_string())
+    }
+
+    /// Resolve an application to the name of its instance.
+    fn apply(
+        &mut self,
+        head: &str,
+        args: &[Term],
+        subst: &HashMap<&str, String>,
+    ) -> Result<String, GrammarError> {
+        let invalid = |message: String| GrammarError::new(GrammarErrorKind::Invalid, message);
+        let Some(&template) = self.templates.get(head) else {
+            return Err(if subst.contains_key(head) {
+                invalid(format!("Parameter '{}' cannot take arguments", head))
+            } else {
+                GrammarError::new(
+                    GrammarErrorKind::UndefinedSymbol,
+                    format!("Unknown parameterized rule: {}", head),
+                )
+            });
+        };
+        if args.len() != template.params.len() {
+            return Err(invalid(format!(
+                "Rule '{}' takes {} argument(s) but {} were given",
+                head,
+                template.params.len(),
+                args.len()
+            )));
+        }
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                Term::Symbol(s) => self.symbol(s, subst),
+                Term::Apply { name, args } => self.apply(name, args, subst),
+                _ => Err(invalid(format!(
+                    "Arguments of '{}' must be symbols, not {}",
+                    head,
+                    crate::format::term_text(arg)
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let parts: Vec<String> = args.iter().map(|arg| name_part(arg)).collect();
+        let instance = format!("{}_{}", head.to_lowercase(), parts.join("_"));
+        let application = format!("{}<{}>", head, args.join(", "));
+        match self.instances.get(&instance) {
+            Some(existing) if *existing != application => {
+                return Err(invalid(format!(
+                    "'{}' and '{}' both expand to rule '{}'",
+                    existing, application, instance
+                )));
+            }
+            Some(_) => {}
+            None => {
+                if self.rule_names.contains(instance.as_str()) {
+                    return Err(invalid(format!(
+                        "'{}' expands to rule '{}', which is already defined",
+                        application, instance
+                    )));
+                }
+                self.instances.insert(instance.clone(), application);
+                self.pending.push((instance.clone(), template, args));
+            }
+        }
+        Ok(instance)
+    }
+}
+
+/// Build the internal grammar, adding reduce-leaning twins for `split` terminals.
+fn desugar(grammar: &Grammar, split: &[&str]) -> Result<GrammarInternal, GrammarError> {
     if grammar.rules.is_empty() {
-        return Err("Grammar has no rules".to_string());
+        return Err(GrammarError::new(
+            GrammarErrorKind::Invalid,
+            "Grammar has no rules",
+        ));
     }
 
     let mut symbols = SymbolTable::new();
@@ -269,6 +819,59 @@ pub(crate) fn to_grammar_internal(grammar: &Grammar) -> Result<GrammarInternal,
         };
         types.insert(sym.id(), type_name);
     }
+    // Literal terminals ('+', "while") are interned on first use in a rule
+    let mut literals = Vec::new();
+    for term in grammar
+        .rules
+        .iter()
+        .flat_map(|r| &r.alts)
+        .flat_map(|a| &a.terms)
+    {
+        collect_literals(term, &mut literals);
+    }
+    let mut variants: HashMap<String, (&str, Option<&Span>)> = grammar
+        .terminals
+        .iter()
+        .map(|def| {
+            (
+                to_camel_case(&def.name),
+                (def.name.as_str(), def.span.as_ref()),
+            )
+        })
+        .collect();
+    for name in &literals {
+        let variant = to_camel_case(name);
+        if let Some(&(other, span)) = variants.get(&variant) {
+            return Err(GrammarError::new(
+                GrammarErrorKind::DuplicateVariant,
+                format!(
+                    "Literal {} and terminal {} would both be named '{}'",
+                    name, other, variant
+                ),
+            )
+            .at(span.cloned()));
+        }
+        variants.insert(variant, (name, None));
+        let sym = symbols.intern_terminal(name);
+        types.insert(sym.id(), None);
+    }
+    // With several start symbols, each entry point gets a marker terminal.
+    let starts: Vec<&String> = grammar.starts().collect();
+    let mut markers = Vec::new();
+    if starts.len() > 1 {
+        for name in &starts {
+            let marker = symbols.intern_terminal(&format!("{ENTRY_MARKER_PREFIX}{name}"));
+            types.insert(marker.id(), None);
+            markers.push(marker);
+        }
+    }
+    let mut twins = Vec::new();
+    for &name in split {
+        let original = symbols.get_id(name).unwrap();
+        let twin = symbols.intern_terminal(&format!("{name}{REDUCE_TWIN_SUFFIX}"));
+        types.insert(twin.id(), types[&original].clone());
+        twins.push((original, twin.id()));
+    }
     symbols.finalize_terminals();
 
     // Register user non-terminals + types
@@ -284,52 +887,357 @@ pub(crate) fn to_grammar_internal(grammar: &Grammar) -> Result<GrammarInternal,
 
     for rule in &grammar.rules {
         let lhs = symbols.get(&rule.name).unwrap();
+        // Variant names as codegen emits them: repeated names get a numeric suffix
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        let mut variants: BTreeSet<String> = BTreeSet::new();
 
         for alt in &rule.alts {
-            let has_empty = alt.terms.iter().any(|t| matches!(t, Term::Empty));
+            let rhs = resolve_seq(
+                &alt.terms,
+                &mut symbols,
+                &mut types,
+                &mut desugared,
+                &mut rules,
+            )
+            .map_err(|e| in_rule(e, &rule.name))?;
 
-            let rhs: Vec<Symbol> = if has_empty {
-                Vec::new()
-            } else {
-                alt.terms
-                    .iter()
-                    .map(|term| {
-                        resolve_term(term, &mut symbols, &mut types, &mut desugared, &mut rules)
-                            .map_err(|e| format!("{e} (in rule '{}')", rule.name))
-                    })
-                    .collect::<Result<Vec<_>, _>>()?
+            check_labels(alt, &rhs, &symbols, &types).map_err(|e| in_rule(e, &rule.name))?;
+            if alt.name.is_empty() {
+                check_passthrough(alt, &rhs, &types).map_err(|e| in_rule(e, &rule.name))?;
+            }
+            if !alt.name.is_empty() {
+                let count = counts.entry(&alt.name).or_insert(0);
+                let name = match *count {
+                    0 => alt.name.clone(),
+                    n => format!("{}{}", alt.name, n),
+                };
+                *count += 1;
+                let variant = to_camel_case(&name);
+                if !variants.insert(variant.clone()) {
+                    return Err(in_rule(
+                        GrammarError::new(
+                            GrammarErrorKind::DuplicateVariant,
+                            format!("Duplicate variant name '{}'", variant),
+                        )
+                        .at(alt.span.clone()),
+                        &rule.name,
+                    ));
+                }
+            }
+            let action = AltAction::Named {
+                name: alt.name.clone(),
+                labels: alt.labels.clone(),
+                doc: alt.doc.clone(),
+                production: crate::format::production(&alt.terms),
+            };
+            let prec = match &alt.prec {
+                None => None,
+                Some(AltPrec::Fixed(prec)) => Some(RulePrec::Fixed(*prec)),
+                Some(AltPrec::Symbol(name)) => {
+                    let invalid = |message: String| {
+                        in_rule(
+                            GrammarError::new(GrammarErrorKind::Invalid, message)
+                                .at(alt.span.clone()),
+                            &rule.name,
+                        )
+                    };
+                    let pos = alt
+                        .terms
+                        .iter()
+                        .position(|t| matches!(&t.node, Term::Symbol(s) if s == name))
+                        .ok_or_else(|| {
+                            invalid(format!(
+                                "prec symbol '{}' does not appear in the alternative",
+                                name
+                            ))
+                        })?;
+                    if !symbols
+                        .get_id(name)
+                        .is_some_and(|id| symbols.is_prec_terminal(id))
+                    {
+                        return Err(invalid(format!(
+                            "prec symbol '{}' is not a prec terminal",
+                            name
+                        )));
+                    }
+                    let pos = u8::try_from(pos).map_err(|_| {
+                        invalid(format!(
+                            "prec symbol '{}' is at position {}; it must be among the first 256 symbols",
+                            name, pos
+                        ))
+                    })?;
+                    Some(RulePrec::Position(pos))
+                }
             };
 
-            let action = AltAction::Named(alt.name.clone());
-
-            rules.push(Rule { lhs, rhs, action });
+            rules.push(Rule {
+                lhs,
+                rhs,
+                action,
+                prec,
+            });
         }
     }
 
-    // Augment with __start -> <original_start>
-    let start = symbols
-        .get(&grammar.start)
-        .ok_or_else(|| format!("Start symbol '{}' not found in grammar", grammar.start))?;
+    // Augment with __start -> <start>, or __start -> <marker> <start> per entry point
+    if grammar.start.is_empty() {
+        return Err(GrammarError::new(
+            GrammarErrorKind::Invalid,
+            "Grammar has no start symbol",
+        ));
+    }
     let aug_start = symbols.intern_non_terminal("__start");
-    let aug_rule = Rule {
-        lhs: aug_start,
-        rhs: vec![start],
-        action: AltAction::Named(String::new()),
-    };
-    let mut aug_rules = vec![aug_rule];
+    let mut aug_rules = Vec::new();
+    for (i, name) in starts.iter().enumerate() {
+        let start = symbols.get(name).ok_or_else(|| {
+            GrammarError::new(
+                GrammarErrorKind::UndefinedSymbol,
+                format!("Start symbol '{}' not found in grammar", name),
+            )
+        })?;
+        if starts[..i].contains(name) {
+            return Err(GrammarError::new(
+                GrammarErrorKind::Invalid,
+                format!("Start symbol '{}' is declared twice", name),
+            ));
+        }
+        let mut rhs: Vec<Symbol> = markers.get(i).copied().into_iter().collect();
+        rhs.push(start);
+        aug_rules.push(Rule {
+            lhs: aug_start,
+            rhs,
+            action: AltAction::Named {
+                name: String::new(),
+                labels: Vec::new(),
+                doc: Vec::new(),
+                production: String::new(),
+            },
+            prec: None,
+        });
+    }
     aug_rules.extend(rules);
 
+    #[cfg(feature = "codegen")]
+    let (docs, attrs, terminal_attrs, inline) = codegen_info(grammar, &symbols);
+
     Ok(GrammarInternal {
         rules: aug_rules,
         symbols,
         types,
+        #[cfg(feature = "codegen")]
+        docs,
+        #[cfg(feature = "codegen")]
+        attrs,
+        #[cfg(feature = "codegen")]
+        terminal_attrs,
+        #[cfg(feature = "codegen")]
+        inline,
+        #[cfg(feature = "codegen")]
+        rewrite: None,
+        twins,
     })
 }
 
-fn resolve(symbols: &SymbolTable, name: &str) -> Result<Symbol, String> {
-    symbols
-        .get(name)
-        .ok_or_else(|| format!("Unknown symbol: {}", name))
+/// Docs, attributes and `#[inline]` marks of the grammar, which only code
+/// generation uses. `inline` is consumed here rather than emitted.
+#[cfg(feature = "codegen")]
+#[allow(clippy::type_complexity)]
+fn codegen_info(
+    grammar: &Grammar,
+    symbols: &SymbolTable,
+) -> (
+    BTreeMap<SymbolId, Vec<String>>,
+    BTreeMap<SymbolId, Vec<String>>,
+    Vec<String>,
+    Vec<Symbol>,
+) {
+    let mut docs = BTreeMap::new();
+    for def in &grammar.terminals {
+        if !def.doc.is_empty() {
+            docs.insert(symbols.get_id(&def.name).unwrap(), def.doc.clone());
+        }
+    }
+    for rule in &grammar.rules {
+        if !rule.doc.is_empty() {
+            docs.insert(symbols.get_id(&rule.name).unwrap(), rule.doc.clone());
+        }
+    }
+    // Lint names in `allow` are for the grammar, not the generated code
+    let for_code = |attr: &String| crate::lint::split_allow(attr).1;
+    let mut attrs = BTreeMap::new();
+    let mut inline = Vec::new();
+    for rule in &grammar.rules {
+        let id = symbols.get_id(&rule.name).unwrap();
+        let rule_attrs: Vec<String> = grammar
+            .attrs
+            .iter()
+            .chain(&rule.attrs)
+            .filter(|attr| *attr != "inline")
+            .filter_map(for_code)
+            .collect();
+        if !rule_attrs.is_empty() {
+            attrs.insert(id, rule_attrs);
+        }
+        if rule.attrs.iter().any(|attr| attr == "inline") {
+            inline.push(symbols.get(&rule.name).unwrap());
+        }
+    }
+    let terminal_attrs = grammar
+        .attrs
+        .iter()
+        .chain(&grammar.terminal_attrs)
+        .filter_map(for_code)
+        .collect();
+    (docs, attrs, terminal_attrs, inline)
+}
+
+fn resolve(symbols: &SymbolTable, name: &str) -> Result<Symbol, GrammarError> {
+    symbols.get(name).ok_or_else(|| {
+        GrammarError::new(
+            GrammarErrorKind::UndefinedSymbol,
+            format!("Unknown symbol: {}", name),
+        )
+    })
+}
+
+/// Note the rule an error was found in.
+fn in_rule(e: GrammarError, rule: &str) -> GrammarError {
+    GrammarError {
+        message: format!("{} (in rule '{}')", e.message, rule),
+        ..e
+    }
+}
+
+/// Check that an unnamed alternative has one typed symbol whose value it can
+/// pass through. A value of another type than the rule's is converted with
+/// `From`.
+fn check_passthrough(
+    alt: &Alt,
+    rhs: &[Symbol],
+    types: &BTreeMap<SymbolId, Option<String>>,
+) -> Result<(), GrammarError> {
+    let typed = rhs
+        .iter()
+        .filter(|sym| types.get(&sym.id()).is_some_and(Option::is_some))
+        .count();
+    let error = match typed {
+        _ if !alt.labels.is_empty() => GrammarError::new(
+            GrammarErrorKind::Invalid,
+            "A labeled alternative needs a variant name (=> name)",
+        ),
+        1 => return Ok(()),
+        0 => GrammarError::new(
+            GrammarErrorKind::MissingType,
+            "Alternative without a variant name has no typed symbol to pass through",
+        ),
+        _ => GrammarError::new(
+            GrammarErrorKind::Invalid,
+            format!(
+                "Alternative without a variant name has {} typed symbols; it can pass through only one",
+                typed
+            ),
+        ),
+    };
+    Err(error.at(alt.span.clone()))
+}
+
+/// Check that a labeled alternative labels exactly its typed symbols, once each.
+fn check_labels(
+    alt: &Alt,
+    rhs: &[Symbol],
+    symbols: &SymbolTable,
+    types: &BTreeMap<SymbolId, Option<String>>,
+) -> Result<(), GrammarError> {
+    if alt.labels.is_empty() || rhs.is_empty() {
+        return Ok(());
+    }
+    let mut seen = BTreeSet::new();
+    for (i, sym) in rhs.iter().enumerate() {
+        let label = alt.labels.get(i).and_then(Option::as_ref);
+        let name = symbols.name(sym.id());
+        let typed = types.get(&sym.id()).is_some_and(|t| t.is_some());
+        let error = match label {
+            Some(label) if !typed => GrammarError::new(
+                GrammarErrorKind::MissingType,
+                format!("Label '{}' is on untyped symbol '{}'", label, name),
+            ),
+            Some(label) if !seen.insert(label) => GrammarError::new(
+                GrammarErrorKind::Invalid,
+                format!("Duplicate label '{}'", label),
+            ),
+            None if typed => GrammarError::new(
+                GrammarErrorKind::Invalid,
+                format!(
+                    "Symbol '{}' needs a label: a labeled alternative must label all typed symbols",
+                    name
+                ),
+            ),
+            _ => continue,
+        };
+        return Err(error.at(alt.terms[i].span.clone()));
+    }
+    Ok(())
+}
+
+/// Collect the literal terminals used in `term`, in order of first use.
+fn collect_literals(term: &Term, literals: &mut Vec<String>) {
+    let names = match term {
+        Term::Symbol(s) | Term::Optional(s) | Term::ZeroOrMore(s) | Term::OneOrMore(s) => {
+            vec![s]
+        }
+        Term::SeparatedBy { symbol, sep } | Term::SeparatedTrailing { symbol, sep } => {
+            vec![symbol, sep]
+        }
+        Term::TerminatedBy { symbol, terminator }
+        | Term::ZeroOrMoreTerminatedBy { symbol, terminator } => vec![symbol, terminator],
+        Term::Group(alts)
+        | Term::OptionalGroup(alts)
+        | Term::ZeroOrMoreGroup(alts)
+        | Term::OneOrMoreGroup(alts) => {
+            for term in alts.iter().flatten() {
+                collect_literals(term, literals);
+            }
+            return;
+        }
+        Term::Permutation(items) => {
+            for item in items {
+                collect_literals(item, literals);
+            }
+            return;
+        }
+        Term::Apply { args, .. } => {
+            for arg in args {
+                collect_literals(arg, literals);
+            }
+            return;
+        }
+        Term::Empty => return,
+    };
+    for name in names {
+        if is_literal(name) && !literals.contains(name) {
+            literals.push(name.clone());
+        }
+    }
+}
+
+/// Resolve the terms of a sequence to RHS symbols; `_` makes it empty.
+fn resolve_seq(
+    terms: &[Spanned<Term>],
+    symbols: &mut SymbolTable,
+    types: &mut BTreeMap<SymbolId, Option<String>>,
+    desugared: &mut HashMap<Term, Symbol>,
+    rules: &mut Vec<Rule>,
+) -> Result<Vec<Symbol>, GrammarError> {
+    if terms.iter().any(|t| matches!(t.node, Term::Empty)) {
+        return Ok(Vec::new());
+    }
+    terms
+        .iter()
+        .map(|term| {
+            resolve_term(term, symbols, types, desugared, rules)
+                .map_err(|e| e.at(term.span.clone()))
+        })
+        .collect()
 }
 
 fn resolve_term(
@@ -338,7 +1246,7 @@ fn resolve_term(
     types: &mut BTreeMap<SymbolId, Option<String>>,
     desugared: &mut HashMap<Term, Symbol>,
     rules: &mut Vec<Rule>,
-) -> Result<Symbol, String> {
+) -> Result<Symbol, GrammarError> {
     if let Term::Symbol(name) = term {
         return resolve(symbols, name);
     }
@@ -347,7 +1255,7 @@ fn resolve_term(
     }
     let lhs = match term {
         Term::Optional(name) => {
-            let lhs = symbols.intern_non_terminal(&format!("__{}_opt", name.to_lowercase()));
+            let lhs = intern_synthetic(symbols, &modified_name(name, "opt"));
             let inner = lookup_type(name, symbols, types);
             types.insert(lhs.id(), inner.map(|t| format!("Option<{}>", t)));
             let sym = resolve(symbols, name)?;
@@ -355,16 +1263,18 @@ fn resolve_term(
                 lhs,
                 rhs: vec![sym],
                 action: AltAction::OptSome,
+                prec: None,
             });
             rules.push(Rule {
                 lhs,
                 rhs: vec![],
                 action: AltAction::OptNone,
+                prec: None,
             });
             lhs
         }
         Term::ZeroOrMore(name) => {
-            let lhs = symbols.intern_non_terminal(&format!("__{}_star", name.to_lowercase()));
+            let lhs = intern_synthetic(symbols, &modified_name(name, "star"));
             let inner = lookup_type(name, symbols, types);
             types.insert(lhs.id(), inner.map(|t| format!("Vec<{}>", t)));
             let sym = resolve(symbols, name)?;
@@ -372,16 +1282,18 @@ fn resolve_term(
                 lhs,
                 rhs: vec![lhs, sym],
                 action: AltAction::VecAppend,
+                prec: None,
             });
             rules.push(Rule {
                 lhs,
                 rhs: vec![],
                 action: AltAction::VecEmpty,
+                prec: None,
             });
             lhs
         }
         Term::OneOrMore(name) => {
-            let lhs = symbols.intern_non_terminal(&format!("__{}_plus", name.to_lowercase()));
+            let lhs = intern_synthetic(symbols, &modified_name(name, "plus"));
             let inner = lookup_type(name, symbols, types);
             types.insert(lhs.id(), inner.map(|t| format!("Vec<{}>", t)));
             let sym = resolve(symbols, name)?;
@@ -389,20 +1301,21 @@ fn resolve_term(
                 lhs,
                 rhs: vec![lhs, sym],
                 action: AltAction::VecAppend,
+                prec: None,
             });
             rules.push(Rule {
                 lhs,
                 rhs: vec![sym],
                 action: AltAction::VecSingle,
+                prec: None,
             });
             lhs
         }
         Term::SeparatedBy { symbol, sep } => {
-            let lhs = symbols.intern_non_terminal(&format!(
-                "__{}_sep_{}",
-                symbol.to_lowercase(),
-                sep.to_lowercase()
-            ));
+            let lhs = intern_synthetic(
+                symbols,
+                &format!("__{}_sep_{}", name_part(symbol), name_part(sep)),
+            );
             let inner = lookup_type(symbol, symbols, types);
             types.insert(lhs.id(), inner.map(|t| format!("Vec<{}>", t)));
             let sym = resolve(symbols, symbol)?;
@@ -411,20 +1324,364 @@ fn resolve_term(
                 lhs,
                 rhs: vec![lhs, sep_sym, sym],
                 action: AltAction::VecAppend,
+                prec: None,
             });
             rules.push(Rule {
                 lhs,
                 rhs: vec![sym],
                 action: AltAction::VecSingle,
+                prec: None,
+            });
+            lhs
+        }
+        Term::SeparatedTrailing { symbol, sep } => {
+            let list = resolve_term(
+                &Term::SeparatedBy {
+                    symbol: symbol.clone(),
+                    sep: sep.clone(),
+                },
+                symbols,
+                types,
+                desugared,
+                rules,
+            )?;
+            let lhs = intern_synthetic(
+                symbols,
+                &format!("__{}_sep_{}_trailing", name_part(symbol), name_part(sep)),
+            );
+            types.insert(lhs.id(), types[&list.id()].clone());
+            let sep_sym = resolve(symbols, sep)?;
+            rules.push(Rule {
+                lhs,
+                rhs: vec![list, sep_sym],
+                action: AltAction::VecKeep,
+                prec: None,
+            });
+            rules.push(Rule {
+                lhs,
+                rhs: vec![list],
+                action: AltAction::VecKeep,
+                prec: None,
+            });
+            lhs
+        }
+        Term::TerminatedBy { symbol, terminator }
+        | Term::ZeroOrMoreTerminatedBy { symbol, terminator } => {
+            // Items followed by terminators are items separated by them, and
+            // one more at the end
+            let list = resolve_term(
+                &Term::SeparatedBy {
+                    symbol: symbol.clone(),
+                    sep: terminator.clone(),
+                },
+                symbols,
+                types,
+                desugared,
+                rules,
+            )?;
+            let star = matches!(term, Term::ZeroOrMoreTerminatedBy { .. });
+            let lhs = intern_synthetic(
+                symbols,
+                &format!(
+                    "__{}_term_{}{}",
+                    name_part(symbol),
+                    name_part(terminator),
+                    if star { "_star" } else { "" }
+                ),
+            );
+            types.insert(lhs.id(), types[&list.id()].clone());
+            let term_sym = resolve(symbols, terminator)?;
+            rules.push(Rule {
+                lhs,
+                rhs: vec![list, term_sym],
+                action: AltAction::VecKeep,
+                prec: None,
+            });
+            if star {
+                rules.push(Rule {
+                    lhs,
+                    rhs: vec![],
+                    action: AltAction::VecEmpty,
+                    prec: None,
+                });
+            }
+            lhs
+        }
+        Term::Group(alts) => {
+            let mut n = 1;
+            while symbols.get(&format!("__group{n}")).is_some() {
+                n += 1;
+            }
+            let lhs = symbols.intern_non_terminal(&format!("__group{n}"));
+            let mut group_type = None;
+            for (i, seq) in alts.iter().enumerate() {
+                let rhs = resolve_seq(seq, symbols, types, desugared, rules)?;
+                let ty = seq_type(&rhs, types);
+                if i == 0 {
+                    group_type = ty;
+                } else if ty != group_type {
+                    let show = |t: &Option<String>| t.clone().unwrap_or_else(|| "()".into());
+                    return Err(GrammarError::new(
+                        GrammarErrorKind::Invalid,
+                        format!(
+                            "Alternatives of a group have different types: {} and {}",
+                            show(&group_type),
+                            show(&ty)
+                        ),
+                    ));
+                }
+                rules.push(Rule {
+                    lhs,
+                    rhs,
+                    action: AltAction::Tuple,
+                    prec: None,
+                });
+            }
+            types.insert(lhs.id(), group_type);
+            lhs
+        }
+        Term::OptionalGroup(alts) | Term::ZeroOrMoreGroup(alts) | Term::OneOrMoreGroup(alts) => {
+            let group = resolve_term(&Term::Group(alts.clone()), symbols, types, desugared, rules)?;
+            let name = symbols.name(group.id()).to_string();
+            let term = match term {
+                Term::OptionalGroup(_) => Term::Optional(name),
+                Term::ZeroOrMoreGroup(_) => Term::ZeroOrMore(name),
+                _ => Term::OneOrMore(name),
+            };
+            resolve_term(&term, symbols, types, desugared, rules)?
+        }
+        Term::Permutation(items) => resolve_permutation(items, symbols, types, rules)?,
+        // Applications are expanded before desugaring
+        Term::Symbol(_) | Term::Apply { .. } | Term::Empty => unreachable!(),
+    };
+    desugared.insert(term.clone(), lhs);
+    Ok(lhs)
+}
+
+/// Most items of a permutation that are limited to one (`a`, `a?`) or
+/// required (`a`, `a+`). With `n` of them among `m` items the permutation
+/// takes `2^n` nonterminals and up to `m * 2^n` rules, which the LR
+/// automaton multiplies again: 6 gives 64 states, enough for C's
+/// declaration specifiers with room to spare.
+const MAX_COUNTED_ITEMS: usize = 6;
+
+/// An item of a permutation being desugared.
+struct PermItem {
+    sym: Symbol,
+    /// Its bit in the state, if it is limited to one or required.
+    bit: Option<usize>,
+    /// Whether it may be read again once its bit is set.
+    repeats: bool,
+    /// Its slot in the value, with whether that is a `Vec`.
+    slot: Option<(usize, bool)>,
+}
+
+/// Desugar `{ a b? c* d+ }` into LR rules.
+///
+/// A state of the permutation records which of its counted items have been
+/// read, and has a left-recursive rule per item it may still read. The value
+/// builds up in a tuple with an `Option` or `Vec` slot per typed item; the
+/// final rule, from each state with every required item read, unwraps the
+/// items read exactly once.
+fn resolve_permutation(
+    items: &[Spanned<Term>],
+    symbols: &mut SymbolTable,
+    types: &mut BTreeMap<SymbolId, Option<String>>,
+    rules: &mut Vec<Rule>,
+) -> Result<Symbol, GrammarError> {
+    let invalid = |message: String| GrammarError::new(GrammarErrorKind::Invalid, message);
+    let mut perm_items: Vec<PermItem> = Vec::new();
+    let mut acc_types = Vec::new();
+    let mut value_types = Vec::new();
+    let mut vecs = Vec::new();
+    let mut unwrap = Vec::new();
+    let mut required = 0u32;
+    for item in items {
+        let invalid_item = |message: String| invalid(message).at(item.span.clone());
+        let (name, once, min) = match &item.node {
+            Term::Symbol(s) => (s, true, true),
+            Term::Optional(s) => (s, true, false),
+            Term::ZeroOrMore(s) => (s, false, false),
+            Term::OneOrMore(s) => (s, false, true),
+            _ => {
+                return Err(invalid_item(format!(
+                    "Permutation items must be symbols with an optional ?, * or +, not {}",
+                    crate::format::term_text(item)
+                )));
+            }
+        };
+        let sym = resolve(symbols, name).map_err(|e| e.at(item.span.clone()))?;
+        if perm_items.iter().any(|item| item.sym == sym) {
+            return Err(invalid_item(format!(
+                "Symbol '{}' appears twice in a permutation",
+                name
+            )));
+        }
+        let counted = once || min;
+        let bit = counted.then(|| perm_items.iter().filter(|i| i.bit.is_some()).count());
+        if let (Some(b), true) = (bit, min) {
+            required |= 1 << b;
+        }
+        let ty = types[&sym.id()].clone();
+        let inner = ty.as_deref().unwrap_or("()");
+        let slot = match (once, min) {
+            (true, true) => ty.map(|ty| {
+                acc_types.push(format!("Option<{}>", ty));
+                value_types.push(ty);
+                (false, true)
+            }),
+            (true, false) => {
+                acc_types.push(format!("Option<{}>", inner));
+                value_types.push(format!("Option<{}>", inner));
+                Some((false, false))
+            }
+            (false, _) => {
+                acc_types.push(format!("Vec<{}>", inner));
+                value_types.push(format!("Vec<{}>", inner));
+                Some((true, false))
+            }
+        }
+        .map(|(is_vec, required_once)| {
+            vecs.push(is_vec);
+            unwrap.push(required_once);
+            (vecs.len() - 1, is_vec)
+        });
+        perm_items.push(PermItem {
+            sym,
+            bit,
+            repeats: !once,
+            slot,
+        });
+    }
+    let counted = perm_items.iter().filter(|i| i.bit.is_some()).count();
+    if counted > MAX_COUNTED_ITEMS {
+        return Err(invalid(format!(
+            "A permutation has {} items without * (at most {} are supported)",
+            counted, MAX_COUNTED_ITEMS
+        )));
+    }
+
+    let mut n = 1;
+    while symbols.get(&format!("__perm{n}")).is_some() {
+        n += 1;
+    }
+    let lhs = symbols.intern_non_terminal(&format!("__perm{n}"));
+    types.insert(
+        lhs.id(),
+        match &value_types[..] {
+            [] => None,
+            [ty] => Some(ty.clone()),
+            _ => Some(format!("({})", value_types.join(", "))),
+        },
+    );
+    let acc_type = (!acc_types.is_empty()).then(|| format!("({},)", acc_types.join(", ")));
+    // A state after reading items; reading nothing is the start, which is
+    // not a symbol of its own, so that it needs no empty rule
+    let repeats_uncounted = perm_items.iter().any(|i| i.bit.is_none());
+    let states: Vec<Option<Symbol>> = (0..1u32 << counted)
+        .map(|state| {
+            (state != 0 || repeats_uncounted).then(|| {
+                let sym = symbols.intern_non_terminal(&format!("__perm{n}_{state}"));
+                types.insert(sym.id(), acc_type.clone());
+                sym
+            })
+        })
+        .collect();
+
+    let mut perm_rules = Vec::new();
+    let sources = states
+        .iter()
+        .enumerate()
+        .filter_map(|(state, sym)| Some((state as u32, Some((*sym)?))));
+    for (state, from) in std::iter::once((0, None)).chain(sources) {
+        for &PermItem {
+            sym,
+            bit,
+            repeats,
+            slot,
+        } in &perm_items
+        {
+            let to = match bit {
+                Some(b) if !repeats && state & (1 << b) != 0 => continue,
+                Some(b) => state | (1 << b),
+                None => state,
+            };
+            let (rhs, action) = match from {
+                Some(from) => (vec![from, sym], AltAction::PermAdd(slot)),
+                None => (vec![sym], AltAction::PermFirst(vecs.clone(), slot)),
+            };
+            perm_rules.push(Rule {
+                lhs: states[to as usize].unwrap(),
+                rhs,
+                action,
+                prec: None,
+            });
+        }
+        if state & required == required {
+            let (rhs, action) = match from {
+                Some(from) => (vec![from], AltAction::PermFinish(unwrap.clone())),
+                None => (vec![], AltAction::PermEmpty(vecs.clone())),
+            };
+            perm_rules.push(Rule {
+                lhs,
+                rhs,
+                action,
+                prec: None,
             });
-            lhs
         }
-        Term::Symbol(_) | Term::Empty => unreachable!(),
-    };
-    desugared.insert(term.clone(), lhs);
+    }
+    // Keep each symbol's rules together
+    perm_rules.sort_by_key(|rule| rule.lhs.id());
+    rules.extend(perm_rules);
     Ok(lhs)
 }
 
+/// Name of the synthetic rule for a modified symbol, e.g. `__expr_opt`.
+fn modified_name(name: &str, modifier: &str) -> String {
+    if name.starts_with("__") {
+        // Already synthetic, e.g. a group
+        format!("{}_{}", name, modifier)
+    } else {
+        format!("__{}_{}", name_part(name), modifier)
+    }
+}
+
+/// A symbol's name as part of a synthetic rule name: lowercased, with
+/// literals spelled out as in their variant names (`','` → `comma`).
+fn name_part(name: &str) -> String {
+    if is_literal(name) {
+        to_camel_case(name).to_lowercase()
+    } else {
+        name.to_lowercase()
+    }
+}
+
+/// A new synthetic non-terminal named `name`, numbered if the name is taken:
+/// `X*` and `'x'*` both read as `__x_star`.
+fn intern_synthetic(symbols: &mut SymbolTable, name: &str) -> Symbol {
+    let mut unique = name.to_string();
+    let mut n = 2;
+    while symbols.get(&unique).is_some() {
+        unique = format!("{}_{}", name, n);
+        n += 1;
+    }
+    symbols.intern_non_terminal(&unique)
+}
+
+/// The value of a group alternative: its one typed symbol, a tuple of
+/// several, or none.
+fn seq_type(rhs: &[Symbol], types: &BTreeMap<SymbolId, Option<String>>) -> Option<String> {
+    let typed: Vec<&str> = rhs
+        .iter()
+        .filter_map(|sym| types.get(&sym.id())?.as_deref())
+        .collect();
+    match typed[..] {
+        [] => None,
+        [ty] => Some(ty.to_string()),
+        _ => Some(format!("({})", typed.join(", "))),
+    }
+}
+
 fn lookup_type(
     name: &str,
     symbols: &SymbolTable,
@@ -441,7 +1698,7 @@ fn lookup_type(
 
 /// A bitset representing a set of terminals (including EOF at bit 0).
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct TerminalSet {
+pub(crate) struct TerminalSet {
     bits: Vec<u64>,
     /// Whether this set can derive epsilon (empty string).
     pub has_epsilon: bool,
@@ -473,8 +1730,8 @@ impl TerminalSet {
         }
     }
 
-    #[cfg(test)]
-    fn contains(&self, id: SymbolId) -> bool {
+    /// Whether the set contains a terminal ID.
+    pub fn contains(&self, id: SymbolId) -> bool {
         let idx = id.0 as usize;
         let word = idx / 64;
         let bit = idx % 64;
@@ -501,7 +1758,7 @@ impl TerminalSet {
 
 /// FIRST sets for all symbols, indexed by SymbolId.
 #[derive(Debug, Clone)]
-struct FirstSets {
+pub(crate) struct FirstSets {
     /// FIRST set for each symbol, indexed by symbol ID.
     sets: Vec<TerminalSet>,
     num_terminals: u32,
@@ -595,6 +1852,11 @@ impl FirstSets {
         &self.sets[id.0 as usize]
     }
 
+    /// FIRST of a sequence of symbols; `has_epsilon` if it can be empty.
+    pub fn of_sequence(&self, symbols: &[SymbolId], symbol_table: &SymbolTable) -> TerminalSet {
+        Self::first_of_sequence(symbols, &self.sets, self.num_terminals, symbol_table)
+    }
+
     /// Compute FIRST of a sequence followed by a lookahead.
     pub fn first_of_sequence_with_lookahead(
         &self,
@@ -668,12 +1930,51 @@ struct DfaLrInfo {
     reduce_rules: Vec<Vec<usize>>,
     /// NFA item indices per state (for error reporting)
     nfa_items: Vec<Vec<usize>>,
+    /// For each DFA state with a shift/reduce conflict that rule precedence
+    /// decides: true if the reduction wins.
+    prec_winner: Vec<Option<bool>>,
 }
 
 impl DfaLrInfo {
     fn has_items(&self, state: usize) -> bool {
         !self.nfa_items[state].is_empty()
     }
+
+    /// Decide shift/reduce conflicts statically by rule precedence.
+    ///
+    /// A conflicted state holds the items that shift the lookahead and the
+    /// rule that reduces on it. If the reduced rule and every rule the shift
+    /// continues have a fixed `prec(N, assoc)`, the higher precedence wins and
+    /// equal levels go by associativity, as with yacc's `%prec`.
+    fn resolve_by_rule_prec(&mut self, nfa_info: &LrNfaInfo, grammar: &GrammarInternal) {
+        let fixed = |rule: usize| match grammar.rules[rule].prec {
+            Some(RulePrec::Fixed(prec)) => Some(prec),
+            _ => None,
+        };
+        for (state, winner) in self.prec_winner.iter_mut().enumerate() {
+            let [reduce] = self.reduce_rules[state][..] else {
+                continue;
+            };
+            let Some(reduce_prec) = fixed(reduce) else {
+                continue;
+            };
+            let mut decisions = self.nfa_items[state]
+                .iter()
+                .map(|&idx| &nfa_info.items[idx])
+                // Kernel items only: the closure adds items that shift nothing yet.
+                .filter(|item| item.dot > 0)
+                .map(|item| {
+                    let shift_prec = fixed(item.rule)?;
+                    crate::runtime::prec_reduces(Some(reduce_prec), Some(shift_prec), None).ok()
+                });
+            let Some(first) = decisions.next().flatten() else {
+                continue;
+            };
+            if decisions.all(|d| d == Some(first)) {
+                *winner = Some(first);
+            }
+        }
+    }
 }
 
 /// Classify DFA states by inspecting which NFA states they contain.
@@ -694,6 +1995,7 @@ fn classify_dfa_states(nfa_sets: &[Vec<usize>], num_items: usize) -> DfaLrInfo {
     }
 
     DfaLrInfo {
+        prec_winner: vec![None; nfa_sets.len()],
         reduce_rules,
         nfa_items,
     }
@@ -790,6 +2092,11 @@ fn build_lr_nfa(grammar: &GrammarInternal, first_sets: &FirstSets) -> (automaton
         }
     }
 
+    // The start state (rule 0, dot 0) also begins every other accept rule.
+    for entry in 1..grammar.num_entries() {
+        nfa.add_epsilon(0, item_state(entry, 0, SymbolId::EOF.0));
+    }
+
     (
         nfa,
         LrNfaInfo {
@@ -818,7 +2125,10 @@ fn detect_conflicts(
             if sym >= num_terminals || nfa_info.reduce_to_real.contains_key(&sym) {
                 continue;
             }
-            if lr.has_items(target) && !lr.reduce_rules[target].is_empty() {
+            if lr.has_items(target)
+                && !lr.reduce_rules[target].is_empty()
+                && lr.prec_winner[target].is_none()
+            {
                 for &rule in &lr.reduce_rules[target] {
                     conflicts.push((source, SymbolId(sym), ConflictKind::ShiftReduce(rule)));
                 }
@@ -848,6 +2158,7 @@ enum DfaStateKind {
 }
 
 /// Resolve conflicts and classify each DFA state:
+/// - SR decided by rule precedence → Items or Reduce(rule)
 /// - SR (mixed states with items + reduces): shift wins → Items
 /// - RR (multiple reduces): lower rule wins → Reduce(winner)
 /// - Pure reduce → Reduce(rule)
@@ -856,8 +2167,11 @@ fn resolve_conflicts(lr: DfaLrInfo, nfa_info: &LrNfaInfo) -> Vec<DfaStateKind> {
     lr.reduce_rules
         .into_iter()
         .zip(lr.nfa_items)
-        .map(|(mut reduces, nfa_items)| {
-            if !nfa_items.is_empty() {
+        .zip(lr.prec_winner)
+        .map(|((mut reduces, nfa_items), prec_winner)| {
+            if prec_winner == Some(true) {
+                DfaStateKind::Reduce(reduces[0])
+            } else if !nfa_items.is_empty() {
                 // SR: shift wins
                 let items = nfa_items
                     .iter()
@@ -1459,6 +2773,9 @@ pub(crate) struct AutomatonResult {
     pub conflicts: Vec<crate::table::Conflict>,
     /// Virtual reduce symbol → real terminal ID (for prec terminals).
     pub reduce_to_real: HashMap<u32, u32>,
+    /// Reductions dropped by conflict resolution as sorted (state, terminal, rule)
+    /// triples. Only populated by [`build_glr_automaton`].
+    pub conflict_reduces: Vec<(u32, u32, u32)>,
 }
 
 /// For each group of DFA states with the same LR(0) core, copy reduce
@@ -1514,28 +2831,100 @@ fn merge_lookaheads(dfa: &mut Dfa, states: &[DfaStateKind]) {
     }
 }
 
+/// For each raw DFA state, the (terminal, rule) reductions that conflict
+/// resolution will drop: every reduce of a shift/reduce conflict, and all but
+/// the winning reduce of a reduce/reduce conflict.
+fn dropped_reduces(
+    dfa: &Dfa,
+    lr: &DfaLrInfo,
+    nfa_info: &LrNfaInfo,
+    num_terminals: u32,
+) -> Vec<Vec<(u32, u32)>> {
+    let mut dropped = vec![Vec::new(); dfa.num_states()];
+    for (source, dropped) in dropped.iter_mut().enumerate() {
+        if !lr.has_items(source) {
+            continue;
+        }
+        for &(sym, target) in &dfa.transitions[source] {
+            let terminal = match nfa_info.reduce_to_real.get(&sym) {
+                Some(&real) => real,
+                None if sym < num_terminals => sym,
+                None => continue,
+            };
+            let rules = &lr.reduce_rules[target];
+            let losers = if lr.prec_winner[target].is_some() {
+                &[][..]
+            } else if lr.has_items(target) {
+                &rules[..]
+            } else {
+                &rules[rules.len().min(1)..]
+            };
+            dropped.extend(losers.iter().map(|&rule| (terminal, rule as u32)));
+        }
+        dropped.sort();
+    }
+    dropped
+}
+
 /// Build a minimal LR(1) automaton for a grammar using NFA → DFA → Hopcroft.
 pub(crate) fn build_minimal_automaton(grammar: &GrammarInternal) -> AutomatonResult {
+    build_automaton(grammar, false)
+}
+
+/// Build a minimal LR(1) automaton that remembers the actions dropped by
+/// conflict resolution, for use by the GLR parser.
+///
+/// States are only merged when they drop the same actions, so the recorded
+/// alternatives stay exact.
+pub(crate) fn build_glr_automaton(grammar: &GrammarInternal) -> AutomatonResult {
+    build_automaton(grammar, true)
+}
+
+fn build_automaton(grammar: &GrammarInternal, keep_conflicts: bool) -> AutomatonResult {
     let first_sets = FirstSets::compute(grammar);
     let (nfa, nfa_info) = build_lr_nfa(grammar, &first_sets);
     let num_items = nfa_info.items.len();
 
     let (mut raw_dfa, raw_nfa_sets) = automaton::subset_construction(&nfa);
-    let dfa_lr_info = classify_dfa_states(&raw_nfa_sets, num_items);
+    let mut dfa_lr_info = classify_dfa_states(&raw_nfa_sets, num_items);
+    dfa_lr_info.resolve_by_rule_prec(&nfa_info, grammar);
     let dfa_conflicts = detect_conflicts(&raw_dfa, &dfa_lr_info, &nfa_info, grammar);
     let conflicts = conflict_examples(&raw_dfa, &dfa_lr_info, &nfa_info, grammar, dfa_conflicts);
+    let dropped = if keep_conflicts {
+        dropped_reduces(
+            &raw_dfa,
+            &dfa_lr_info,
+            &nfa_info,
+            grammar.symbols.num_terminals(),
+        )
+    } else {
+        vec![Vec::new(); raw_dfa.num_states()]
+    };
     let resolved = resolve_conflicts(dfa_lr_info, &nfa_info);
+    // A state where rule precedence chose the reduction no longer shifts.
+    for (state, kind) in resolved.iter().enumerate() {
+        if matches!(kind, DfaStateKind::Reduce(_)) {
+            raw_dfa.transitions[state].clear();
+        }
+    }
     merge_lookaheads(&mut raw_dfa, &resolved);
 
     // Initial partition for Hopcroft: reduce states grouped by rule,
     // all item states in one partition. (Reduce states are leaves — Hopcroft
-    // can't distinguish them by transitions alone.)
+    // can't distinguish them by transitions alone.) Item states that drop
+    // different conflicting reductions are kept apart.
     let num_rules = grammar.rules.len();
+    let mut signatures: HashMap<&[(u32, u32)], usize> = HashMap::new();
+    signatures.insert(&[], 0);
     let initial_partition: Vec<usize> = resolved
         .iter()
-        .map(|kind| match kind {
+        .zip(&dropped)
+        .map(|(kind, dropped)| match kind {
             DfaStateKind::Reduce(rule) => *rule,
-            DfaStateKind::Items(_) => num_rules,
+            DfaStateKind::Items(_) => {
+                let next = signatures.len();
+                num_rules + *signatures.entry(dropped).or_insert(next)
+            }
         })
         .collect();
 
@@ -1543,8 +2932,10 @@ pub(crate) fn build_minimal_automaton(grammar: &GrammarInternal) -> AutomatonRes
 
     // Map resolved classification through Hopcroft's state_map.
     let mut min_states = vec![const { DfaStateKind::Reduce(0) }; min_dfa.num_states()];
+    let mut min_dropped = vec![&[][..]; min_dfa.num_states()];
     for (raw_state, kind) in resolved.into_iter().enumerate() {
         min_states[state_map[raw_state]] = kind;
+        min_dropped[state_map[raw_state]] = &dropped[raw_state];
     }
 
     // Permute: item states first [0, num_item_states), then reduce states.
@@ -1587,12 +2978,21 @@ pub(crate) fn build_minimal_automaton(grammar: &GrammarInternal) -> AutomatonRes
         }
     }
 
+    let mut conflict_reduces = Vec::new();
+    for (state, dropped) in min_dropped.iter().enumerate() {
+        for &(terminal, rule) in *dropped {
+            conflict_reduces.push((permutation[state] as u32, terminal, rule));
+        }
+    }
+    conflict_reduces.sort();
+
     AutomatonResult {
         dfa: permuted_dfa,
         num_item_states,
         state_items,
         conflicts,
         reduce_to_real: nfa_info.reduce_to_real,
+        conflict_reduces,
     }
 }
 
@@ -1780,4 +3180,537 @@ mod tests {
         assert_eq!(rr, 0, "minimal LR should have no reduce/reduce conflicts");
         assert_eq!(sr, 0, "minimal LR should have no shift/reduce conflicts");
     }
+
+    #[test]
+    fn test_rule_prec() {
+        let grammar = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start expr;
+            terminals { prec OP, MINUS, NUM }
+            expr = expr OP expr => binop
+                 | MINUS expr => neg prec(9, right)
+                 | expr OP expr OP expr => pair prec(OP)
+                 | NUM => num;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let precs: Vec<_> = grammar.rules.iter().map(|r| r.prec).collect();
+        assert_eq!(
+            precs,
+            [
+                None,
+                None,
+                Some(RulePrec::Fixed(crate::Precedence::Right(9))),
+                Some(RulePrec::Position(1)),
+                None
+            ]
+        );
+
+        let err = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start expr;
+            terminals { MINUS, NUM }
+            expr = MINUS expr => neg prec(OP) | NUM => num;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.message.contains("prec symbol 'OP'"), "{}", err);
+
+        let err = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start expr;
+            terminals { MINUS, NUM }
+            expr = MINUS expr => neg prec(MINUS) | NUM => num;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap_err();
+        assert!(
+            err.message
+                .contains("prec symbol 'MINUS' is not a prec terminal"),
+            "{}",
+            err
+        );
+
+        let padding = "A ".repeat(300);
+        let err = to_grammar_internal(
+            &parse_grammar(&format!(
+                "start s; terminals {{ A, prec OP }} s = {padding} OP => s prec(OP);"
+            ))
+            .unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.message.contains("at position 300"), "{}", err);
+    }
+
+    #[test]
+    fn test_static_rule_prec() {
+        use crate::runtime::{Cst, CstParser, Token};
+        // Plain (non-prec) terminals: the conflicts are decided at table
+        // construction by comparing the rules' fixed precedences.
+        let grammar = parse_grammar(
+            r#"
+            start stmt;
+            terminals { NUM, PLUS, STAR, POW, IF, ELSE }
+            stmt = IF stmt => if_then prec(1, right)
+                 | IF stmt ELSE stmt => if_else prec(2, right)
+                 | expr => expr;
+            expr = expr PLUS expr => add prec(1, left)
+                 | expr STAR expr => mul prec(2, left)
+                 | expr POW expr => pow prec(3, right)
+                 | NUM => num;
+        "#,
+        )
+        .unwrap();
+        let compiled = crate::table::CompiledTable::build(&grammar).unwrap();
+        assert!(
+            !compiled.has_conflicts(),
+            "{:?}",
+            compiled.format_conflicts()
+        );
+
+        let parse = |input: &[&str]| {
+            let mut parser = CstParser::new(compiled.table());
+            for name in input {
+                parser
+                    .push(Token::new(compiled.symbol_id(name).unwrap()))
+                    .unwrap();
+            }
+            parser.finish().map_err(|(_, e)| e).unwrap()
+        };
+        // Renders a tree as nested parentheses of its leaves.
+        fn shape(tree: &Cst) -> String {
+            match tree {
+                Cst::Leaf { token_index, .. } => token_index.to_string(),
+                Cst::Node { children, .. } if children.len() == 1 => shape(&children[0]),
+                Cst::Node { children, .. } => format!(
+                    "({})",
+                    children.iter().map(shape).collect::<Vec<_>>().join(" ")
+                ),
+            }
+        }
+
+        // 0 + 1 * 2 + 3 ^ 4 ^ 5
+        let tree = parse(&[
+            "NUM", "PLUS", "NUM", "STAR", "NUM", "PLUS", "NUM", "POW", "NUM", "POW", "NUM",
+        ]);
+        assert_eq!(shape(&tree), "((0 1 (2 3 4)) 5 (6 7 (8 9 10)))");
+        // The ELSE binds to the inner IF.
+        let tree = parse(&["IF", "IF", "NUM", "ELSE", "NUM"]);
+        assert_eq!(shape(&tree), "(0 (1 2 3 4))");
+    }
+
+    #[test]
+    fn test_parameterized_rules() {
+        let grammar = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start items;
+            terminals { LPAREN, RPAREN, COMMA, NUM, IDENT }
+            comma_list<T> = comma_list<T> COMMA T => more | T => one;
+            delimited<open, T, close> = open T close => delimited;
+            items = delimited<LPAREN, comma_list<NUM>, RPAREN> comma_list<IDENT>? => items;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let rules: Vec<String> = grammar
+            .rules
+            .iter()
+            .map(|r| {
+                let rhs: Vec<_> = r.rhs.iter().map(|s| grammar.symbols.name(s.id())).collect();
+                format!("{} -> {}", grammar.symbols.name(r.lhs.id()), rhs.join(" "))
+            })
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                "__start -> items",
+                "__comma_list_ident_opt -> comma_list_ident",
+                "__comma_list_ident_opt -> ",
+                "items -> delimited_lparen_comma_list_num_rparen __comma_list_ident_opt",
+                "comma_list_num -> comma_list_num COMMA NUM",
+                "comma_list_num -> NUM",
+                "delimited_lparen_comma_list_num_rparen -> LPAREN comma_list_num RPAREN",
+                "comma_list_ident -> comma_list_ident COMMA IDENT",
+                "comma_list_ident -> IDENT",
+            ]
+        );
+
+        let err = |src: &str| {
+            to_grammar_internal(&parse_grammar(src).unwrap())
+                .map(|_| ())
+                .unwrap_err()
+        };
+        let e = err(r#"
+            start a;
+            terminals { X }
+            list<T> = T => one;
+            a = list<X, X> => a;
+        "#);
+        assert!(
+            e.message.contains("takes 1 argument(s) but 2 were given"),
+            "{}",
+            e
+        );
+        let e = err(r#"
+            start a;
+            terminals { X }
+            list<T> = T => one;
+            a = list => a;
+        "#);
+        assert!(e.message.contains("used without arguments"), "{}", e);
+        let e = err(r#"
+            start a;
+            terminals { X }
+            nest<T> = nest<list<T>> => more | T => one;
+            list<T> = T => one;
+            a = nest<X> => a;
+        "#);
+        assert!(e.message.contains("does not terminate"), "{}", e);
+    }
+
+    #[test]
+    fn test_group_types() {
+        let grammar = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start s;
+            terminals { A: _, B: _, COMMA, SEMI }
+            s = (COMMA A)* (A B | A COMMA B)? (SEMI | COMMA)+ => s;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let ty = |name: &str| grammar.types[&grammar.symbols.get_id(name).unwrap()].clone();
+        assert_eq!(ty("__group1"), Some("A".to_string()));
+        assert_eq!(ty("__group1_star"), Some("Vec<A>".to_string()));
+        assert_eq!(ty("__group2_opt"), Some("Option<(A, B)>".to_string()));
+        assert_eq!(ty("__group3"), None);
+        assert_eq!(ty("__group3_plus"), Some("Vec<()>".to_string()));
+
+        let err = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start s;
+            terminals { A: _, B: _ }
+            s = (A | B) => s;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap_err();
+        assert!(err.message.contains("different types: A and B"), "{}", err);
+    }
+
+    #[test]
+    fn test_list_modifiers() {
+        let grammar = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start s;
+            terminals { A: _, COMMA, SEMI: _ }
+            s = (A %% COMMA) (A ; SEMI) (s ; COMMA)* => s;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let ty = |name: &str| grammar.types[&grammar.symbols.get_id(name).unwrap()].clone();
+        assert_eq!(ty("__a_sep_comma_trailing"), Some("Vec<A>".to_string()));
+        // A typed terminator is dropped, like a typed separator
+        assert_eq!(ty("__a_term_semi"), Some("Vec<A>".to_string()));
+        assert_eq!(ty("__s_term_comma_star"), Some("Vec<S>".to_string()));
+        let rules = |name: &str| -> Vec<_> {
+            let id = grammar.symbols.get_id(name).unwrap();
+            grammar
+                .rules
+                .iter()
+                .filter(|r| r.lhs.id() == id)
+                .map(|r| {
+                    let rhs: Vec<_> = r.rhs.iter().map(|s| grammar.symbols.name(s.id())).collect();
+                    (rhs.join(" "), r.action.clone())
+                })
+                .collect()
+        };
+        assert_eq!(
+            rules("__a_sep_comma_trailing"),
+            [
+                ("__a_sep_comma COMMA".to_string(), AltAction::VecKeep),
+                ("__a_sep_comma".to_string(), AltAction::VecKeep),
+            ]
+        );
+        assert_eq!(
+            rules("__s_term_comma_star"),
+            [
+                ("__s_sep_comma COMMA".to_string(), AltAction::VecKeep),
+                (String::new(), AltAction::VecEmpty),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_permutation() {
+        let grammar = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start s;
+            terminals { A: _, B: _, C: _, D }
+            s = { A B? C* D } { A? B } => s;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let ty = |name: &str| grammar.types[&grammar.symbols.get_id(name).unwrap()].clone();
+        assert_eq!(ty("__perm1"), Some("(A, Option<B>, Vec<C>)".to_string()));
+        assert_eq!(
+            ty("__perm1_0"),
+            Some("(Option<A>, Option<B>, Vec<C>,)".to_string())
+        );
+        // A, B? and D each have a state bit; C* does not
+        assert!(grammar.symbols.get_id("__perm1_7").is_some());
+        assert!(grammar.symbols.get_id("__perm1_8").is_none());
+        assert_eq!(ty("__perm2"), Some("(Option<A>, B)".to_string()));
+        // Without * items nothing stays in the start state
+        assert!(grammar.symbols.get_id("__perm2_0").is_none());
+
+        let err = |term: &str| {
+            let src = format!("start s; terminals {{ A: _, B: _ }} s = {} => s;", term);
+            to_grammar_internal(&parse_grammar(&src).unwrap())
+                .map(|_| ())
+                .unwrap_err()
+        };
+        assert!(
+            err("{ A (B | A) }").message.contains(
+                "Permutation items must be symbols with an optional ?, * or +, not (B | A)"
+            )
+        );
+        assert!(
+            err("{ A B A? }")
+                .message
+                .contains("Symbol 'A' appears twice in a permutation")
+        );
+        let src = "start s; terminals { A, B, C, D, E, F, G } s = { A B C D E F G? } => s;";
+        let err = to_grammar_internal(&parse_grammar(src).unwrap()).unwrap_err();
+        assert!(
+            err.message
+                .contains("A permutation has 7 items without * (at most 6 are supported)"),
+            "{}",
+            err.message
+        );
+    }
+
+    #[test]
+    fn test_permutation_declaration_specifiers() {
+        use crate::runtime::{CstParser, Token};
+
+        // C11 declaration specifiers: one storage class, which `_Thread_local`
+        // may join, any qualifiers, function and alignment specifiers, and
+        // at least one type specifier
+        let grammar = parse_grammar(
+            r#"
+            start decl;
+            terminals {
+                STORAGE, THREAD_LOCAL, QUAL, TYPE, INLINE, NORETURN, ALIGNAS, IDENT, SEMI
+            }
+            decl = { STORAGE? THREAD_LOCAL? QUAL* TYPE+ INLINE* NORETURN* ALIGNAS* } IDENT SEMI
+                 => decl;
+        "#,
+        )
+        .unwrap();
+        let internal = to_grammar_internal(&grammar).unwrap();
+        // Three counted items: 2^3 states, each with a rule per item it can read
+        let states = (0..)
+            .take_while(|i| internal.symbols.get_id(&format!("__perm1_{i}")).is_some())
+            .count();
+        assert_eq!(states, 8);
+        let compiled = crate::table::CompiledTable::build(&grammar).unwrap();
+        assert!(
+            !compiled.has_conflicts(),
+            "{:?}",
+            compiled.format_conflicts()
+        );
+
+        let accepts = |input: &str| {
+            let mut parser = CstParser::new(compiled.table());
+            input.split(' ').chain(["IDENT", "SEMI"]).all(|name| {
+                parser
+                    .push(Token::new(compiled.symbol_id(name).unwrap()))
+                    .is_ok()
+            }) && parser.finish().is_ok()
+        };
+        // static _Thread_local const unsigned long inline x;
+        assert!(accepts("STORAGE THREAD_LOCAL QUAL TYPE TYPE INLINE"));
+        // long const volatile _Alignas(8) long static _Noreturn x;
+        assert!(accepts("TYPE QUAL QUAL ALIGNAS TYPE STORAGE NORETURN"));
+        assert!(accepts("THREAD_LOCAL TYPE STORAGE"));
+        // static extern int x;
+        assert!(!accepts("STORAGE STORAGE TYPE"));
+        assert!(!accepts("TYPE THREAD_LOCAL THREAD_LOCAL"));
+        // const static x;
+        assert!(!accepts("QUAL STORAGE"));
+    }
+
+    #[test]
+    fn test_label_errors() {
+        let err = |alt: &str| {
+            let src = format!(
+                "start s; terminals {{ A: _, B: _, COMMA }} s = {} => s;",
+                alt
+            );
+            to_grammar_internal(&parse_grammar(&src).unwrap())
+                .map(|_| ())
+                .unwrap_err()
+        };
+        assert!(
+            err("a:A sep:COMMA b:B")
+                .message
+                .contains("Label 'sep' is on untyped symbol 'COMMA'")
+        );
+        assert!(err("x:A COMMA x:B").message.contains("Duplicate label 'x'"));
+        assert!(
+            err("a:A COMMA B")
+                .message
+                .contains("Symbol 'B' needs a label")
+        );
+    }
+
+    #[test]
+    fn test_passthrough_types() {
+        let grammar = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start s;
+            terminals { NUM: _, PLUS, LPAREN, RPAREN }
+            s = expr* => s;
+            expr = expr PLUS term => add | term;
+            term = NUM | LPAREN expr RPAREN;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let ty = |name: &str| grammar.types[&grammar.symbols.get_id(name).unwrap()].clone();
+        // Each rule keeps its own type; values of other types convert
+        assert_eq!(ty("expr"), Some("Expr".to_string()));
+        assert_eq!(ty("term"), Some("Term".to_string()));
+        assert_eq!(ty("s"), Some("S".to_string()));
+
+        let src = |rules: &str| {
+            format!(
+                "start s; terminals {{ A: _, B: _, COMMA }} {} t = A => t;",
+                rules
+            )
+        };
+        let err = |rules: &str| {
+            to_grammar_internal(&parse_grammar(&src(rules)).unwrap())
+                .map(|_| ())
+                .unwrap_err()
+        };
+        for rules in ["s = A | B;", "s = t* => s | COMMA t?;"] {
+            assert!(to_grammar_internal(&parse_grammar(&src(rules)).unwrap()).is_ok());
+        }
+        assert!(
+            err("s = COMMA;")
+                .message
+                .contains("has no typed symbol to pass through")
+        );
+        assert!(err("s = A B;").message.contains("has 2 typed symbols"));
+        assert!(
+            err("s = a:A;")
+                .message
+                .contains("A labeled alternative needs a variant name")
+        );
+    }
+
+    #[test]
+    fn test_literal_terminals() {
+        let grammar = parse_grammar(
+            r#"start s; terminals { ID } s = "while" ID "+=" ID ';' => a | ID '+' ID ';' => b;"#,
+        )
+        .unwrap();
+        let internal = to_grammar_internal(&grammar).unwrap();
+        let names: Vec<_> = internal
+            .symbols
+            .terminal_ids()
+            .map(|id| internal.symbols.name(id))
+            .collect();
+        assert_eq!(names, ["$", "ID", "\"while\"", "\"+=\"", "';'", "'+'"]);
+
+        assert_eq!(to_camel_case("'+'"), "Plus");
+        assert_eq!(to_camel_case("\"+=\""), "PlusEq");
+        assert_eq!(to_camel_case("\"while\""), "While");
+        assert_eq!(literal_text("'\\n'"), "\n");
+
+        let grammar = parse_grammar("start s; terminals { PLUS } s = PLUS '+' => s;").unwrap();
+        let err = to_grammar_internal(&grammar).unwrap_err();
+        assert!(
+            err.message
+                .contains("Literal '+' and terminal PLUS would both be named 'Plus'")
+        );
+        assert_eq!(err.kind, GrammarErrorKind::DuplicateVariant);
+    }
+
+    #[test]
+    fn test_error_locations() {
+        let located = |src: &str| {
+            let err = to_grammar_internal(&parse_grammar(src).unwrap()).unwrap_err();
+            let text = src[err.span.clone().expect("error has no span")].to_string();
+            (err.kind, text)
+        };
+        assert_eq!(
+            located("start s; terminals { A } s = A B => ab | A => a;"),
+            (GrammarErrorKind::UndefinedSymbol, "B".to_string())
+        );
+        assert_eq!(
+            located("start s; terminals { A } s = A (A | A C)* => a;"),
+            (GrammarErrorKind::UndefinedSymbol, "C".to_string())
+        );
+        assert_eq!(
+            located("start s; terminals { A } l<T> = T => l; s = (A l<A, A>)? => s;"),
+            (GrammarErrorKind::Invalid, "l<A, A>".to_string())
+        );
+        assert_eq!(
+            located("start s; terminals { A } s = { A A? } => s;"),
+            (GrammarErrorKind::Invalid, "A?".to_string())
+        );
+        assert_eq!(
+            located("start s; terminals { A } s = A => a | A A => A;"),
+            (GrammarErrorKind::DuplicateVariant, "A A => A".to_string())
+        );
+        assert_eq!(
+            located("start s; terminals { A } s = A => a | A A => a | A A A => a1;"),
+            (
+                GrammarErrorKind::DuplicateVariant,
+                "A A A => a1".to_string()
+            )
+        );
+        assert_eq!(
+            located("start s; terminals { A: _, B } s = x:A y:B => s;"),
+            (GrammarErrorKind::MissingType, "y:B".to_string())
+        );
+        assert_eq!(
+            located("start s; terminals { A } s = A => a prec(B);"),
+            (GrammarErrorKind::Invalid, "A => a prec(B)".to_string())
+        );
+        assert_eq!(
+            located("start s; terminals { A } s = list<A> => s;"),
+            (GrammarErrorKind::UndefinedSymbol, "list<A>".to_string())
+        );
+
+        // Repeated names are numbered, as in the generated enums
+        let grammar = parse_grammar("start s; terminals { A } s = A => a | A A => a;").unwrap();
+        assert!(to_grammar_internal(&grammar).is_ok());
+    }
 }
@@ -3,10 +3,12 @@
 #[cfg(feature = "codegen")]
 use gazelle::codegen::{self, CodegenContext};
 #[cfg(not(feature = "bootstrap"))]
-use gazelle::{CompiledTable, SymbolId, parse_grammar};
+use gazelle::{CompiledTable, GrammarError, SymbolId, parse_grammar_with};
 use std::env;
 use std::fs;
 use std::io::{self, Read};
+#[cfg(not(feature = "bootstrap"))]
+use std::path::Path;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -24,10 +26,26 @@ ARGS:
 OPTIONS:
     --rust    Output generated Rust parser code (requires 'codegen' feature)
     --yacc    Output Bison-compatible .y format (requires 'codegen' feature)
+    --tree-sitter
+              Output a tree-sitter grammar.js (requires 'codegen' feature)
+    --prec-table <FILE>
+              Operators of the prec terminals for --tree-sitter, one per
+              line as 'TERMINAL TEXT left|right|nonassoc LEVEL'
+    --railroad <DIR>
+              Write a railroad diagram of each rule to DIR as SVG, with an
+              index.html that shows them all
+    --lint    Check the grammar for unused and unreachable symbols
+    --fmt     Format the grammar file in place (stdin: print to stdout)
+    --from-yacc
+              Import a Bison/yacc .y grammar and print it as .gzl
+    --from-antlr
+              Import an ANTLR 4 .g4 grammar and print it as .gzl
+    --from-ebnf
+              Import a W3C EBNF grammar and print it as .gzl
     --help    Print this help message
     --version Print version
 
-Without --rust or --yacc, outputs JSON parse tables."
+Without --rust, --yacc or --tree-sitter, outputs JSON parse tables."
     );
 }
 
@@ -36,13 +54,40 @@ fn main() {
 
     let mut rust_mode = false;
     let mut yacc_mode = false;
+    let mut tree_sitter_mode = false;
+    let mut prec_table: Option<&str> = None;
+    let mut railroad_dir: Option<&str> = None;
+    let mut lint_mode = false;
+    let mut fmt_mode = false;
+    let mut import_from: Option<&str> = None;
     let mut bootstrap_meta = false;
     let mut input_file: Option<&str> = None;
 
-    for arg in args.iter().skip(1) {
+    let mut args_iter = args.iter().skip(1);
+    while let Some(arg) = args_iter.next() {
         match arg.as_str() {
             "--rust" => rust_mode = true,
             "--yacc" => yacc_mode = true,
+            "--tree-sitter" => tree_sitter_mode = true,
+            "--prec-table" => match args_iter.next() {
+                Some(file) => prec_table = Some(file),
+                None => {
+                    eprintln!("--prec-table requires a file");
+                    std::process::exit(1);
+                }
+            },
+            "--railroad" => match args_iter.next() {
+                Some(dir) => railroad_dir = Some(dir),
+                None => {
+                    eprintln!("--railroad requires an output directory");
+                    std::process::exit(1);
+                }
+            },
+            "--lint" => lint_mode = true,
+            "--fmt" => fmt_mode = true,
+            "--from-yacc" => import_from = Some("yacc"),
+            "--from-antlr" => import_from = Some("antlr"),
+            "--from-ebnf" => import_from = Some("ebnf"),
             "--bootstrap-meta" => bootstrap_meta = true,
             "--help" | "-h" => {
                 print_help();
@@ -99,55 +144,165 @@ fn main() {
         buf
     };
 
-    if yacc_mode {
+    if let Some(from) = import_from {
+        #[cfg(not(feature = "bootstrap"))]
+        output_import(&input, input_file, from);
+        #[cfg(feature = "bootstrap")]
+        {
+            let _ = (&input, input_file);
+            eprintln!("--from-{from} mode not available in bootstrap build");
+            std::process::exit(1);
+        }
+    } else if fmt_mode {
+        #[cfg(not(feature = "bootstrap"))]
+        output_fmt(&input, input_file);
+        #[cfg(feature = "bootstrap")]
+        {
+            let _ = (&input, input_file);
+            eprintln!("--fmt mode not available in bootstrap build");
+            std::process::exit(1);
+        }
+    } else if lint_mode {
+        #[cfg(not(feature = "bootstrap"))]
+        output_lint(&input, input_file);
+        #[cfg(feature = "bootstrap")]
+        {
+            let _ = (&input, input_file);
+            eprintln!("--lint mode not available in bootstrap build");
+            std::process::exit(1);
+        }
+    } else if let Some(dir) = railroad_dir {
+        #[cfg(not(feature = "bootstrap"))]
+        output_railroad(&input, input_file, dir);
+        #[cfg(feature = "bootstrap")]
+        {
+            let _ = (&input, input_file, dir);
+            eprintln!("--railroad mode not available in bootstrap build");
+            std::process::exit(1);
+        }
+    } else if tree_sitter_mode {
         #[cfg(not(feature = "bootstrap"))]
-        output_yacc(&input);
+        output_tree_sitter(&input, input_file, prec_table);
         #[cfg(feature = "bootstrap")]
         {
-            let _ = &input;
+            let _ = (&input, input_file, prec_table);
+            eprintln!("--tree-sitter mode not available in bootstrap build");
+            std::process::exit(1);
+        }
+    } else if yacc_mode {
+        #[cfg(not(feature = "bootstrap"))]
+        output_yacc(&input, input_file);
+        #[cfg(feature = "bootstrap")]
+        {
+            let _ = (&input, input_file);
             eprintln!("--yacc mode not available in bootstrap build");
             std::process::exit(1);
         }
     } else if rust_mode {
         #[cfg(all(feature = "codegen", not(feature = "bootstrap")))]
         {
-            output_rust(&input);
+            output_rust(&input, input_file);
         }
         #[cfg(not(all(feature = "codegen", not(feature = "bootstrap"))))]
         {
-            let _ = &input;
+            let _ = (&input, input_file);
             eprintln!("--rust mode requires the 'codegen' feature (without bootstrap)");
             std::process::exit(1);
         }
     } else {
         #[cfg(not(feature = "bootstrap"))]
-        output_json(&input);
+        output_json(&input, input_file);
         #[cfg(feature = "bootstrap")]
         {
-            let _ = &input;
+            let _ = (&input, input_file);
             eprintln!("JSON mode not available in bootstrap build");
             std::process::exit(1);
         }
     }
 }
 
-#[cfg(all(feature = "codegen", not(feature = "bootstrap")))]
-fn output_rust(input: &str) {
-    let grammar_def = match gazelle::parse_grammar(input) {
-        Ok(g) => g,
-        Err(e) => {
-            eprintln!("Parse error: {}", e);
-            std::process::exit(1);
+/// Parse a grammar read from `file` (stdin if `None`), exiting on error.
+#[cfg(not(feature = "bootstrap"))]
+fn parse_input(input: &str, file: Option<&str>) -> gazelle::Grammar {
+    // Imports are relative to the grammar file, or the working directory for stdin
+    let dir = file
+        .and_then(|file| Path::new(file).parent())
+        .unwrap_or(Path::new(""));
+    parse_grammar_with(input, |path| {
+        fs::read_to_string(dir.join(path)).map_err(|e| e.to_string())
+    })
+    .unwrap_or_else(|e| fail(&e, input, file))
+}
+
+/// Print lint warnings for a grammar; exit with an error if there are any.
+#[cfg(not(feature = "bootstrap"))]
+fn output_lint(input: &str, file: Option<&str>) {
+    let grammar = parse_input(input, file);
+    let warnings = gazelle::lint::lint(&grammar).unwrap_or_else(|e| fail(&e, input, file));
+    let file = file.unwrap_or("<stdin>");
+    for w in &warnings {
+        match w.span {
+            Some(_) => eprintln!("{}:{}", file, w.format(input)),
+            None => eprintln!("{}: {}", file, w),
         }
-    };
+    }
+    if !warnings.is_empty() {
+        std::process::exit(1);
+    }
+}
 
-    let ctx = match CodegenContext::from_grammar(&grammar_def, "", "pub ", false) {
-        Ok(ctx) => ctx,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
+/// Rewrite a grammar file in canonical format, or print it if read from stdin.
+#[cfg(not(feature = "bootstrap"))]
+fn output_fmt(input: &str, file: Option<&str>) {
+    let formatted = gazelle::format_grammar(input).unwrap_or_else(|e| fail(&e, input, file));
+    match file {
+        Some(path) if formatted != input => {
+            if let Err(e) = fs::write(path, formatted) {
+                eprintln!("{path}: {e}");
+                std::process::exit(1);
+            }
         }
+        Some(_) => {}
+        None => print!("{}", formatted),
+    }
+}
+
+/// Print a grammar imported from another format as .gzl, and report what
+/// could not be translated.
+#[cfg(not(feature = "bootstrap"))]
+fn output_import(input: &str, file: Option<&str>, from: &str) {
+    let import = match from {
+        "yacc" => gazelle::import::yacc::parse(input),
+        "antlr" => gazelle::import::antlr::parse(input),
+        _ => gazelle::import::ebnf::parse(input),
     };
+    let import = import.unwrap_or_else(|e| fail(&e, input, file));
+    let file = file.unwrap_or("<stdin>");
+    for w in &import.warnings {
+        match w.span {
+            Some(_) => eprintln!("{}:{}", file, w.format(input)),
+            None => eprintln!("{}: {}", file, w),
+        }
+    }
+    print!("{}", import.grammar.to_gzl());
+}
+
+/// Report an error in the grammar `input` read from `file`, and exit.
+#[cfg(not(feature = "bootstrap"))]
+fn fail(e: &GrammarError, input: &str, file: Option<&str>) -> ! {
+    let file = file.unwrap_or("<stdin>");
+    match e.span {
+        Some(_) => eprintln!("{}:{}", file, e.format(input)),
+        None => eprintln!("{}: {}", file, e),
+    }
+    std::process::exit(1);
+}
+
+#[cfg(all(feature = "codegen", not(feature = "bootstrap")))]
+fn output_rust(input: &str, file: Option<&str>) {
+    let grammar_def = parse_input(input, file);
+    let ctx = CodegenContext::from_grammar(&grammar_def, "", "pub ", false)
+        .unwrap_or_else(|e| fail(&e, input, file));
 
     match codegen::generate_items(&ctx) {
         Ok(tokens) => {
@@ -161,180 +316,336 @@ fn output_rust(input: &str) {
             let formatted = prettyplease::unparse(&syntax_tree);
             println!("{}", formatted);
         }
-        Err(e) => {
-            eprintln!("Codegen error: {}", e);
-            std::process::exit(1);
-        }
+        Err(e) => fail(&e, input, file),
     }
 }
 
 #[cfg(not(feature = "bootstrap"))]
-fn output_yacc(input: &str) {
+fn output_yacc(input: &str, file: Option<&str>) {
     #[cfg(feature = "codegen")]
     {
-        let grammar = match parse_grammar(input) {
-            Ok(g) => g,
-            Err(e) => {
-                eprintln!("Parse error: {}", e);
-                std::process::exit(1);
-            }
-        };
+        let grammar = parse_input(input, file);
         match codegen::to_yacc(&grammar) {
             Ok(yacc) => print!("{}", yacc),
-            Err(e) => {
-                eprintln!("Error: {}", e);
-                std::process::exit(1);
-            }
+            Err(e) => fail(&e, input, file),
         }
     }
     #[cfg(not(feature = "codegen"))]
     {
-        let _ = input;
+        let _ = (input, file);
         eprintln!("--yacc mode requires the 'codegen' feature");
         std::process::exit(1);
     }
 }
 
+/// Write railroad diagrams of the grammar's rules and an index page to `dir`.
+#[cfg(not(feature = "bootstrap"))]
+fn output_railroad(input: &str, file: Option<&str>, dir: &str) {
+    use gazelle::diagram;
+
+    let grammar = parse_input(input, file);
+    let title = file
+        .and_then(|f| Path::new(f).file_stem())
+        .map_or("Grammar".into(), |stem| stem.to_string_lossy().into_owned());
+    let dir = Path::new(dir);
+    let mut files = vec![(
+        "index.html".to_string(),
+        diagram::index_html(&grammar, &title),
+    )];
+    for rule in &grammar.rules {
+        files.push((
+            format!("{}.svg", rule.name),
+            diagram::rule_svg(&grammar, rule),
+        ));
+    }
+    let written = fs::create_dir_all(dir).and_then(|()| {
+        files
+            .iter()
+            .try_for_each(|(name, contents)| fs::write(dir.join(name), contents))
+    });
+    if let Err(e) = written {
+        eprintln!("{}: {e}", dir.display());
+        std::process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "bootstrap"))]
+fn output_tree_sitter(input: &str, file: Option<&str>, prec_table: Option<&str>) {
+    #[cfg(feature = "codegen")]
+    {
+        let levels = match prec_table {
+            Some(table_file) => {
+                let table = fs::read_to_string(table_file).unwrap_or_else(|e| {
+                    eprintln!("{table_file}: {e}");
+                    std::process::exit(1);
+                });
+                codegen::PrecLevel::parse_table(&table)
+                    .unwrap_or_else(|e| fail(&e, &table, Some(table_file)))
+            }
+            None => Vec::new(),
+        };
+        let grammar = parse_input(input, file);
+        // Tree-sitter names a grammar like an identifier
+        let name: String = file
+            .and_then(|f| Path::new(f).file_stem())
+            .map_or("grammar".into(), |stem| stem.to_string_lossy().into_owned())
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        match codegen::to_tree_sitter(&grammar, &name, &levels) {
+            Ok(js) => print!("{}", js),
+            Err(e) => fail(&e, input, file),
+        }
+    }
+    #[cfg(not(feature = "codegen"))]
+    {
+        let _ = (input, file, prec_table);
+        eprintln!("--tree-sitter mode requires the 'codegen' feature");
+        std::process::exit(1);
+    }
+}
+
 #[cfg(feature = "codegen")]
 fn do_bootstrap_meta() {
     use gazelle::grammar as g;
 
     let grammar = g::Grammar {
         start: "grammar_def".to_string(),
+        extra_starts: Vec::new(),
         expect_rr: 0,
         expect_sr: 0,
+        split_tokens: false,
         terminals: vec![
             g::TerminalDef {
                 name: "IDENT".into(),
                 has_type: true,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "NUM".into(),
                 has_type: true,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "REGEX".into(),
                 has_type: true,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "LITERAL".into(),
+                has_type: true,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "DOC".into(),
+                has_type: true,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "ATTR".into(),
+                has_type: true,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "INNER_ATTR".into(),
+                has_type: true,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "KW_START".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "KW_TERMINALS".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "KW_PREC".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "KW_EXPECT".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "KW_MODE".into(),
+                has_type: false,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "KW_IMPORT".into(),
+                has_type: false,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "KW_EXTENDS".into(),
+                has_type: false,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "UNDERSCORE".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "LBRACE".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "RBRACE".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "LPAREN".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "RPAREN".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "COLON".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "COMMA".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "EQ".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "PIPE".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "PIPE_EQ".into(),
+                has_type: false,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "MINUS_EQ".into(),
+                has_type: false,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "SEMI".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "FAT_ARROW".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "QUESTION".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "STAR".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "PLUS".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
             },
             g::TerminalDef {
                 name: "PERCENT".into(),
                 has_type: false,
                 is_prec: false,
                 pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "LT".into(),
+                has_type: false,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
+            },
+            g::TerminalDef {
+                name: "GT".into(),
+                has_type: false,
+                is_prec: false,
+                pattern: None,
+                ..Default::default()
             },
         ],
         rules: vec![
@@ -342,146 +653,478 @@ fn do_bootstrap_meta() {
                 name: "grammar_def".into(),
                 alts: vec![g::Alt {
                     terms: vec![
-                        g::Term::Symbol("KW_START".into()),
-                        g::Term::Symbol("IDENT".into()),
-                        g::Term::Symbol("SEMI".into()),
-                        g::Term::ZeroOrMore("expect_decl".into()),
-                        g::Term::Symbol("KW_TERMINALS".into()),
-                        g::Term::Symbol("LBRACE".into()),
+                        g::Term::ZeroOrMore("INNER_ATTR".into()).into(),
+                        g::Term::Optional("start_decl".into()).into(),
+                        g::Term::ZeroOrMore("decl".into()).into(),
+                        g::Term::Optional("terminals_block".into()).into(),
+                        g::Term::ZeroOrMore("rule".into()).into(),
+                    ],
+                    name: "grammar_def".into(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            g::Rule {
+                name: "start_decl".into(),
+                alts: vec![g::Alt {
+                    terms: vec![
+                        g::Term::Symbol("KW_START".into()).into(),
                         g::Term::SeparatedBy {
-                            symbol: "terminal_item".into(),
+                            symbol: "IDENT".into(),
                             sep: "COMMA".into(),
-                        },
-                        g::Term::Symbol("RBRACE".into()),
-                        g::Term::OneOrMore("rule".into()),
+                        }
+                        .into(),
+                        g::Term::Symbol("SEMI".into()).into(),
                     ],
-                    name: "grammar_def".into(),
+                    name: "start_decl".into(),
+                    ..Default::default()
                 }],
+                ..Default::default()
+            },
+            g::Rule {
+                name: "decl".into(),
+                alts: vec![
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("KW_EXPECT".into()).into(),
+                            g::Term::Symbol("NUM".into()).into(),
+                            g::Term::Symbol("IDENT".into()).into(),
+                            g::Term::Symbol("SEMI".into()).into(),
+                        ],
+                        name: "expect_decl".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("KW_MODE".into()).into(),
+                            g::Term::Symbol("IDENT".into()).into(),
+                            g::Term::Symbol("SEMI".into()).into(),
+                        ],
+                        name: "mode_decl".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("KW_IMPORT".into()).into(),
+                            g::Term::Symbol("LITERAL".into()).into(),
+                            g::Term::Symbol("SEMI".into()).into(),
+                        ],
+                        name: "import_decl".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("KW_EXTENDS".into()).into(),
+                            g::Term::Symbol("LITERAL".into()).into(),
+                            g::Term::Symbol("SEMI".into()).into(),
+                        ],
+                        name: "extends_decl".into(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
             },
             g::Rule {
-                name: "expect_decl".into(),
+                name: "terminals_block".into(),
                 alts: vec![g::Alt {
                     terms: vec![
-                        g::Term::Symbol("KW_EXPECT".into()),
-                        g::Term::Symbol("NUM".into()),
-                        g::Term::Symbol("IDENT".into()),
-                        g::Term::Symbol("SEMI".into()),
+                        g::Term::Symbol("KW_TERMINALS".into()).into(),
+                        g::Term::Symbol("LBRACE".into()).into(),
+                        g::Term::ZeroOrMore("INNER_ATTR".into()).into(),
+                        g::Term::SeparatedBy {
+                            symbol: "terminal_item".into(),
+                            sep: "COMMA".into(),
+                        }
+                        .into(),
+                        g::Term::Symbol("RBRACE".into()).into(),
                     ],
-                    name: "expect_decl".into(),
+                    name: "terminals_block".into(),
+                    ..Default::default()
                 }],
+                ..Default::default()
             },
             g::Rule {
                 name: "terminal_item".into(),
                 alts: vec![g::Alt {
                     terms: vec![
-                        g::Term::Optional("KW_PREC".into()),
-                        g::Term::Symbol("IDENT".into()),
-                        g::Term::Optional("type_annot".into()),
-                        g::Term::Optional("regex_annot".into()),
+                        g::Term::ZeroOrMore("DOC".into()).into(),
+                        g::Term::Optional("KW_PREC".into()).into(),
+                        g::Term::Symbol("IDENT".into()).into(),
+                        g::Term::Optional("type_annot".into()).into(),
+                        g::Term::Optional("regex_annot".into()).into(),
                     ],
                     name: "terminal_item".into(),
+                    ..Default::default()
                 }],
+                ..Default::default()
             },
             g::Rule {
                 name: "type_annot".into(),
                 alts: vec![g::Alt {
                     terms: vec![
-                        g::Term::Symbol("COLON".into()),
-                        g::Term::Symbol("UNDERSCORE".into()),
+                        g::Term::Symbol("COLON".into()).into(),
+                        g::Term::Symbol("UNDERSCORE".into()).into(),
                     ],
                     name: "type_annot".into(),
+                    ..Default::default()
                 }],
+                ..Default::default()
             },
             g::Rule {
                 name: "regex_annot".into(),
                 alts: vec![g::Alt {
                     terms: vec![
-                        g::Term::Symbol("EQ".into()),
-                        g::Term::Symbol("REGEX".into()),
+                        g::Term::Symbol("EQ".into()).into(),
+                        g::Term::Symbol("REGEX".into()).into(),
                     ],
                     name: "regex_annot".into(),
+                    ..Default::default()
                 }],
+                ..Default::default()
             },
             g::Rule {
                 name: "rule".into(),
+                alts: vec![
+                    g::Alt {
+                        terms: vec![
+                            g::Term::ZeroOrMore("DOC".into()).into(),
+                            g::Term::ZeroOrMore("ATTR".into()).into(),
+                            g::Term::Symbol("IDENT".into()).into(),
+                            g::Term::Optional("params".into()).into(),
+                            g::Term::Symbol("EQ".into()).into(),
+                            g::Term::SeparatedBy {
+                                symbol: "alt".into(),
+                                sep: "PIPE".into(),
+                            }
+                            .into(),
+                            g::Term::Symbol("SEMI".into()).into(),
+                        ],
+                        name: "rule".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::ZeroOrMore("DOC".into()).into(),
+                            g::Term::ZeroOrMore("ATTR".into()).into(),
+                            g::Term::Symbol("IDENT".into()).into(),
+                            g::Term::Symbol("PIPE_EQ".into()).into(),
+                            g::Term::SeparatedBy {
+                                symbol: "alt".into(),
+                                sep: "PIPE".into(),
+                            }
+                            .into(),
+                            g::Term::Symbol("SEMI".into()).into(),
+                        ],
+                        name: "extend_rule".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::ZeroOrMore("DOC".into()).into(),
+                            g::Term::ZeroOrMore("ATTR".into()).into(),
+                            g::Term::Symbol("IDENT".into()).into(),
+                            g::Term::Symbol("MINUS_EQ".into()).into(),
+                            g::Term::SeparatedBy {
+                                symbol: "IDENT".into(),
+                                sep: "COMMA".into(),
+                            }
+                            .into(),
+                            g::Term::Symbol("SEMI".into()).into(),
+                        ],
+                        name: "remove_alts".into(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            g::Rule {
+                name: "params".into(),
                 alts: vec![g::Alt {
                     terms: vec![
-                        g::Term::Symbol("IDENT".into()),
-                        g::Term::Symbol("EQ".into()),
+                        g::Term::Symbol("LT".into()).into(),
                         g::Term::SeparatedBy {
-                            symbol: "alt".into(),
-                            sep: "PIPE".into(),
-                        },
-                        g::Term::Symbol("SEMI".into()),
+                            symbol: "IDENT".into(),
+                            sep: "COMMA".into(),
+                        }
+                        .into(),
+                        g::Term::Symbol("GT".into()).into(),
                     ],
-                    name: "rule".into(),
+                    name: "params".into(),
+                    ..Default::default()
                 }],
+                ..Default::default()
             },
             g::Rule {
                 name: "alt".into(),
                 alts: vec![g::Alt {
                     terms: vec![
-                        g::Term::OneOrMore("term".into()),
-                        g::Term::Symbol("variant".into()),
+                        g::Term::ZeroOrMore("DOC".into()).into(),
+                        g::Term::OneOrMore("field".into()).into(),
+                        g::Term::Optional("variant".into()).into(),
+                        g::Term::Optional("prec_annot".into()).into(),
                     ],
                     name: "alt".into(),
+                    ..Default::default()
                 }],
+                ..Default::default()
+            },
+            g::Rule {
+                name: "field".into(),
+                alts: vec![
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("IDENT".into()).into(),
+                            g::Term::Symbol("COLON".into()).into(),
+                            g::Term::Symbol("term".into()).into(),
+                        ],
+                        name: "labeled".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![g::Term::Symbol("term".into()).into()],
+                        name: "unlabeled".into(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
             },
             g::Rule {
                 name: "variant".into(),
                 alts: vec![g::Alt {
                     terms: vec![
-                        g::Term::Symbol("FAT_ARROW".into()),
-                        g::Term::Symbol("IDENT".into()),
+                        g::Term::Symbol("FAT_ARROW".into()).into(),
+                        g::Term::Symbol("IDENT".into()).into(),
                     ],
-                    name: "variant".into(),
+                    name: String::new(),
+                    ..Default::default()
                 }],
+                ..Default::default()
+            },
+            g::Rule {
+                name: "prec_annot".into(),
+                alts: vec![
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("KW_PREC".into()).into(),
+                            g::Term::Symbol("LPAREN".into()).into(),
+                            g::Term::Symbol("NUM".into()).into(),
+                            g::Term::Symbol("COMMA".into()).into(),
+                            g::Term::Symbol("IDENT".into()).into(),
+                            g::Term::Symbol("RPAREN".into()).into(),
+                        ],
+                        name: "prec_fixed".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("KW_PREC".into()).into(),
+                            g::Term::Symbol("LPAREN".into()).into(),
+                            g::Term::Symbol("IDENT".into()).into(),
+                            g::Term::Symbol("RPAREN".into()).into(),
+                        ],
+                        name: "prec_symbol".into(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
             },
             g::Rule {
                 name: "term".into(),
                 alts: vec![
                     g::Alt {
                         terms: vec![
-                            g::Term::Symbol("LPAREN".into()),
-                            g::Term::Symbol("IDENT".into()),
-                            g::Term::Symbol("PERCENT".into()),
-                            g::Term::Symbol("IDENT".into()),
-                            g::Term::Symbol("RPAREN".into()),
+                            g::Term::Symbol("LPAREN".into()).into(),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("PERCENT".into()).into(),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("RPAREN".into()).into(),
                         ],
                         name: "sym_sep".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("LPAREN".into()).into(),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("PERCENT".into()).into(),
+                            g::Term::Symbol("PERCENT".into()).into(),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("RPAREN".into()).into(),
+                        ],
+                        name: "sym_sep_trailing".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("LPAREN".into()).into(),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("SEMI".into()).into(),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("RPAREN".into()).into(),
+                            g::Term::Symbol("STAR".into()).into(),
+                        ],
+                        name: "sym_term_star".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("LPAREN".into()).into(),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("SEMI".into()).into(),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("RPAREN".into()).into(),
+                        ],
+                        name: "sym_term".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("LPAREN".into()).into(),
+                            g::Term::SeparatedBy {
+                                symbol: "seq".into(),
+                                sep: "PIPE".into(),
+                            }
+                            .into(),
+                            g::Term::Symbol("RPAREN".into()).into(),
+                            g::Term::Symbol("QUESTION".into()).into(),
+                        ],
+                        name: "group_opt".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("LPAREN".into()).into(),
+                            g::Term::SeparatedBy {
+                                symbol: "seq".into(),
+                                sep: "PIPE".into(),
+                            }
+                            .into(),
+                            g::Term::Symbol("RPAREN".into()).into(),
+                            g::Term::Symbol("STAR".into()).into(),
+                        ],
+                        name: "group_star".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("LPAREN".into()).into(),
+                            g::Term::SeparatedBy {
+                                symbol: "seq".into(),
+                                sep: "PIPE".into(),
+                            }
+                            .into(),
+                            g::Term::Symbol("RPAREN".into()).into(),
+                            g::Term::Symbol("PLUS".into()).into(),
+                        ],
+                        name: "group_plus".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("LPAREN".into()).into(),
+                            g::Term::SeparatedBy {
+                                symbol: "seq".into(),
+                                sep: "PIPE".into(),
+                            }
+                            .into(),
+                            g::Term::Symbol("RPAREN".into()).into(),
+                        ],
+                        name: "group".into(),
+                        ..Default::default()
                     },
                     g::Alt {
                         terms: vec![
-                            g::Term::Symbol("IDENT".into()),
-                            g::Term::Symbol("QUESTION".into()),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("QUESTION".into()).into(),
                         ],
                         name: "sym_opt".into(),
+                        ..Default::default()
                     },
                     g::Alt {
                         terms: vec![
-                            g::Term::Symbol("IDENT".into()),
-                            g::Term::Symbol("STAR".into()),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("STAR".into()).into(),
                         ],
                         name: "sym_star".into(),
+                        ..Default::default()
                     },
                     g::Alt {
                         terms: vec![
-                            g::Term::Symbol("IDENT".into()),
-                            g::Term::Symbol("PLUS".into()),
+                            g::Term::Symbol("symbol".into()).into(),
+                            g::Term::Symbol("PLUS".into()).into(),
                         ],
                         name: "sym_plus".into(),
+                        ..Default::default()
                     },
                     g::Alt {
-                        terms: vec![g::Term::Symbol("IDENT".into())],
+                        terms: vec![g::Term::Symbol("symbol".into()).into()],
                         name: "sym_plain".into(),
+                        ..Default::default()
                     },
                     g::Alt {
-                        terms: vec![g::Term::Symbol("UNDERSCORE".into())],
+                        terms: vec![
+                            g::Term::Symbol("LBRACE".into()).into(),
+                            g::Term::OneOrMore("term".into()).into(),
+                            g::Term::Symbol("RBRACE".into()).into(),
+                        ],
+                        name: "permutation".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![g::Term::Symbol("UNDERSCORE".into()).into()],
                         name: "sym_empty".into(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            g::Rule {
+                name: "seq".into(),
+                alts: vec![g::Alt {
+                    terms: vec![g::Term::OneOrMore("term".into()).into()],
+                    name: "seq".into(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            g::Rule {
+                name: "symbol".into(),
+                alts: vec![
+                    g::Alt {
+                        terms: vec![
+                            g::Term::Symbol("IDENT".into()).into(),
+                            g::Term::Optional("args".into()).into(),
+                        ],
+                        name: "symbol".into(),
+                        ..Default::default()
+                    },
+                    g::Alt {
+                        terms: vec![g::Term::Symbol("LITERAL".into()).into()],
+                        name: "literal".into(),
+                        ..Default::default()
                     },
                 ],
+                ..Default::default()
+            },
+            g::Rule {
+                name: "args".into(),
+                alts: vec![g::Alt {
+                    terms: vec![
+                        g::Term::Symbol("LT".into()).into(),
+                        g::Term::SeparatedBy {
+                            symbol: "symbol".into(),
+                            sep: "COMMA".into(),
+                        }
+                        .into(),
+                        g::Term::Symbol("GT".into()).into(),
+                    ],
+                    name: "args".into(),
+                    ..Default::default()
+                }],
+                ..Default::default()
             },
         ],
+        ..Default::default()
     };
 
     let ctx = CodegenContext::from_grammar(&grammar, "", "pub ", false)
@@ -507,22 +1150,9 @@ fn do_bootstrap_meta() {
 }
 
 #[cfg(not(feature = "bootstrap"))]
-fn output_json(input: &str) {
-    let grammar = match parse_grammar(input) {
-        Ok(g) => g,
-        Err(e) => {
-            eprintln!("Parse error: {}", e);
-            std::process::exit(1);
-        }
-    };
-
-    let table = match CompiledTable::build(&grammar) {
-        Ok(t) => t,
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            std::process::exit(1);
-        }
-    };
+fn output_json(input: &str, file: Option<&str>) {
+    let grammar = parse_input(input, file);
+    let table = CompiledTable::build(&grammar).unwrap_or_else(|e| fail(&e, input, file));
 
     if table.has_conflicts() {
         for msg in table.format_conflicts() {
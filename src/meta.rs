@@ -12,7 +12,8 @@
 #![allow(dead_code)]
 
 use crate as gazelle;
-use crate::grammar;
+use crate::format::{self, Line};
+use crate::grammar::{self, Comment, GrammarError, GrammarErrorKind, Span, Spanned};
 use crate::lexer::Scanner;
 
 // ============================================================================
@@ -26,52 +27,206 @@ include!("meta_generated.rs");
 // ============================================================================
 
 #[doc(hidden)]
-pub struct AstBuilder;
+#[derive(Default)]
+pub struct AstBuilder {
+    /// First semantic error (e.g. a malformed `prec(...)`), reported after parsing.
+    error: Option<GrammarError>,
+    /// Byte span of each token; empty when parsing tokens without source.
+    token_spans: Vec<Span>,
+    /// Token range of the node being built.
+    range: std::ops::Range<usize>,
+}
+
+impl AstBuilder {
+    /// Source span of the node being built.
+    fn span(&self) -> Option<Span> {
+        self.span_after(0)
+    }
+
+    /// Source span of the node being built, without its first `skip` tokens
+    /// (e.g. leading doc comments).
+    fn span_after(&self, skip: usize) -> Option<Span> {
+        let start = self.range.start + skip;
+        if start >= self.range.end {
+            return None;
+        }
+        let first = self.token_spans.get(start)?;
+        let last = self.token_spans.get(self.range.end - 1)?;
+        Some(first.start..last.end)
+    }
+
+    /// Source span of the `i`th token of the node being built.
+    fn token_span(&self, i: usize) -> Option<Span> {
+        let i = self.range.start + i;
+        if i >= self.range.end {
+            return None;
+        }
+        self.token_spans.get(i).cloned()
+    }
+
+    fn invalid(&mut self, message: String, span: Option<Span>) {
+        if self.error.is_none() {
+            self.error = Some(GrammarError::new(GrammarErrorKind::Invalid, message).at(span));
+        }
+    }
+
+    /// Rule edits have no enum of their own to document or attribute.
+    fn only_on_definition(&mut self, doc: &[String], attrs: &[String], name: &str) {
+        let what = if !doc.is_empty() {
+            "doc comment"
+        } else if !attrs.is_empty() {
+            "attribute"
+        } else {
+            return;
+        };
+        let span = self.token_span(0);
+        self.invalid(
+            format!(
+                "{} on an edit of rule '{}'; put it on the definition",
+                what, name
+            ),
+            span,
+        );
+    }
+}
 
 impl Types for AstBuilder {
     type Error = crate::ParseError;
     type Ident = String;
     type Num = String;
     type Regex = String;
-    type GrammarDef = grammar::Grammar;
-    type ExpectDecl = ExpectDecl<Self>;
+    type Literal = String;
+    type Doc = String;
+    type Attr = String;
+    type InnerAttr = String;
+    type GrammarDef = GrammarFile;
+    type StartDecl = (Vec<(String, Option<Span>)>, Option<Span>);
+    type Decl = (Decl<Self>, Option<Span>);
+    type TerminalsBlock = TerminalsBlockNode;
     type TerminalItem = grammar::TerminalDef;
     type TypeAnnot = crate::Ignore;
     type RegexAnnot = String;
-    type Rule = grammar::Rule;
+    type Rule = RuleItem;
+    type Params = Vec<String>;
     type Alt = grammar::Alt;
     type Variant = String;
-    type Term = grammar::Term;
-}
-
-impl gazelle::Action<Variant<Self>> for AstBuilder {
-    fn build(&mut self, node: Variant<Self>) -> Result<String, crate::ParseError> {
-        let Variant::Variant(name) = node;
-        Ok(name)
+    type Field = (Option<String>, Spanned<grammar::Term>);
+    type PrecAnnot = Option<grammar::AltPrec>;
+    type Term = Spanned<grammar::Term>;
+    type Symbol = grammar::Term;
+    type Args = Vec<grammar::Term>;
+    type Seq = Vec<Spanned<grammar::Term>>;
+
+    fn set_token_range(&mut self, start: usize, end: usize) {
+        self.range = start..end;
     }
 }
 
 impl gazelle::Action<GrammarDef<Self>> for AstBuilder {
-    fn build(&mut self, node: GrammarDef<Self>) -> Result<grammar::Grammar, crate::ParseError> {
-        let GrammarDef::GrammarDef(start, expects, terminals, rules) = node;
-        let mut expect_rr = 0;
-        let mut expect_sr = 0;
-        for e in expects {
-            let ExpectDecl::ExpectDecl(count, kind) = e;
-            let count: usize = count.parse().unwrap_or(0);
-            match kind.as_str() {
-                "rr" => expect_rr = count,
-                "sr" => expect_sr = count,
-                _ => {}
-            }
-        }
-        Ok(grammar::Grammar {
-            start,
-            expect_rr,
-            expect_sr,
+    fn build(&mut self, node: GrammarDef<Self>) -> Result<GrammarFile, crate::ParseError> {
+        let GrammarDef::GrammarDef(attrs, start, decls, terminals, rules) = node;
+        let (terminal_attrs, terminals, terminals_span) = terminals.unwrap_or_default();
+        let mut file = GrammarFile {
+            terminal_attrs,
             terminals,
+            terminals_span,
             rules,
-        })
+            ..GrammarFile::default()
+        };
+        for (i, attr) in attrs.into_iter().enumerate() {
+            file.decls
+                .push((format::inner_attr(&attr), self.token_span(i)));
+            file.attrs.push(attr);
+        }
+        if let Some((names, span)) = start {
+            file.decls
+                .push((format::start_decl(names.iter().map(|(name, _)| name)), span));
+            file.start = Some(names);
+        }
+        for (d, span) in decls {
+            let text = match &d {
+                Decl::ExpectDecl(count, kind) => format!("expect {} {};", count, kind),
+                Decl::ModeDecl(mode) => format!("mode {};", mode),
+                Decl::ImportDecl(path) => format!("import {};", format::quote(path)),
+                Decl::ExtendsDecl(path) => format!("extends {};", format::quote(path)),
+            };
+            file.decls.push((text, span.clone()));
+            match d {
+                Decl::ExpectDecl(count, kind) => {
+                    let count: usize = count.parse().unwrap_or(0);
+                    match kind.as_str() {
+                        "rr" => file.expect_rr = Some(count),
+                        "sr" => file.expect_sr = Some(count),
+                        _ => {}
+                    }
+                }
+                Decl::ModeDecl(mode) => match mode.as_str() {
+                    "split" => file.split_tokens = Some(true),
+                    "default" => file.split_tokens = Some(false),
+                    _ => self.invalid(
+                        format!("unknown mode '{}'; expected 'split' or 'default'", mode),
+                        span,
+                    ),
+                },
+                Decl::ImportDecl(path) => file.imports.push(path),
+                Decl::ExtendsDecl(path) => {
+                    if file.extends.is_some() {
+                        self.invalid(
+                            "a grammar can extend only one base grammar".into(),
+                            self.span(),
+                        );
+                    }
+                    file.extends = Some(path);
+                }
+            }
+        }
+        Ok(file)
+    }
+}
+
+impl gazelle::Action<StartDecl<Self>> for AstBuilder {
+    fn build(
+        &mut self,
+        node: StartDecl<Self>,
+    ) -> Result<(Vec<(String, Option<Span>)>, Option<Span>), crate::ParseError> {
+        let StartDecl::StartDecl(names) = node;
+        // `start a, b;`: the names are every other token after `start`
+        let names = names
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| (name, self.token_span(1 + 2 * i)))
+            .collect();
+        Ok((names, self.span()))
+    }
+}
+
+impl gazelle::Action<Decl<Self>> for AstBuilder {
+    fn build(&mut self, node: Decl<Self>) -> Result<(Decl<Self>, Option<Span>), crate::ParseError> {
+        Ok((node, self.span()))
+    }
+}
+
+/// Inner attributes and terminals of a `terminals { ... }` block, with its
+/// location.
+type TerminalsBlockNode = (
+    Vec<(String, Option<Span>)>,
+    Vec<grammar::TerminalDef>,
+    Option<Span>,
+);
+
+impl gazelle::Action<TerminalsBlock<Self>> for AstBuilder {
+    fn build(
+        &mut self,
+        node: TerminalsBlock<Self>,
+    ) -> Result<TerminalsBlockNode, crate::ParseError> {
+        let TerminalsBlock::TerminalsBlock(attrs, terminals) = node;
+        // The attributes follow `terminals {`
+        let attrs = attrs
+            .into_iter()
+            .enumerate()
+            .map(|(i, attr)| (attr, self.token_span(2 + i)))
+            .collect();
+        Ok((attrs, terminals, self.span()))
     }
 }
 
@@ -87,56 +242,248 @@ impl gazelle::Action<TerminalItem<Self>> for AstBuilder {
         &mut self,
         node: TerminalItem<Self>,
     ) -> Result<grammar::TerminalDef, crate::ParseError> {
-        let TerminalItem::TerminalItem(is_prec, name, has_type, regex_pattern) = node;
+        let TerminalItem::TerminalItem(doc, is_prec, name, has_type, regex_pattern) = node;
         Ok(grammar::TerminalDef {
+            span: self.span_after(doc.len()),
             name,
             has_type: has_type.is_some(),
             is_prec: is_prec.is_some(),
             pattern: regex_pattern,
+            doc,
         })
     }
 }
 
 impl gazelle::Action<Rule<Self>> for AstBuilder {
-    fn build(&mut self, node: Rule<Self>) -> Result<grammar::Rule, crate::ParseError> {
-        let Rule::Rule(name, alts) = node;
-        Ok(grammar::Rule { name, alts })
+    fn build(&mut self, node: Rule<Self>) -> Result<RuleItem, crate::ParseError> {
+        Ok(match node {
+            Rule::Rule(doc, attrs, name, params, alts) => RuleItem::Define(grammar::Rule {
+                span: self.token_span(doc.len() + attrs.len()),
+                name,
+                params: params.unwrap_or_default(),
+                alts,
+                doc,
+                attrs,
+            }),
+            Rule::ExtendRule(doc, attrs, name, alts) => {
+                let span = self.token_span(doc.len() + attrs.len());
+                self.only_on_definition(&doc, &attrs, &name);
+                RuleItem::Extend(name, alts, span)
+            }
+            Rule::RemoveAlts(doc, attrs, name, variants) => {
+                let span = self.token_span(doc.len() + attrs.len());
+                self.only_on_definition(&doc, &attrs, &name);
+                RuleItem::Remove(name, variants, span)
+            }
+        })
+    }
+}
+
+impl gazelle::Action<Params<Self>> for AstBuilder {
+    fn build(&mut self, node: Params<Self>) -> Result<Vec<String>, crate::ParseError> {
+        let Params::Params(names) = node;
+        Ok(names)
     }
 }
 
 impl gazelle::Action<Alt<Self>> for AstBuilder {
     fn build(&mut self, node: Alt<Self>) -> Result<grammar::Alt, crate::ParseError> {
-        let Alt::Alt(terms, name) = node;
-        Ok(grammar::Alt { terms, name })
+        let Alt::Alt(doc, fields, name, prec) = node;
+        let span = self.span_after(doc.len());
+        let (mut labels, terms): (Vec<_>, Vec<_>) = fields.into_iter().unzip();
+        if labels.iter().all(Option::is_none) {
+            labels.clear();
+        }
+        Ok(grammar::Alt {
+            terms,
+            labels,
+            name: name.unwrap_or_default(),
+            prec: prec.flatten(),
+            doc,
+            span,
+        })
     }
 }
 
-impl gazelle::Action<Term<Self>> for AstBuilder {
-    fn build(&mut self, node: Term<Self>) -> Result<grammar::Term, crate::ParseError> {
+impl gazelle::Action<Field<Self>> for AstBuilder {
+    fn build(
+        &mut self,
+        node: Field<Self>,
+    ) -> Result<(Option<String>, Spanned<grammar::Term>), crate::ParseError> {
         Ok(match node {
-            Term::SymSep(name, sep) => grammar::Term::SeparatedBy { symbol: name, sep },
-            Term::SymOpt(name) => grammar::Term::Optional(name),
-            Term::SymStar(name) => grammar::Term::ZeroOrMore(name),
-            Term::SymPlus(name) => grammar::Term::OneOrMore(name),
-            Term::SymPlain(name) => grammar::Term::Symbol(name),
+            // A labeled term's location includes its label
+            Field::Labeled(label, term) => (Some(label), Spanned::new(term.node, self.span())),
+            Field::Unlabeled(term) => (None, term),
+        })
+    }
+}
+
+impl gazelle::Action<PrecAnnot<Self>> for AstBuilder {
+    fn build(
+        &mut self,
+        node: PrecAnnot<Self>,
+    ) -> Result<Option<grammar::AltPrec>, crate::ParseError> {
+        Ok(match node {
+            PrecAnnot::PrecFixed(level, assoc) => {
+                let prec = level.parse().ok().and_then(|level| match assoc.as_str() {
+                    "left" => Some(crate::Precedence::Left(level)),
+                    "right" => Some(crate::Precedence::Right(level)),
+                    "nonassoc" => Some(crate::Precedence::NonAssoc(level)),
+                    _ => None,
+                });
+                if prec.is_none() {
+                    self.invalid(
+                        format!(
+                            "invalid precedence 'prec({}, {})': expected a level 0-255 and left, right or nonassoc",
+                            level, assoc
+                        ),
+                        self.span(),
+                    );
+                }
+                prec.map(grammar::AltPrec::Fixed)
+            }
+            PrecAnnot::PrecSymbol(name) => Some(grammar::AltPrec::Symbol(name)),
+        })
+    }
+}
+
+impl gazelle::Action<Term<Self>> for AstBuilder {
+    fn build(&mut self, node: Term<Self>) -> Result<Spanned<grammar::Term>, crate::ParseError> {
+        let span = self.span();
+        // The operands of `%` and `;` are plain symbols.
+        let mut operand = |term: grammar::Term| match term {
+            grammar::Term::Symbol(name) => name,
+            term => {
+                let text = format::term_text(&term);
+                self.invalid(
+                    format!(
+                        "'{}' can't be used with '%' or ';'; define a rule for it",
+                        text
+                    ),
+                    self.span(),
+                );
+                text
+            }
+        };
+        let term = match node {
+            Term::SymSep(name, sep) => grammar::Term::SeparatedBy {
+                symbol: operand(name),
+                sep: operand(sep),
+            },
+            Term::SymSepTrailing(name, sep) => grammar::Term::SeparatedTrailing {
+                symbol: operand(name),
+                sep: operand(sep),
+            },
+            Term::SymTermStar(name, terminator) => grammar::Term::ZeroOrMoreTerminatedBy {
+                symbol: operand(name),
+                terminator: operand(terminator),
+            },
+            Term::SymTerm(name, terminator) => grammar::Term::TerminatedBy {
+                symbol: operand(name),
+                terminator: operand(terminator),
+            },
+            Term::GroupOpt(alts) => grammar::Term::OptionalGroup(alts),
+            Term::GroupStar(alts) => grammar::Term::ZeroOrMoreGroup(alts),
+            Term::GroupPlus(alts) => grammar::Term::OneOrMoreGroup(alts),
+            Term::Group(alts) => grammar::Term::Group(alts),
+            Term::SymOpt(grammar::Term::Symbol(name)) => grammar::Term::Optional(name),
+            Term::SymStar(grammar::Term::Symbol(name)) => grammar::Term::ZeroOrMore(name),
+            Term::SymPlus(grammar::Term::Symbol(name)) => grammar::Term::OneOrMore(name),
+            Term::SymOpt(apply) => {
+                grammar::Term::OptionalGroup(vec![vec![Spanned::new(apply, span.clone())]])
+            }
+            Term::SymStar(apply) => {
+                grammar::Term::ZeroOrMoreGroup(vec![vec![Spanned::new(apply, span.clone())]])
+            }
+            Term::SymPlus(apply) => {
+                grammar::Term::OneOrMoreGroup(vec![vec![Spanned::new(apply, span.clone())]])
+            }
+            Term::SymPlain(term) => term,
+            Term::Permutation(items) => grammar::Term::Permutation(items),
             Term::SymEmpty => grammar::Term::Empty,
+        };
+        Ok(Spanned::new(term, span))
+    }
+}
+
+impl gazelle::Action<Seq<Self>> for AstBuilder {
+    fn build(&mut self, node: Seq<Self>) -> Result<Vec<Spanned<grammar::Term>>, crate::ParseError> {
+        let Seq::Seq(terms) = node;
+        Ok(terms)
+    }
+}
+
+impl gazelle::Action<Symbol<Self>> for AstBuilder {
+    fn build(&mut self, node: Symbol<Self>) -> Result<grammar::Term, crate::ParseError> {
+        Ok(match node {
+            Symbol::Symbol(name, Some(args)) => grammar::Term::Apply { name, args },
+            Symbol::Symbol(name, None) => grammar::Term::Symbol(name),
+            Symbol::Literal(text) => grammar::Term::Symbol(crate::lr::literal_name(&text)),
         })
     }
 }
 
+impl gazelle::Action<Args<Self>> for AstBuilder {
+    fn build(&mut self, node: Args<Self>) -> Result<Vec<grammar::Term>, crate::ParseError> {
+        let Args::Args(args) = node;
+        Ok(args)
+    }
+}
+
 // ============================================================================
 // Lexer
 // ============================================================================
 
-/// Lex grammar syntax using the composable Scanner API.
-fn lex_grammar(input: &str) -> Result<Vec<Terminal<AstBuilder>>, String> {
+/// Lex grammar syntax using the composable Scanner API, returning each
+/// token with its byte span, and the comments.
+type Lexed = (Vec<(Terminal<AstBuilder>, Span)>, Vec<Comment>);
+
+fn lex_grammar(input: &str) -> Result<Lexed, GrammarError> {
+    let syntax = |message: String, span: Span| {
+        GrammarError::new(GrammarErrorKind::Syntax, message).at(Some(span))
+    };
     let mut src = Scanner::new(input);
-    let mut tokens = Vec::new();
+    let mut tokens: Vec<(Terminal<AstBuilder>, Span)> = Vec::new();
+    let mut comments = Vec::new();
 
     loop {
-        // Skip whitespace and comments
+        // Skip whitespace, keeping comments
         src.skip_whitespace();
-        while src.skip_line_comment("//") || src.skip_block_comment("/*", "*/") {
+        loop {
+            let start = src.offset();
+            // Doc comment (`///`, but not a `////` divider), unless it
+            // follows code on its line other than the `=` or `|` before
+            // an alternative
+            let after_code = tokens.last().is_some_and(|(tok, span)| {
+                !matches!(tok, Terminal::Eq | Terminal::PipeEq | Terminal::Pipe)
+                    && !input[span.end..start].contains('\n')
+            });
+            if src.starts_with("///") && !src.starts_with("////") && !after_code {
+                src.skip_line_comment("///");
+                let text = input[start + 3..src.offset()].trim_end();
+                tokens.push((
+                    Terminal::Doc(text.to_string()),
+                    start..start + 3 + text.len(),
+                ));
+                src.skip_whitespace();
+                continue;
+            }
+            if !(src.skip_line_comment("//") || src.skip_block_comment("/*", "*/")) {
+                break;
+            }
+            let text = input[start..src.offset()].trim_end();
+            // Trailing: code before it on its line, and none after it
+            let end = start + text.len();
+            let line_rest = input[end..].split('\n').next().unwrap_or("");
+            let trailing = line_rest.trim().is_empty()
+                && tokens
+                    .last()
+                    .is_some_and(|(_, span)| !input[span.end..start].contains('\n'));
+            comments.push(Comment {
+                text: text.to_string(),
+                trailing,
+                span: start..end,
+            });
             src.skip_whitespace();
         }
 
@@ -146,28 +493,44 @@ fn lex_grammar(input: &str) -> Result<Vec<Terminal<AstBuilder>>, String> {
 
         // Identifier or keyword
         if let Some(span) = src.read_ident() {
-            let s = &input[span];
+            let s = &input[span.clone()];
+            // `import` and `extends` are keywords only before a path, and
+            // `mode` only before a name at the start of a declaration, so
+            // they stay usable as names
+            src.skip_whitespace();
+            let before_path = src.peek() == Some('"');
+            let at_decl = matches!(
+                tokens
+                    .iter()
+                    .rfind(|(tok, _)| !matches!(tok, Terminal::Doc(_))),
+                None | Some((Terminal::Semi | Terminal::InnerAttr(_), _))
+            );
+            let before_name = src.peek().is_some_and(|c| c.is_alphabetic() || c == '_');
             let tok = match s {
                 "start" => Terminal::KwStart,
                 "terminals" => Terminal::KwTerminals,
                 "prec" => Terminal::KwPrec,
                 "expect" => Terminal::KwExpect,
+                "mode" if at_decl && before_name => Terminal::KwMode,
+                "import" if before_path => Terminal::KwImport,
+                "extends" if before_path => Terminal::KwExtends,
 
                 "_" => Terminal::Underscore,
                 _ => Terminal::Ident(s.to_string()),
             };
-            tokens.push(tok);
+            tokens.push((tok, span));
             continue;
         }
 
         // Number
         if let Some(span) = src.read_digits() {
-            let s = &input[span];
-            tokens.push(Terminal::Num(s.to_string()));
+            let s = &input[span.clone()];
+            tokens.push((Terminal::Num(s.to_string()), span));
             continue;
         }
 
         // Single-char operators and punctuation
+        let tok_start = src.offset();
         if let Some(c) = src.peek() {
             let tok = match c {
                 '=' => {
@@ -185,10 +548,9 @@ fn lex_grammar(input: &str) -> Result<Vec<Terminal<AstBuilder>>, String> {
                     loop {
                         match src.peek() {
                             None => {
-                                let (line, col) = src.line_col(start - 1);
-                                return Err(format!(
-                                    "{}:{}: unterminated regex pattern",
-                                    line, col
+                                return Err(syntax(
+                                    "unterminated regex pattern".into(),
+                                    tok_start..src.offset(),
                                 ));
                             }
                             Some('\\') => {
@@ -206,9 +568,100 @@ fn lex_grammar(input: &str) -> Result<Vec<Terminal<AstBuilder>>, String> {
                     let pattern = input[start..end].to_string();
                     Terminal::Regex(pattern)
                 }
+                '\'' | '"' => {
+                    let quote = c;
+                    let start = src.offset();
+                    src.advance(); // consume opening quote
+                    let mut text = String::new();
+                    loop {
+                        match src.peek() {
+                            None => {
+                                return Err(syntax(
+                                    "unterminated literal".into(),
+                                    start..src.offset(),
+                                ));
+                            }
+                            Some('\\') => {
+                                src.advance();
+                                match src.peek() {
+                                    Some('n') => text.push('\n'),
+                                    Some('t') => text.push('\t'),
+                                    Some('r') => text.push('\r'),
+                                    Some('0') => text.push('\0'),
+                                    Some(c) => text.push(c),
+                                    None => continue,
+                                }
+                                src.advance();
+                            }
+                            Some(c) if c == quote => break,
+                            Some(c) => {
+                                text.push(c);
+                                src.advance();
+                            }
+                        }
+                    }
+                    src.advance(); // consume closing quote
+                    if text.is_empty() {
+                        return Err(syntax("empty literal".into(), start..src.offset()));
+                    }
+                    Terminal::Literal(text)
+                }
+                '#' => {
+                    src.advance();
+                    let inner = src.peek() == Some('!');
+                    if inner {
+                        src.advance();
+                    }
+                    if src.peek() != Some('[') {
+                        return Err(syntax(
+                            "expected '[' after '#'".into(),
+                            tok_start..src.offset(),
+                        ));
+                    }
+                    src.advance();
+                    let start = src.offset();
+                    // Up to the matching `]`, skipping brackets in strings
+                    let mut depth = 0;
+                    let mut in_string = false;
+                    loop {
+                        match (src.advance(), in_string) {
+                            (None, _) => {
+                                return Err(syntax(
+                                    "unterminated attribute".into(),
+                                    tok_start..src.offset(),
+                                ));
+                            }
+                            (Some('\\'), true) => {
+                                src.advance();
+                            }
+                            (Some('"'), _) => in_string = !in_string,
+                            (Some(_), true) => {}
+                            (Some('(' | '[' | '{'), false) => depth += 1,
+                            (Some(']'), false) if depth == 0 => break,
+                            (Some(')' | ']' | '}'), false) => depth -= 1,
+                            (Some(_), false) => {}
+                        }
+                    }
+                    let attr = input[start..src.offset() - 1].trim().to_string();
+                    if inner {
+                        Terminal::InnerAttr(attr)
+                    } else {
+                        Terminal::Attr(attr)
+                    }
+                }
                 '|' => {
                     src.advance();
-                    Terminal::Pipe
+                    if src.peek() == Some('=') {
+                        src.advance();
+                        Terminal::PipeEq
+                    } else {
+                        Terminal::Pipe
+                    }
+                }
+                '-' if src.peek_n(1) == Some('=') => {
+                    src.advance();
+                    src.advance();
+                    Terminal::MinusEq
                 }
                 ':' => {
                     src.advance();
@@ -230,6 +683,14 @@ fn lex_grammar(input: &str) -> Result<Vec<Terminal<AstBuilder>>, String> {
                     src.advance();
                     Terminal::Percent
                 }
+                '<' => {
+                    src.advance();
+                    Terminal::Lt
+                }
+                '>' => {
+                    src.advance();
+                    Terminal::Gt
+                }
                 ';' => {
                     src.advance();
                     Terminal::Semi
@@ -255,48 +716,702 @@ fn lex_grammar(input: &str) -> Result<Vec<Terminal<AstBuilder>>, String> {
                     Terminal::Rparen
                 }
                 _ => {
-                    let (line, col) = src.line_col(src.offset());
-                    return Err(format!("{}:{}: unexpected character: {:?}", line, col, c));
+                    return Err(syntax(
+                        format!("unexpected character: {:?}", c),
+                        tok_start..tok_start + c.len_utf8(),
+                    ));
                 }
             };
-            tokens.push(tok);
+            let span = tok_start..src.offset();
+            if matches!(tok, Terminal::Pipe) {
+                // Docs before `|` document the alternative after it
+                let docs = tokens
+                    .iter()
+                    .rev()
+                    .take_while(|(tok, _)| matches!(tok, Terminal::Doc(_)))
+                    .count();
+                tokens.insert(tokens.len() - docs, (tok, span));
+            } else {
+                tokens.push((tok, span));
+            }
             continue;
         }
     }
 
-    Ok(tokens)
+    for span in take_stray_docs(&mut tokens) {
+        let trailing = tokens
+            .iter()
+            .map(|(_, prev)| prev.end)
+            .filter(|&end| end <= span.start)
+            .max()
+            .is_some_and(|end| !input[end..span.start].contains('\n'));
+        comments.push(Comment {
+            text: input[span.clone()].to_string(),
+            trailing,
+            span,
+        });
+    }
+    comments.sort_by_key(|c| c.span.start);
+    Ok((tokens, comments))
+}
+
+/// Take out the doc comments that document nothing, returning their spans:
+/// only terminals, rules and alternatives take docs, so `///` anywhere else
+/// is a plain comment.
+fn take_stray_docs(tokens: &mut Vec<(Terminal<AstBuilder>, Span)>) -> Vec<Span> {
+    let mut kept: Vec<(Terminal<AstBuilder>, Span)> = Vec::with_capacity(tokens.len());
+    let mut stray = Vec::new();
+    let mut docs = Vec::new();
+    let mut in_terminals = false;
+    // Parentheses and braces open within a rule
+    let mut depth = 0usize;
+    for tok in std::mem::take(tokens).into_iter().map(Some).chain([None]) {
+        if let Some((Terminal::Doc(_), _)) = &tok {
+            docs.extend(tok);
+            continue;
+        }
+        if !docs.is_empty() {
+            let prev = kept.last().map(|(tok, _)| tok);
+            let next = tok.as_ref().map(|(tok, _)| tok);
+            let documents = if in_terminals {
+                matches!(
+                    prev,
+                    Some(Terminal::Lbrace | Terminal::Comma | Terminal::InnerAttr(_))
+                ) && matches!(next, Some(Terminal::Ident(_) | Terminal::KwPrec))
+            } else {
+                let alt = matches!(prev, Some(Terminal::Eq | Terminal::PipeEq | Terminal::Pipe));
+                let item = matches!(
+                    prev,
+                    None | Some(Terminal::Semi | Terminal::Rbrace | Terminal::InnerAttr(_))
+                ) && matches!(next, Some(Terminal::Ident(_) | Terminal::Attr(_)));
+                depth == 0 && (alt || item)
+            };
+            if documents {
+                kept.append(&mut docs);
+            } else {
+                stray.extend(docs.drain(..).map(|(_, span)| span));
+            }
+        }
+        let Some((tok, span)) = tok else { break };
+        match tok {
+            Terminal::Lbrace if matches!(kept.last(), Some((Terminal::KwTerminals, _))) => {
+                in_terminals = true
+            }
+            Terminal::Rbrace if in_terminals => in_terminals = false,
+            Terminal::Lparen | Terminal::Lbrace => depth += 1,
+            Terminal::Rparen | Terminal::Rbrace => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        kept.push((tok, span));
+    }
+    *tokens = kept;
+    stray
 }
 
 // ============================================================================
 // Parsing API
 // ============================================================================
 
-/// Parse tokens into typed AST.
-pub fn parse_tokens_typed<I>(tokens: I) -> Result<grammar::Grammar, String>
+/// A parsed grammar file, before its base grammar and imports are resolved.
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct GrammarFile {
+    /// Header declarations (`start`, `expect`, ...) as formatted text, in
+    /// source order.
+    decls: Vec<(String, Option<Span>)>,
+    comments: Vec<Comment>,
+    attrs: Vec<String>,
+    terminal_attrs: Vec<(String, Option<Span>)>,
+    /// Location of the `terminals { ... }` block.
+    terminals_span: Option<Span>,
+    /// Start symbols, each with its location.
+    start: Option<Vec<(String, Option<Span>)>>,
+    expect_rr: Option<usize>,
+    expect_sr: Option<usize>,
+    split_tokens: Option<bool>,
+    extends: Option<String>,
+    imports: Vec<String>,
+    terminals: Vec<grammar::TerminalDef>,
+    rules: Vec<RuleItem>,
+}
+
+/// A rule definition, or an edit of a rule defined elsewhere.
+#[doc(hidden)]
+#[derive(Debug)]
+pub enum RuleItem {
+    /// `name = ...;`
+    Define(grammar::Rule),
+    /// `name |= ...;`, with the span of the rule name.
+    Extend(String, Vec<grammar::Alt>, Option<Span>),
+    /// `name -= variant, ...;`, with the span of the rule name.
+    Remove(String, Vec<String>, Option<Span>),
+}
+
+/// Parse the tokens of one file; `spans` gives their source locations,
+/// or is empty.
+fn parse_file<I>(tokens: I, spans: Vec<Span>) -> Result<GrammarFile, GrammarError>
 where
     I: IntoIterator<Item = Terminal<AstBuilder>>,
 {
     let mut parser = Parser::<AstBuilder>::new();
-    let mut actions = AstBuilder;
-
-    for tok in tokens {
+    let mut actions = AstBuilder {
+        token_spans: spans,
+        ..AstBuilder::default()
+    };
+    let syntax = |message: String, span: Option<&Span>| {
+        GrammarError::new(GrammarErrorKind::Syntax, message).at(span.cloned())
+    };
+
+    for (i, tok) in tokens.into_iter().enumerate() {
         if let Err(e) = parser.push(tok, &mut actions) {
-            return Err(parser.format_error(&e, None, None));
+            let message = parser.format_error(&e, None, None);
+            return Err(syntax(message, actions.token_spans.get(i)));
         }
     }
 
-    parser
+    let end = actions.token_spans.last().map(|s| s.end..s.end);
+    let file = parser
         .finish(&mut actions)
-        .map_err(|(p, e)| p.format_error(&e, None, None))
+        .map_err(|(p, e)| syntax(p.format_error(&e, None, None), end.as_ref()))?;
+    match actions.error {
+        Some(error) => Err(error),
+        None => Ok(file),
+    }
+}
+
+fn no_resolver(path: &str) -> Result<String, String> {
+    Err(format!(
+        "cannot import \"{}\": no resolver given (use parse_grammar_with)",
+        path
+    ))
+}
+
+/// Parse tokens into typed AST.
+pub fn parse_tokens_typed<I>(tokens: I) -> Result<grammar::Grammar, GrammarError>
+where
+    I: IntoIterator<Item = Terminal<AstBuilder>>,
+{
+    parse_tokens_with(tokens, no_resolver)
+}
+
+/// Parse tokens into typed AST, loading imported files with `resolve`.
+/// See [`parse_grammar_with`].
+pub fn parse_tokens_with<I, F>(tokens: I, resolve: F) -> Result<grammar::Grammar, GrammarError>
+where
+    I: IntoIterator<Item = Terminal<AstBuilder>>,
+    F: FnMut(&str) -> Result<String, String>,
+{
+    let root = parse_file(tokens, Vec::new())?;
+    Loader::new(resolve).resolve_root(root)
+}
+
+/// Like [`parse_tokens_with`], for tokens paired with their locations.
+/// Spans in errors and in the grammar are built from these; a caller can
+/// pass token indexes (`i..i + 1`) to map them back to its own tokens.
+/// `DOC` tokens where no doc is allowed are dropped as plain comments.
+pub fn parse_located_tokens<F>(
+    mut tokens: Vec<(Terminal<AstBuilder>, Span)>,
+    resolve: F,
+) -> Result<grammar::Grammar, GrammarError>
+where
+    F: FnMut(&str) -> Result<String, String>,
+{
+    take_stray_docs(&mut tokens);
+    let (tokens, spans): (Vec<_>, Vec<_>) = tokens.into_iter().unzip();
+    let root = parse_file(tokens, spans)?;
+    Loader::new(resolve).resolve_root(root)
 }
 
 /// Parse a grammar string into a Grammar AST.
-pub fn parse_grammar(input: &str) -> Result<grammar::Grammar, String> {
-    let tokens = lex_grammar(input)?;
+///
+/// Grammars that `import` other files need [`parse_grammar_with`].
+/// Errors carry the byte span of the problem in `input`; use
+/// [`GrammarError::format`] to show it as a line and column.
+pub fn parse_grammar(input: &str) -> Result<grammar::Grammar, GrammarError> {
+    parse_grammar_with(input, no_resolver)
+}
+
+/// Parse a grammar string, loading the files it imports with `resolve`.
+///
+/// `resolve` maps an import path to the contents of the file. Paths in an
+/// imported file are relative to that file: if `common/expr.gzl` has
+/// `import "tokens.gzl";`, `resolve` is asked for `common/tokens.gzl`. Paths
+/// use `/` separators and are normalized, so `resolve` gets `lib/a.gzl` for
+/// `sub/../lib/a.gzl`. Each file is loaded once, however often it is imported.
+///
+/// The terminals and rules of imported files are merged into the grammar,
+/// ahead of its own. Only the importing grammar's `start`, `expect`,
+/// `mode` and `#![...]` declarations apply.
+///
+/// ```
+/// let grammar = gazelle::parse_grammar_with(
+///     r#"start expr; import "atoms.gzl"; terminals { PLUS } expr = atom PLUS atom => add;"#,
+///     |path| match path {
+///         "atoms.gzl" => Ok("terminals { NUM: _ } atom = NUM => num;".to_string()),
+///         _ => Err(format!("{} not found", path)),
+///     },
+/// )
+/// .unwrap();
+/// assert_eq!(grammar.terminals.len(), 2);
+/// assert_eq!(grammar.rules.len(), 2);
+/// ```
+pub fn parse_grammar_with<F>(input: &str, resolve: F) -> Result<grammar::Grammar, GrammarError>
+where
+    F: FnMut(&str) -> Result<String, String>,
+{
+    let mut root = parse_source(input)?;
+    let comments = std::mem::take(&mut root.comments);
+    let mut grammar = Loader::new(resolve).resolve_root(root)?;
+    grammar.comments = comments;
+    Ok(grammar)
+}
+
+/// Format the text of a grammar file canonically, as
+/// [`Grammar::to_gzl`](grammar::Grammar::to_gzl) does, but without resolving
+/// it: imports, `extends` and rule edits stay as written, and so do single
+/// blank lines between items.
+///
+/// ```
+/// let src = "import \"atoms.gzl\";\n\nexpr = atom => atom;  // more to come\n";
+/// let formatted = gazelle::format_grammar(src).unwrap();
+/// assert_eq!(formatted, src);
+/// ```
+pub fn format_grammar(input: &str) -> Result<String, GrammarError> {
+    let file = parse_source(input)?;
+    let mut lines: Vec<Line> = file
+        .decls
+        .into_iter()
+        .map(|(text, span)| Line::new(text, span))
+        .collect();
+    lines.extend(format::terminals_block(
+        &file.terminal_attrs,
+        &file.terminals,
+        file.terminals_span,
+    ));
+    for (i, item) in file.rules.iter().enumerate() {
+        let item_lines = match item {
+            RuleItem::Define(rule) => {
+                let head = format::rule_head(&rule.name, &rule.params);
+                let header = format::header_lines(&rule.doc, &rule.attrs, &rule.span);
+                format::rule_lines(&head, "=", &rule.alts, header, rule.span.clone())
+            }
+            RuleItem::Extend(name, alts, span) => {
+                format::rule_lines(name, "|=", alts, Vec::new(), span.clone())
+            }
+            RuleItem::Remove(name, variants, span) => vec![Line::new(
+                format!("{} -= {};", name, variants.join(", ")),
+                span.clone(),
+            )],
+        };
+        if i == 0 {
+            lines.extend(format::start_section(item_lines));
+        } else {
+            lines.extend(item_lines);
+        }
+    }
+    Ok(format::render(lines, &file.comments, Some(input)))
+}
+
+/// Lex and parse the text of one grammar file.
+fn parse_source(input: &str) -> Result<GrammarFile, GrammarError> {
+    let (tokens, comments) = lex_grammar(input)?;
+    let (tokens, spans): (Vec<_>, Vec<_>) = tokens.into_iter().unzip();
     if tokens.is_empty() {
-        return Err("Empty grammar".to_string());
+        return Err(GrammarError::new(GrammarErrorKind::Syntax, "Empty grammar"));
+    }
+    let mut file = parse_file(tokens, spans)?;
+    file.comments = comments;
+    Ok(file)
+}
+
+/// The path of `path` as written in the file `importer`, with `.` and `..`
+/// removed so each file has one name. Paths are split at `/` only, the
+/// same on every platform.
+fn locate(importer: &str, path: &str) -> String {
+    let dir = match importer.rfind('/') {
+        Some(i) if !path.starts_with('/') => &importer[..i],
+        _ => "",
+    };
+    let mut parts: Vec<&str> = Vec::new();
+    for part in dir.split('/').chain(path.split('/')) {
+        match part {
+            "" | "." => {}
+            ".." if parts.last().is_some_and(|&p| p != "..") => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    let root = if path.starts_with('/') { "/" } else { "" };
+    format!("{}{}", root, parts.join("/"))
+}
+
+/// An error in another file, located by path, line and column instead of
+/// a span in the main grammar.
+fn in_file(e: GrammarError, path: &str, source: &str) -> GrammarError {
+    let message = match e.span {
+        Some(_) => format!("{}:{}", path, e.format(source)),
+        None => format!("{}: {}", path, e.message),
+    };
+    GrammarError {
+        message,
+        span: None,
+        ..e
+    }
+}
+
+fn import_error(message: String) -> GrammarError {
+    GrammarError::new(GrammarErrorKind::Import, message)
+}
+
+/// Drop the spans of terminals and rules read from another file, which do
+/// not point into the main grammar.
+fn clear_spans(terminals: &mut [grammar::TerminalDef], rules: &mut [grammar::Rule]) {
+    for t in terminals {
+        t.span = None;
+    }
+    for rule in rules {
+        rule.span = None;
+        for alt in &mut rule.alts {
+            alt.span = None;
+            clear_term_spans(&mut alt.terms);
+        }
+    }
+}
+
+fn clear_term_spans(terms: &mut [Spanned<grammar::Term>]) {
+    use grammar::Term;
+    for term in terms {
+        term.span = None;
+        match &mut term.node {
+            Term::Group(seqs)
+            | Term::OptionalGroup(seqs)
+            | Term::ZeroOrMoreGroup(seqs)
+            | Term::OneOrMoreGroup(seqs) => {
+                for seq in seqs {
+                    clear_term_spans(seq);
+                }
+            }
+            Term::Permutation(items) => clear_term_spans(items),
+            _ => {}
+        }
+    }
+}
+
+/// Reads the base grammars and imports of a grammar file.
+struct Loader<F> {
+    resolve: F,
+    /// Imported files, each merged only once.
+    loaded: Vec<String>,
+    /// The chain of base grammars being resolved, to catch cycles.
+    extending: Vec<String>,
+}
+
+impl<F: FnMut(&str) -> Result<String, String>> Loader<F> {
+    fn new(resolve: F) -> Self {
+        Loader {
+            resolve,
+            loaded: Vec::new(),
+            extending: Vec::new(),
+        }
+    }
+
+    fn resolve_root(&mut self, root: GrammarFile) -> Result<grammar::Grammar, GrammarError> {
+        self.resolve_file("", root)
+    }
+
+    /// Read and parse the file at `path`, returning it with its source.
+    fn read(&mut self, path: &str) -> Result<(GrammarFile, String), GrammarError> {
+        let source = (self.resolve)(path)
+            .map_err(|e| import_error(format!("cannot read \"{}\": {}", path, e)))?;
+        let file = parse_source(&source).map_err(|e| in_file(e, path, &source))?;
+        Ok((file, source))
+    }
+
+    /// Resolve `file`, read from `path` (empty for the main grammar), into
+    /// a plain grammar: its base first, then its imports, then its own
+    /// terminals and rules.
+    fn resolve_file(
+        &mut self,
+        path: &str,
+        file: GrammarFile,
+    ) -> Result<grammar::Grammar, GrammarError> {
+        let mut merger = Merger::default();
+        let mut base = None;
+        if let Some(base_path) = &file.extends {
+            let base_path = locate(path, base_path);
+            if self.extending.contains(&base_path) {
+                return Err(import_error(format!(
+                    "circular extends: {} -> {}",
+                    self.extending.join(" -> "),
+                    base_path
+                )));
+            }
+            self.extending.push(base_path.clone());
+            let (base_file, source) = self.read(&base_path)?;
+            let mut grammar = self
+                .resolve_file(&base_path, base_file)
+                .map_err(|e| in_file(e, &base_path, &source))?;
+            self.extending.pop();
+            clear_spans(&mut grammar.terminals, &mut grammar.rules);
+            merger.add_base(&base_path, &grammar);
+            base = Some(grammar);
+        }
+        self.import(path, file.imports, &mut merger)?;
+        let label = if path.is_empty() {
+            "the main grammar"
+        } else {
+            path
+        };
+        for t in file.terminals {
+            merger.terminal(t, label, true)?;
+        }
+        for item in file.rules {
+            merger.rule_item(item, label, true)?;
+        }
+
+        let (starts, expect_rr, expect_sr, split_tokens, attrs, terminal_attrs) = match base {
+            Some(b) => (
+                b.starts().cloned().collect(),
+                b.expect_rr,
+                b.expect_sr,
+                b.split_tokens,
+                b.attrs,
+                b.terminal_attrs,
+            ),
+            None => Default::default(),
+        };
+        if let Some(names) = &file.start {
+            for (i, (name, span)) in names.iter().enumerate() {
+                let error = if !merger.rules.iter().any(|r| r.name == *name) {
+                    GrammarError::new(
+                        GrammarErrorKind::UndefinedSymbol,
+                        format!("Start symbol '{}' not found in grammar", name),
+                    )
+                } else if names[..i].iter().any(|(other, _)| other == name) {
+                    GrammarError::new(
+                        GrammarErrorKind::Invalid,
+                        format!("Start symbol '{}' is declared twice", name),
+                    )
+                } else {
+                    continue;
+                };
+                return Err(error.at(span.clone()));
+            }
+        }
+        let or_base = |own: Vec<String>, base: Vec<String>| if own.is_empty() { base } else { own };
+        let own_starts: Option<Vec<String>> = file
+            .start
+            .map(|names| names.into_iter().map(|(name, _)| name).collect());
+        let mut starts = own_starts.unwrap_or(starts).into_iter();
+        Ok(grammar::Grammar {
+            start: starts.next().unwrap_or_default(),
+            extra_starts: starts.collect(),
+            expect_rr: file.expect_rr.unwrap_or(expect_rr),
+            expect_sr: file.expect_sr.unwrap_or(expect_sr),
+            split_tokens: file.split_tokens.unwrap_or(split_tokens),
+            terminals: merger.terminals,
+            rules: merger.rules,
+            attrs: or_base(file.attrs, attrs),
+            terminal_attrs: or_base(
+                file.terminal_attrs
+                    .into_iter()
+                    .map(|(attr, _)| attr)
+                    .collect(),
+                terminal_attrs,
+            ),
+            comments: Vec::new(),
+        })
+    }
+
+    /// Merge imported files depth-first, so a file comes after its own imports.
+    fn import(
+        &mut self,
+        importer: &str,
+        paths: Vec<String>,
+        merger: &mut Merger,
+    ) -> Result<(), GrammarError> {
+        for path in paths {
+            let path = locate(importer, &path);
+            if self.loaded.contains(&path) {
+                continue;
+            }
+            self.loaded.push(path.clone());
+            let (file, _) = self.read(&path)?;
+            let mut rules = Vec::with_capacity(file.rules.len());
+            for item in file.rules {
+                match item {
+                    RuleItem::Define(rule) if file.extends.is_none() => rules.push(rule),
+                    _ => {
+                        return Err(import_error(format!(
+                            "{}: an imported grammar cannot use extends, |= or -=",
+                            path
+                        )));
+                    }
+                }
+            }
+            let mut terminals = file.terminals;
+            clear_spans(&mut terminals, &mut rules);
+            self.import(&path, file.imports, merger)?;
+            for t in terminals {
+                merger.terminal(t, &path, false)?;
+            }
+            for rule in rules {
+                merger.rule_item(RuleItem::Define(rule), &path, false)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Where a merged terminal or rule came from.
+struct Origin {
+    file: String,
+    base: bool,
+}
+
+/// Terminals and rules merged from several files.
+#[derive(Default)]
+struct Merger {
+    terminals: Vec<grammar::TerminalDef>,
+    rules: Vec<grammar::Rule>,
+    terminal_origin: std::collections::HashMap<String, Origin>,
+    rule_origin: std::collections::HashMap<String, Origin>,
+}
+
+impl Merger {
+    fn add_base(&mut self, file: &str, base: &grammar::Grammar) {
+        let origin = || Origin {
+            file: file.to_string(),
+            base: true,
+        };
+        for t in &base.terminals {
+            self.terminal_origin.insert(t.name.clone(), origin());
+        }
+        for rule in &base.rules {
+            self.rule_origin.insert(rule.name.clone(), origin());
+        }
+        self.terminals.extend(base.terminals.iter().cloned());
+        self.rules.extend(base.rules.iter().cloned());
+    }
+
+    /// Add a terminal declared in `file`. Declarations of the grammar being
+    /// resolved (`own`) override those of its base.
+    fn terminal(
+        &mut self,
+        t: grammar::TerminalDef,
+        file: &str,
+        own: bool,
+    ) -> Result<(), GrammarError> {
+        let origin = Origin {
+            file: file.to_string(),
+            base: false,
+        };
+        let Some(prev) = self.terminal_origin.insert(t.name.clone(), origin) else {
+            self.terminals.push(t);
+            return Ok(());
+        };
+        let i = self
+            .terminals
+            .iter()
+            .position(|x| x.name == t.name)
+            .unwrap();
+        let unlocated = |t: &grammar::TerminalDef| grammar::TerminalDef {
+            span: None,
+            ..t.clone()
+        };
+        if prev.base && own {
+            self.terminals[i] = t;
+        } else if prev.file == file {
+            self.terminals.push(t);
+        } else if unlocated(&self.terminals[i]) != unlocated(&t) {
+            return Err(import_error(format!(
+                "Terminal '{}' is declared differently in {} and {}",
+                t.name, prev.file, file
+            ))
+            .at(t.span));
+        }
+        Ok(())
+    }
+
+    /// Add a rule or rule edit from `file`. Rules of the grammar being
+    /// resolved (`own`) replace those of its base.
+    fn rule_item(&mut self, item: RuleItem, file: &str, own: bool) -> Result<(), GrammarError> {
+        match item {
+            RuleItem::Define(rule) => {
+                let origin = Origin {
+                    file: file.to_string(),
+                    base: false,
+                };
+                match self.rule_origin.insert(rule.name.clone(), origin) {
+                    Some(prev) if prev.base && own => {
+                        let i = self.rules.iter().position(|r| r.name == rule.name).unwrap();
+                        self.rules[i] = rule;
+                    }
+                    Some(prev) if prev.file != file => {
+                        return Err(import_error(format!(
+                            "Rule '{}' is defined in both {} and {}",
+                            rule.name, prev.file, file
+                        ))
+                        .at(rule.span));
+                    }
+                    _ => self.rules.push(rule),
+                }
+            }
+            RuleItem::Extend(name, alts, span) => {
+                let rule = self.edited_rule(&name, span)?;
+                // A named alternative replaces every alternative of its
+                // variant; more of the same name in the edit follow it.
+                let mut replaced: Vec<String> = Vec::new();
+                for alt in alts {
+                    let same = |a: &grammar::Alt| !alt.name.is_empty() && a.name == alt.name;
+                    if replaced.contains(&alt.name) {
+                        let last = rule.alts.iter().rposition(same).unwrap();
+                        rule.alts.insert(last + 1, alt);
+                    } else if let Some(first) = rule.alts.iter().position(same) {
+                        let rest: Vec<_> = rule.alts.split_off(first + 1);
+                        rule.alts.extend(rest.into_iter().filter(|a| !same(a)));
+                        replaced.push(alt.name.clone());
+                        rule.alts[first] = alt;
+                    } else {
+                        rule.alts.push(alt);
+                    }
+                }
+            }
+            RuleItem::Remove(name, variants, span) => {
+                let rule = self.edited_rule(&name, span.clone())?;
+                for variant in variants {
+                    let before = rule.alts.len();
+                    rule.alts.retain(|a| a.name != variant);
+                    if rule.alts.len() == before {
+                        return Err(GrammarError::new(
+                            GrammarErrorKind::UndefinedSymbol,
+                            format!("Rule '{}' has no alternative '{}'", name, variant),
+                        )
+                        .at(span.clone()));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn edited_rule(
+        &mut self,
+        name: &str,
+        span: Option<Span>,
+    ) -> Result<&mut grammar::Rule, GrammarError> {
+        self.rules
+            .iter_mut()
+            .find(|r| r.name == name)
+            .ok_or_else(|| {
+                GrammarError::new(
+                    GrammarErrorKind::UndefinedSymbol,
+                    format!("Cannot edit undefined rule '{}'", name),
+                )
+                .at(span)
+            })
     }
-    parse_tokens_typed(tokens)
 }
 
 #[cfg(test)]
@@ -306,9 +1421,9 @@ mod tests {
 
     #[test]
     fn test_lex() {
-        let tokens = lex_grammar("start s; terminals { A } s: S = A;").unwrap();
-        assert!(matches!(&tokens[0], Terminal::<AstBuilder>::KwStart));
-        assert!(matches!(&tokens[1], Terminal::<AstBuilder>::Ident(s) if s == "s"));
+        let (tokens, _) = lex_grammar("start s; terminals { A } s: S = A;").unwrap();
+        assert!(matches!(&tokens[0].0, Terminal::<AstBuilder>::KwStart));
+        assert!(matches!(&tokens[1].0, Terminal::<AstBuilder>::Ident(s) if s == "s"));
     }
 
     #[test]
@@ -532,6 +1647,609 @@ mod tests {
 
         assert_eq!(grammar.expect_sr, 2);
         assert_eq!(grammar.expect_rr, 1);
+        assert!(!grammar.split_tokens);
+    }
+
+    #[test]
+    fn test_mode_split() {
+        let grammar = parse_grammar(
+            r#"
+            start s;
+            mode split;
+            expect 1 rr;
+            terminals { A }
+            s = A => a;
+        "#,
+        )
+        .unwrap();
+
+        assert!(grammar.split_tokens);
+        assert_eq!(grammar.expect_rr, 1);
+
+        let err = parse_grammar("start s; mode splti; terminals { A } s = A => a;").unwrap_err();
+        assert_eq!(
+            err.message,
+            "unknown mode 'splti'; expected 'split' or 'default'"
+        );
+        assert_eq!(err.span, Some(9..20));
+
+        // `mode` is only a keyword at the start of a declaration.
+        let grammar = parse_grammar(
+            r#"
+            start mode;
+            terminals { A, B }
+            mode = A mode B => nested | mode_item => item;
+            mode_item = A => a;
+        "#,
+        )
+        .unwrap();
+        assert_eq!(grammar.start, "mode");
+        assert_eq!(grammar.rules[0].name, "mode");
+    }
+
+    #[test]
+    fn test_prec_annotation() {
+        let grammar = parse_grammar(
+            r#"
+            start expr;
+            terminals { prec OP, MINUS, NUM }
+            expr = MINUS expr => neg prec(15, right) | expr OP expr => binop prec(OP) | NUM => num;
+        "#,
+        )
+        .unwrap();
+
+        let alts = &grammar.rules[0].alts;
+        assert_eq!(
+            alts[0].prec,
+            Some(grammar::AltPrec::Fixed(crate::Precedence::Right(15)))
+        );
+        assert_eq!(alts[1].prec, Some(grammar::AltPrec::Symbol("OP".into())));
+        assert_eq!(alts[2].prec, None);
+
+        let err = parse_grammar(
+            r#"
+            start expr;
+            terminals { MINUS, NUM }
+            expr = MINUS expr => neg prec(15, up) | NUM => num;
+        "#,
+        )
+        .unwrap_err();
+        assert!(err.message.contains("invalid precedence"), "{}", err);
+    }
+
+    #[test]
+    fn test_parameterized_rule() {
+        let grammar = parse_grammar(
+            r#"
+            start args;
+            terminals { LPAREN, RPAREN, COMMA, NUM }
+            comma_list<T> = (T % COMMA) => items;
+            delimited<open, T, close> = open T close => delimited;
+            args = delimited<LPAREN, comma_list<NUM>, RPAREN>? => args;
+        "#,
+        )
+        .unwrap();
+        assert_eq!(grammar.rules[0].params, ["T"]);
+        assert_eq!(grammar.rules[1].params, ["open", "T", "close"]);
+        assert!(grammar.rules[2].params.is_empty());
+        let symbol = |name: &str| grammar::Term::Symbol(name.into());
+        let apply = |name: &str, args| grammar::Term::Apply {
+            name: name.into(),
+            args,
+        };
+        let delimited = apply(
+            "delimited",
+            vec![
+                symbol("LPAREN"),
+                apply("comma_list", vec![symbol("NUM")]),
+                symbol("RPAREN"),
+            ],
+        );
+        assert_eq!(
+            grammar.rules[2].alts[0].terms,
+            [grammar::Term::OptionalGroup(vec![vec![delimited.into()]])]
+        );
+        assert!(
+            grammar
+                .to_gzl()
+                .contains("args = delimited<LPAREN, comma_list<NUM>, RPAREN>? => args;")
+        );
+
+        let err =
+            parse_grammar("start a; terminals { X } list<T> = T => one; a = (list<X> % X) => a;")
+                .unwrap_err();
+        assert!(
+            err.message.contains("'list<X>' can't be used with '%'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_groups() {
+        let grammar = parse_grammar(
+            r#"
+            start list;
+            terminals { ELSE, PLUS, MINUS, COMMA, ITEM, STMT }
+            list = ITEM (COMMA ITEM)* (ELSE STMT)? (PLUS | MINUS _)+ (ITEM) => list;
+        "#,
+        )
+        .unwrap();
+        use grammar::Term;
+        let sym = |s: &str| Term::Symbol(s.into());
+        assert_eq!(
+            grammar.rules[0].alts[0].terms,
+            [
+                sym("ITEM"),
+                Term::ZeroOrMoreGroup(vec![vec![sym("COMMA").into(), sym("ITEM").into()]]),
+                Term::OptionalGroup(vec![vec![sym("ELSE").into(), sym("STMT").into()]]),
+                Term::OneOrMoreGroup(vec![
+                    vec![sym("PLUS").into()],
+                    vec![sym("MINUS").into(), Term::Empty.into()]
+                ]),
+                Term::Group(vec![vec![sym("ITEM").into()]]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_labels() {
+        let grammar = parse_grammar(
+            r#"
+            start expr;
+            terminals { prec OP: _, NUM: _ }
+            expr = lhs:expr op:OP rhs:expr => binop | NUM => num;
+        "#,
+        )
+        .unwrap();
+        let alts = &grammar.rules[0].alts;
+        assert_eq!(
+            alts[0].labels,
+            [Some("lhs".into()), Some("op".into()), Some("rhs".into())]
+        );
+        assert!(alts[1].labels.is_empty());
+    }
+
+    #[test]
+    fn test_literals() {
+        let grammar = parse_grammar(
+            r#"
+            start s;
+            terminals { ID }
+            s = "while" ID '+' "a\"b" '\'' => s;
+        "#,
+        )
+        .unwrap();
+        let names: Vec<_> = grammar.rules[0].alts[0]
+            .terms
+            .iter()
+            .map(|t| match &t.node {
+                grammar::Term::Symbol(name) => name.as_str(),
+                _ => panic!("expected a symbol"),
+            })
+            .collect();
+        assert_eq!(names, ["\"while\"", "ID", "'+'", "\"a\\\"b\"", "'\\''"]);
+        assert!(parse_grammar("start s; terminals { A } s = '' => s;").is_err());
+    }
+
+    #[test]
+    fn test_format_roundtrip() {
+        let without_spans = |src: &str| {
+            let mut grammar = parse_grammar(src).unwrap();
+            clear_spans(&mut grammar.terminals, &mut grammar.rules);
+            grammar
+        };
+        for src in [
+            include_str!("../grammars/c11.gzl"),
+            include_str!("../grammars/python.gzl"),
+            include_str!("../grammars/meta.gzl"),
+            include_str!("../grammars/regex.gzl"),
+        ] {
+            let formatted = format_grammar(src).unwrap();
+            assert_eq!(format_grammar(&formatted).unwrap(), formatted);
+            assert_eq!(without_spans(&formatted), without_spans(src));
+
+            let grammar = parse_grammar(src).unwrap();
+            assert_eq!(without_spans(&grammar.to_gzl()), without_spans(src));
+        }
+    }
+
+    #[test]
+    fn test_format_file() {
+        let src = r#"extends "base.gzl";  // the C grammar
+import "extra.gzl";
+terminals { A, /* B is new */ B }
+// Statements
+stmt |= A B => ab | B => b;
+stmt -= empty, other;
+
+
+expr = A   => a;
+"#;
+        let expected = r#"extends "base.gzl";  // the C grammar
+import "extra.gzl";
+
+terminals {
+    A,
+    /* B is new */
+    B
+}
+
+// Statements
+stmt |= A B => ab
+     | B => b;
+stmt -= empty, other;
+
+expr = A => a;
+"#;
+        assert_eq!(format_grammar(src).unwrap(), expected);
+        assert_eq!(
+            format_grammar("start s; s = ;").unwrap_err().kind,
+            GrammarErrorKind::Syntax
+        );
+    }
+
+    #[test]
+    fn test_doc_comments() {
+        let src = r#"terminals {
+    /// An integer.
+    NUM: _,
+    PLUS
+}
+
+//// not a doc comment
+/// A sum.
+/// Left-associative.
+sum = /// Two or more numbers.
+      sum PLUS NUM => add
+    /// A single number.
+    | NUM => num;
+"#;
+        let g = parse_grammar(src).unwrap();
+        assert_eq!(g.terminals[0].doc, [" An integer."]);
+        assert!(g.terminals[1].doc.is_empty());
+        assert_eq!(g.rules[0].doc, [" A sum.", " Left-associative."]);
+        assert_eq!(g.rules[0].alts[0].doc, [" Two or more numbers."]);
+        assert_eq!(g.rules[0].alts[1].doc, [" A single number."]);
+        // Spans start after the docs
+        assert_eq!(&src[g.rules[0].span.clone().unwrap()], "sum");
+        assert_eq!(&src[g.rules[0].alts[1].span.clone().unwrap()], "NUM => num");
+        assert_eq!(g.comments.len(), 1);
+        assert_eq!(format_grammar(src).unwrap(), src);
+
+        let err =
+            parse_grammar("terminals { A } s = A => a;\n/// no\ns |= A A => aa;").unwrap_err();
+        assert_eq!(err.kind, GrammarErrorKind::Invalid);
+    }
+
+    #[test]
+    fn test_stray_doc_comments() {
+        // `///` where nothing can be documented is a plain comment
+        for (src, comment) in [
+            ("/// top\nstart s; terminals { A } s = A => a;", "/// top"),
+            (
+                "start s;\n/// m\nmode split; terminals { A } s = A => a;",
+                "/// m",
+            ),
+            ("start s;\n/// t\nterminals { A } s = A => a;", "/// t"),
+            (
+                "terminals { A } s = A => a; /// trailing\nt = A => t;",
+                "/// trailing",
+            ),
+            ("terminals { A } s = A => a;\n/// end\n", "/// end"),
+            ("terminals { A } s = A /// inside\n A => a;", "/// inside"),
+            ("terminals { A } s = (A\n/// group\n| A) => a;", "/// group"),
+            ("terminals { A /// x\n}\ns = A => a;", "/// x"),
+            ("terminals { A\n/// last\n} s = A => a;", "/// last"),
+        ] {
+            let g = parse_grammar(src).unwrap_or_else(|e| panic!("{src:?}: {}", e.message));
+            assert!(g.terminals.iter().all(|t| t.doc.is_empty()), "{src:?}");
+            assert!(g.rules.iter().all(|r| r.doc.is_empty()), "{src:?}");
+            assert!(
+                g.rules
+                    .iter()
+                    .flat_map(|r| &r.alts)
+                    .all(|a| a.doc.is_empty()),
+                "{src:?}"
+            );
+            let texts: Vec<&str> = g.comments.iter().map(|c| c.text.as_str()).collect();
+            assert_eq!(texts, [comment], "{src:?}");
+            // The formatter keeps it a comment
+            let formatted = format_grammar(src).unwrap();
+            assert_eq!(parse_grammar(&formatted).unwrap(), g);
+            assert_eq!(format_grammar(&formatted).unwrap(), formatted);
+        }
+    }
+
+    #[test]
+    fn test_attributes() {
+        let src = r#"#![derive(Clone, PartialEq)]
+start sum;
+
+terminals {
+    #![derive(Hash)]
+    NUM: _
+}
+
+/// A sum.
+#[derive(Eq)]
+#[serde(rename = "]")]
+sum = sum NUM => add
+    | NUM => num;
+"#;
+        let g = parse_grammar(src).unwrap();
+        assert_eq!(g.attrs, ["derive(Clone, PartialEq)"]);
+        assert_eq!(g.terminal_attrs, ["derive(Hash)"]);
+        assert_eq!(g.rules[0].attrs, ["derive(Eq)", r#"serde(rename = "]")"#]);
+        assert_eq!(&src[g.rules[0].span.clone().unwrap()], "sum");
+        assert_eq!(format_grammar(src).unwrap(), src);
+        assert_eq!(parse_grammar(&g.to_gzl()).unwrap().attrs, g.attrs);
+
+        let err = parse_grammar("terminals { A } s = A => a; #[derive(Clone)] s |= A A => aa;")
+            .unwrap_err();
+        assert_eq!(err.kind, GrammarErrorKind::Invalid);
+        let err = parse_grammar("terminals { A } #[derive(Clone) s = A => a;").unwrap_err();
+        assert_eq!(err.kind, GrammarErrorKind::Syntax);
+    }
+
+    #[test]
+    fn test_imports() {
+        let files = [
+            (
+                "lib/expr.gzl",
+                r#"import "atoms.gzl"; terminals { PLUS } expr = atom PLUS atom => add;"#,
+            ),
+            ("lib/atoms.gzl", "terminals { NUM: _ } atom = NUM => num;"),
+            (
+                "lib/other.gzl",
+                r#"import "atoms.gzl"; terminals { NUM: _ } pair = atom atom => pair;"#,
+            ),
+            ("clash.gzl", "terminals { NUM } atom = NUM => num;"),
+            (
+                "sub/sum.gzl",
+                r#"import "../lib/atoms.gzl"; terminals { PLUS } sum = atom PLUS atom => sum;"#,
+            ),
+        ];
+        let mut requested = Vec::new();
+        let mut parse = |src: &str| {
+            parse_grammar_with(src, |path| {
+                requested.push(path.to_string());
+                files
+                    .iter()
+                    .find(|(name, _)| *name == path)
+                    .map(|(_, src)| src.to_string())
+                    .ok_or_else(|| "not found".to_string())
+            })
+        };
+
+        // Imports are relative to the importing file and loaded once
+        let grammar = parse(
+            r#"start s; import "lib/expr.gzl"; import "lib/other.gzl"; terminals { SEMI } s = expr SEMI => s;"#,
+        )
+        .unwrap();
+        let terminals: Vec<_> = grammar.terminals.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(terminals, ["NUM", "PLUS", "SEMI"]);
+        let rules: Vec<_> = grammar.rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(rules, ["atom", "expr", "pair", "s"]);
+        assert_eq!(grammar.start, "s");
+        assert!(to_grammar_internal(&grammar).is_ok());
+
+        let err = parse(r#"start s; import "lib/atoms.gzl"; import "clash.gzl";"#).unwrap_err();
+        assert!(
+            err.message
+                .contains("Terminal 'NUM' is declared differently in lib/atoms.gzl and clash.gzl")
+        );
+        let err = parse(r#"start s; import "lib/atoms.gzl"; atom = _ => none;"#).unwrap_err();
+        assert!(
+            err.message
+                .contains("Rule 'atom' is defined in both lib/atoms.gzl and the main grammar")
+        );
+        let err = parse(r#"start s; import "missing.gzl";"#).unwrap_err();
+        assert!(
+            err.message
+                .contains("cannot read \"missing.gzl\": not found")
+        );
+        assert!(parse_grammar(r#"start s; import "lib/atoms.gzl";"#).is_err());
+        // Only a keyword before a path
+        assert!(parse_grammar("start s; terminals { A } s = A => import;").is_ok());
+
+        // `..` and `.` are resolved before files are deduplicated
+        let grammar =
+            parse(r#"start s; import "./lib/atoms.gzl"; import "sub/sum.gzl"; s = sum => s;"#)
+                .unwrap();
+        let rules: Vec<_> = grammar.rules.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(rules, ["atom", "sum", "s"]);
+        assert_eq!(locate("a/b/c.gzl", "../../../d.gzl"), "../d.gzl");
+        assert_eq!(locate("a/b.gzl", "/lib/./d.gzl"), "/lib/d.gzl");
+        assert_eq!(
+            requested[..3],
+            ["lib/expr.gzl", "lib/atoms.gzl", "lib/other.gzl"]
+        );
+    }
+
+    #[test]
+    fn test_extends() {
+        let files = [
+            (
+                "c/base.gzl",
+                r#"
+                #![derive(Clone)]
+                start expr;
+                expect 1 sr;
+                terminals { NUM: _, PLUS, STAR }
+                expr = expr PLUS expr => add | expr STAR expr => mul | NUM => num;
+                atom = NUM => num;
+                "#,
+            ),
+            (
+                "c/twice.gzl",
+                "start s; mode split; terminals { A, B, C } s = A => x | B => x | C => c;",
+            ),
+            ("c/loop.gzl", r#"extends "loop2.gzl";"#),
+            ("c/loop2.gzl", r#"extends "loop.gzl";"#),
+        ];
+        let parse = |src: &str| {
+            parse_grammar_with(src, |path| {
+                files
+                    .iter()
+                    .find(|(name, _)| *name == path)
+                    .map(|(_, src)| src.to_string())
+                    .ok_or_else(|| "not found".to_string())
+            })
+        };
+
+        let grammar = parse(
+            r#"
+            extends "c/base.gzl";
+            terminals { MINUS, NUM }
+            expr |= expr MINUS expr => sub | NUM NUM => num | atom;
+            expr -= mul;
+            atom = _ => none;
+            "#,
+        )
+        .unwrap();
+        assert_eq!(grammar.start, "expr");
+        assert_eq!(grammar.expect_sr, 1);
+        assert_eq!(grammar.attrs, ["derive(Clone)"]);
+        let terminals: Vec<_> = grammar
+            .terminals
+            .iter()
+            .map(|t| (t.name.as_str(), t.has_type))
+            .collect();
+        assert_eq!(
+            terminals,
+            [
+                ("NUM", false),
+                ("PLUS", false),
+                ("STAR", false),
+                ("MINUS", false)
+            ]
+        );
+        let alts = |i: usize| -> Vec<_> {
+            let rule: &grammar::Rule = &grammar.rules[i];
+            rule.alts
+                .iter()
+                .map(|a| (a.name.as_str(), a.terms.len()))
+                .collect()
+        };
+        assert_eq!(alts(0), [("add", 3), ("num", 2), ("sub", 3), ("", 1)]);
+        assert_eq!(alts(1), [("none", 1)]);
+
+        // Edits act on every alternative of a repeated variant
+        let variants = |src: &str| -> Vec<(String, usize)> {
+            parse(src).unwrap().rules[0]
+                .alts
+                .iter()
+                .map(|a| (a.name.clone(), a.terms.len()))
+                .collect()
+        };
+        let x = |n| ("x".to_string(), n);
+        let c = ("c".to_string(), 1);
+        assert_eq!(
+            variants(r#"extends "c/twice.gzl"; s |= C C => x;"#),
+            [x(2), c.clone()]
+        );
+        assert_eq!(
+            variants(r#"extends "c/twice.gzl"; s |= C C => x | A A A => x;"#),
+            [x(2), x(3), c.clone()]
+        );
+        assert_eq!(variants(r#"extends "c/twice.gzl"; s -= x;"#), [c]);
+
+        // A derived grammar can turn `mode split` back off
+        assert!(parse(r#"extends "c/twice.gzl";"#).unwrap().split_tokens);
+        let grammar = parse(r#"extends "c/twice.gzl"; mode default;"#).unwrap();
+        assert!(!grammar.split_tokens);
+
+        let err = parse(r#"extends "c/base.gzl"; stmt |= NUM => num;"#).unwrap_err();
+        assert!(err.message.contains("Cannot edit undefined rule 'stmt'"));
+        let err = parse(r#"extends "c/base.gzl"; expr -= div;"#).unwrap_err();
+        assert!(err.message.contains("Rule 'expr' has no alternative 'div'"));
+        let err = parse(r#"extends "c/loop.gzl";"#).unwrap_err();
+        assert!(
+            err.message
+                .contains("circular extends: c/loop.gzl -> c/loop2.gzl -> c/loop.gzl")
+        );
+        let err = parse(r#"extends "c/base.gzl"; extends "c/base.gzl";"#).unwrap_err();
+        assert!(err.message.contains("only one base grammar"));
+    }
+
+    #[test]
+    fn test_spans() {
+        let src = "start s;\nterminals { A, B: _ }\ns = A x:B (A | B A)* => ab\n  | _ => none;";
+        let grammar = parse_grammar(src).unwrap();
+        let text = |span: &Option<Span>| &src[span.clone().unwrap()];
+        assert_eq!(text(&grammar.terminals[1].span), "B: _");
+        let rule = &grammar.rules[0];
+        assert_eq!(text(&rule.span), "s");
+        assert_eq!(text(&rule.alts[0].span), "A x:B (A | B A)* => ab");
+        let terms: Vec<_> = rule.alts[0].terms.iter().map(|t| text(&t.span)).collect();
+        assert_eq!(terms, ["A", "x:B", "(A | B A)*"]);
+        let grammar::Term::ZeroOrMoreGroup(seqs) = &rule.alts[0].terms[2].node else {
+            panic!("expected a group");
+        };
+        assert_eq!(text(&seqs[1][1].span), "A");
+        assert_eq!(
+            seqs[1][1].span,
+            Some(src.rfind("A)").unwrap()..src.rfind(")").unwrap())
+        );
+        assert_eq!(text(&rule.alts[1].span), "_ => none");
+        // Spans take no part in comparisons
+        assert_eq!(grammar, parse_grammar(&grammar.to_gzl()).unwrap());
+
+        // Errors in the start declaration point at the symbol
+        let src = "start s, t, s;\nterminals { A }\ns = A => s;";
+        let err = parse_grammar(src).unwrap_err();
+        assert_eq!(err.kind, GrammarErrorKind::UndefinedSymbol);
+        assert_eq!(
+            err.format(src),
+            "1:10: Start symbol 't' not found in grammar"
+        );
+        let src = "start s, s;\nterminals { A }\ns = A => s;";
+        let err = parse_grammar(src).unwrap_err();
+        assert_eq!(err.format(src), "1:10: Start symbol 's' is declared twice");
+
+        // Syntax errors point at the offending token
+        let src = "start s;\nterminals { A }\ns = A => a |;";
+        let err = parse_grammar(src).unwrap_err();
+        assert_eq!(err.kind, GrammarErrorKind::Syntax);
+        assert_eq!(&src[err.span.clone().unwrap()], ";");
+        assert!(
+            err.format(src).starts_with("3:13: unexpected 'SEMI'"),
+            "{}",
+            err.format(src)
+        );
+        let err = parse_grammar("start s;\n  s = A @ B;").unwrap_err();
+        assert_eq!(err.span, Some(17..18));
+        assert_eq!(
+            err.format("start s;\n  s = A @ B;"),
+            "2:9: unexpected character: '@'"
+        );
+
+        // Edits of a base grammar are located in the extending grammar
+        let src = "extends \"base.gzl\";\nstmt |= A => a;";
+        let err = parse_grammar_with(src, |_| Ok("start s; terminals { A } s = A => a;".into()))
+            .unwrap_err();
+        assert_eq!(err.kind, GrammarErrorKind::UndefinedSymbol);
+        assert_eq!(err.format(src), "2:1: Cannot edit undefined rule 'stmt'");
+        // Errors in other files name the file, and spans of their items are dropped
+        let err = parse_grammar_with(src, |_| Ok("start s;\ns = A =>;".into())).unwrap_err();
+        assert_eq!(err.span, None);
+        assert!(
+            err.message.starts_with("base.gzl:2:9: unexpected"),
+            "{}",
+            err
+        );
+        let grammar = parse_grammar_with("extends \"base.gzl\";", |_| {
+            Ok("start s; terminals { A } s = A => a;".into())
+        })
+        .unwrap();
+        assert_eq!(grammar.rules[0].span, None);
+        assert!(
+            grammar.rules[0].alts[0]
+                .terms
+                .iter()
+                .all(|t| t.span.is_none())
+        );
     }
 
     #[test]
@@ -584,7 +2302,7 @@ mod tests {
 
         let result = to_grammar_internal(&grammar);
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Unknown symbol: B"));
+        assert!(result.unwrap_err().message.contains("Unknown symbol: B"));
     }
 
     #[test]
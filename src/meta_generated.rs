@@ -2,25 +2,72 @@
 mod __table {
     use super::gazelle;
     pub static DATA: &[u32] = &[
-        15u32, 12u32, 13u32, 14u32, 15u32, 26u32, 34u32, 16u32, 27u32, 8u32, 22u32,
-        16u32, 25u32, 5u32, 22u32, 20u32, 4294967295u32, 19u32, 46u32, 6u32, 38u32,
-        45u32, 50u32, 52u32, 53u32, 54u32, 42u32, 21u32, 33u32, 43u32, 18u32, 30u32,
-        47u32, 9u32, 0u32, 0u32, 7u32, 2u32, 0u32, 0u32, 35u32, 11u32, 0u32, 28u32, 0u32,
-        24u32, 0u32, 0u32, 31u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        55u32, 2u32, 80u32, 57u32, 22u32, 55u32, 109u32, 115u32, 57u32, 54u32, 25u32,
+        81u32, 57u32, 4294967295u32, 52u32, 73u32, 22u32, 70u32, 82u32, 52u32, 73u32,
+        28u32, 70u32, 52u32, 73u32, 51u32, 70u32, 54u32, 48u32, 4294967284u32, 57u32,
+        59u32, 105u32, 106u32, 107u32, 113u32, 114u32, 91u32, 72u32, 83u32, 64u32, 85u32,
+        86u32, 76u32, 2u32, 44u32, 45u32, 46u32, 74u32, 54u32, 78u32, 22u32, 57u32,
+        35u32, 35u32, 49u32, 49u32, 75u32, 31u32, 34u32, 40u32, 41u32, 42u32, 52u32,
+        73u32, 121u32, 70u32, 44u32, 45u32, 46u32, 30u32, 38u32, 54u32, 29u32, 29u32,
+        57u32, 17u32, 43u32, 62u32, 88u32, 27u32, 71u32, 62u32, 12u32, 62u32, 23u32,
+        77u32, 20u32, 18u32, 23u32, 116u32, 117u32, 19u32, 95u32, 108u32, 111u32, 122u32,
+        132u32, 58u32, 78u32, 133u32, 112u32, 134u32, 63u32, 99u32, 100u32, 21u32,
+        135u32, 90u32, 84u32, 36u32, 13u32, 14u32, 65u32, 102u32, 103u32, 37u32, 32u32,
+        4u32, 0u32, 0u32, 0u32, 68u32, 0u32, 39u32, 128u32, 129u32, 0u32, 0u32, 61u32,
+        0u32, 24u32, 0u32, 0u32, 0u32, 0u32, 15u32, 16u32, 124u32, 92u32, 130u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
         0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
         0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
         0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
         0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
         0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
         0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
-        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
     ];
     pub static CHECK: &[u32] = &[
-        1u32, 19u32, 20u32, 21u32, 1u32, 10u32, 5u32, 8u32, 7u32, 14u32, 11u32, 8u32,
-        16u32, 17u32, 11u32, 4u32, 0u32, 18u32, 1u32, 6u32, 1u32, 1u32, 1u32, 1u32, 1u32,
-        1u32, 8u32, 2u32, 13u32, 3u32, 22u32, 17u32, 25u32, 23u32, 4294967295u32,
-        4294967295u32, 9u32, 4u32, 4294967295u32, 4294967295u32, 8u32, 12u32,
-        4294967295u32, 15u32, 4294967295u32, 15u32, 4294967295u32, 4294967295u32, 17u32,
+        1u32, 7u32, 8u32, 4u32, 5u32, 1u32, 1u32, 2u32, 4u32, 1u32, 24u32, 25u32, 4u32,
+        0u32, 15u32, 16u32, 5u32, 18u32, 32u32, 15u32, 16u32, 10u32, 18u32, 15u32, 16u32,
+        17u32, 18u32, 1u32, 70u32, 0u32, 4u32, 27u32, 74u32, 75u32, 76u32, 77u32, 78u32,
+        9u32, 20u32, 11u32, 12u32, 13u32, 14u32, 26u32, 7u32, 28u32, 29u32, 30u32, 31u32,
+        1u32, 32u32, 5u32, 4u32, 66u32, 67u32, 68u32, 69u32, 31u32, 11u32, 72u32, 28u32,
+        29u32, 30u32, 15u32, 16u32, 1u32, 18u32, 28u32, 29u32, 30u32, 6u32, 29u32, 1u32,
+        26u32, 27u32, 4u32, 17u32, 19u32, 21u32, 1u32, 21u32, 23u32, 21u32, 26u32, 21u32,
+        23u32, 21u32, 26u32, 26u32, 23u32, 80u32, 81u32, 26u32, 1u32, 1u32, 1u32, 33u32,
+        1u32, 33u32, 32u32, 1u32, 1u32, 4u32, 2u32, 15u32, 3u32, 16u32, 4u32, 20u32,
+        22u32, 19u32, 26u32, 26u32, 18u32, 10u32, 71u32, 19u32, 67u32, 0u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 73u32, 4294967295u32, 19u32, 19u32,
+        19u32, 4294967295u32, 4294967295u32, 21u32, 4294967295u32, 22u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 26u32, 26u32, 23u32, 27u32, 24u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
         4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
         4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
         4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
@@ -40,133 +87,324 @@ mod __table {
         4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
         4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
         4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
-        4294967295u32, 4294967295u32,
+        4294967295u32, 4294967295u32, 4294967295u32, 4294967295u32,
     ];
     pub static ACTION_BASE: &[i32] = &[
-        11i32,
-        16i32,
         0i32,
-        2i32,
-        17i32,
-        4i32,
-        5i32,
-        13i32,
         13i32,
+        1i32,
+        2i32,
+        -6i32,
+        3i32,
         6i32,
+        7i32,
+        28i32,
         9i32,
         12i32,
-        34i32,
-        35i32,
-        36i32,
-        -18i32,
-        37i32,
+        29i32,
+        14i32,
+        15i32,
+        16i32,
         19i32,
         20i32,
         21i32,
         22i32,
         23i32,
         24i32,
-        -1i32,
-        3i32,
-        3i32,
-        17i32,
         25i32,
-        26i32,
+        27i32,
+        30i32,
+        31i32,
+        33i32,
+        37i32,
+        34i32,
+        35i32,
+        11i32,
+        36i32,
+        46i32,
         38i32,
-        39i32,
         40i32,
-        1i32,
-        18i32,
-        27i32,
         41i32,
-        42i32,
         43i32,
-        15i32,
-        44i32,
-        28i32,
         45i32,
-        46i32,
-        47i32,
-        -5i32,
-        29i32,
-        30i32,
-        48i32,
-        49i32,
-        50i32,
         51i32,
-        -4i32,
-        14i32,
-        31i32,
+        52i32,
+        42i32,
+        53i32,
+        54i32,
+        55i32,
+        32i32,
+        56i32,
+        60i32,
+        68i32,
+        39i32,
+        17i32,
+        69i32,
+        70i32,
+        72i32,
+        73i32,
+        74i32,
+        67i32,
+        18i32,
+        75i32,
+        76i32,
+        77i32,
+        78i32,
+        92i32,
+        93i32,
+        94i32,
+        96i32,
+        99i32,
+        5i32,
+        -1i32,
+        4i32,
         8i32,
+        48i32,
+        48i32,
+        48i32,
+        48i32,
+        48i32,
+        26i32,
+        71i32,
+        71i32,
+        71i32,
+        71i32,
+        64i32,
+        100i32,
+        100i32,
+        100i32,
+        101i32,
+        102i32,
+        98i32,
+        103i32,
+        79i32,
+        80i32,
+        104i32,
+        89i32,
+        90i32,
+        81i32,
+        82i32,
+        83i32,
+        88i32,
+        84i32,
+        87i32,
+        113i32,
+        114i32,
+        117i32,
+        59i32,
+        95i32,
+        119i32,
+        120i32,
+        91i32,
+        97i32,
+        105i32,
+        106i32,
+        107i32,
+        58i32,
+        121i32,
+        122i32,
+        123i32,
+        124i32,
+        108i32,
+        57i32,
+        61i32,
+        63i32,
+        65i32,
+        125i32,
+        -14i32,
+        126i32,
+        109i32,
+        127i32,
+        128i32,
+        129i32,
+        130i32,
+        131i32,
+        132i32,
+        62i32,
+        66i32,
+        85i32,
+        86i32,
+        110i32,
+        111i32,
     ];
     pub static GOTO_BASE: &[i32] = &[
-        52i32, 53i32, 32i32, 54i32, 55i32, 33i32, 7i32, 56i32, 10i32, 57i32, 58i32,
-        59i32, 60i32, 61i32, 62i32, 63i32, 64i32, 65i32,
+        133i32,
+        134i32,
+        135i32,
+        136i32,
+        112i32,
+        137i32,
+        138i32,
+        139i32,
+        140i32,
+        115i32,
+        50i32,
+        141i32,
+        142i32,
+        -13i32,
+        44i32,
+        -42i32,
+        143i32,
+        118i32,
+        144i32,
+        145i32,
+        146i32,
+        147i32,
+        10i32,
+        148i32,
+        47i32,
+        149i32,
+        150i32,
+        151i32,
+        152i32,
+        153i32,
+        116i32,
+        154i32,
+        155i32,
+        156i32,
+        157i32,
+        49i32,
+        158i32,
+        159i32,
+        160i32,
     ];
     pub static RULES: &[(u32, u8)] = &[
-        (40u32, 1u8),
-        (32u32, 2u8),
-        (32u32, 0u8),
-        (33u32, 3u8),
-        (33u32, 1u8),
-        (34u32, 2u8),
-        (34u32, 1u8),
-        (23u32, 9u8),
-        (24u32, 4u8),
-        (35u32, 1u8),
-        (35u32, 0u8),
-        (36u32, 1u8),
-        (36u32, 0u8),
-        (37u32, 1u8),
-        (37u32, 0u8),
-        (25u32, 4u8),
-        (26u32, 2u8),
-        (27u32, 2u8),
-        (38u32, 3u8),
-        (38u32, 1u8),
-        (28u32, 4u8),
+        (72u32, 1u8),
+        (51u32, 2u8),
+        (51u32, 0u8),
+        (52u32, 1u8),
+        (52u32, 0u8),
+        (53u32, 2u8),
+        (53u32, 0u8),
+        (54u32, 1u8),
+        (54u32, 0u8),
+        (55u32, 2u8),
+        (55u32, 0u8),
+        (34u32, 5u8),
+        (56u32, 3u8),
+        (56u32, 1u8),
+        (35u32, 3u8),
+        (36u32, 4u8),
+        (36u32, 3u8),
+        (36u32, 3u8),
+        (36u32, 3u8),
+        (57u32, 3u8),
+        (57u32, 1u8),
+        (37u32, 5u8),
+        (58u32, 2u8),
+        (58u32, 0u8),
+        (59u32, 1u8),
+        (59u32, 0u8),
+        (60u32, 1u8),
+        (60u32, 0u8),
+        (61u32, 1u8),
+        (61u32, 0u8),
+        (38u32, 5u8),
         (39u32, 2u8),
-        (39u32, 1u8),
-        (29u32, 2u8),
-        (30u32, 2u8),
-        (31u32, 5u8),
-        (31u32, 2u8),
-        (31u32, 2u8),
-        (31u32, 2u8),
-        (31u32, 1u8),
-        (31u32, 1u8),
+        (40u32, 2u8),
+        (62u32, 2u8),
+        (62u32, 0u8),
+        (63u32, 1u8),
+        (63u32, 0u8),
+        (64u32, 3u8),
+        (64u32, 1u8),
+        (41u32, 7u8),
+        (41u32, 6u8),
+        (41u32, 6u8),
+        (42u32, 3u8),
+        (65u32, 2u8),
+        (65u32, 1u8),
+        (66u32, 1u8),
+        (66u32, 0u8),
+        (67u32, 1u8),
+        (67u32, 0u8),
+        (43u32, 4u8),
+        (44u32, 3u8),
+        (44u32, 1u8),
+        (45u32, 2u8),
+        (46u32, 6u8),
+        (46u32, 4u8),
+        (47u32, 5u8),
+        (47u32, 6u8),
+        (47u32, 6u8),
+        (47u32, 5u8),
+        (68u32, 3u8),
+        (68u32, 1u8),
+        (47u32, 4u8),
+        (47u32, 4u8),
+        (47u32, 4u8),
+        (47u32, 3u8),
+        (47u32, 2u8),
+        (47u32, 2u8),
+        (47u32, 2u8),
+        (47u32, 1u8),
+        (69u32, 2u8),
+        (69u32, 1u8),
+        (47u32, 3u8),
+        (47u32, 1u8),
+        (48u32, 1u8),
+        (70u32, 1u8),
+        (70u32, 0u8),
+        (49u32, 2u8),
+        (49u32, 1u8),
+        (71u32, 3u8),
+        (71u32, 1u8),
+        (50u32, 3u8),
     ];
     pub static STATE_SYMBOL: &[u32] = &[
-        0u32, 23u32, 28u32, 28u32, 34u32, 17u32, 6u32, 9u32, 14u32, 31u32, 31u32, 12u32,
-        19u32, 20u32, 21u32, 1u32, 8u32, 35u32, 22u32, 18u32, 4u32, 2u32, 11u32, 39u32,
-        15u32, 16u32, 10u32, 7u32, 15u32, 24u32, 17u32, 17u32, 32u32, 13u32, 5u32, 25u32,
-        25u32, 26u32, 1u32, 27u32, 36u32, 37u32, 8u32, 3u32, 33u32, 1u32, 1u32, 29u32,
-        29u32, 30u32, 1u32, 38u32, 1u32, 1u32, 1u32,
+        0u32, 34u32, 7u32, 35u32, 51u32, 36u32, 52u32, 37u32, 53u32, 41u32, 54u32, 55u32,
+        26u32, 26u32, 26u32, 26u32, 26u32, 17u32, 26u32, 26u32, 26u32, 16u32, 5u32,
+        23u32, 22u32, 24u32, 51u32, 21u32, 10u32, 58u32, 6u32, 58u32, 44u32, 44u32,
+        47u32, 47u32, 19u32, 19u32, 29u32, 19u32, 28u32, 29u32, 30u32, 19u32, 28u32,
+        29u32, 30u32, 49u32, 49u32, 47u32, 47u32, 17u32, 15u32, 50u32, 1u32, 1u32, 70u32,
+        4u32, 33u32, 27u32, 59u32, 21u32, 21u32, 2u32, 12u32, 18u32, 58u32, 65u32, 69u32,
+        69u32, 18u32, 23u32, 20u32, 16u32, 31u32, 31u32, 26u32, 21u32, 32u32, 62u32,
+        8u32, 25u32, 32u32, 11u32, 22u32, 13u32, 14u32, 45u32, 1u32, 66u32, 20u32, 9u32,
+        38u32, 38u32, 39u32, 1u32, 40u32, 60u32, 61u32, 15u32, 3u32, 57u32, 10u32, 48u32,
+        48u32, 49u32, 49u32, 49u32, 1u32, 1u32, 68u32, 1u32, 1u32, 49u32, 49u32, 2u32,
+        56u32, 56u32, 56u32, 71u32, 42u32, 1u32, 33u32, 63u32, 43u32, 43u32, 46u32,
+        67u32, 19u32, 19u32, 64u32, 64u32, 1u32, 1u32, 4u32, 4u32,
     ];
     pub static DEFAULT_REDUCE: &[u32] = &[
-        0u32, 0u32, 5u32, 6u32, 7u32, 20u32, 9u32, 10u32, 10u32, 21u32, 22u32, 25u32,
-        26u32, 27u32, 28u32, 29u32, 30u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
-        0u32, 0u32, 0u32, 0u32, 0u32, 1u32, 2u32, 8u32, 0u32, 0u32, 0u32, 3u32, 4u32,
-        11u32, 12u32, 13u32, 14u32, 15u32, 16u32, 17u32, 0u32, 0u32, 0u32, 18u32, 19u32,
-        23u32, 24u32, 0u32, 0u32, 0u32, 0u32,
+        2u32, 0u32, 1u32, 3u32, 4u32, 5u32, 6u32, 7u32, 8u32, 9u32, 10u32, 23u32, 14u32,
+        15u32, 16u32, 17u32, 18u32, 21u32, 39u32, 40u32, 41u32, 2u32, 22u32, 23u32,
+        23u32, 23u32, 23u32, 23u32, 24u32, 25u32, 33u32, 34u32, 43u32, 44u32, 50u32,
+        51u32, 55u32, 56u32, 57u32, 58u32, 61u32, 62u32, 63u32, 64u32, 65u32, 66u32,
+        67u32, 68u32, 68u32, 69u32, 70u32, 71u32, 72u32, 74u32, 75u32, 75u32, 76u32,
+        77u32, 80u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 46u32, 0u32, 73u32,
+        0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 45u32, 52u32, 48u32, 0u32, 0u32, 19u32, 20u32, 26u32,
+        27u32, 28u32, 29u32, 30u32, 31u32, 32u32, 0u32, 0u32, 59u32, 60u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32, 12u32, 13u32, 78u32, 79u32, 0u32, 0u32, 0u32, 0u32, 0u32,
+        35u32, 36u32, 42u32, 0u32, 37u32, 38u32, 47u32, 49u32, 53u32, 54u32, 0u32, 0u32,
+        0u32, 0u32, 0u32, 0u32,
     ];
     pub static DEFAULT_GOTO: &[u32] = &[
-        1u32, 29u32, 36u32, 37u32, 39u32, 3u32, 48u32, 49u32, 10u32, 32u32, 44u32, 4u32,
-        17u32, 40u32, 41u32, 51u32, 23u32, 4294967295u32,
+        1u32, 3u32, 5u32, 7u32, 93u32, 94u32, 96u32, 9u32, 120u32, 125u32, 33u32, 87u32,
+        126u32, 50u32, 104u32, 47u32, 53u32, 26u32, 6u32, 8u32, 10u32, 11u32, 118u32,
+        101u32, 66u32, 60u32, 97u32, 98u32, 79u32, 123u32, 131u32, 67u32, 89u32, 127u32,
+        110u32, 69u32, 56u32, 119u32, 4294967295u32,
     ];
-    pub const NUM_STATES: usize = 55usize;
-    pub const NUM_TERMINALS: u32 = 23u32;
+    pub const NUM_STATES: usize = 136usize;
+    pub const NUM_TERMINALS: u32 = 34u32;
     #[allow(dead_code)]
-    pub const NUM_NON_TERMINALS: u32 = 18u32;
+    pub const NUM_NON_TERMINALS: u32 = 39u32;
     pub static SYMBOL_NAMES: &[&str] = &[
         "$",
         "IDENT",
         "NUM",
         "REGEX",
+        "LITERAL",
+        "DOC",
+        "ATTR",
+        "INNER_ATTR",
         "KW_START",
         "KW_TERMINALS",
         "KW_PREC",
         "KW_EXPECT",
+        "KW_MODE",
+        "KW_IMPORT",
+        "KW_EXTENDS",
         "UNDERSCORE",
         "LBRACE",
         "RBRACE",
@@ -176,358 +414,2654 @@ mod __table {
         "COMMA",
         "EQ",
         "PIPE",
+        "PIPE_EQ",
+        "MINUS_EQ",
         "SEMI",
         "FAT_ARROW",
         "QUESTION",
         "STAR",
         "PLUS",
         "PERCENT",
+        "LT",
+        "GT",
         "grammar_def",
-        "expect_decl",
+        "start_decl",
+        "decl",
+        "terminals_block",
         "terminal_item",
         "type_annot",
         "regex_annot",
         "rule",
+        "params",
         "alt",
+        "field",
         "variant",
+        "prec_annot",
         "term",
-        "__expect_decl_star",
+        "seq",
+        "symbol",
+        "args",
+        "__inner_attr_star",
+        "__start_decl_opt",
+        "__decl_star",
+        "__terminals_block_opt",
+        "__rule_star",
+        "__ident_sep_comma",
         "__terminal_item_sep_comma",
-        "__rule_plus",
+        "__doc_star",
         "__kw_prec_opt",
         "__type_annot_opt",
         "__regex_annot_opt",
+        "__attr_star",
+        "__params_opt",
         "__alt_sep_pipe",
+        "__field_plus",
+        "__variant_opt",
+        "__prec_annot_opt",
+        "__seq_sep_pipe",
         "__term_plus",
+        "__args_opt",
+        "__symbol_sep_comma",
         "__start",
     ];
-    static STATE_ITEMS_0: &[(u16, u8)] = &[(0u16, 0u8), (7u16, 0u8)];
+    static STATE_ITEMS_0: &[(u16, u8)] = &[
+        (0u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (11u16, 0u8),
+    ];
     static STATE_ITEMS_1: &[(u16, u8)] = &[(0u16, 1u8)];
-    static STATE_ITEMS_2: &[(u16, u8)] = &[(5u16, 2u8), (5u16, 2u8)];
-    static STATE_ITEMS_3: &[(u16, u8)] = &[(6u16, 1u8), (6u16, 1u8)];
-    static STATE_ITEMS_4: &[(u16, u8)] = &[
-        (5u16, 1u8),
-        (5u16, 1u8),
-        (7u16, 9u8),
-        (20u16, 0u8),
-        (20u16, 0u8),
+    static STATE_ITEMS_2: &[(u16, u8)] = &[
+        (1u16, 2u8),
+        (1u16, 2u8),
+        (1u16, 2u8),
+        (1u16, 2u8),
     ];
-    static STATE_ITEMS_5: &[(u16, u8)] = &[(20u16, 4u8), (20u16, 4u8)];
-    static STATE_ITEMS_6: &[(u16, u8)] = &[(9u16, 1u8)];
-    static STATE_ITEMS_7: &[(u16, u8)] = &[
+    static STATE_ITEMS_3: &[(u16, u8)] = &[
+        (3u16, 1u8),
+        (3u16, 1u8),
+        (3u16, 1u8),
+        (3u16, 1u8),
+        (3u16, 1u8),
+        (3u16, 1u8),
+        (3u16, 1u8),
+        (3u16, 1u8),
+        (3u16, 1u8),
+    ];
+    static STATE_ITEMS_4: &[(u16, u8)] = &[
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (3u16, 0u8),
+        (3u16, 0u8),
+        (3u16, 0u8),
+        (3u16, 0u8),
+        (3u16, 0u8),
+        (3u16, 0u8),
+        (3u16, 0u8),
         (3u16, 0u8),
         (3u16, 0u8),
         (4u16, 0u8),
         (4u16, 0u8),
-        (7u16, 6u8),
-        (9u16, 0u8),
-        (10u16, 0u8),
-        (15u16, 0u8),
-        (15u16, 0u8),
+        (4u16, 0u8),
+        (4u16, 0u8),
+        (4u16, 0u8),
+        (4u16, 0u8),
+        (4u16, 0u8),
+        (4u16, 0u8),
+        (4u16, 0u8),
+        (11u16, 1u8),
+        (14u16, 0u8),
+        (14u16, 0u8),
+        (14u16, 0u8),
+        (14u16, 0u8),
+        (14u16, 0u8),
+        (14u16, 0u8),
+        (14u16, 0u8),
+        (14u16, 0u8),
+        (14u16, 0u8),
+    ];
+    static STATE_ITEMS_5: &[(u16, u8)] = &[
+        (5u16, 2u8),
+        (5u16, 2u8),
+        (5u16, 2u8),
+        (5u16, 2u8),
+        (5u16, 2u8),
+        (5u16, 2u8),
+        (5u16, 2u8),
+        (5u16, 2u8),
+        (5u16, 2u8),
+    ];
+    static STATE_ITEMS_6: &[(u16, u8)] = &[
+        (5u16, 0u8),
+        (5u16, 0u8),
+        (5u16, 0u8),
+        (5u16, 0u8),
+        (5u16, 0u8),
+        (5u16, 0u8),
+        (5u16, 0u8),
+        (5u16, 0u8),
+        (5u16, 0u8),
+        (6u16, 0u8),
+        (6u16, 0u8),
+        (6u16, 0u8),
+        (6u16, 0u8),
+        (6u16, 0u8),
+        (6u16, 0u8),
+        (6u16, 0u8),
+        (6u16, 0u8),
+        (6u16, 0u8),
+        (11u16, 2u8),
+    ];
+    static STATE_ITEMS_7: &[(u16, u8)] = &[
+        (7u16, 1u8),
+        (7u16, 1u8),
+        (7u16, 1u8),
+        (7u16, 1u8),
     ];
     static STATE_ITEMS_8: &[(u16, u8)] = &[
-        (3u16, 2u8),
-        (3u16, 2u8),
-        (9u16, 0u8),
-        (10u16, 0u8),
+        (5u16, 1u8),
+        (5u16, 1u8),
+        (5u16, 1u8),
+        (5u16, 1u8),
+        (5u16, 1u8),
+        (5u16, 1u8),
+        (5u16, 1u8),
+        (5u16, 1u8),
+        (5u16, 1u8),
+        (7u16, 0u8),
+        (7u16, 0u8),
+        (7u16, 0u8),
+        (7u16, 0u8),
+        (8u16, 0u8),
+        (8u16, 0u8),
+        (8u16, 0u8),
+        (8u16, 0u8),
+        (11u16, 3u8),
+        (15u16, 0u8),
         (15u16, 0u8),
         (15u16, 0u8),
+        (15u16, 0u8),
+        (15u16, 0u8),
+        (15u16, 0u8),
+        (15u16, 0u8),
+        (15u16, 0u8),
+        (15u16, 0u8),
+        (16u16, 0u8),
+        (16u16, 0u8),
+        (16u16, 0u8),
+        (16u16, 0u8),
+        (16u16, 0u8),
+        (16u16, 0u8),
+        (16u16, 0u8),
+        (16u16, 0u8),
+        (16u16, 0u8),
+        (17u16, 0u8),
+        (17u16, 0u8),
+        (17u16, 0u8),
+        (17u16, 0u8),
+        (17u16, 0u8),
+        (17u16, 0u8),
+        (17u16, 0u8),
+        (17u16, 0u8),
+        (17u16, 0u8),
+        (18u16, 0u8),
+        (18u16, 0u8),
+        (18u16, 0u8),
+        (18u16, 0u8),
+        (18u16, 0u8),
+        (18u16, 0u8),
+        (18u16, 0u8),
+        (18u16, 0u8),
+        (18u16, 0u8),
+        (21u16, 0u8),
+        (21u16, 0u8),
+        (21u16, 0u8),
+        (21u16, 0u8),
     ];
     static STATE_ITEMS_9: &[(u16, u8)] = &[
-        (21u16, 2u8),
-        (21u16, 2u8),
-        (21u16, 2u8),
-        (21u16, 2u8),
+        (9u16, 2u8),
+        (9u16, 2u8),
+        (9u16, 2u8),
+        (9u16, 2u8),
     ];
     static STATE_ITEMS_10: &[(u16, u8)] = &[
-        (22u16, 1u8),
-        (22u16, 1u8),
-        (22u16, 1u8),
-        (22u16, 1u8),
-    ];
-    static STATE_ITEMS_11: &[(u16, u8)] = &[
-        (25u16, 5u8),
-        (25u16, 5u8),
-        (25u16, 5u8),
-        (25u16, 5u8),
-    ];
+        (9u16, 0u8),
+        (9u16, 0u8),
+        (9u16, 0u8),
+        (9u16, 0u8),
+        (10u16, 0u8),
+        (10u16, 0u8),
+        (10u16, 0u8),
+        (10u16, 0u8),
+        (11u16, 4u8),
+    ];
+    static STATE_ITEMS_11: &[(u16, u8)] = &[
+        (9u16, 1u8),
+        (9u16, 1u8),
+        (9u16, 1u8),
+        (9u16, 1u8),
+        (11u16, 5u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (39u16, 0u8),
+        (39u16, 0u8),
+        (39u16, 0u8),
+        (39u16, 0u8),
+        (40u16, 0u8),
+        (40u16, 0u8),
+        (40u16, 0u8),
+        (40u16, 0u8),
+        (41u16, 0u8),
+        (41u16, 0u8),
+        (41u16, 0u8),
+        (41u16, 0u8),
+    ];
     static STATE_ITEMS_12: &[(u16, u8)] = &[
-        (26u16, 2u8),
-        (26u16, 2u8),
-        (26u16, 2u8),
-        (26u16, 2u8),
+        (14u16, 3u8),
+        (14u16, 3u8),
+        (14u16, 3u8),
+        (14u16, 3u8),
+        (14u16, 3u8),
+        (14u16, 3u8),
+        (14u16, 3u8),
+        (14u16, 3u8),
+        (14u16, 3u8),
     ];
     static STATE_ITEMS_13: &[(u16, u8)] = &[
-        (27u16, 2u8),
-        (27u16, 2u8),
-        (27u16, 2u8),
-        (27u16, 2u8),
+        (15u16, 4u8),
+        (15u16, 4u8),
+        (15u16, 4u8),
+        (15u16, 4u8),
+        (15u16, 4u8),
+        (15u16, 4u8),
+        (15u16, 4u8),
+        (15u16, 4u8),
+        (15u16, 4u8),
     ];
     static STATE_ITEMS_14: &[(u16, u8)] = &[
-        (28u16, 2u8),
-        (28u16, 2u8),
-        (28u16, 2u8),
-        (28u16, 2u8),
+        (16u16, 3u8),
+        (16u16, 3u8),
+        (16u16, 3u8),
+        (16u16, 3u8),
+        (16u16, 3u8),
+        (16u16, 3u8),
+        (16u16, 3u8),
+        (16u16, 3u8),
+        (16u16, 3u8),
     ];
     static STATE_ITEMS_15: &[(u16, u8)] = &[
-        (26u16, 1u8),
-        (26u16, 1u8),
-        (26u16, 1u8),
-        (26u16, 1u8),
-        (27u16, 1u8),
-        (27u16, 1u8),
-        (27u16, 1u8),
-        (27u16, 1u8),
-        (28u16, 1u8),
-        (28u16, 1u8),
-        (28u16, 1u8),
-        (28u16, 1u8),
-        (29u16, 1u8),
-        (29u16, 1u8),
-        (29u16, 1u8),
-        (29u16, 1u8),
+        (17u16, 3u8),
+        (17u16, 3u8),
+        (17u16, 3u8),
+        (17u16, 3u8),
+        (17u16, 3u8),
+        (17u16, 3u8),
+        (17u16, 3u8),
+        (17u16, 3u8),
+        (17u16, 3u8),
     ];
     static STATE_ITEMS_16: &[(u16, u8)] = &[
-        (30u16, 1u8),
-        (30u16, 1u8),
-        (30u16, 1u8),
-        (30u16, 1u8),
+        (18u16, 3u8),
+        (18u16, 3u8),
+        (18u16, 3u8),
+        (18u16, 3u8),
+        (18u16, 3u8),
+        (18u16, 3u8),
+        (18u16, 3u8),
+        (18u16, 3u8),
+        (18u16, 3u8),
+    ];
+    static STATE_ITEMS_17: &[(u16, u8)] = &[
+        (21u16, 5u8),
+        (21u16, 5u8),
+        (21u16, 5u8),
+        (21u16, 5u8),
     ];
-    static STATE_ITEMS_17: &[(u16, u8)] = &[(15u16, 1u8), (15u16, 1u8)];
     static STATE_ITEMS_18: &[(u16, u8)] = &[
-        (25u16, 3u8),
-        (25u16, 3u8),
-        (25u16, 3u8),
-        (25u16, 3u8),
-    ];
-    static STATE_ITEMS_19: &[(u16, u8)] = &[(24u16, 1u8), (24u16, 1u8)];
-    static STATE_ITEMS_20: &[(u16, u8)] = &[(7u16, 1u8)];
-    static STATE_ITEMS_21: &[(u16, u8)] = &[(8u16, 2u8), (8u16, 2u8)];
-    static STATE_ITEMS_22: &[(u16, u8)] = &[
-        (25u16, 1u8),
-        (25u16, 1u8),
-        (25u16, 1u8),
-        (25u16, 1u8),
+        (39u16, 7u8),
+        (39u16, 7u8),
+        (39u16, 7u8),
+        (39u16, 7u8),
+    ];
+    static STATE_ITEMS_19: &[(u16, u8)] = &[
+        (40u16, 6u8),
+        (40u16, 6u8),
+        (40u16, 6u8),
+        (40u16, 6u8),
+    ];
+    static STATE_ITEMS_20: &[(u16, u8)] = &[
+        (41u16, 6u8),
+        (41u16, 6u8),
+        (41u16, 6u8),
+        (41u16, 6u8),
+    ];
+    static STATE_ITEMS_21: &[(u16, u8)] = &[
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (1u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (2u16, 0u8),
+        (21u16, 2u8),
+        (21u16, 2u8),
+        (21u16, 2u8),
+        (21u16, 2u8),
     ];
+    static STATE_ITEMS_22: &[(u16, u8)] = &[(22u16, 2u8), (22u16, 2u8), (22u16, 2u8)];
     static STATE_ITEMS_23: &[(u16, u8)] = &[
-        (21u16, 1u8),
-        (21u16, 1u8),
-        (21u16, 1u8),
-        (21u16, 1u8),
-        (23u16, 1u8),
-        (23u16, 1u8),
-        (24u16, 0u8),
-        (24u16, 0u8),
-        (25u16, 0u8),
-        (25u16, 0u8),
-        (25u16, 0u8),
-        (25u16, 0u8),
-        (26u16, 0u8),
-        (26u16, 0u8),
-        (26u16, 0u8),
-        (26u16, 0u8),
-        (27u16, 0u8),
-        (27u16, 0u8),
-        (27u16, 0u8),
-        (27u16, 0u8),
-        (28u16, 0u8),
-        (28u16, 0u8),
-        (28u16, 0u8),
-        (28u16, 0u8),
-        (29u16, 0u8),
-        (29u16, 0u8),
-        (29u16, 0u8),
-        (29u16, 0u8),
-        (30u16, 0u8),
-        (30u16, 0u8),
-        (30u16, 0u8),
-        (30u16, 0u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (37u16, 2u8),
+        (37u16, 2u8),
+        (49u16, 0u8),
+        (49u16, 0u8),
     ];
     static STATE_ITEMS_24: &[(u16, u8)] = &[
-        (18u16, 0u8),
-        (18u16, 0u8),
-        (19u16, 0u8),
-        (19u16, 0u8),
-        (20u16, 2u8),
-        (20u16, 2u8),
-        (21u16, 0u8),
-        (21u16, 0u8),
-        (21u16, 0u8),
-        (21u16, 0u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
         (22u16, 0u8),
         (22u16, 0u8),
         (22u16, 0u8),
         (22u16, 0u8),
         (23u16, 0u8),
         (23u16, 0u8),
-        (25u16, 0u8),
-        (25u16, 0u8),
-        (25u16, 0u8),
-        (25u16, 0u8),
-        (26u16, 0u8),
-        (26u16, 0u8),
-        (26u16, 0u8),
-        (26u16, 0u8),
-        (27u16, 0u8),
-        (27u16, 0u8),
-        (27u16, 0u8),
-        (27u16, 0u8),
-        (28u16, 0u8),
-        (28u16, 0u8),
-        (28u16, 0u8),
-        (28u16, 0u8),
-        (29u16, 0u8),
-        (29u16, 0u8),
-        (29u16, 0u8),
-        (29u16, 0u8),
-        (30u16, 0u8),
-        (30u16, 0u8),
-        (30u16, 0u8),
-        (30u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (37u16, 0u8),
+        (37u16, 0u8),
+        (38u16, 0u8),
+        (38u16, 0u8),
+        (39u16, 5u8),
+        (39u16, 5u8),
+        (39u16, 5u8),
+        (39u16, 5u8),
+        (49u16, 0u8),
+        (49u16, 0u8),
     ];
     static STATE_ITEMS_25: &[(u16, u8)] = &[
-        (18u16, 2u8),
-        (18u16, 2u8),
-        (21u16, 0u8),
-        (21u16, 0u8),
-        (21u16, 0u8),
-        (21u16, 0u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
         (22u16, 0u8),
         (22u16, 0u8),
         (22u16, 0u8),
         (22u16, 0u8),
         (23u16, 0u8),
         (23u16, 0u8),
-        (25u16, 0u8),
-        (25u16, 0u8),
-        (25u16, 0u8),
-        (25u16, 0u8),
-        (26u16, 0u8),
-        (26u16, 0u8),
-        (26u16, 0u8),
-        (26u16, 0u8),
-        (27u16, 0u8),
-        (27u16, 0u8),
-        (27u16, 0u8),
-        (27u16, 0u8),
-        (28u16, 0u8),
-        (28u16, 0u8),
-        (28u16, 0u8),
-        (28u16, 0u8),
-        (29u16, 0u8),
-        (29u16, 0u8),
-        (29u16, 0u8),
-        (29u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (37u16, 0u8),
+        (37u16, 0u8),
+        (38u16, 0u8),
+        (38u16, 0u8),
+        (40u16, 4u8),
+        (40u16, 4u8),
+        (40u16, 4u8),
+        (40u16, 4u8),
+        (49u16, 0u8),
+        (49u16, 0u8),
+    ];
+    static STATE_ITEMS_26: &[(u16, u8)] = &[
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (1u16, 1u8),
+        (19u16, 0u8),
+        (19u16, 0u8),
+        (20u16, 0u8),
+        (20u16, 0u8),
+        (21u16, 3u8),
+        (21u16, 3u8),
+        (21u16, 3u8),
+        (21u16, 3u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
         (30u16, 0u8),
         (30u16, 0u8),
+    ];
+    static STATE_ITEMS_27: &[(u16, u8)] = &[
+        (19u16, 2u8),
+        (19u16, 2u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (22u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
+        (23u16, 0u8),
         (30u16, 0u8),
         (30u16, 0u8),
     ];
-    static STATE_ITEMS_26: &[(u16, u8)] = &[
-        (5u16, 0u8),
-        (5u16, 0u8),
-        (6u16, 0u8),
-        (6u16, 0u8),
-        (7u16, 8u8),
-        (20u16, 0u8),
-        (20u16, 0u8),
+    static STATE_ITEMS_28: &[(u16, u8)] = &[(24u16, 1u8)];
+    static STATE_ITEMS_29: &[(u16, u8)] = &[
+        (22u16, 1u8),
+        (22u16, 1u8),
+        (22u16, 1u8),
+        (24u16, 0u8),
+        (25u16, 0u8),
+        (30u16, 1u8),
+        (30u16, 1u8),
     ];
-    static STATE_ITEMS_27: &[(u16, u8)] = &[(8u16, 1u8), (8u16, 1u8)];
-    static STATE_ITEMS_28: &[(u16, u8)] = &[(17u16, 1u8), (17u16, 1u8)];
-    static STATE_ITEMS_29: &[(u16, u8)] = &[(1u16, 2u8), (1u16, 2u8)];
-    static STATE_ITEMS_30: &[(u16, u8)] = &[
-        (1u16, 0u8),
-        (1u16, 0u8),
-        (2u16, 0u8),
-        (2u16, 0u8),
-        (7u16, 3u8),
+    static STATE_ITEMS_30: &[(u16, u8)] = &[(33u16, 2u8), (33u16, 2u8)];
+    static STATE_ITEMS_31: &[(u16, u8)] = &[
+        (22u16, 1u8),
+        (22u16, 1u8),
+        (22u16, 1u8),
+        (33u16, 0u8),
+        (33u16, 0u8),
+        (34u16, 0u8),
+        (34u16, 0u8),
+        (39u16, 1u8),
+        (39u16, 1u8),
+        (39u16, 1u8),
+        (39u16, 1u8),
+        (40u16, 1u8),
+        (40u16, 1u8),
+        (40u16, 1u8),
+        (40u16, 1u8),
+        (41u16, 1u8),
+        (41u16, 1u8),
+        (41u16, 1u8),
+        (41u16, 1u8),
     ];
-    static STATE_ITEMS_31: &[(u16, u8)] = &[(8u16, 4u8), (8u16, 4u8)];
     static STATE_ITEMS_32: &[(u16, u8)] = &[
-        (1u16, 1u8),
-        (1u16, 1u8),
-        (7u16, 4u8),
-        (8u16, 0u8),
-        (8u16, 0u8),
+        (43u16, 2u8),
+        (43u16, 2u8),
+        (43u16, 2u8),
+        (43u16, 2u8),
+        (43u16, 2u8),
+        (43u16, 2u8),
+        (43u16, 2u8),
+        (43u16, 2u8),
+        (43u16, 2u8),
+    ];
+    static STATE_ITEMS_33: &[(u16, u8)] = &[
+        (44u16, 1u8),
+        (44u16, 1u8),
+        (44u16, 1u8),
+        (44u16, 1u8),
+        (44u16, 1u8),
+        (44u16, 1u8),
+        (44u16, 1u8),
+        (44u16, 1u8),
+        (44u16, 1u8),
+    ];
+    static STATE_ITEMS_34: &[(u16, u8)] = &[
+        (50u16, 3u8),
+        (50u16, 3u8),
+        (50u16, 3u8),
+        (50u16, 3u8),
+        (50u16, 3u8),
+        (50u16, 3u8),
+        (50u16, 3u8),
+        (50u16, 3u8),
+        (50u16, 3u8),
+    ];
+    static STATE_ITEMS_35: &[(u16, u8)] = &[
+        (51u16, 1u8),
+        (51u16, 1u8),
+        (51u16, 1u8),
+        (51u16, 1u8),
+        (51u16, 1u8),
+        (51u16, 1u8),
+        (51u16, 1u8),
+        (51u16, 1u8),
+        (51u16, 1u8),
+    ];
+    static STATE_ITEMS_36: &[(u16, u8)] = &[
+        (55u16, 5u8),
+        (55u16, 5u8),
+        (55u16, 5u8),
+        (55u16, 5u8),
+        (55u16, 5u8),
+        (55u16, 5u8),
+    ];
+    static STATE_ITEMS_37: &[(u16, u8)] = &[
+        (56u16, 6u8),
+        (56u16, 6u8),
+        (56u16, 6u8),
+        (56u16, 6u8),
+        (56u16, 6u8),
+        (56u16, 6u8),
     ];
-    static STATE_ITEMS_33: &[(u16, u8)] = &[(16u16, 1u8), (16u16, 1u8), (16u16, 1u8)];
-    static STATE_ITEMS_34: &[(u16, u8)] = &[(7u16, 5u8)];
-    static STATE_ITEMS_35: &[(u16, u8)] = &[(3u16, 3u8), (3u16, 3u8)];
-    static STATE_ITEMS_36: &[(u16, u8)] = &[(4u16, 1u8), (4u16, 1u8)];
-    static STATE_ITEMS_37: &[(u16, u8)] = &[(11u16, 1u8), (11u16, 1u8), (11u16, 1u8)];
     static STATE_ITEMS_38: &[(u16, u8)] = &[
-        (11u16, 0u8),
-        (11u16, 0u8),
-        (11u16, 0u8),
-        (12u16, 0u8),
-        (12u16, 0u8),
-        (12u16, 0u8),
-        (15u16, 2u8),
-        (15u16, 2u8),
-        (16u16, 0u8),
-        (16u16, 0u8),
-        (16u16, 0u8),
+        (57u16, 6u8),
+        (57u16, 6u8),
+        (57u16, 6u8),
+        (57u16, 6u8),
+        (57u16, 6u8),
+        (57u16, 6u8),
+    ];
+    static STATE_ITEMS_39: &[(u16, u8)] = &[
+        (57u16, 5u8),
+        (57u16, 5u8),
+        (57u16, 5u8),
+        (57u16, 5u8),
+        (57u16, 5u8),
+        (57u16, 5u8),
+        (58u16, 5u8),
+        (58u16, 5u8),
+        (58u16, 5u8),
+        (58u16, 5u8),
+        (58u16, 5u8),
+        (58u16, 5u8),
     ];
-    static STATE_ITEMS_39: &[(u16, u8)] = &[(13u16, 1u8), (13u16, 1u8)];
     static STATE_ITEMS_40: &[(u16, u8)] = &[
-        (13u16, 0u8),
-        (13u16, 0u8),
-        (14u16, 0u8),
-        (14u16, 0u8),
-        (15u16, 3u8),
-        (15u16, 3u8),
-        (17u16, 0u8),
-        (17u16, 0u8),
+        (61u16, 4u8),
+        (61u16, 4u8),
+        (61u16, 4u8),
+        (61u16, 4u8),
+        (61u16, 4u8),
+        (61u16, 4u8),
+    ];
+    static STATE_ITEMS_41: &[(u16, u8)] = &[
+        (62u16, 4u8),
+        (62u16, 4u8),
+        (62u16, 4u8),
+        (62u16, 4u8),
+        (62u16, 4u8),
+        (62u16, 4u8),
+    ];
+    static STATE_ITEMS_42: &[(u16, u8)] = &[
+        (63u16, 4u8),
+        (63u16, 4u8),
+        (63u16, 4u8),
+        (63u16, 4u8),
+        (63u16, 4u8),
+        (63u16, 4u8),
+    ];
+    static STATE_ITEMS_43: &[(u16, u8)] = &[
+        (61u16, 3u8),
+        (61u16, 3u8),
+        (61u16, 3u8),
+        (61u16, 3u8),
+        (61u16, 3u8),
+        (61u16, 3u8),
+        (62u16, 3u8),
+        (62u16, 3u8),
+        (62u16, 3u8),
+        (62u16, 3u8),
+        (62u16, 3u8),
+        (62u16, 3u8),
+        (63u16, 3u8),
+        (63u16, 3u8),
+        (63u16, 3u8),
+        (63u16, 3u8),
+        (63u16, 3u8),
+        (63u16, 3u8),
+        (64u16, 3u8),
+        (64u16, 3u8),
+        (64u16, 3u8),
+        (64u16, 3u8),
+        (64u16, 3u8),
+        (64u16, 3u8),
+    ];
+    static STATE_ITEMS_44: &[(u16, u8)] = &[
+        (65u16, 2u8),
+        (65u16, 2u8),
+        (65u16, 2u8),
+        (65u16, 2u8),
+        (65u16, 2u8),
+        (65u16, 2u8),
     ];
-    static STATE_ITEMS_41: &[(u16, u8)] = &[(15u16, 4u8), (15u16, 4u8)];
-    static STATE_ITEMS_42: &[(u16, u8)] = &[(16u16, 2u8), (16u16, 2u8), (16u16, 2u8)];
-    static STATE_ITEMS_43: &[(u16, u8)] = &[(17u16, 2u8), (17u16, 2u8)];
-    static STATE_ITEMS_44: &[(u16, u8)] = &[(3u16, 1u8), (3u16, 1u8), (7u16, 7u8)];
     static STATE_ITEMS_45: &[(u16, u8)] = &[
-        (25u16, 4u8),
-        (25u16, 4u8),
-        (25u16, 4u8),
-        (25u16, 4u8),
-    ];
-    static STATE_ITEMS_46: &[(u16, u8)] = &[(20u16, 1u8), (20u16, 1u8)];
-    static STATE_ITEMS_47: &[(u16, u8)] = &[(18u16, 3u8), (18u16, 3u8)];
-    static STATE_ITEMS_48: &[(u16, u8)] = &[(19u16, 1u8), (19u16, 1u8)];
-    static STATE_ITEMS_49: &[(u16, u8)] = &[(23u16, 2u8), (23u16, 2u8)];
-    static STATE_ITEMS_50: &[(u16, u8)] = &[(24u16, 2u8), (24u16, 2u8)];
+        (66u16, 2u8),
+        (66u16, 2u8),
+        (66u16, 2u8),
+        (66u16, 2u8),
+        (66u16, 2u8),
+        (66u16, 2u8),
+    ];
+    static STATE_ITEMS_46: &[(u16, u8)] = &[
+        (67u16, 2u8),
+        (67u16, 2u8),
+        (67u16, 2u8),
+        (67u16, 2u8),
+        (67u16, 2u8),
+        (67u16, 2u8),
+    ];
+    static STATE_ITEMS_47: &[(u16, u8)] = &[
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+    ];
+    static STATE_ITEMS_48: &[(u16, u8)] = &[
+        (55u16, 2u8),
+        (55u16, 2u8),
+        (55u16, 2u8),
+        (55u16, 2u8),
+        (55u16, 2u8),
+        (55u16, 2u8),
+        (56u16, 2u8),
+        (56u16, 2u8),
+        (56u16, 2u8),
+        (56u16, 2u8),
+        (56u16, 2u8),
+        (56u16, 2u8),
+        (57u16, 2u8),
+        (57u16, 2u8),
+        (57u16, 2u8),
+        (57u16, 2u8),
+        (57u16, 2u8),
+        (57u16, 2u8),
+        (58u16, 2u8),
+        (58u16, 2u8),
+        (58u16, 2u8),
+        (58u16, 2u8),
+        (58u16, 2u8),
+        (58u16, 2u8),
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (65u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (66u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (67u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+        (68u16, 1u8),
+    ];
+    static STATE_ITEMS_49: &[(u16, u8)] = &[
+        (69u16, 2u8),
+        (69u16, 2u8),
+        (69u16, 2u8),
+        (69u16, 2u8),
+        (69u16, 2u8),
+        (69u16, 2u8),
+    ];
+    static STATE_ITEMS_50: &[(u16, u8)] = &[
+        (70u16, 1u8),
+        (70u16, 1u8),
+        (70u16, 1u8),
+        (70u16, 1u8),
+        (70u16, 1u8),
+        (70u16, 1u8),
+    ];
     static STATE_ITEMS_51: &[(u16, u8)] = &[
-        (18u16, 1u8),
-        (18u16, 1u8),
-        (20u16, 3u8),
-        (20u16, 3u8),
+        (71u16, 3u8),
+        (71u16, 3u8),
+        (71u16, 3u8),
+        (71u16, 3u8),
+        (71u16, 3u8),
+        (71u16, 3u8),
+        (71u16, 3u8),
+        (71u16, 3u8),
+        (71u16, 3u8),
+    ];
+    static STATE_ITEMS_52: &[(u16, u8)] = &[
+        (72u16, 1u8),
+        (72u16, 1u8),
+        (72u16, 1u8),
+        (72u16, 1u8),
+        (72u16, 1u8),
+        (72u16, 1u8),
+    ];
+    static STATE_ITEMS_53: &[(u16, u8)] = &[
+        (74u16, 1u8),
+        (74u16, 1u8),
+        (74u16, 1u8),
+        (74u16, 1u8),
+        (74u16, 1u8),
+        (74u16, 1u8),
+        (74u16, 1u8),
+        (74u16, 1u8),
+        (74u16, 1u8),
+        (74u16, 1u8),
+        (74u16, 1u8),
+        (74u16, 1u8),
     ];
-    static STATE_ITEMS_52: &[(u16, u8)] = &[(7u16, 2u8)];
-    static STATE_ITEMS_53: &[(u16, u8)] = &[(8u16, 3u8), (8u16, 3u8)];
     static STATE_ITEMS_54: &[(u16, u8)] = &[
-        (25u16, 2u8),
-        (25u16, 2u8),
-        (25u16, 2u8),
-        (25u16, 2u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
     ];
-    pub static STATE_ITEMS: &[&[(u16, u8)]] = &[
-        STATE_ITEMS_0,
-        STATE_ITEMS_1,
-        STATE_ITEMS_2,
-        STATE_ITEMS_3,
-        STATE_ITEMS_4,
-        STATE_ITEMS_5,
-        STATE_ITEMS_6,
-        STATE_ITEMS_7,
-        STATE_ITEMS_8,
-        STATE_ITEMS_9,
-        STATE_ITEMS_10,
-        STATE_ITEMS_11,
-        STATE_ITEMS_12,
-        STATE_ITEMS_13,
-        STATE_ITEMS_14,
-        STATE_ITEMS_15,
-        STATE_ITEMS_16,
-        STATE_ITEMS_17,
-        STATE_ITEMS_18,
+    static STATE_ITEMS_55: &[(u16, u8)] = &[
+        (50u16, 1u8),
+        (50u16, 1u8),
+        (50u16, 1u8),
+        (50u16, 1u8),
+        (50u16, 1u8),
+        (50u16, 1u8),
+        (50u16, 1u8),
+        (50u16, 1u8),
+        (50u16, 1u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (74u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (75u16, 0u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (76u16, 1u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+        (80u16, 0u8),
+    ];
+    static STATE_ITEMS_56: &[(u16, u8)] = &[
+        (76u16, 2u8),
+        (76u16, 2u8),
+        (76u16, 2u8),
+        (76u16, 2u8),
+        (76u16, 2u8),
+        (76u16, 2u8),
+        (76u16, 2u8),
+        (76u16, 2u8),
+        (76u16, 2u8),
+        (76u16, 2u8),
+        (76u16, 2u8),
+        (76u16, 2u8),
+    ];
+    static STATE_ITEMS_57: &[(u16, u8)] = &[
+        (77u16, 1u8),
+        (77u16, 1u8),
+        (77u16, 1u8),
+        (77u16, 1u8),
+        (77u16, 1u8),
+        (77u16, 1u8),
+        (77u16, 1u8),
+        (77u16, 1u8),
+        (77u16, 1u8),
+    ];
+    static STATE_ITEMS_58: &[(u16, u8)] = &[
+        (80u16, 3u8),
+        (80u16, 3u8),
+        (80u16, 3u8),
+        (80u16, 3u8),
+        (80u16, 3u8),
+        (80u16, 3u8),
+        (80u16, 3u8),
+        (80u16, 3u8),
+        (80u16, 3u8),
+        (80u16, 3u8),
+        (80u16, 3u8),
+        (80u16, 3u8),
+    ];
+    static STATE_ITEMS_59: &[(u16, u8)] = &[(52u16, 1u8), (52u16, 1u8), (52u16, 1u8)];
+    static STATE_ITEMS_60: &[(u16, u8)] = &[(30u16, 2u8), (30u16, 2u8)];
+    static STATE_ITEMS_61: &[(u16, u8)] = &[(53u16, 4u8), (53u16, 4u8)];
+    static STATE_ITEMS_62: &[(u16, u8)] = &[(12u16, 2u8), (12u16, 2u8)];
+    static STATE_ITEMS_63: &[(u16, u8)] = &[
+        (15u16, 2u8),
+        (15u16, 2u8),
+        (15u16, 2u8),
+        (15u16, 2u8),
+        (15u16, 2u8),
+        (15u16, 2u8),
+        (15u16, 2u8),
+        (15u16, 2u8),
+        (15u16, 2u8),
+    ];
+    static STATE_ITEMS_64: &[(u16, u8)] = &[
+        (16u16, 1u8),
+        (16u16, 1u8),
+        (16u16, 1u8),
+        (16u16, 1u8),
+        (16u16, 1u8),
+        (16u16, 1u8),
+        (16u16, 1u8),
+        (16u16, 1u8),
+        (16u16, 1u8),
+    ];
+    static STATE_ITEMS_65: &[(u16, u8)] = &[
+        (53u16, 2u8),
+        (53u16, 2u8),
+        (54u16, 2u8),
+        (54u16, 2u8),
+    ];
+    static STATE_ITEMS_66: &[(u16, u8)] = &[
+        (22u16, 1u8),
+        (22u16, 1u8),
+        (22u16, 1u8),
+        (22u16, 1u8),
+        (22u16, 1u8),
+        (22u16, 1u8),
+        (43u16, 0u8),
+        (43u16, 0u8),
+        (43u16, 0u8),
+        (43u16, 0u8),
+        (43u16, 0u8),
+        (43u16, 0u8),
+        (43u16, 0u8),
+        (43u16, 0u8),
+        (43u16, 0u8),
+        (44u16, 0u8),
+        (44u16, 0u8),
+        (44u16, 0u8),
+        (44u16, 0u8),
+        (44u16, 0u8),
+        (44u16, 0u8),
+        (44u16, 0u8),
+        (44u16, 0u8),
+        (44u16, 0u8),
+        (49u16, 1u8),
+        (49u16, 1u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_67: &[(u16, u8)] = &[
+        (43u16, 1u8),
+        (43u16, 1u8),
+        (43u16, 1u8),
+        (43u16, 1u8),
+        (43u16, 1u8),
+        (43u16, 1u8),
+        (43u16, 1u8),
+        (43u16, 1u8),
+        (43u16, 1u8),
+        (45u16, 0u8),
+        (45u16, 0u8),
+        (45u16, 0u8),
+        (46u16, 0u8),
+        (46u16, 0u8),
+        (46u16, 0u8),
+        (49u16, 2u8),
+        (49u16, 2u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (50u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (51u16, 0u8),
+        (52u16, 0u8),
+        (52u16, 0u8),
+        (52u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_68: &[(u16, u8)] = &[
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 2u8),
+        (71u16, 2u8),
+        (71u16, 2u8),
+        (71u16, 2u8),
+        (71u16, 2u8),
+        (71u16, 2u8),
+        (71u16, 2u8),
+        (71u16, 2u8),
+        (71u16, 2u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_69: &[(u16, u8)] = &[
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (69u16, 1u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (73u16, 1u8),
+        (73u16, 1u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_70: &[(u16, u8)] = &[
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 1u8),
+        (55u16, 1u8),
+        (55u16, 1u8),
+        (55u16, 1u8),
+        (55u16, 1u8),
+        (55u16, 1u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 1u8),
+        (56u16, 1u8),
+        (56u16, 1u8),
+        (56u16, 1u8),
+        (56u16, 1u8),
+        (56u16, 1u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 1u8),
+        (57u16, 1u8),
+        (57u16, 1u8),
+        (57u16, 1u8),
+        (57u16, 1u8),
+        (57u16, 1u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 1u8),
+        (58u16, 1u8),
+        (58u16, 1u8),
+        (58u16, 1u8),
+        (58u16, 1u8),
+        (58u16, 1u8),
+        (59u16, 0u8),
+        (59u16, 0u8),
+        (60u16, 0u8),
+        (60u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 1u8),
+        (61u16, 1u8),
+        (61u16, 1u8),
+        (61u16, 1u8),
+        (61u16, 1u8),
+        (61u16, 1u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 1u8),
+        (62u16, 1u8),
+        (62u16, 1u8),
+        (62u16, 1u8),
+        (62u16, 1u8),
+        (62u16, 1u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 1u8),
+        (63u16, 1u8),
+        (63u16, 1u8),
+        (63u16, 1u8),
+        (63u16, 1u8),
+        (63u16, 1u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 1u8),
+        (64u16, 1u8),
+        (64u16, 1u8),
+        (64u16, 1u8),
+        (64u16, 1u8),
+        (64u16, 1u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (73u16, 0u8),
+        (73u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_71: &[(u16, u8)] = &[
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (59u16, 2u8),
+        (59u16, 2u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (73u16, 0u8),
+        (73u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_72: &[(u16, u8)] = &[
+        (50u16, 2u8),
+        (50u16, 2u8),
+        (50u16, 2u8),
+        (50u16, 2u8),
+        (50u16, 2u8),
+        (50u16, 2u8),
+        (50u16, 2u8),
+        (50u16, 2u8),
+        (50u16, 2u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_73: &[(u16, u8)] = &[
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (55u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (56u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (57u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (58u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (61u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (62u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (63u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (64u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (65u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (66u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (67u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (68u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (69u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (70u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 0u8),
+        (71u16, 1u8),
+        (71u16, 1u8),
+        (71u16, 1u8),
+        (71u16, 1u8),
+        (71u16, 1u8),
+        (71u16, 1u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (72u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_74: &[(u16, u8)] = &[
+        (55u16, 3u8),
+        (55u16, 3u8),
+        (55u16, 3u8),
+        (55u16, 3u8),
+        (55u16, 3u8),
+        (55u16, 3u8),
+        (56u16, 3u8),
+        (56u16, 3u8),
+        (56u16, 3u8),
+        (56u16, 3u8),
+        (56u16, 3u8),
+        (56u16, 3u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_75: &[(u16, u8)] = &[
+        (56u16, 4u8),
+        (56u16, 4u8),
+        (56u16, 4u8),
+        (56u16, 4u8),
+        (56u16, 4u8),
+        (56u16, 4u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_76: &[(u16, u8)] = &[
+        (57u16, 3u8),
+        (57u16, 3u8),
+        (57u16, 3u8),
+        (57u16, 3u8),
+        (57u16, 3u8),
+        (57u16, 3u8),
+        (58u16, 3u8),
+        (58u16, 3u8),
+        (58u16, 3u8),
+        (58u16, 3u8),
+        (58u16, 3u8),
+        (58u16, 3u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+    ];
+    static STATE_ITEMS_77: &[(u16, u8)] = &[
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (78u16, 2u8),
+        (78u16, 2u8),
+    ];
+    static STATE_ITEMS_78: &[(u16, u8)] = &[
+        (76u16, 0u8),
+        (76u16, 0u8),
+        (77u16, 0u8),
+        (77u16, 0u8),
+        (78u16, 0u8),
+        (78u16, 0u8),
+        (79u16, 0u8),
+        (79u16, 0u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+        (80u16, 1u8),
+    ];
+    static STATE_ITEMS_79: &[(u16, u8)] = &[
+        (33u16, 1u8),
+        (33u16, 1u8),
+        (39u16, 2u8),
+        (39u16, 2u8),
+        (39u16, 2u8),
+        (39u16, 2u8),
+        (40u16, 2u8),
+        (40u16, 2u8),
+        (40u16, 2u8),
+        (40u16, 2u8),
+        (41u16, 2u8),
+        (41u16, 2u8),
+        (41u16, 2u8),
+        (41u16, 2u8),
+    ];
+    static STATE_ITEMS_80: &[(u16, u8)] = &[
+        (12u16, 0u8),
+        (12u16, 0u8),
+        (13u16, 0u8),
+        (13u16, 0u8),
+        (14u16, 1u8),
+        (14u16, 1u8),
+        (14u16, 1u8),
+        (14u16, 1u8),
+        (14u16, 1u8),
+        (14u16, 1u8),
+        (14u16, 1u8),
+        (14u16, 1u8),
+        (14u16, 1u8),
+    ];
+    static STATE_ITEMS_81: &[(u16, u8)] = &[
+        (12u16, 0u8),
+        (12u16, 0u8),
+        (13u16, 0u8),
+        (13u16, 0u8),
+        (41u16, 4u8),
+        (41u16, 4u8),
+        (41u16, 4u8),
+        (41u16, 4u8),
+    ];
+    static STATE_ITEMS_82: &[(u16, u8)] = &[
+        (12u16, 0u8),
+        (12u16, 0u8),
+        (13u16, 0u8),
+        (13u16, 0u8),
+        (42u16, 1u8),
+    ];
+    static STATE_ITEMS_83: &[(u16, u8)] = &[
+        (15u16, 1u8),
+        (15u16, 1u8),
+        (15u16, 1u8),
+        (15u16, 1u8),
+        (15u16, 1u8),
+        (15u16, 1u8),
+        (15u16, 1u8),
+        (15u16, 1u8),
+        (15u16, 1u8),
+    ];
+    static STATE_ITEMS_84: &[(u16, u8)] = &[(32u16, 1u8), (32u16, 1u8)];
+    static STATE_ITEMS_85: &[(u16, u8)] = &[
+        (17u16, 1u8),
+        (17u16, 1u8),
+        (17u16, 1u8),
+        (17u16, 1u8),
+        (17u16, 1u8),
+        (17u16, 1u8),
+        (17u16, 1u8),
+        (17u16, 1u8),
+        (17u16, 1u8),
+    ];
+    static STATE_ITEMS_86: &[(u16, u8)] = &[
+        (18u16, 1u8),
+        (18u16, 1u8),
+        (18u16, 1u8),
+        (18u16, 1u8),
+        (18u16, 1u8),
+        (18u16, 1u8),
+        (18u16, 1u8),
+        (18u16, 1u8),
+        (18u16, 1u8),
+    ];
+    static STATE_ITEMS_87: &[(u16, u8)] = &[(45u16, 1u8), (45u16, 1u8), (45u16, 1u8)];
+    static STATE_ITEMS_88: &[(u16, u8)] = &[(52u16, 2u8), (52u16, 2u8), (52u16, 2u8)];
+    static STATE_ITEMS_89: &[(u16, u8)] = &[
+        (47u16, 0u8),
+        (47u16, 0u8),
+        (48u16, 0u8),
+        (48u16, 0u8),
+        (49u16, 3u8),
+        (49u16, 3u8),
+        (53u16, 0u8),
+        (53u16, 0u8),
+        (54u16, 0u8),
+        (54u16, 0u8),
+    ];
+    static STATE_ITEMS_90: &[(u16, u8)] = &[(31u16, 1u8), (31u16, 1u8), (31u16, 1u8)];
+    static STATE_ITEMS_91: &[(u16, u8)] = &[
+        (21u16, 1u8),
+        (21u16, 1u8),
+        (21u16, 1u8),
+        (21u16, 1u8),
+    ];
+    static STATE_ITEMS_92: &[(u16, u8)] = &[(19u16, 3u8), (19u16, 3u8)];
+    static STATE_ITEMS_93: &[(u16, u8)] = &[(20u16, 1u8), (20u16, 1u8)];
+    static STATE_ITEMS_94: &[(u16, u8)] = &[(26u16, 1u8), (26u16, 1u8), (26u16, 1u8)];
+    static STATE_ITEMS_95: &[(u16, u8)] = &[
+        (26u16, 0u8),
+        (26u16, 0u8),
+        (26u16, 0u8),
+        (27u16, 0u8),
+        (27u16, 0u8),
+        (27u16, 0u8),
+        (30u16, 3u8),
+        (30u16, 3u8),
+        (31u16, 0u8),
+        (31u16, 0u8),
+        (31u16, 0u8),
+    ];
+    static STATE_ITEMS_96: &[(u16, u8)] = &[(28u16, 1u8), (28u16, 1u8)];
+    static STATE_ITEMS_97: &[(u16, u8)] = &[
+        (28u16, 0u8),
+        (28u16, 0u8),
+        (29u16, 0u8),
+        (29u16, 0u8),
+        (30u16, 4u8),
+        (30u16, 4u8),
+        (32u16, 0u8),
+        (32u16, 0u8),
+    ];
+    static STATE_ITEMS_98: &[(u16, u8)] = &[(30u16, 5u8), (30u16, 5u8)];
+    static STATE_ITEMS_99: &[(u16, u8)] = &[(31u16, 2u8), (31u16, 2u8), (31u16, 2u8)];
+    static STATE_ITEMS_100: &[(u16, u8)] = &[(32u16, 2u8), (32u16, 2u8)];
+    static STATE_ITEMS_101: &[(u16, u8)] = &[
+        (19u16, 1u8),
+        (19u16, 1u8),
+        (21u16, 4u8),
+        (21u16, 4u8),
+        (21u16, 4u8),
+        (21u16, 4u8),
+    ];
+    static STATE_ITEMS_102: &[(u16, u8)] = &[
+        (53u16, 1u8),
+        (53u16, 1u8),
+        (54u16, 1u8),
+        (54u16, 1u8),
+    ];
+    static STATE_ITEMS_103: &[(u16, u8)] = &[(59u16, 3u8), (59u16, 3u8)];
+    static STATE_ITEMS_104: &[(u16, u8)] = &[(60u16, 1u8), (60u16, 1u8)];
+    static STATE_ITEMS_105: &[(u16, u8)] = &[
+        (55u16, 4u8),
+        (55u16, 4u8),
+        (55u16, 4u8),
+        (55u16, 4u8),
+        (55u16, 4u8),
+        (55u16, 4u8),
+    ];
+    static STATE_ITEMS_106: &[(u16, u8)] = &[
+        (56u16, 5u8),
+        (56u16, 5u8),
+        (56u16, 5u8),
+        (56u16, 5u8),
+        (56u16, 5u8),
+        (56u16, 5u8),
+    ];
+    static STATE_ITEMS_107: &[(u16, u8)] = &[
+        (57u16, 4u8),
+        (57u16, 4u8),
+        (57u16, 4u8),
+        (57u16, 4u8),
+        (57u16, 4u8),
+        (57u16, 4u8),
+        (58u16, 4u8),
+        (58u16, 4u8),
+        (58u16, 4u8),
+        (58u16, 4u8),
+        (58u16, 4u8),
+        (58u16, 4u8),
+    ];
+    static STATE_ITEMS_108: &[(u16, u8)] = &[(53u16, 5u8), (53u16, 5u8)];
+    static STATE_ITEMS_109: &[(u16, u8)] = &[(54u16, 3u8), (54u16, 3u8)];
+    static STATE_ITEMS_110: &[(u16, u8)] = &[
+        (59u16, 1u8),
+        (59u16, 1u8),
+        (61u16, 2u8),
+        (61u16, 2u8),
+        (61u16, 2u8),
+        (61u16, 2u8),
+        (61u16, 2u8),
+        (61u16, 2u8),
+        (62u16, 2u8),
+        (62u16, 2u8),
+        (62u16, 2u8),
+        (62u16, 2u8),
+        (62u16, 2u8),
+        (62u16, 2u8),
+        (63u16, 2u8),
+        (63u16, 2u8),
+        (63u16, 2u8),
+        (63u16, 2u8),
+        (63u16, 2u8),
+        (63u16, 2u8),
+        (64u16, 2u8),
+        (64u16, 2u8),
+        (64u16, 2u8),
+        (64u16, 2u8),
+        (64u16, 2u8),
+        (64u16, 2u8),
+    ];
+    static STATE_ITEMS_111: &[(u16, u8)] = &[(12u16, 3u8), (12u16, 3u8)];
+    static STATE_ITEMS_112: &[(u16, u8)] = &[(13u16, 1u8), (13u16, 1u8)];
+    static STATE_ITEMS_113: &[(u16, u8)] = &[(78u16, 3u8), (78u16, 3u8)];
+    static STATE_ITEMS_114: &[(u16, u8)] = &[(79u16, 1u8), (79u16, 1u8)];
+    static STATE_ITEMS_115: &[(u16, u8)] = &[(53u16, 3u8), (53u16, 3u8)];
+    static STATE_ITEMS_116: &[(u16, u8)] = &[
+        (12u16, 1u8),
+        (12u16, 1u8),
+        (14u16, 2u8),
+        (14u16, 2u8),
+        (14u16, 2u8),
+        (14u16, 2u8),
+        (14u16, 2u8),
+        (14u16, 2u8),
+        (14u16, 2u8),
+        (14u16, 2u8),
+        (14u16, 2u8),
+    ];
+    static STATE_ITEMS_117: &[(u16, u8)] = &[
+        (12u16, 1u8),
+        (12u16, 1u8),
+        (41u16, 5u8),
+        (41u16, 5u8),
+        (41u16, 5u8),
+        (41u16, 5u8),
+    ];
+    static STATE_ITEMS_118: &[(u16, u8)] = &[(12u16, 1u8), (12u16, 1u8), (42u16, 2u8)];
+    static STATE_ITEMS_119: &[(u16, u8)] = &[
+        (78u16, 1u8),
+        (78u16, 1u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+        (80u16, 2u8),
+    ];
+    static STATE_ITEMS_120: &[(u16, u8)] = &[(35u16, 1u8)];
+    static STATE_ITEMS_121: &[(u16, u8)] = &[
+        (35u16, 0u8),
+        (36u16, 0u8),
+        (39u16, 3u8),
+        (39u16, 3u8),
+        (39u16, 3u8),
+        (39u16, 3u8),
+        (40u16, 3u8),
+        (40u16, 3u8),
+        (40u16, 3u8),
+        (40u16, 3u8),
+        (41u16, 3u8),
+        (41u16, 3u8),
+        (41u16, 3u8),
+        (41u16, 3u8),
+        (42u16, 0u8),
+    ];
+    static STATE_ITEMS_122: &[(u16, u8)] = &[(42u16, 3u8)];
+    static STATE_ITEMS_123: &[(u16, u8)] = &[
+        (39u16, 4u8),
+        (39u16, 4u8),
+        (39u16, 4u8),
+        (39u16, 4u8),
+    ];
+    static STATE_ITEMS_124: &[(u16, u8)] = &[(37u16, 3u8), (37u16, 3u8)];
+    static STATE_ITEMS_125: &[(u16, u8)] = &[(38u16, 1u8), (38u16, 1u8)];
+    static STATE_ITEMS_126: &[(u16, u8)] = &[(47u16, 1u8), (47u16, 1u8)];
+    static STATE_ITEMS_127: &[(u16, u8)] = &[(49u16, 4u8), (49u16, 4u8)];
+    static STATE_ITEMS_128: &[(u16, u8)] = &[(53u16, 6u8), (53u16, 6u8)];
+    static STATE_ITEMS_129: &[(u16, u8)] = &[(54u16, 4u8), (54u16, 4u8)];
+    static STATE_ITEMS_130: &[(u16, u8)] = &[
+        (37u16, 1u8),
+        (37u16, 1u8),
+        (39u16, 6u8),
+        (39u16, 6u8),
+        (39u16, 6u8),
+        (39u16, 6u8),
+    ];
+    static STATE_ITEMS_131: &[(u16, u8)] = &[
+        (37u16, 1u8),
+        (37u16, 1u8),
+        (40u16, 5u8),
+        (40u16, 5u8),
+        (40u16, 5u8),
+        (40u16, 5u8),
+    ];
+    static STATE_ITEMS_132: &[(u16, u8)] = &[
+        (15u16, 3u8),
+        (15u16, 3u8),
+        (15u16, 3u8),
+        (15u16, 3u8),
+        (15u16, 3u8),
+        (15u16, 3u8),
+        (15u16, 3u8),
+        (15u16, 3u8),
+        (15u16, 3u8),
+    ];
+    static STATE_ITEMS_133: &[(u16, u8)] = &[
+        (16u16, 2u8),
+        (16u16, 2u8),
+        (16u16, 2u8),
+        (16u16, 2u8),
+        (16u16, 2u8),
+        (16u16, 2u8),
+        (16u16, 2u8),
+        (16u16, 2u8),
+        (16u16, 2u8),
+    ];
+    static STATE_ITEMS_134: &[(u16, u8)] = &[
+        (17u16, 2u8),
+        (17u16, 2u8),
+        (17u16, 2u8),
+        (17u16, 2u8),
+        (17u16, 2u8),
+        (17u16, 2u8),
+        (17u16, 2u8),
+        (17u16, 2u8),
+        (17u16, 2u8),
+    ];
+    static STATE_ITEMS_135: &[(u16, u8)] = &[
+        (18u16, 2u8),
+        (18u16, 2u8),
+        (18u16, 2u8),
+        (18u16, 2u8),
+        (18u16, 2u8),
+        (18u16, 2u8),
+        (18u16, 2u8),
+        (18u16, 2u8),
+        (18u16, 2u8),
+    ];
+    pub static STATE_ITEMS: &[&[(u16, u8)]] = &[
+        STATE_ITEMS_0,
+        STATE_ITEMS_1,
+        STATE_ITEMS_2,
+        STATE_ITEMS_3,
+        STATE_ITEMS_4,
+        STATE_ITEMS_5,
+        STATE_ITEMS_6,
+        STATE_ITEMS_7,
+        STATE_ITEMS_8,
+        STATE_ITEMS_9,
+        STATE_ITEMS_10,
+        STATE_ITEMS_11,
+        STATE_ITEMS_12,
+        STATE_ITEMS_13,
+        STATE_ITEMS_14,
+        STATE_ITEMS_15,
+        STATE_ITEMS_16,
+        STATE_ITEMS_17,
+        STATE_ITEMS_18,
         STATE_ITEMS_19,
         STATE_ITEMS_20,
         STATE_ITEMS_21,
@@ -564,40 +3098,169 @@ mod __table {
         STATE_ITEMS_52,
         STATE_ITEMS_53,
         STATE_ITEMS_54,
+        STATE_ITEMS_55,
+        STATE_ITEMS_56,
+        STATE_ITEMS_57,
+        STATE_ITEMS_58,
+        STATE_ITEMS_59,
+        STATE_ITEMS_60,
+        STATE_ITEMS_61,
+        STATE_ITEMS_62,
+        STATE_ITEMS_63,
+        STATE_ITEMS_64,
+        STATE_ITEMS_65,
+        STATE_ITEMS_66,
+        STATE_ITEMS_67,
+        STATE_ITEMS_68,
+        STATE_ITEMS_69,
+        STATE_ITEMS_70,
+        STATE_ITEMS_71,
+        STATE_ITEMS_72,
+        STATE_ITEMS_73,
+        STATE_ITEMS_74,
+        STATE_ITEMS_75,
+        STATE_ITEMS_76,
+        STATE_ITEMS_77,
+        STATE_ITEMS_78,
+        STATE_ITEMS_79,
+        STATE_ITEMS_80,
+        STATE_ITEMS_81,
+        STATE_ITEMS_82,
+        STATE_ITEMS_83,
+        STATE_ITEMS_84,
+        STATE_ITEMS_85,
+        STATE_ITEMS_86,
+        STATE_ITEMS_87,
+        STATE_ITEMS_88,
+        STATE_ITEMS_89,
+        STATE_ITEMS_90,
+        STATE_ITEMS_91,
+        STATE_ITEMS_92,
+        STATE_ITEMS_93,
+        STATE_ITEMS_94,
+        STATE_ITEMS_95,
+        STATE_ITEMS_96,
+        STATE_ITEMS_97,
+        STATE_ITEMS_98,
+        STATE_ITEMS_99,
+        STATE_ITEMS_100,
+        STATE_ITEMS_101,
+        STATE_ITEMS_102,
+        STATE_ITEMS_103,
+        STATE_ITEMS_104,
+        STATE_ITEMS_105,
+        STATE_ITEMS_106,
+        STATE_ITEMS_107,
+        STATE_ITEMS_108,
+        STATE_ITEMS_109,
+        STATE_ITEMS_110,
+        STATE_ITEMS_111,
+        STATE_ITEMS_112,
+        STATE_ITEMS_113,
+        STATE_ITEMS_114,
+        STATE_ITEMS_115,
+        STATE_ITEMS_116,
+        STATE_ITEMS_117,
+        STATE_ITEMS_118,
+        STATE_ITEMS_119,
+        STATE_ITEMS_120,
+        STATE_ITEMS_121,
+        STATE_ITEMS_122,
+        STATE_ITEMS_123,
+        STATE_ITEMS_124,
+        STATE_ITEMS_125,
+        STATE_ITEMS_126,
+        STATE_ITEMS_127,
+        STATE_ITEMS_128,
+        STATE_ITEMS_129,
+        STATE_ITEMS_130,
+        STATE_ITEMS_131,
+        STATE_ITEMS_132,
+        STATE_ITEMS_133,
+        STATE_ITEMS_134,
+        STATE_ITEMS_135,
     ];
-    static RULE_RHS_0: &[u32] = &[23u32];
-    static RULE_RHS_1: &[u32] = &[32u32, 24u32];
+    static RULE_RHS_0: &[u32] = &[34u32];
+    static RULE_RHS_1: &[u32] = &[51u32, 7u32];
     static RULE_RHS_2: &[u32] = &[];
-    static RULE_RHS_3: &[u32] = &[33u32, 14u32, 25u32];
-    static RULE_RHS_4: &[u32] = &[25u32];
-    static RULE_RHS_5: &[u32] = &[34u32, 28u32];
-    static RULE_RHS_6: &[u32] = &[28u32];
-    static RULE_RHS_7: &[u32] = &[
-        4u32, 1u32, 17u32, 32u32, 5u32, 9u32, 33u32, 10u32, 34u32,
-    ];
-    static RULE_RHS_8: &[u32] = &[7u32, 2u32, 1u32, 17u32];
-    static RULE_RHS_9: &[u32] = &[6u32];
+    static RULE_RHS_3: &[u32] = &[35u32];
+    static RULE_RHS_4: &[u32] = &[];
+    static RULE_RHS_5: &[u32] = &[53u32, 36u32];
+    static RULE_RHS_6: &[u32] = &[];
+    static RULE_RHS_7: &[u32] = &[37u32];
+    static RULE_RHS_8: &[u32] = &[];
+    static RULE_RHS_9: &[u32] = &[55u32, 41u32];
     static RULE_RHS_10: &[u32] = &[];
-    static RULE_RHS_11: &[u32] = &[26u32];
-    static RULE_RHS_12: &[u32] = &[];
-    static RULE_RHS_13: &[u32] = &[27u32];
-    static RULE_RHS_14: &[u32] = &[];
-    static RULE_RHS_15: &[u32] = &[35u32, 1u32, 36u32, 37u32];
-    static RULE_RHS_16: &[u32] = &[13u32, 8u32];
-    static RULE_RHS_17: &[u32] = &[15u32, 3u32];
-    static RULE_RHS_18: &[u32] = &[38u32, 16u32, 29u32];
-    static RULE_RHS_19: &[u32] = &[29u32];
-    static RULE_RHS_20: &[u32] = &[1u32, 15u32, 38u32, 17u32];
-    static RULE_RHS_21: &[u32] = &[39u32, 31u32];
-    static RULE_RHS_22: &[u32] = &[31u32];
-    static RULE_RHS_23: &[u32] = &[39u32, 30u32];
-    static RULE_RHS_24: &[u32] = &[18u32, 1u32];
-    static RULE_RHS_25: &[u32] = &[11u32, 1u32, 22u32, 1u32, 12u32];
-    static RULE_RHS_26: &[u32] = &[1u32, 19u32];
-    static RULE_RHS_27: &[u32] = &[1u32, 20u32];
-    static RULE_RHS_28: &[u32] = &[1u32, 21u32];
-    static RULE_RHS_29: &[u32] = &[1u32];
-    static RULE_RHS_30: &[u32] = &[8u32];
+    static RULE_RHS_11: &[u32] = &[51u32, 52u32, 53u32, 54u32, 55u32];
+    static RULE_RHS_12: &[u32] = &[56u32, 21u32, 1u32];
+    static RULE_RHS_13: &[u32] = &[1u32];
+    static RULE_RHS_14: &[u32] = &[8u32, 56u32, 26u32];
+    static RULE_RHS_15: &[u32] = &[11u32, 2u32, 1u32, 26u32];
+    static RULE_RHS_16: &[u32] = &[12u32, 1u32, 26u32];
+    static RULE_RHS_17: &[u32] = &[13u32, 4u32, 26u32];
+    static RULE_RHS_18: &[u32] = &[14u32, 4u32, 26u32];
+    static RULE_RHS_19: &[u32] = &[57u32, 21u32, 38u32];
+    static RULE_RHS_20: &[u32] = &[38u32];
+    static RULE_RHS_21: &[u32] = &[9u32, 16u32, 51u32, 57u32, 17u32];
+    static RULE_RHS_22: &[u32] = &[58u32, 5u32];
+    static RULE_RHS_23: &[u32] = &[];
+    static RULE_RHS_24: &[u32] = &[10u32];
+    static RULE_RHS_25: &[u32] = &[];
+    static RULE_RHS_26: &[u32] = &[39u32];
+    static RULE_RHS_27: &[u32] = &[];
+    static RULE_RHS_28: &[u32] = &[40u32];
+    static RULE_RHS_29: &[u32] = &[];
+    static RULE_RHS_30: &[u32] = &[58u32, 59u32, 1u32, 60u32, 61u32];
+    static RULE_RHS_31: &[u32] = &[20u32, 15u32];
+    static RULE_RHS_32: &[u32] = &[22u32, 3u32];
+    static RULE_RHS_33: &[u32] = &[62u32, 6u32];
+    static RULE_RHS_34: &[u32] = &[];
+    static RULE_RHS_35: &[u32] = &[42u32];
+    static RULE_RHS_36: &[u32] = &[];
+    static RULE_RHS_37: &[u32] = &[64u32, 23u32, 43u32];
+    static RULE_RHS_38: &[u32] = &[43u32];
+    static RULE_RHS_39: &[u32] = &[58u32, 62u32, 1u32, 63u32, 22u32, 64u32, 26u32];
+    static RULE_RHS_40: &[u32] = &[58u32, 62u32, 1u32, 24u32, 64u32, 26u32];
+    static RULE_RHS_41: &[u32] = &[58u32, 62u32, 1u32, 25u32, 56u32, 26u32];
+    static RULE_RHS_42: &[u32] = &[32u32, 56u32, 33u32];
+    static RULE_RHS_43: &[u32] = &[65u32, 44u32];
+    static RULE_RHS_44: &[u32] = &[44u32];
+    static RULE_RHS_45: &[u32] = &[45u32];
+    static RULE_RHS_46: &[u32] = &[];
+    static RULE_RHS_47: &[u32] = &[46u32];
+    static RULE_RHS_48: &[u32] = &[];
+    static RULE_RHS_49: &[u32] = &[58u32, 65u32, 66u32, 67u32];
+    static RULE_RHS_50: &[u32] = &[1u32, 20u32, 47u32];
+    static RULE_RHS_51: &[u32] = &[47u32];
+    static RULE_RHS_52: &[u32] = &[27u32, 1u32];
+    static RULE_RHS_53: &[u32] = &[10u32, 18u32, 2u32, 21u32, 1u32, 19u32];
+    static RULE_RHS_54: &[u32] = &[10u32, 18u32, 1u32, 19u32];
+    static RULE_RHS_55: &[u32] = &[18u32, 49u32, 31u32, 49u32, 19u32];
+    static RULE_RHS_56: &[u32] = &[18u32, 49u32, 31u32, 31u32, 49u32, 19u32];
+    static RULE_RHS_57: &[u32] = &[18u32, 49u32, 26u32, 49u32, 19u32, 29u32];
+    static RULE_RHS_58: &[u32] = &[18u32, 49u32, 26u32, 49u32, 19u32];
+    static RULE_RHS_59: &[u32] = &[68u32, 23u32, 48u32];
+    static RULE_RHS_60: &[u32] = &[48u32];
+    static RULE_RHS_61: &[u32] = &[18u32, 68u32, 19u32, 28u32];
+    static RULE_RHS_62: &[u32] = &[18u32, 68u32, 19u32, 29u32];
+    static RULE_RHS_63: &[u32] = &[18u32, 68u32, 19u32, 30u32];
+    static RULE_RHS_64: &[u32] = &[18u32, 68u32, 19u32];
+    static RULE_RHS_65: &[u32] = &[49u32, 28u32];
+    static RULE_RHS_66: &[u32] = &[49u32, 29u32];
+    static RULE_RHS_67: &[u32] = &[49u32, 30u32];
+    static RULE_RHS_68: &[u32] = &[49u32];
+    static RULE_RHS_69: &[u32] = &[69u32, 47u32];
+    static RULE_RHS_70: &[u32] = &[47u32];
+    static RULE_RHS_71: &[u32] = &[16u32, 69u32, 17u32];
+    static RULE_RHS_72: &[u32] = &[15u32];
+    static RULE_RHS_73: &[u32] = &[69u32];
+    static RULE_RHS_74: &[u32] = &[50u32];
+    static RULE_RHS_75: &[u32] = &[];
+    static RULE_RHS_76: &[u32] = &[1u32, 70u32];
+    static RULE_RHS_77: &[u32] = &[4u32];
+    static RULE_RHS_78: &[u32] = &[71u32, 21u32, 49u32];
+    static RULE_RHS_79: &[u32] = &[49u32];
+    static RULE_RHS_80: &[u32] = &[32u32, 71u32, 33u32];
     pub static RULE_RHS: &[&[u32]] = &[
         RULE_RHS_0,
         RULE_RHS_1,
@@ -630,49 +3293,131 @@ mod __table {
         RULE_RHS_28,
         RULE_RHS_29,
         RULE_RHS_30,
+        RULE_RHS_31,
+        RULE_RHS_32,
+        RULE_RHS_33,
+        RULE_RHS_34,
+        RULE_RHS_35,
+        RULE_RHS_36,
+        RULE_RHS_37,
+        RULE_RHS_38,
+        RULE_RHS_39,
+        RULE_RHS_40,
+        RULE_RHS_41,
+        RULE_RHS_42,
+        RULE_RHS_43,
+        RULE_RHS_44,
+        RULE_RHS_45,
+        RULE_RHS_46,
+        RULE_RHS_47,
+        RULE_RHS_48,
+        RULE_RHS_49,
+        RULE_RHS_50,
+        RULE_RHS_51,
+        RULE_RHS_52,
+        RULE_RHS_53,
+        RULE_RHS_54,
+        RULE_RHS_55,
+        RULE_RHS_56,
+        RULE_RHS_57,
+        RULE_RHS_58,
+        RULE_RHS_59,
+        RULE_RHS_60,
+        RULE_RHS_61,
+        RULE_RHS_62,
+        RULE_RHS_63,
+        RULE_RHS_64,
+        RULE_RHS_65,
+        RULE_RHS_66,
+        RULE_RHS_67,
+        RULE_RHS_68,
+        RULE_RHS_69,
+        RULE_RHS_70,
+        RULE_RHS_71,
+        RULE_RHS_72,
+        RULE_RHS_73,
+        RULE_RHS_74,
+        RULE_RHS_75,
+        RULE_RHS_76,
+        RULE_RHS_77,
+        RULE_RHS_78,
+        RULE_RHS_79,
+        RULE_RHS_80,
     ];
     pub fn symbol_id(name: &str) -> gazelle::SymbolId {
         match name {
             "IDENT" => gazelle::SymbolId::new(1u32),
             "NUM" => gazelle::SymbolId::new(2u32),
             "REGEX" => gazelle::SymbolId::new(3u32),
-            "KW_START" => gazelle::SymbolId::new(4u32),
-            "KW_TERMINALS" => gazelle::SymbolId::new(5u32),
-            "KW_PREC" => gazelle::SymbolId::new(6u32),
-            "KW_EXPECT" => gazelle::SymbolId::new(7u32),
-            "UNDERSCORE" => gazelle::SymbolId::new(8u32),
-            "LBRACE" => gazelle::SymbolId::new(9u32),
-            "RBRACE" => gazelle::SymbolId::new(10u32),
-            "LPAREN" => gazelle::SymbolId::new(11u32),
-            "RPAREN" => gazelle::SymbolId::new(12u32),
-            "COLON" => gazelle::SymbolId::new(13u32),
-            "COMMA" => gazelle::SymbolId::new(14u32),
-            "EQ" => gazelle::SymbolId::new(15u32),
-            "PIPE" => gazelle::SymbolId::new(16u32),
-            "SEMI" => gazelle::SymbolId::new(17u32),
-            "FAT_ARROW" => gazelle::SymbolId::new(18u32),
-            "QUESTION" => gazelle::SymbolId::new(19u32),
-            "STAR" => gazelle::SymbolId::new(20u32),
-            "PLUS" => gazelle::SymbolId::new(21u32),
-            "PERCENT" => gazelle::SymbolId::new(22u32),
-            "grammar_def" => gazelle::SymbolId::new(23u32),
-            "expect_decl" => gazelle::SymbolId::new(24u32),
-            "terminal_item" => gazelle::SymbolId::new(25u32),
-            "type_annot" => gazelle::SymbolId::new(26u32),
-            "regex_annot" => gazelle::SymbolId::new(27u32),
-            "rule" => gazelle::SymbolId::new(28u32),
-            "alt" => gazelle::SymbolId::new(29u32),
-            "variant" => gazelle::SymbolId::new(30u32),
-            "term" => gazelle::SymbolId::new(31u32),
-            "__expect_decl_star" => gazelle::SymbolId::new(32u32),
-            "__terminal_item_sep_comma" => gazelle::SymbolId::new(33u32),
-            "__rule_plus" => gazelle::SymbolId::new(34u32),
-            "__kw_prec_opt" => gazelle::SymbolId::new(35u32),
-            "__type_annot_opt" => gazelle::SymbolId::new(36u32),
-            "__regex_annot_opt" => gazelle::SymbolId::new(37u32),
-            "__alt_sep_pipe" => gazelle::SymbolId::new(38u32),
-            "__term_plus" => gazelle::SymbolId::new(39u32),
-            "__start" => gazelle::SymbolId::new(40u32),
+            "LITERAL" => gazelle::SymbolId::new(4u32),
+            "DOC" => gazelle::SymbolId::new(5u32),
+            "ATTR" => gazelle::SymbolId::new(6u32),
+            "INNER_ATTR" => gazelle::SymbolId::new(7u32),
+            "KW_START" => gazelle::SymbolId::new(8u32),
+            "KW_TERMINALS" => gazelle::SymbolId::new(9u32),
+            "KW_PREC" => gazelle::SymbolId::new(10u32),
+            "KW_EXPECT" => gazelle::SymbolId::new(11u32),
+            "KW_MODE" => gazelle::SymbolId::new(12u32),
+            "KW_IMPORT" => gazelle::SymbolId::new(13u32),
+            "KW_EXTENDS" => gazelle::SymbolId::new(14u32),
+            "UNDERSCORE" => gazelle::SymbolId::new(15u32),
+            "LBRACE" => gazelle::SymbolId::new(16u32),
+            "RBRACE" => gazelle::SymbolId::new(17u32),
+            "LPAREN" => gazelle::SymbolId::new(18u32),
+            "RPAREN" => gazelle::SymbolId::new(19u32),
+            "COLON" => gazelle::SymbolId::new(20u32),
+            "COMMA" => gazelle::SymbolId::new(21u32),
+            "EQ" => gazelle::SymbolId::new(22u32),
+            "PIPE" => gazelle::SymbolId::new(23u32),
+            "PIPE_EQ" => gazelle::SymbolId::new(24u32),
+            "MINUS_EQ" => gazelle::SymbolId::new(25u32),
+            "SEMI" => gazelle::SymbolId::new(26u32),
+            "FAT_ARROW" => gazelle::SymbolId::new(27u32),
+            "QUESTION" => gazelle::SymbolId::new(28u32),
+            "STAR" => gazelle::SymbolId::new(29u32),
+            "PLUS" => gazelle::SymbolId::new(30u32),
+            "PERCENT" => gazelle::SymbolId::new(31u32),
+            "LT" => gazelle::SymbolId::new(32u32),
+            "GT" => gazelle::SymbolId::new(33u32),
+            "grammar_def" => gazelle::SymbolId::new(34u32),
+            "start_decl" => gazelle::SymbolId::new(35u32),
+            "decl" => gazelle::SymbolId::new(36u32),
+            "terminals_block" => gazelle::SymbolId::new(37u32),
+            "terminal_item" => gazelle::SymbolId::new(38u32),
+            "type_annot" => gazelle::SymbolId::new(39u32),
+            "regex_annot" => gazelle::SymbolId::new(40u32),
+            "rule" => gazelle::SymbolId::new(41u32),
+            "params" => gazelle::SymbolId::new(42u32),
+            "alt" => gazelle::SymbolId::new(43u32),
+            "field" => gazelle::SymbolId::new(44u32),
+            "variant" => gazelle::SymbolId::new(45u32),
+            "prec_annot" => gazelle::SymbolId::new(46u32),
+            "term" => gazelle::SymbolId::new(47u32),
+            "seq" => gazelle::SymbolId::new(48u32),
+            "symbol" => gazelle::SymbolId::new(49u32),
+            "args" => gazelle::SymbolId::new(50u32),
+            "__inner_attr_star" => gazelle::SymbolId::new(51u32),
+            "__start_decl_opt" => gazelle::SymbolId::new(52u32),
+            "__decl_star" => gazelle::SymbolId::new(53u32),
+            "__terminals_block_opt" => gazelle::SymbolId::new(54u32),
+            "__rule_star" => gazelle::SymbolId::new(55u32),
+            "__ident_sep_comma" => gazelle::SymbolId::new(56u32),
+            "__terminal_item_sep_comma" => gazelle::SymbolId::new(57u32),
+            "__doc_star" => gazelle::SymbolId::new(58u32),
+            "__kw_prec_opt" => gazelle::SymbolId::new(59u32),
+            "__type_annot_opt" => gazelle::SymbolId::new(60u32),
+            "__regex_annot_opt" => gazelle::SymbolId::new(61u32),
+            "__attr_star" => gazelle::SymbolId::new(62u32),
+            "__params_opt" => gazelle::SymbolId::new(63u32),
+            "__alt_sep_pipe" => gazelle::SymbolId::new(64u32),
+            "__field_plus" => gazelle::SymbolId::new(65u32),
+            "__variant_opt" => gazelle::SymbolId::new(66u32),
+            "__prec_annot_opt" => gazelle::SymbolId::new(67u32),
+            "__seq_sep_pipe" => gazelle::SymbolId::new(68u32),
+            "__term_plus" => gazelle::SymbolId::new(69u32),
+            "__args_opt" => gazelle::SymbolId::new(70u32),
+            "__symbol_sep_comma" => gazelle::SymbolId::new(71u32),
+            "__start" => gazelle::SymbolId::new(72u32),
             _ => panic!("unknown symbol: {}", name),
         }
     }
@@ -698,10 +3443,17 @@ pub enum Terminal<A: Types> {
     Ident(A::Ident),
     Num(A::Num),
     Regex(A::Regex),
+    Literal(A::Literal),
+    Doc(A::Doc),
+    Attr(A::Attr),
+    InnerAttr(A::InnerAttr),
     KwStart,
     KwTerminals,
     KwPrec,
     KwExpect,
+    KwMode,
+    KwImport,
+    KwExtends,
     Underscore,
     Lbrace,
     Rbrace,
@@ -711,12 +3463,16 @@ pub enum Terminal<A: Types> {
     Comma,
     Eq,
     Pipe,
+    PipeEq,
+    MinusEq,
     Semi,
     FatArrow,
     Question,
     Star,
     Plus,
     Percent,
+    Lt,
+    Gt,
     #[doc(hidden)]
     __Phantom(std::marker::PhantomData<A>),
 }
@@ -727,25 +3483,36 @@ impl<A: Types> Terminal<A> {
             Self::Ident(_) => gazelle::SymbolId::new(1u32),
             Self::Num(_) => gazelle::SymbolId::new(2u32),
             Self::Regex(_) => gazelle::SymbolId::new(3u32),
-            Self::KwStart => gazelle::SymbolId::new(4u32),
-            Self::KwTerminals => gazelle::SymbolId::new(5u32),
-            Self::KwPrec => gazelle::SymbolId::new(6u32),
-            Self::KwExpect => gazelle::SymbolId::new(7u32),
-            Self::Underscore => gazelle::SymbolId::new(8u32),
-            Self::Lbrace => gazelle::SymbolId::new(9u32),
-            Self::Rbrace => gazelle::SymbolId::new(10u32),
-            Self::Lparen => gazelle::SymbolId::new(11u32),
-            Self::Rparen => gazelle::SymbolId::new(12u32),
-            Self::Colon => gazelle::SymbolId::new(13u32),
-            Self::Comma => gazelle::SymbolId::new(14u32),
-            Self::Eq => gazelle::SymbolId::new(15u32),
-            Self::Pipe => gazelle::SymbolId::new(16u32),
-            Self::Semi => gazelle::SymbolId::new(17u32),
-            Self::FatArrow => gazelle::SymbolId::new(18u32),
-            Self::Question => gazelle::SymbolId::new(19u32),
-            Self::Star => gazelle::SymbolId::new(20u32),
-            Self::Plus => gazelle::SymbolId::new(21u32),
-            Self::Percent => gazelle::SymbolId::new(22u32),
+            Self::Literal(_) => gazelle::SymbolId::new(4u32),
+            Self::Doc(_) => gazelle::SymbolId::new(5u32),
+            Self::Attr(_) => gazelle::SymbolId::new(6u32),
+            Self::InnerAttr(_) => gazelle::SymbolId::new(7u32),
+            Self::KwStart => gazelle::SymbolId::new(8u32),
+            Self::KwTerminals => gazelle::SymbolId::new(9u32),
+            Self::KwPrec => gazelle::SymbolId::new(10u32),
+            Self::KwExpect => gazelle::SymbolId::new(11u32),
+            Self::KwMode => gazelle::SymbolId::new(12u32),
+            Self::KwImport => gazelle::SymbolId::new(13u32),
+            Self::KwExtends => gazelle::SymbolId::new(14u32),
+            Self::Underscore => gazelle::SymbolId::new(15u32),
+            Self::Lbrace => gazelle::SymbolId::new(16u32),
+            Self::Rbrace => gazelle::SymbolId::new(17u32),
+            Self::Lparen => gazelle::SymbolId::new(18u32),
+            Self::Rparen => gazelle::SymbolId::new(19u32),
+            Self::Colon => gazelle::SymbolId::new(20u32),
+            Self::Comma => gazelle::SymbolId::new(21u32),
+            Self::Eq => gazelle::SymbolId::new(22u32),
+            Self::Pipe => gazelle::SymbolId::new(23u32),
+            Self::PipeEq => gazelle::SymbolId::new(24u32),
+            Self::MinusEq => gazelle::SymbolId::new(25u32),
+            Self::Semi => gazelle::SymbolId::new(26u32),
+            Self::FatArrow => gazelle::SymbolId::new(27u32),
+            Self::Question => gazelle::SymbolId::new(28u32),
+            Self::Star => gazelle::SymbolId::new(29u32),
+            Self::Plus => gazelle::SymbolId::new(30u32),
+            Self::Percent => gazelle::SymbolId::new(31u32),
+            Self::Lt => gazelle::SymbolId::new(32u32),
+            Self::Gt => gazelle::SymbolId::new(33u32),
             Self::__Phantom(_) => unreachable!(),
         }
     }
@@ -758,10 +3525,17 @@ impl<A: Types> Terminal<A> {
             Self::Ident(_) => gazelle::Token::new(symbol_ids("IDENT")),
             Self::Num(_) => gazelle::Token::new(symbol_ids("NUM")),
             Self::Regex(_) => gazelle::Token::new(symbol_ids("REGEX")),
+            Self::Literal(_) => gazelle::Token::new(symbol_ids("LITERAL")),
+            Self::Doc(_) => gazelle::Token::new(symbol_ids("DOC")),
+            Self::Attr(_) => gazelle::Token::new(symbol_ids("ATTR")),
+            Self::InnerAttr(_) => gazelle::Token::new(symbol_ids("INNER_ATTR")),
             Self::KwStart => gazelle::Token::new(symbol_ids("KW_START")),
             Self::KwTerminals => gazelle::Token::new(symbol_ids("KW_TERMINALS")),
             Self::KwPrec => gazelle::Token::new(symbol_ids("KW_PREC")),
             Self::KwExpect => gazelle::Token::new(symbol_ids("KW_EXPECT")),
+            Self::KwMode => gazelle::Token::new(symbol_ids("KW_MODE")),
+            Self::KwImport => gazelle::Token::new(symbol_ids("KW_IMPORT")),
+            Self::KwExtends => gazelle::Token::new(symbol_ids("KW_EXTENDS")),
             Self::Underscore => gazelle::Token::new(symbol_ids("UNDERSCORE")),
             Self::Lbrace => gazelle::Token::new(symbol_ids("LBRACE")),
             Self::Rbrace => gazelle::Token::new(symbol_ids("RBRACE")),
@@ -771,12 +3545,16 @@ impl<A: Types> Terminal<A> {
             Self::Comma => gazelle::Token::new(symbol_ids("COMMA")),
             Self::Eq => gazelle::Token::new(symbol_ids("EQ")),
             Self::Pipe => gazelle::Token::new(symbol_ids("PIPE")),
+            Self::PipeEq => gazelle::Token::new(symbol_ids("PIPE_EQ")),
+            Self::MinusEq => gazelle::Token::new(symbol_ids("MINUS_EQ")),
             Self::Semi => gazelle::Token::new(symbol_ids("SEMI")),
             Self::FatArrow => gazelle::Token::new(symbol_ids("FAT_ARROW")),
             Self::Question => gazelle::Token::new(symbol_ids("QUESTION")),
             Self::Star => gazelle::Token::new(symbol_ids("STAR")),
             Self::Plus => gazelle::Token::new(symbol_ids("PLUS")),
             Self::Percent => gazelle::Token::new(symbol_ids("PERCENT")),
+            Self::Lt => gazelle::Token::new(symbol_ids("LT")),
+            Self::Gt => gazelle::Token::new(symbol_ids("GT")),
             Self::__Phantom(_) => unreachable!(),
         }
     }
@@ -786,10 +3564,17 @@ impl<A: Types> Terminal<A> {
             Self::Ident(_) => None,
             Self::Num(_) => None,
             Self::Regex(_) => None,
+            Self::Literal(_) => None,
+            Self::Doc(_) => None,
+            Self::Attr(_) => None,
+            Self::InnerAttr(_) => None,
             Self::KwStart => None,
             Self::KwTerminals => None,
             Self::KwPrec => None,
             Self::KwExpect => None,
+            Self::KwMode => None,
+            Self::KwImport => None,
+            Self::KwExtends => None,
             Self::Underscore => None,
             Self::Lbrace => None,
             Self::Rbrace => None,
@@ -799,56 +3584,136 @@ impl<A: Types> Terminal<A> {
             Self::Comma => None,
             Self::Eq => None,
             Self::Pipe => None,
+            Self::PipeEq => None,
+            Self::MinusEq => None,
             Self::Semi => None,
             Self::FatArrow => None,
             Self::Question => None,
             Self::Star => None,
             Self::Plus => None,
             Self::Percent => None,
+            Self::Lt => None,
+            Self::Gt => None,
             Self::__Phantom(_) => unreachable!(),
         }
     }
 }
 pub enum Alt<A: Types> {
-    Alt(Vec<A::Term>, A::Variant),
+    /// `DOC* field+ variant? prec_annot?`
+    Alt(Vec<A::Doc>, Vec<A::Field>, Option<A::Variant>, Option<A::PrecAnnot>),
 }
 impl<A: Types> std::fmt::Debug for Alt<A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Alt(f0, f1) => f.debug_tuple("Alt").field(f0).field(f1).finish(),
+            Self::Alt(f0, f1, f2, f3) => {
+                f.debug_tuple("Alt").field(f0).field(f1).field(f2).field(f3).finish()
+            }
+        }
+    }
+}
+pub enum Args<A: Types> {
+    /// `LT (symbol % COMMA) GT`
+    Args(Vec<A::Symbol>),
+}
+impl<A: Types> std::fmt::Debug for Args<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Args(f0) => f.debug_tuple("Args").field(f0).finish(),
         }
     }
 }
-pub enum ExpectDecl<A: Types> {
+pub enum Decl<A: Types> {
+    /// `KW_EXPECT NUM IDENT SEMI`
     ExpectDecl(A::Num, A::Ident),
+    /// `KW_MODE IDENT SEMI`
+    ModeDecl(A::Ident),
+    /// `KW_IMPORT LITERAL SEMI`
+    ImportDecl(A::Literal),
+    /// `KW_EXTENDS LITERAL SEMI`
+    ExtendsDecl(A::Literal),
 }
-impl<A: Types> std::fmt::Debug for ExpectDecl<A> {
+impl<A: Types> std::fmt::Debug for Decl<A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::ExpectDecl(f0, f1) => {
                 f.debug_tuple("ExpectDecl").field(f0).field(f1).finish()
             }
+            Self::ModeDecl(f0) => f.debug_tuple("ModeDecl").field(f0).finish(),
+            Self::ImportDecl(f0) => f.debug_tuple("ImportDecl").field(f0).finish(),
+            Self::ExtendsDecl(f0) => f.debug_tuple("ExtendsDecl").field(f0).finish(),
+        }
+    }
+}
+pub enum Field<A: Types> {
+    /// `IDENT COLON term`
+    Labeled(A::Ident, A::Term),
+    /// `term`
+    Unlabeled(A::Term),
+}
+impl<A: Types> std::fmt::Debug for Field<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Labeled(f0, f1) => {
+                f.debug_tuple("Labeled").field(f0).field(f1).finish()
+            }
+            Self::Unlabeled(f0) => f.debug_tuple("Unlabeled").field(f0).finish(),
         }
     }
 }
 pub enum GrammarDef<A: Types> {
-    GrammarDef(A::Ident, Vec<A::ExpectDecl>, Vec<A::TerminalItem>, Vec<A::Rule>),
+    /// `INNER_ATTR* start_decl? decl* terminals_block? rule*`
+    GrammarDef(
+        Vec<A::InnerAttr>,
+        Option<A::StartDecl>,
+        Vec<A::Decl>,
+        Option<A::TerminalsBlock>,
+        Vec<A::Rule>,
+    ),
 }
 impl<A: Types> std::fmt::Debug for GrammarDef<A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::GrammarDef(f0, f1, f2, f3) => {
+            Self::GrammarDef(f0, f1, f2, f3, f4) => {
                 f.debug_tuple("GrammarDef")
                     .field(f0)
                     .field(f1)
                     .field(f2)
                     .field(f3)
+                    .field(f4)
                     .finish()
             }
         }
     }
 }
+pub enum Params<A: Types> {
+    /// `LT (IDENT % COMMA) GT`
+    Params(Vec<A::Ident>),
+}
+impl<A: Types> std::fmt::Debug for Params<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Params(f0) => f.debug_tuple("Params").field(f0).finish(),
+        }
+    }
+}
+pub enum PrecAnnot<A: Types> {
+    /// `KW_PREC LPAREN NUM COMMA IDENT RPAREN`
+    PrecFixed(A::Num, A::Ident),
+    /// `KW_PREC LPAREN IDENT RPAREN`
+    PrecSymbol(A::Ident),
+}
+impl<A: Types> std::fmt::Debug for PrecAnnot<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PrecFixed(f0, f1) => {
+                f.debug_tuple("PrecFixed").field(f0).field(f1).finish()
+            }
+            Self::PrecSymbol(f0) => f.debug_tuple("PrecSymbol").field(f0).finish(),
+        }
+    }
+}
 pub enum RegexAnnot<A: Types> {
+    /// `EQ REGEX`
     RegexAnnot(A::Regex),
 }
 impl<A: Types> std::fmt::Debug for RegexAnnot<A> {
@@ -859,53 +3724,176 @@ impl<A: Types> std::fmt::Debug for RegexAnnot<A> {
     }
 }
 pub enum Rule<A: Types> {
-    Rule(A::Ident, Vec<A::Alt>),
+    /// `DOC* ATTR* IDENT params? EQ (alt % PIPE) SEMI`
+    Rule(Vec<A::Doc>, Vec<A::Attr>, A::Ident, Option<A::Params>, Vec<A::Alt>),
+    /// `DOC* ATTR* IDENT PIPE_EQ (alt % PIPE) SEMI`
+    ExtendRule(Vec<A::Doc>, Vec<A::Attr>, A::Ident, Vec<A::Alt>),
+    /// `DOC* ATTR* IDENT MINUS_EQ (IDENT % COMMA) SEMI`
+    RemoveAlts(Vec<A::Doc>, Vec<A::Attr>, A::Ident, Vec<A::Ident>),
 }
 impl<A: Types> std::fmt::Debug for Rule<A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Rule(f0, f1) => f.debug_tuple("Rule").field(f0).field(f1).finish(),
+            Self::Rule(f0, f1, f2, f3, f4) => {
+                f.debug_tuple("Rule")
+                    .field(f0)
+                    .field(f1)
+                    .field(f2)
+                    .field(f3)
+                    .field(f4)
+                    .finish()
+            }
+            Self::ExtendRule(f0, f1, f2, f3) => {
+                f.debug_tuple("ExtendRule")
+                    .field(f0)
+                    .field(f1)
+                    .field(f2)
+                    .field(f3)
+                    .finish()
+            }
+            Self::RemoveAlts(f0, f1, f2, f3) => {
+                f.debug_tuple("RemoveAlts")
+                    .field(f0)
+                    .field(f1)
+                    .field(f2)
+                    .field(f3)
+                    .finish()
+            }
+        }
+    }
+}
+pub enum Seq<A: Types> {
+    /// `term+`
+    Seq(Vec<A::Term>),
+}
+impl<A: Types> std::fmt::Debug for Seq<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Seq(f0) => f.debug_tuple("Seq").field(f0).finish(),
+        }
+    }
+}
+pub enum StartDecl<A: Types> {
+    /// `KW_START (IDENT % COMMA) SEMI`
+    StartDecl(Vec<A::Ident>),
+}
+impl<A: Types> std::fmt::Debug for StartDecl<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StartDecl(f0) => f.debug_tuple("StartDecl").field(f0).finish(),
+        }
+    }
+}
+pub enum Symbol<A: Types> {
+    /// `IDENT args?`
+    Symbol(A::Ident, Option<A::Args>),
+    /// `LITERAL`
+    Literal(A::Literal),
+}
+impl<A: Types> std::fmt::Debug for Symbol<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Symbol(f0, f1) => f.debug_tuple("Symbol").field(f0).field(f1).finish(),
+            Self::Literal(f0) => f.debug_tuple("Literal").field(f0).finish(),
         }
     }
 }
 pub enum Term<A: Types> {
-    SymSep(A::Ident, A::Ident),
-    SymOpt(A::Ident),
-    SymStar(A::Ident),
-    SymPlus(A::Ident),
-    SymPlain(A::Ident),
+    /// `LPAREN symbol PERCENT symbol RPAREN`
+    SymSep(A::Symbol, A::Symbol),
+    /// `LPAREN symbol PERCENT PERCENT symbol RPAREN`
+    SymSepTrailing(A::Symbol, A::Symbol),
+    /// `LPAREN symbol SEMI symbol RPAREN STAR`
+    SymTermStar(A::Symbol, A::Symbol),
+    /// `LPAREN symbol SEMI symbol RPAREN`
+    SymTerm(A::Symbol, A::Symbol),
+    /// `LPAREN (seq % PIPE) RPAREN QUESTION`
+    GroupOpt(Vec<A::Seq>),
+    /// `LPAREN (seq % PIPE) RPAREN STAR`
+    GroupStar(Vec<A::Seq>),
+    /// `LPAREN (seq % PIPE) RPAREN PLUS`
+    GroupPlus(Vec<A::Seq>),
+    /// `LPAREN (seq % PIPE) RPAREN`
+    Group(Vec<A::Seq>),
+    /// `symbol QUESTION`
+    SymOpt(A::Symbol),
+    /// `symbol STAR`
+    SymStar(A::Symbol),
+    /// `symbol PLUS`
+    SymPlus(A::Symbol),
+    /// `symbol`
+    SymPlain(A::Symbol),
+    /// `LBRACE term+ RBRACE`
+    Permutation(Vec<A::Term>),
+    /// `UNDERSCORE`
     SymEmpty,
 }
 impl<A: Types> std::fmt::Debug for Term<A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::SymSep(f0, f1) => f.debug_tuple("SymSep").field(f0).field(f1).finish(),
+            Self::SymSepTrailing(f0, f1) => {
+                f.debug_tuple("SymSepTrailing").field(f0).field(f1).finish()
+            }
+            Self::SymTermStar(f0, f1) => {
+                f.debug_tuple("SymTermStar").field(f0).field(f1).finish()
+            }
+            Self::SymTerm(f0, f1) => {
+                f.debug_tuple("SymTerm").field(f0).field(f1).finish()
+            }
+            Self::GroupOpt(f0) => f.debug_tuple("GroupOpt").field(f0).finish(),
+            Self::GroupStar(f0) => f.debug_tuple("GroupStar").field(f0).finish(),
+            Self::GroupPlus(f0) => f.debug_tuple("GroupPlus").field(f0).finish(),
+            Self::Group(f0) => f.debug_tuple("Group").field(f0).finish(),
             Self::SymOpt(f0) => f.debug_tuple("SymOpt").field(f0).finish(),
             Self::SymStar(f0) => f.debug_tuple("SymStar").field(f0).finish(),
             Self::SymPlus(f0) => f.debug_tuple("SymPlus").field(f0).finish(),
             Self::SymPlain(f0) => f.debug_tuple("SymPlain").field(f0).finish(),
+            Self::Permutation(f0) => f.debug_tuple("Permutation").field(f0).finish(),
             Self::SymEmpty => f.write_str("SymEmpty"),
         }
     }
 }
 pub enum TerminalItem<A: Types> {
-    TerminalItem(Option<()>, A::Ident, Option<A::TypeAnnot>, Option<A::RegexAnnot>),
+    /// `DOC* KW_PREC? IDENT type_annot? regex_annot?`
+    TerminalItem(
+        Vec<A::Doc>,
+        Option<()>,
+        A::Ident,
+        Option<A::TypeAnnot>,
+        Option<A::RegexAnnot>,
+    ),
 }
 impl<A: Types> std::fmt::Debug for TerminalItem<A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::TerminalItem(f0, f1, f2, f3) => {
+            Self::TerminalItem(f0, f1, f2, f3, f4) => {
                 f.debug_tuple("TerminalItem")
                     .field(f0)
                     .field(f1)
                     .field(f2)
                     .field(f3)
+                    .field(f4)
                     .finish()
             }
         }
     }
 }
+pub enum TerminalsBlock<A: Types> {
+    /// `KW_TERMINALS LBRACE INNER_ATTR* (terminal_item % COMMA) RBRACE`
+    TerminalsBlock(Vec<A::InnerAttr>, Vec<A::TerminalItem>),
+}
+impl<A: Types> std::fmt::Debug for TerminalsBlock<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TerminalsBlock(f0, f1) => {
+                f.debug_tuple("TerminalsBlock").field(f0).field(f1).finish()
+            }
+        }
+    }
+}
 pub enum TypeAnnot<A: Types> {
+    /// `COLON UNDERSCORE`
     TypeAnnot,
     #[doc(hidden)]
     _Phantom(std::convert::Infallible, std::marker::PhantomData<A>),
@@ -918,31 +3906,33 @@ impl<A: Types> std::fmt::Debug for TypeAnnot<A> {
         }
     }
 }
-pub enum Variant<A: Types> {
-    Variant(A::Ident),
-}
-impl<A: Types> std::fmt::Debug for Variant<A> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::Variant(f0) => f.debug_tuple("Variant").field(f0).finish(),
-        }
-    }
-}
 /// Associated types for parser symbols.
 pub trait Types: Sized {
     type Error: From<gazelle::ParseError>;
     type Ident: std::fmt::Debug;
     type Num: std::fmt::Debug;
     type Regex: std::fmt::Debug;
+    type Literal: std::fmt::Debug;
+    type Doc: std::fmt::Debug;
+    type Attr: std::fmt::Debug;
+    type InnerAttr: std::fmt::Debug;
     type GrammarDef: std::fmt::Debug;
-    type ExpectDecl: std::fmt::Debug;
+    type StartDecl: std::fmt::Debug;
+    type Decl: std::fmt::Debug;
+    type TerminalsBlock: std::fmt::Debug;
     type TerminalItem: std::fmt::Debug;
     type TypeAnnot: std::fmt::Debug;
     type RegexAnnot: std::fmt::Debug;
     type Rule: std::fmt::Debug;
+    type Params: std::fmt::Debug;
     type Alt: std::fmt::Debug;
-    type Variant: std::fmt::Debug;
+    type Field: std::fmt::Debug;
+    type Variant: std::fmt::Debug + std::convert::From<Self::Ident>;
+    type PrecAnnot: std::fmt::Debug;
     type Term: std::fmt::Debug;
+    type Seq: std::fmt::Debug;
+    type Symbol: std::fmt::Debug;
+    type Args: std::fmt::Debug;
     /// Called before each reduction with the token range `[start..end)`.
     /// Override to track source spans. Default is no-op.
     #[allow(unused_variables)]
@@ -952,8 +3942,16 @@ impl<A: Types> gazelle::AstNode for GrammarDef<A> {
     type Output = A::GrammarDef;
     type Error = A::Error;
 }
-impl<A: Types> gazelle::AstNode for ExpectDecl<A> {
-    type Output = A::ExpectDecl;
+impl<A: Types> gazelle::AstNode for StartDecl<A> {
+    type Output = A::StartDecl;
+    type Error = A::Error;
+}
+impl<A: Types> gazelle::AstNode for Decl<A> {
+    type Output = A::Decl;
+    type Error = A::Error;
+}
+impl<A: Types> gazelle::AstNode for TerminalsBlock<A> {
+    type Output = A::TerminalsBlock;
     type Error = A::Error;
 }
 impl<A: Types> gazelle::AstNode for TerminalItem<A> {
@@ -972,40 +3970,85 @@ impl<A: Types> gazelle::AstNode for Rule<A> {
     type Output = A::Rule;
     type Error = A::Error;
 }
+impl<A: Types> gazelle::AstNode for Params<A> {
+    type Output = A::Params;
+    type Error = A::Error;
+}
 impl<A: Types> gazelle::AstNode for Alt<A> {
     type Output = A::Alt;
     type Error = A::Error;
 }
-impl<A: Types> gazelle::AstNode for Variant<A> {
-    type Output = A::Variant;
+impl<A: Types> gazelle::AstNode for Field<A> {
+    type Output = A::Field;
+    type Error = A::Error;
+}
+impl<A: Types> gazelle::AstNode for PrecAnnot<A> {
+    type Output = A::PrecAnnot;
     type Error = A::Error;
 }
 impl<A: Types> gazelle::AstNode for Term<A> {
     type Output = A::Term;
     type Error = A::Error;
 }
+impl<A: Types> gazelle::AstNode for Seq<A> {
+    type Output = A::Seq;
+    type Error = A::Error;
+}
+impl<A: Types> gazelle::AstNode for Symbol<A> {
+    type Output = A::Symbol;
+    type Error = A::Error;
+}
+impl<A: Types> gazelle::AstNode for Args<A> {
+    type Output = A::Args;
+    type Error = A::Error;
+}
 #[doc(hidden)]
 union __Value<A: Types> {
     __ident: std::mem::ManuallyDrop<A::Ident>,
     __num: std::mem::ManuallyDrop<A::Num>,
     __regex: std::mem::ManuallyDrop<A::Regex>,
+    __literal: std::mem::ManuallyDrop<A::Literal>,
+    __doc: std::mem::ManuallyDrop<A::Doc>,
+    __attr: std::mem::ManuallyDrop<A::Attr>,
+    __inner_attr: std::mem::ManuallyDrop<A::InnerAttr>,
     __grammar_def: std::mem::ManuallyDrop<A::GrammarDef>,
-    __expect_decl: std::mem::ManuallyDrop<A::ExpectDecl>,
+    __start_decl: std::mem::ManuallyDrop<A::StartDecl>,
+    __decl: std::mem::ManuallyDrop<A::Decl>,
+    __terminals_block: std::mem::ManuallyDrop<A::TerminalsBlock>,
     __terminal_item: std::mem::ManuallyDrop<A::TerminalItem>,
     __type_annot: std::mem::ManuallyDrop<A::TypeAnnot>,
     __regex_annot: std::mem::ManuallyDrop<A::RegexAnnot>,
     __rule: std::mem::ManuallyDrop<A::Rule>,
+    __params: std::mem::ManuallyDrop<A::Params>,
     __alt: std::mem::ManuallyDrop<A::Alt>,
+    __field: std::mem::ManuallyDrop<A::Field>,
     __variant: std::mem::ManuallyDrop<A::Variant>,
+    __prec_annot: std::mem::ManuallyDrop<A::PrecAnnot>,
     __term: std::mem::ManuallyDrop<A::Term>,
-    ____expect_decl_star: std::mem::ManuallyDrop<Vec<A::ExpectDecl>>,
+    __seq: std::mem::ManuallyDrop<A::Seq>,
+    __symbol: std::mem::ManuallyDrop<A::Symbol>,
+    __args: std::mem::ManuallyDrop<A::Args>,
+    ____inner_attr_star: std::mem::ManuallyDrop<Vec<A::InnerAttr>>,
+    ____start_decl_opt: std::mem::ManuallyDrop<Option<A::StartDecl>>,
+    ____decl_star: std::mem::ManuallyDrop<Vec<A::Decl>>,
+    ____terminals_block_opt: std::mem::ManuallyDrop<Option<A::TerminalsBlock>>,
+    ____rule_star: std::mem::ManuallyDrop<Vec<A::Rule>>,
+    ____ident_sep_comma: std::mem::ManuallyDrop<Vec<A::Ident>>,
     ____terminal_item_sep_comma: std::mem::ManuallyDrop<Vec<A::TerminalItem>>,
-    ____rule_plus: std::mem::ManuallyDrop<Vec<A::Rule>>,
+    ____doc_star: std::mem::ManuallyDrop<Vec<A::Doc>>,
     ____kw_prec_opt: std::mem::ManuallyDrop<Option<()>>,
     ____type_annot_opt: std::mem::ManuallyDrop<Option<A::TypeAnnot>>,
     ____regex_annot_opt: std::mem::ManuallyDrop<Option<A::RegexAnnot>>,
+    ____attr_star: std::mem::ManuallyDrop<Vec<A::Attr>>,
+    ____params_opt: std::mem::ManuallyDrop<Option<A::Params>>,
     ____alt_sep_pipe: std::mem::ManuallyDrop<Vec<A::Alt>>,
+    ____field_plus: std::mem::ManuallyDrop<Vec<A::Field>>,
+    ____variant_opt: std::mem::ManuallyDrop<Option<A::Variant>>,
+    ____prec_annot_opt: std::mem::ManuallyDrop<Option<A::PrecAnnot>>,
+    ____seq_sep_pipe: std::mem::ManuallyDrop<Vec<A::Seq>>,
     ____term_plus: std::mem::ManuallyDrop<Vec<A::Term>>,
+    ____args_opt: std::mem::ManuallyDrop<Option<A::Args>>,
+    ____symbol_sep_comma: std::mem::ManuallyDrop<Vec<A::Symbol>>,
     __unit: (),
     __phantom: std::mem::ManuallyDrop<std::marker::PhantomData<A>>,
 }
@@ -1058,63 +4101,144 @@ impl<A: Types> Parser<A> {
                     3u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.__regex);
                     }
-                    23u32 => {
+                    4u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__literal);
+                    }
+                    5u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__doc);
+                    }
+                    6u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__attr);
+                    }
+                    7u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__inner_attr);
+                    }
+                    34u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.__grammar_def);
                     }
-                    24u32 => {
-                        std::mem::ManuallyDrop::into_inner(union_val.__expect_decl);
+                    35u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__start_decl);
+                    }
+                    36u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__decl);
+                    }
+                    37u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__terminals_block);
                     }
-                    25u32 => {
+                    38u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.__terminal_item);
                     }
-                    26u32 => {
+                    39u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.__type_annot);
                     }
-                    27u32 => {
+                    40u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.__regex_annot);
                     }
-                    28u32 => {
+                    41u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.__rule);
                     }
-                    29u32 => {
+                    42u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__params);
+                    }
+                    43u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.__alt);
                     }
-                    30u32 => {
+                    44u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__field);
+                    }
+                    45u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.__variant);
                     }
-                    31u32 => {
+                    46u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__prec_annot);
+                    }
+                    47u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.__term);
                     }
-                    32u32 => {
+                    48u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__seq);
+                    }
+                    49u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__symbol);
+                    }
+                    50u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.__args);
+                    }
+                    51u32 => {
                         std::mem::ManuallyDrop::into_inner(
-                            union_val.____expect_decl_star,
+                            union_val.____inner_attr_star,
                         );
                     }
-                    33u32 => {
+                    52u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____start_decl_opt);
+                    }
+                    53u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____decl_star);
+                    }
+                    54u32 => {
                         std::mem::ManuallyDrop::into_inner(
-                            union_val.____terminal_item_sep_comma,
+                            union_val.____terminals_block_opt,
                         );
                     }
-                    34u32 => {
-                        std::mem::ManuallyDrop::into_inner(union_val.____rule_plus);
+                    55u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____rule_star);
                     }
-                    35u32 => {
-                        std::mem::ManuallyDrop::into_inner(union_val.____kw_prec_opt);
+                    56u32 => {
+                        std::mem::ManuallyDrop::into_inner(
+                            union_val.____ident_sep_comma,
+                        );
                     }
-                    36u32 => {
+                    57u32 => {
+                        std::mem::ManuallyDrop::into_inner(
+                            union_val.____terminal_item_sep_comma,
+                        );
+                    }
+                    58u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____doc_star);
+                    }
+                    59u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____kw_prec_opt);
+                    }
+                    60u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.____type_annot_opt);
                     }
-                    37u32 => {
+                    61u32 => {
                         std::mem::ManuallyDrop::into_inner(
                             union_val.____regex_annot_opt,
                         );
                     }
-                    38u32 => {
+                    62u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____attr_star);
+                    }
+                    63u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____params_opt);
+                    }
+                    64u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.____alt_sep_pipe);
                     }
-                    39u32 => {
+                    65u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____field_plus);
+                    }
+                    66u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____variant_opt);
+                    }
+                    67u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____prec_annot_opt);
+                    }
+                    68u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____seq_sep_pipe);
+                    }
+                    69u32 => {
                         std::mem::ManuallyDrop::into_inner(union_val.____term_plus);
                     }
+                    70u32 => {
+                        std::mem::ManuallyDrop::into_inner(union_val.____args_opt);
+                    }
+                    71u32 => {
+                        std::mem::ManuallyDrop::into_inner(
+                            union_val.____symbol_sep_comma,
+                        );
+                    }
                     _ => {}
                 }
             }
@@ -1123,11 +4247,14 @@ impl<A: Types> Parser<A> {
 }
 #[allow(clippy::result_large_err)]
 impl<
-    A: Types + gazelle::Action<GrammarDef<A>> + gazelle::Action<ExpectDecl<A>>
+    A: Types + gazelle::Action<GrammarDef<A>> + gazelle::Action<StartDecl<A>>
+        + gazelle::Action<Decl<A>> + gazelle::Action<TerminalsBlock<A>>
         + gazelle::Action<TerminalItem<A>> + gazelle::Action<TypeAnnot<A>>
         + gazelle::Action<RegexAnnot<A>> + gazelle::Action<Rule<A>>
-        + gazelle::Action<Alt<A>> + gazelle::Action<Variant<A>>
-        + gazelle::Action<Term<A>>,
+        + gazelle::Action<Params<A>> + gazelle::Action<Alt<A>>
+        + gazelle::Action<Field<A>> + gazelle::Action<PrecAnnot<A>>
+        + gazelle::Action<Term<A>> + gazelle::Action<Seq<A>> + gazelle::Action<Symbol<A>>
+        + gazelle::Action<Args<A>>,
 > Parser<A> {
     /// Push a terminal, performing any reductions.
     pub fn push(
@@ -1172,6 +4299,30 @@ impl<
                         __regex: std::mem::ManuallyDrop::new(v),
                     });
             }
+            Terminal::Literal(v) => {
+                self.value_stack
+                    .push(__Value {
+                        __literal: std::mem::ManuallyDrop::new(v),
+                    });
+            }
+            Terminal::Doc(v) => {
+                self.value_stack
+                    .push(__Value {
+                        __doc: std::mem::ManuallyDrop::new(v),
+                    });
+            }
+            Terminal::Attr(v) => {
+                self.value_stack
+                    .push(__Value {
+                        __attr: std::mem::ManuallyDrop::new(v),
+                    });
+            }
+            Terminal::InnerAttr(v) => {
+                self.value_stack
+                    .push(__Value {
+                        __inner_attr: std::mem::ManuallyDrop::new(v),
+                    });
+            }
             Terminal::KwStart => {
                 self.value_stack.push(__Value { __unit: () });
             }
@@ -1184,6 +4335,15 @@ impl<
             Terminal::KwExpect => {
                 self.value_stack.push(__Value { __unit: () });
             }
+            Terminal::KwMode => {
+                self.value_stack.push(__Value { __unit: () });
+            }
+            Terminal::KwImport => {
+                self.value_stack.push(__Value { __unit: () });
+            }
+            Terminal::KwExtends => {
+                self.value_stack.push(__Value { __unit: () });
+            }
             Terminal::Underscore => {
                 self.value_stack.push(__Value { __unit: () });
             }
@@ -1211,6 +4371,12 @@ impl<
             Terminal::Pipe => {
                 self.value_stack.push(__Value { __unit: () });
             }
+            Terminal::PipeEq => {
+                self.value_stack.push(__Value { __unit: () });
+            }
+            Terminal::MinusEq => {
+                self.value_stack.push(__Value { __unit: () });
+            }
             Terminal::Semi => {
                 self.value_stack.push(__Value { __unit: () });
             }
@@ -1229,6 +4395,12 @@ impl<
             Terminal::Percent => {
                 self.value_stack.push(__Value { __unit: () });
             }
+            Terminal::Lt => {
+                self.value_stack.push(__Value { __unit: () });
+            }
+            Terminal::Gt => {
+                self.value_stack.push(__Value { __unit: () });
+            }
             Terminal::__Phantom(_) => unreachable!(),
         }
         Ok(())
@@ -1272,283 +4444,989 @@ impl<
             0usize => {
                 let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__expect_decl,
+                        self.value_stack.pop().unwrap().__inner_attr,
                     )
                 };
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____expect_decl_star,
+                        self.value_stack.pop().unwrap().____inner_attr_star,
                     )
                 };
-                {
-                    let mut v0 = v0;
-                    v0.push(v1);
-                    __Value {
-                        ____expect_decl_star: std::mem::ManuallyDrop::new(v0),
-                    }
+                __Value {
+                    ____inner_attr_star: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v1);
+                        v0
+                    }),
                 }
             }
             1usize => {
                 __Value {
-                    ____expect_decl_star: std::mem::ManuallyDrop::new(Vec::new()),
+                    ____inner_attr_star: std::mem::ManuallyDrop::new(Vec::new()),
+                }
+            }
+            2usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__start_decl,
+                    )
+                };
+                __Value {
+                    ____start_decl_opt: std::mem::ManuallyDrop::new(Some(v0)),
+                }
+            }
+            3usize => {
+                __Value {
+                    ____start_decl_opt: std::mem::ManuallyDrop::new(None),
+                }
+            }
+            4usize => {
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__decl,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____decl_star,
+                    )
+                };
+                __Value {
+                    ____decl_star: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v1);
+                        v0
+                    }),
+                }
+            }
+            5usize => {
+                __Value {
+                    ____decl_star: std::mem::ManuallyDrop::new(Vec::new()),
+                }
+            }
+            6usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__terminals_block,
+                    )
+                };
+                __Value {
+                    ____terminals_block_opt: std::mem::ManuallyDrop::new(Some(v0)),
+                }
+            }
+            7usize => {
+                __Value {
+                    ____terminals_block_opt: std::mem::ManuallyDrop::new(None),
+                }
+            }
+            8usize => {
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__rule,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____rule_star,
+                    )
+                };
+                __Value {
+                    ____rule_star: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v1);
+                        v0
+                    }),
+                }
+            }
+            9usize => {
+                __Value {
+                    ____rule_star: std::mem::ManuallyDrop::new(Vec::new()),
+                }
+            }
+            10usize => {
+                let v4 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____rule_star,
+                    )
+                };
+                let v3 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____terminals_block_opt,
+                    )
+                };
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____decl_star,
+                    )
+                };
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____start_decl_opt,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____inner_attr_star,
+                    )
+                };
+                __Value {
+                    __grammar_def: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(
+                            actions,
+                            GrammarDef::GrammarDef(v0, v1, v2, v3, v4),
+                        )?,
+                    ),
+                }
+            }
+            11usize => {
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__ident,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____ident_sep_comma,
+                    )
+                };
+                __Value {
+                    ____ident_sep_comma: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v2);
+                        v0
+                    }),
+                }
+            }
+            12usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__ident,
+                    )
+                };
+                __Value {
+                    ____ident_sep_comma: std::mem::ManuallyDrop::new(vec![v0]),
+                }
+            }
+            13usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____ident_sep_comma,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __start_decl: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, StartDecl::StartDecl(v1))?,
+                    ),
+                }
+            }
+            14usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__ident,
+                    )
+                };
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__num,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __decl: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Decl::ExpectDecl(v1, v2))?,
+                    ),
+                }
+            }
+            15usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__ident,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __decl: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Decl::ModeDecl(v1))?,
+                    ),
+                }
+            }
+            16usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__literal,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __decl: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Decl::ImportDecl(v1))?,
+                    ),
+                }
+            }
+            17usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__literal,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __decl: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Decl::ExtendsDecl(v1))?,
+                    ),
+                }
+            }
+            18usize => {
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__terminal_item,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____terminal_item_sep_comma,
+                    )
+                };
+                __Value {
+                    ____terminal_item_sep_comma: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v2);
+                        v0
+                    }),
+                }
+            }
+            19usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__terminal_item,
+                    )
+                };
+                __Value {
+                    ____terminal_item_sep_comma: std::mem::ManuallyDrop::new(vec![v0]),
+                }
+            }
+            20usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v3 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____terminal_item_sep_comma,
+                    )
+                };
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____inner_attr_star,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __terminals_block: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(
+                            actions,
+                            TerminalsBlock::TerminalsBlock(v2, v3),
+                        )?,
+                    ),
+                }
+            }
+            21usize => {
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__doc,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____doc_star,
+                    )
+                };
+                __Value {
+                    ____doc_star: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v1);
+                        v0
+                    }),
+                }
+            }
+            22usize => {
+                __Value {
+                    ____doc_star: std::mem::ManuallyDrop::new(Vec::new()),
+                }
+            }
+            23usize => {
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    ____kw_prec_opt: std::mem::ManuallyDrop::new(Some(())),
+                }
+            }
+            24usize => {
+                __Value {
+                    ____kw_prec_opt: std::mem::ManuallyDrop::new(None),
+                }
+            }
+            25usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__type_annot,
+                    )
+                };
+                __Value {
+                    ____type_annot_opt: std::mem::ManuallyDrop::new(Some(v0)),
+                }
+            }
+            26usize => {
+                __Value {
+                    ____type_annot_opt: std::mem::ManuallyDrop::new(None),
+                }
+            }
+            27usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__regex_annot,
+                    )
+                };
+                __Value {
+                    ____regex_annot_opt: std::mem::ManuallyDrop::new(Some(v0)),
+                }
+            }
+            28usize => {
+                __Value {
+                    ____regex_annot_opt: std::mem::ManuallyDrop::new(None),
+                }
+            }
+            29usize => {
+                let v4 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____regex_annot_opt,
+                    )
+                };
+                let v3 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____type_annot_opt,
+                    )
+                };
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__ident,
+                    )
+                };
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____kw_prec_opt,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____doc_star,
+                    )
+                };
+                __Value {
+                    __terminal_item: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(
+                            actions,
+                            TerminalItem::TerminalItem(v0, v1, v2, v3, v4),
+                        )?,
+                    ),
+                }
+            }
+            30usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __type_annot: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, TypeAnnot::TypeAnnot)?,
+                    ),
+                }
+            }
+            31usize => {
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__regex,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __regex_annot: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, RegexAnnot::RegexAnnot(v1))?,
+                    ),
+                }
+            }
+            32usize => {
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__attr,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____attr_star,
+                    )
+                };
+                __Value {
+                    ____attr_star: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v1);
+                        v0
+                    }),
+                }
+            }
+            33usize => {
+                __Value {
+                    ____attr_star: std::mem::ManuallyDrop::new(Vec::new()),
+                }
+            }
+            34usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__params,
+                    )
+                };
+                __Value {
+                    ____params_opt: std::mem::ManuallyDrop::new(Some(v0)),
+                }
+            }
+            35usize => {
+                __Value {
+                    ____params_opt: std::mem::ManuallyDrop::new(None),
+                }
+            }
+            36usize => {
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__alt,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____alt_sep_pipe,
+                    )
+                };
+                __Value {
+                    ____alt_sep_pipe: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v2);
+                        v0
+                    }),
+                }
+            }
+            37usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__alt,
+                    )
+                };
+                __Value {
+                    ____alt_sep_pipe: std::mem::ManuallyDrop::new(vec![v0]),
+                }
+            }
+            38usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v5 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____alt_sep_pipe,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                let v3 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____params_opt,
+                    )
+                };
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__ident,
+                    )
+                };
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____attr_star,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____doc_star,
+                    )
+                };
+                __Value {
+                    __rule: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Rule::Rule(v0, v1, v2, v3, v5))?,
+                    ),
+                }
+            }
+            39usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v4 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____alt_sep_pipe,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__ident,
+                    )
+                };
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____attr_star,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____doc_star,
+                    )
+                };
+                __Value {
+                    __rule: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(
+                            actions,
+                            Rule::ExtendRule(v0, v1, v2, v4),
+                        )?,
+                    ),
+                }
+            }
+            40usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v4 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____ident_sep_comma,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__ident,
+                    )
+                };
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____attr_star,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____doc_star,
+                    )
+                };
+                __Value {
+                    __rule: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(
+                            actions,
+                            Rule::RemoveAlts(v0, v1, v2, v4),
+                        )?,
+                    ),
+                }
+            }
+            41usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____ident_sep_comma,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __params: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Params::Params(v1))?,
+                    ),
+                }
+            }
+            42usize => {
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__field,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____field_plus,
+                    )
+                };
+                __Value {
+                    ____field_plus: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v1);
+                        v0
+                    }),
+                }
+            }
+            43usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__field,
+                    )
+                };
+                __Value {
+                    ____field_plus: std::mem::ManuallyDrop::new(vec![v0]),
+                }
+            }
+            44usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__variant,
+                    )
+                };
+                __Value {
+                    ____variant_opt: std::mem::ManuallyDrop::new(Some(v0)),
+                }
+            }
+            45usize => {
+                __Value {
+                    ____variant_opt: std::mem::ManuallyDrop::new(None),
+                }
+            }
+            46usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__prec_annot,
+                    )
+                };
+                __Value {
+                    ____prec_annot_opt: std::mem::ManuallyDrop::new(Some(v0)),
+                }
+            }
+            47usize => {
+                __Value {
+                    ____prec_annot_opt: std::mem::ManuallyDrop::new(None),
+                }
+            }
+            48usize => {
+                let v3 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____prec_annot_opt,
+                    )
+                };
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____variant_opt,
+                    )
+                };
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____field_plus,
+                    )
+                };
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____doc_star,
+                    )
+                };
+                __Value {
+                    __alt: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Alt::Alt(v0, v1, v2, v3))?,
+                    ),
+                }
+            }
+            49usize => {
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__term,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__ident,
+                    )
+                };
+                __Value {
+                    __field: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Field::Labeled(v0, v2))?,
+                    ),
+                }
+            }
+            50usize => {
+                let v0 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__term,
+                    )
+                };
+                __Value {
+                    __field: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Field::Unlabeled(v0))?,
+                    ),
+                }
+            }
+            51usize => {
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__ident,
+                    )
+                };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __variant: std::mem::ManuallyDrop::new(
+                        <A::Variant as std::convert::From<A::Ident>>::from(v1),
+                    ),
                 }
             }
-            2usize => {
-                let v2 = unsafe {
+            52usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v4 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__terminal_item,
+                        self.value_stack.pop().unwrap().__ident,
                     )
                 };
                 let _ = self.value_stack.pop().unwrap();
-                let v0 = unsafe {
+                let v2 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____terminal_item_sep_comma,
+                        self.value_stack.pop().unwrap().__num,
                     )
                 };
-                {
-                    let mut v0 = v0;
-                    v0.push(v2);
-                    __Value {
-                        ____terminal_item_sep_comma: std::mem::ManuallyDrop::new(v0),
-                    }
+                let _ = self.value_stack.pop().unwrap();
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __prec_annot: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, PrecAnnot::PrecFixed(v2, v4))?,
+                    ),
                 }
             }
-            3usize => {
-                let v0 = unsafe {
+            53usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v2 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__terminal_item,
+                        self.value_stack.pop().unwrap().__ident,
                     )
                 };
+                let _ = self.value_stack.pop().unwrap();
+                let _ = self.value_stack.pop().unwrap();
                 __Value {
-                    ____terminal_item_sep_comma: std::mem::ManuallyDrop::new(vec![v0]),
+                    __prec_annot: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, PrecAnnot::PrecSymbol(v2))?,
+                    ),
                 }
             }
-            4usize => {
-                let v1 = unsafe {
-                    std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__rule,
-                    )
-                };
-                let v0 = unsafe {
+            54usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v3 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____rule_plus,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
-                {
-                    let mut v0 = v0;
-                    v0.push(v1);
-                    __Value {
-                        ____rule_plus: std::mem::ManuallyDrop::new(v0),
-                    }
-                }
-            }
-            5usize => {
-                let v0 = unsafe {
+                let _ = self.value_stack.pop().unwrap();
+                let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__rule,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
+                let _ = self.value_stack.pop().unwrap();
                 __Value {
-                    ____rule_plus: std::mem::ManuallyDrop::new(vec![v0]),
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::SymSep(v1, v3))?,
+                    ),
                 }
             }
-            6usize => {
-                let v8 = unsafe {
+            55usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v4 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____rule_plus,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
                 let _ = self.value_stack.pop().unwrap();
-                let v6 = unsafe {
+                let _ = self.value_stack.pop().unwrap();
+                let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____terminal_item_sep_comma,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::SymSepTrailing(v1, v4))?,
+                    ),
+                }
+            }
+            56usize => {
                 let _ = self.value_stack.pop().unwrap();
                 let _ = self.value_stack.pop().unwrap();
                 let v3 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____expect_decl_star,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
                 let _ = self.value_stack.pop().unwrap();
                 let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__ident,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
                 let _ = self.value_stack.pop().unwrap();
                 __Value {
-                    __grammar_def: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(
-                            actions,
-                            GrammarDef::GrammarDef(v1, v3, v6, v8),
-                        )?,
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::SymTermStar(v1, v3))?,
                     ),
                 }
             }
-            7usize => {
+            57usize => {
                 let _ = self.value_stack.pop().unwrap();
-                let v2 = unsafe {
+                let v3 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__ident,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
+                let _ = self.value_stack.pop().unwrap();
                 let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__num,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
                 let _ = self.value_stack.pop().unwrap();
                 __Value {
-                    __expect_decl: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, ExpectDecl::ExpectDecl(v1, v2))?,
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::SymTerm(v1, v3))?,
                     ),
                 }
             }
-            8usize => {
+            58usize => {
+                let v2 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().__seq,
+                    )
+                };
                 let _ = self.value_stack.pop().unwrap();
-                __Value {
-                    ____kw_prec_opt: std::mem::ManuallyDrop::new(Some(())),
-                }
-            }
-            9usize => {
-                __Value {
-                    ____kw_prec_opt: std::mem::ManuallyDrop::new(None),
-                }
-            }
-            10usize => {
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__type_annot,
+                        self.value_stack.pop().unwrap().____seq_sep_pipe,
                     )
                 };
                 __Value {
-                    ____type_annot_opt: std::mem::ManuallyDrop::new(Some(v0)),
-                }
-            }
-            11usize => {
-                __Value {
-                    ____type_annot_opt: std::mem::ManuallyDrop::new(None),
+                    ____seq_sep_pipe: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v2);
+                        v0
+                    }),
                 }
             }
-            12usize => {
+            59usize => {
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__regex_annot,
+                        self.value_stack.pop().unwrap().__seq,
                     )
                 };
                 __Value {
-                    ____regex_annot_opt: std::mem::ManuallyDrop::new(Some(v0)),
-                }
-            }
-            13usize => {
-                __Value {
-                    ____regex_annot_opt: std::mem::ManuallyDrop::new(None),
+                    ____seq_sep_pipe: std::mem::ManuallyDrop::new(vec![v0]),
                 }
             }
-            14usize => {
-                let v3 = unsafe {
-                    std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____regex_annot_opt,
-                    )
-                };
-                let v2 = unsafe {
-                    std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____type_annot_opt,
-                    )
-                };
+            60usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let _ = self.value_stack.pop().unwrap();
                 let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__ident,
-                    )
-                };
-                let v0 = unsafe {
-                    std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____kw_prec_opt,
+                        self.value_stack.pop().unwrap().____seq_sep_pipe,
                     )
                 };
+                let _ = self.value_stack.pop().unwrap();
                 __Value {
-                    __terminal_item: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(
-                            actions,
-                            TerminalItem::TerminalItem(v0, v1, v2, v3),
-                        )?,
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::GroupOpt(v1))?,
                     ),
                 }
             }
-            15usize => {
+            61usize => {
+                let _ = self.value_stack.pop().unwrap();
                 let _ = self.value_stack.pop().unwrap();
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____seq_sep_pipe,
+                    )
+                };
                 let _ = self.value_stack.pop().unwrap();
                 __Value {
-                    __type_annot: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, TypeAnnot::TypeAnnot)?,
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::GroupStar(v1))?,
                     ),
                 }
             }
-            16usize => {
+            62usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let _ = self.value_stack.pop().unwrap();
                 let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__regex,
+                        self.value_stack.pop().unwrap().____seq_sep_pipe,
                     )
                 };
                 let _ = self.value_stack.pop().unwrap();
                 __Value {
-                    __regex_annot: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, RegexAnnot::RegexAnnot(v1))?,
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::GroupPlus(v1))?,
                     ),
                 }
             }
-            17usize => {
-                let v2 = unsafe {
+            63usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__alt,
+                        self.value_stack.pop().unwrap().____seq_sep_pipe,
                     )
                 };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::Group(v1))?,
+                    ),
+                }
+            }
+            64usize => {
                 let _ = self.value_stack.pop().unwrap();
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____alt_sep_pipe,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
-                {
-                    let mut v0 = v0;
-                    v0.push(v2);
-                    __Value {
-                        ____alt_sep_pipe: std::mem::ManuallyDrop::new(v0),
-                    }
+                __Value {
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::SymOpt(v0))?,
+                    ),
                 }
             }
-            18usize => {
+            65usize => {
+                let _ = self.value_stack.pop().unwrap();
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__alt,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
                 __Value {
-                    ____alt_sep_pipe: std::mem::ManuallyDrop::new(vec![v0]),
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::SymStar(v0))?,
+                    ),
                 }
             }
-            19usize => {
+            66usize => {
                 let _ = self.value_stack.pop().unwrap();
-                let v2 = unsafe {
+                let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().____alt_sep_pipe,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
-                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::SymPlus(v0))?,
+                    ),
+                }
+            }
+            67usize => {
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__ident,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
                 __Value {
-                    __rule: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, Rule::Rule(v0, v2))?,
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::SymPlain(v0))?,
                     ),
                 }
             }
-            20usize => {
+            68usize => {
                 let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
                         self.value_stack.pop().unwrap().__term,
@@ -1559,15 +5437,15 @@ impl<
                         self.value_stack.pop().unwrap().____term_plus,
                     )
                 };
-                {
-                    let mut v0 = v0;
-                    v0.push(v1);
-                    __Value {
-                        ____term_plus: std::mem::ManuallyDrop::new(v0),
-                    }
+                __Value {
+                    ____term_plus: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v1);
+                        v0
+                    }),
                 }
             }
-            21usize => {
+            69usize => {
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
                         self.value_stack.pop().unwrap().__term,
@@ -1577,112 +5455,125 @@ impl<
                     ____term_plus: std::mem::ManuallyDrop::new(vec![v0]),
                 }
             }
-            22usize => {
+            70usize => {
+                let _ = self.value_stack.pop().unwrap();
                 let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__variant,
+                        self.value_stack.pop().unwrap().____term_plus,
                     )
                 };
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::Permutation(v1))?,
+                    ),
+                }
+            }
+            71usize => {
+                let _ = self.value_stack.pop().unwrap();
+                __Value {
+                    __term: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Term::SymEmpty)?,
+                    ),
+                }
+            }
+            72usize => {
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
                         self.value_stack.pop().unwrap().____term_plus,
                     )
                 };
                 __Value {
-                    __alt: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, Alt::Alt(v0, v1))?,
+                    __seq: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Seq::Seq(v0))?,
                     ),
                 }
             }
-            23usize => {
-                let v1 = unsafe {
+            73usize => {
+                let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__ident,
+                        self.value_stack.pop().unwrap().__args,
                     )
                 };
-                let _ = self.value_stack.pop().unwrap();
                 __Value {
-                    __variant: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, Variant::Variant(v1))?,
-                    ),
+                    ____args_opt: std::mem::ManuallyDrop::new(Some(v0)),
                 }
             }
-            24usize => {
-                let _ = self.value_stack.pop().unwrap();
-                let v3 = unsafe {
+            74usize => {
+                __Value {
+                    ____args_opt: std::mem::ManuallyDrop::new(None),
+                }
+            }
+            75usize => {
+                let v1 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__ident,
+                        self.value_stack.pop().unwrap().____args_opt,
                     )
                 };
-                let _ = self.value_stack.pop().unwrap();
-                let v1 = unsafe {
+                let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
                         self.value_stack.pop().unwrap().__ident,
                     )
                 };
-                let _ = self.value_stack.pop().unwrap();
                 __Value {
-                    __term: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, Term::SymSep(v1, v3))?,
+                    __symbol: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Symbol::Symbol(v0, v1))?,
                     ),
                 }
             }
-            25usize => {
-                let _ = self.value_stack.pop().unwrap();
+            76usize => {
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__ident,
+                        self.value_stack.pop().unwrap().__literal,
                     )
                 };
                 __Value {
-                    __term: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, Term::SymOpt(v0))?,
+                    __symbol: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Symbol::Literal(v0))?,
                     ),
                 }
             }
-            26usize => {
-                let _ = self.value_stack.pop().unwrap();
-                let v0 = unsafe {
+            77usize => {
+                let v2 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__ident,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
-                __Value {
-                    __term: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, Term::SymStar(v0))?,
-                    ),
-                }
-            }
-            27usize => {
                 let _ = self.value_stack.pop().unwrap();
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__ident,
+                        self.value_stack.pop().unwrap().____symbol_sep_comma,
                     )
                 };
                 __Value {
-                    __term: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, Term::SymPlus(v0))?,
-                    ),
+                    ____symbol_sep_comma: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v2);
+                        v0
+                    }),
                 }
             }
-            28usize => {
+            78usize => {
                 let v0 = unsafe {
                     std::mem::ManuallyDrop::into_inner(
-                        self.value_stack.pop().unwrap().__ident,
+                        self.value_stack.pop().unwrap().__symbol,
                     )
                 };
                 __Value {
-                    __term: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, Term::SymPlain(v0))?,
-                    ),
+                    ____symbol_sep_comma: std::mem::ManuallyDrop::new(vec![v0]),
                 }
             }
-            29usize => {
+            79usize => {
+                let _ = self.value_stack.pop().unwrap();
+                let v1 = unsafe {
+                    std::mem::ManuallyDrop::into_inner(
+                        self.value_stack.pop().unwrap().____symbol_sep_comma,
+                    )
+                };
                 let _ = self.value_stack.pop().unwrap();
                 __Value {
-                    __term: std::mem::ManuallyDrop::new(
-                        gazelle::Action::build(actions, Term::SymEmpty)?,
+                    __args: std::mem::ManuallyDrop::new(
+                        gazelle::Action::build(actions, Args::Args(v1))?,
                     ),
                 }
             }
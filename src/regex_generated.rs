@@ -2197,13 +2197,21 @@ impl<A: Types> Terminal<A> {
     }
 }
 pub enum Atom<A: Types> {
+    /// `CHAR`
     Char(A::Char),
+    /// `DOT`
     Dot,
+    /// `DASH`
     Dash,
+    /// `CARET`
     Caret,
+    /// `RBRACKET`
     Rbracket,
+    /// `SHORTHAND`
     Shorthand(A::Shorthand),
+    /// `LPAREN regex RPAREN`
     Group(A::Regex),
+    /// `char_class`
     Class(A::CharClass),
 }
 impl<A: Types> std::fmt::Debug for Atom<A> {
@@ -2221,6 +2229,7 @@ impl<A: Types> std::fmt::Debug for Atom<A> {
     }
 }
 pub enum CharClass<A: Types> {
+    /// `LBRACKET CARET? class_item+ RBRACKET`
     Class(Option<()>, Vec<A::ClassItem>),
 }
 impl<A: Types> std::fmt::Debug for CharClass<A> {
@@ -2231,15 +2240,25 @@ impl<A: Types> std::fmt::Debug for CharClass<A> {
     }
 }
 pub enum ClassChar<A: Types> {
+    /// `CHAR`
     Char(A::Char),
+    /// `DOT`
     Dot,
+    /// `STAR`
     Star,
+    /// `PLUS`
     Plus,
+    /// `QUESTION`
     Question,
+    /// `PIPE`
     Pipe,
+    /// `LPAREN`
     Lparen,
+    /// `RPAREN`
     Rparen,
+    /// `CARET`
     Caret,
+    /// `DASH`
     Dash,
 }
 impl<A: Types> std::fmt::Debug for ClassChar<A> {
@@ -2259,8 +2278,11 @@ impl<A: Types> std::fmt::Debug for ClassChar<A> {
     }
 }
 pub enum ClassItem<A: Types> {
+    /// `class_char DASH class_char`
     Range(A::ClassChar, A::ClassChar),
+    /// `class_char`
     Char(A::ClassChar),
+    /// `SHORTHAND`
     Shorthand(A::Shorthand),
 }
 impl<A: Types> std::fmt::Debug for ClassItem<A> {
@@ -2273,6 +2295,7 @@ impl<A: Types> std::fmt::Debug for ClassItem<A> {
     }
 }
 pub enum Concat<A: Types> {
+    /// `repetition+`
     Concat(Vec<A::Repetition>),
 }
 impl<A: Types> std::fmt::Debug for Concat<A> {
@@ -2283,6 +2306,7 @@ impl<A: Types> std::fmt::Debug for Concat<A> {
     }
 }
 pub enum Regex<A: Types> {
+    /// `(concat % PIPE)`
     Regex(Vec<A::Concat>),
 }
 impl<A: Types> std::fmt::Debug for Regex<A> {
@@ -2293,9 +2317,13 @@ impl<A: Types> std::fmt::Debug for Regex<A> {
     }
 }
 pub enum Repetition<A: Types> {
+    /// `atom STAR`
     Star(A::Atom),
+    /// `atom PLUS`
     Plus(A::Atom),
+    /// `atom QUESTION`
     Opt(A::Atom),
+    /// `atom`
     Atom(A::Atom),
 }
 impl<A: Types> std::fmt::Debug for Repetition<A> {
@@ -2591,12 +2619,12 @@ impl<
                         self.value_stack.pop().unwrap().____concat_sep_pipe,
                     )
                 };
-                {
-                    let mut v0 = v0;
-                    v0.push(v2);
-                    __Value {
-                        ____concat_sep_pipe: std::mem::ManuallyDrop::new(v0),
-                    }
+                __Value {
+                    ____concat_sep_pipe: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v2);
+                        v0
+                    }),
                 }
             }
             1usize => {
@@ -2632,12 +2660,12 @@ impl<
                         self.value_stack.pop().unwrap().____repetition_plus,
                     )
                 };
-                {
-                    let mut v0 = v0;
-                    v0.push(v1);
-                    __Value {
-                        ____repetition_plus: std::mem::ManuallyDrop::new(v0),
-                    }
+                __Value {
+                    ____repetition_plus: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v1);
+                        v0
+                    }),
                 }
             }
             4usize => {
@@ -2817,12 +2845,12 @@ impl<
                         self.value_stack.pop().unwrap().____class_item_plus,
                     )
                 };
-                {
-                    let mut v0 = v0;
-                    v0.push(v1);
-                    __Value {
-                        ____class_item_plus: std::mem::ManuallyDrop::new(v0),
-                    }
+                __Value {
+                    ____class_item_plus: std::mem::ManuallyDrop::new({
+                        let mut v0 = v0;
+                        v0.push(v1);
+                        v0
+                    }),
                 }
             }
             21usize => {
@@ -0,0 +1,356 @@
+//! Grammar rewrites that remove spurious (non-ambiguity) conflicts.
+//!
+//! Some unambiguous grammars are not LR(1) as written: the parser must commit
+//! to a reduction before it has seen the token that decides it. Inlining the
+//! reduced non-terminal into its use sites delays that decision until the
+//! enclosing rule is reduced, which is often enough to make the grammar LR(1):
+//!
+//! ```text
+//! s = a X Y => ay | b X Z => bz;    // reduce a or b on X?
+//! a = P => a;
+//! b = P => b;
+//! ```
+//!
+//! becomes, internally, `s = P X Y | P X Z`. Each rewritten rule carries a
+//! [`Recipe`] so codegen still builds `A`, `B` and `S` nodes exactly as the
+//! user wrote them; the rewrite is invisible in the generated API.
+//!
+//! Inlining is opt-in: only rules marked `#[inline]` are inlined. It changes
+//! when actions run, since the inlined rule's node is only built when the
+//! enclosing rule is reduced, so a rule whose action must run early (e.g. to
+//! register a typedef name with the lexer) should not be marked.
+//!
+//! Rewrites are only attempted when the grammar has more conflicts than its
+//! `expect` declarations allow, and only kept when they reduce the number of
+//! conflicts.
+//!
+//! Inlining of non-recursive rules is the only rewrite. It does not help when
+//! the decision has to wait past a recursive non-terminal, as with C11
+//! declaration specifiers or two interleaved lists: those still need a
+//! hand-written grammar (or a permutation, for counting constraints) until
+//! CPS-style unfolding and lookahead-delaying splits land; see `TODO.md`.
+
+use crate::lr::{GrammarInternal, Recipe, Rewrite, Rule, Symbol};
+use crate::runtime::RulePrec;
+use crate::table::{CompiledTable, Conflict};
+
+/// Maximum number of inlining rounds.
+const MAX_ROUNDS: usize = 4;
+
+/// Build parse tables, rewriting the grammar if that removes unexpected conflicts.
+///
+/// The returned table's grammar is the rewritten one; use
+/// [`GrammarInternal::source_rules`] and [`GrammarInternal::recipe`] to map
+/// back to the rules as written.
+pub(crate) fn build_table(
+    grammar: &GrammarInternal,
+    expect_rr: usize,
+    expect_sr: usize,
+) -> CompiledTable {
+    let mut best = CompiledTable::build_from_internal(grammar);
+    for _ in 0..MAX_ROUNDS {
+        let (rr, sr) = conflict_counts(best.conflicts());
+        if rr <= expect_rr && sr <= expect_sr {
+            break;
+        }
+        let candidates = inline_candidates(&best);
+        if candidates.is_empty() {
+            break;
+        }
+        let rewritten = inline(&best.grammar, &candidates);
+        // Inlining multiplies alternatives; give up before the grammar explodes.
+        if rewritten.rules.len() > 4 * grammar.rules.len() + 64 {
+            break;
+        }
+        let table = CompiledTable::build_from_internal(&rewritten);
+        if table.conflicts().len() >= best.conflicts().len() {
+            break;
+        }
+        best = table;
+    }
+    best
+}
+
+fn conflict_counts(conflicts: &[Conflict]) -> (usize, usize) {
+    let rr = conflicts
+        .iter()
+        .filter(|c| matches!(c, Conflict::ReduceReduce { .. }))
+        .count();
+    (rr, conflicts.len() - rr)
+}
+
+/// Non-terminals whose reductions take part in a conflict and can be inlined:
+/// marked `#[inline]`, not a start symbol, not (directly) recursive, and with
+/// no `prec(...)` alternative used by a rule that has its own `prec(...)`,
+/// since the inlined rule could only keep one of the two.
+fn inline_candidates(table: &CompiledTable) -> Vec<Symbol> {
+    let grammar = &table.grammar;
+    let starts: Vec<Symbol> = grammar.start_symbols().collect();
+    let mut candidates = Vec::new();
+    for conflict in table.conflicts() {
+        let rules: &[usize] = match conflict {
+            Conflict::ShiftReduce { reduce_rule, .. } => &[*reduce_rule],
+            Conflict::ReduceReduce { rule1, rule2, .. } => &[*rule1, *rule2],
+        };
+        for &rule in rules {
+            let lhs = grammar.rules[rule].lhs;
+            if rule < grammar.num_entries()
+                || !grammar.inline.contains(&lhs)
+                || starts.contains(&lhs)
+                || candidates.contains(&lhs)
+            {
+                continue;
+            }
+            let recursive = grammar.rules_for(lhs).any(|(_, r)| r.rhs.contains(&lhs));
+            let has_prec = grammar.rules_for(lhs).any(|(_, r)| r.prec.is_some());
+            let prec_clash = has_prec
+                && grammar
+                    .rules
+                    .iter()
+                    .any(|r| r.lhs != lhs && r.prec.is_some() && r.rhs.contains(&lhs));
+            if !recursive && !prec_clash {
+                candidates.push(lhs);
+            }
+        }
+    }
+    candidates
+}
+
+/// Replace every use of each candidate by each of its alternatives.
+///
+/// The candidates' own rules are kept (they may be unreachable afterwards),
+/// so rule indices of the source grammar stay valid.
+fn inline(grammar: &GrammarInternal, candidates: &[Symbol]) -> GrammarInternal {
+    let mut rules: Vec<(Rule, Recipe)> = grammar
+        .rules
+        .iter()
+        .enumerate()
+        .map(|(i, rule)| (rule.clone(), grammar.recipe(i)))
+        .collect();
+
+    for &symbol in candidates {
+        let alternatives: Vec<(Rule, Recipe)> = rules
+            .iter()
+            .filter(|(rule, _)| rule.lhs == symbol)
+            .cloned()
+            .collect();
+
+        let mut i = 0;
+        while i < rules.len() {
+            let (rule, recipe) = &rules[i];
+            let Some(pos) = rule.rhs.iter().position(|&s| s == symbol) else {
+                i += 1;
+                continue;
+            };
+            if rule.lhs == symbol {
+                i += 1;
+                continue;
+            }
+            let expanded: Vec<(Rule, Recipe)> = alternatives
+                .iter()
+                .map(|(alt, alt_recipe)| {
+                    let mut rhs = rule.rhs[..pos].to_vec();
+                    rhs.extend_from_slice(&alt.rhs);
+                    rhs.extend_from_slice(&rule.rhs[pos + 1..]);
+                    let mut recipe = recipe.clone();
+                    substitute(&mut recipe, &mut { pos }, alt_recipe);
+                    // A `prec(SYM)` position after the inlined symbol moves along.
+                    // The inlined alternative's own precedence is carried over
+                    // when the enclosing rule has none (candidates never have
+                    // both, see `inline_candidates`).
+                    let prec = match (rule.prec, alt.prec) {
+                        (Some(RulePrec::Position(p)), _) if p as usize > pos => {
+                            Some(RulePrec::Position((p as usize + alt.rhs.len() - 1) as u8))
+                        }
+                        (Some(prec), _) => Some(prec),
+                        (None, Some(RulePrec::Position(p))) => {
+                            Some(RulePrec::Position((pos + p as usize) as u8))
+                        }
+                        (None, prec) => prec,
+                    };
+                    (
+                        Rule {
+                            lhs: rule.lhs,
+                            rhs,
+                            action: rule.action.clone(),
+                            prec,
+                        },
+                        recipe,
+                    )
+                })
+                .collect();
+            // Re-examine the expansions: the rule may use the symbol again.
+            rules.splice(i..=i, expanded);
+        }
+    }
+
+    let (rules, recipes) = rules.into_iter().unzip();
+    GrammarInternal {
+        rules,
+        symbols: grammar.symbols.clone(),
+        types: grammar.types.clone(),
+        docs: grammar.docs.clone(),
+        attrs: grammar.attrs.clone(),
+        terminal_attrs: grammar.terminal_attrs.clone(),
+        inline: grammar.inline.clone(),
+        rewrite: Some(Rewrite {
+            source_rules: grammar.source_rules().to_vec(),
+            recipes,
+        }),
+        twins: grammar.twins.clone(),
+    }
+}
+
+/// Replace the `n`-th `Value` leaf of `recipe` with `replacement`.
+/// Returns true once the replacement has happened.
+fn substitute(recipe: &mut Recipe, n: &mut usize, replacement: &Recipe) -> bool {
+    match recipe {
+        Recipe::Value if *n == 0 => {
+            *recipe = replacement.clone();
+            true
+        }
+        Recipe::Value => {
+            *n -= 1;
+            false
+        }
+        Recipe::Build(_, parts) => parts
+            .iter_mut()
+            .any(|part| substitute(part, n, replacement)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lr::to_grammar_internal;
+    use crate::meta::parse_grammar;
+    use crate::runtime::Precedence;
+
+    fn internal(src: &str) -> GrammarInternal {
+        to_grammar_internal(&parse_grammar(src).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_inline_removes_rr_conflict() {
+        let grammar = internal(
+            r#"
+            start s;
+            terminals { P, X, Y, Z }
+            s = a X Y => ay | b X Z => bz;
+            #[inline] a = P => a;
+            #[inline] b = P => b;
+        "#,
+        );
+        assert!(CompiledTable::build_from_internal(&grammar).has_conflicts());
+
+        let table = build_table(&grammar, 0, 0);
+        assert!(!table.has_conflicts(), "{:?}", table.format_conflicts());
+
+        let rewritten = &table.grammar;
+        assert_eq!(rewritten.source_rules(), &grammar.rules[..]);
+        // s = P X Y rebuilds a(P) and then s(a, X, Y).
+        let (idx, _) = rewritten
+            .rules
+            .iter()
+            .enumerate()
+            .find(|(_, r)| r.lhs == grammar.rules[1].lhs && r.rhs.len() == 3)
+            .unwrap();
+        assert_eq!(
+            rewritten.recipe(idx),
+            Recipe::Build(
+                1,
+                vec![
+                    Recipe::Build(3, vec![Recipe::Value]),
+                    Recipe::Value,
+                    Recipe::Value
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn test_expected_conflicts_are_left_alone() {
+        let grammar = internal(
+            r#"
+            start s;
+            terminals { P, X, Y, Z }
+            s = a X Y => ay | b X Z => bz;
+            #[inline] a = P => a;
+            #[inline] b = P => b;
+        "#,
+        );
+        let table = build_table(&grammar, 1, 0);
+        assert!(table.has_conflicts());
+        assert!(table.grammar.rewrite.is_none());
+    }
+
+    #[test]
+    fn test_only_marked_rules_are_inlined() {
+        let grammar = internal(
+            r#"
+            start s;
+            terminals { P, X, Y, Z }
+            s = a X Y => ay | b X Z => bz;
+            a = P => a;
+            b = P => b;
+        "#,
+        );
+        let table = build_table(&grammar, 0, 0);
+        assert!(table.has_conflicts());
+        assert!(table.grammar.rewrite.is_none());
+    }
+
+    #[test]
+    fn test_inlined_prec_is_carried_over() {
+        let grammar = internal(
+            r#"
+            start s;
+            terminals { P, X, Y, Z }
+            s = a X Y => ay | b X Z => bz;
+            #[inline] a = P => a prec(3, left);
+            #[inline] b = P => b;
+        "#,
+        );
+        let table = build_table(&grammar, 0, 0);
+        assert!(!table.has_conflicts(), "{:?}", table.format_conflicts());
+        let s = grammar.rules[1].lhs;
+        let precs: Vec<_> = table.grammar.rules_for(s).map(|(_, r)| r.prec).collect();
+        assert_eq!(precs, [Some(RulePrec::Fixed(Precedence::Left(3))), None]);
+    }
+
+    #[test]
+    fn test_prec_clash_is_not_inlined() {
+        let grammar = internal(
+            r#"
+            start s;
+            terminals { P, X, Y, Z }
+            s = a X Y => ay prec(1, left) | b X Z => bz;
+            #[inline] a = P => a prec(3, left);
+            #[inline] b = P => b;
+        "#,
+        );
+        let table = build_table(&grammar, 0, 0);
+        let s = grammar.rules[1].lhs;
+        // `s` keeps using `a`: its `prec` would replace `s`'s own.
+        assert!(
+            table
+                .grammar
+                .rules_for(s)
+                .any(|(_, r)| r.rhs.len() == 3 && r.rhs[0] == grammar.rules[3].lhs)
+        );
+    }
+
+    #[test]
+    fn test_ambiguity_is_not_rewritten() {
+        let grammar = internal(
+            r#"
+            start expr;
+            terminals { NUM, PLUS }
+            expr = expr PLUS expr => add | NUM => num;
+        "#,
+        );
+        let table = build_table(&grammar, 0, 0);
+        assert!(table.has_conflicts());
+        assert!(table.grammar.rewrite.is_none());
+    }
+}
@@ -2,6 +2,7 @@ use crate::grammar::SymbolId;
 use std::cmp::Reverse;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::rc::Rc;
+use std::sync::Arc;
 
 /// Marker trait for generated AST node types.
 ///
@@ -158,6 +159,16 @@ fn format_sym(s: &str) -> String {
     }
 }
 
+/// A symbol name quoted for a message: `'PLUS'`, but a literal terminal such
+/// as `'+'` already reads as quoted.
+pub(crate) fn quoted(name: &str) -> String {
+    if name.starts_with('\'') || name.starts_with('"') {
+        name.to_string()
+    } else {
+        format!("'{}'", name)
+    }
+}
+
 /// This is the runtime representation used by the parser. It borrows slices
 /// from either static data (generated code) or a [`CompiledTable`](crate::table::CompiledTable).
 ///
@@ -174,6 +185,8 @@ pub struct ParseTable<'a> {
     num_terminals: u32,
     default_reduce: &'a [u32],
     default_goto: &'a [u32],
+    conflict_reduces: &'a [(u32, u32, u32)],
+    rule_prec: &'a [(u32, RulePrec)],
 }
 
 impl<'a> ParseTable<'a> {
@@ -198,9 +211,25 @@ impl<'a> ParseTable<'a> {
             num_terminals,
             default_reduce,
             default_goto,
+            conflict_reduces: &[],
+            rule_prec: &[],
         }
     }
 
+    /// Attach rule precedences declared with `prec(...)`, as (rule, precedence)
+    /// pairs sorted by rule.
+    pub const fn with_rule_prec(mut self, rule_prec: &'a [(u32, RulePrec)]) -> Self {
+        self.rule_prec = rule_prec;
+        self
+    }
+
+    /// Attach the reductions dropped by conflict resolution, as sorted
+    /// (state, terminal, rule) triples. Only the GLR parser consults them.
+    pub(crate) fn with_conflict_reduces(mut self, conflict_reduces: &'a [(u32, u32, u32)]) -> Self {
+        self.conflict_reduces = conflict_reduces;
+        self
+    }
+
     /// Displacement table lookup: data[base[row] + col] if check matches.
     fn lookup(&self, base: &[i32], row: usize, col: u32) -> Option<u32> {
         let idx = (base[row] + col as i32) as usize;
@@ -247,10 +276,40 @@ impl<'a> ParseTable<'a> {
         (SymbolId(lhs), len as usize)
     }
 
+    /// True if `rule` is an accept rule (`__start -> ...`): rule 0, or one of
+    /// the leading rules of a grammar with several start symbols.
+    pub(crate) fn is_accept(&self, rule: usize) -> bool {
+        self.rules[rule].0 == self.rules[0].0
+    }
+
     /// Get all rules as (lhs_id, rhs_len) pairs.
     pub(crate) fn rules(&self) -> &[(u32, u8)] {
         self.rules
     }
+
+    /// The declared precedence of a rule, if any.
+    pub(crate) fn rule_prec(&self, rule: usize) -> Option<RulePrec> {
+        let idx = self
+            .rule_prec
+            .binary_search_by_key(&(rule as u32), |&(r, _)| r)
+            .ok()?;
+        Some(self.rule_prec[idx].1)
+    }
+
+    /// Reductions that conflict resolution dropped for a state and terminal.
+    pub(crate) fn conflict_reduces(
+        &self,
+        state: usize,
+        terminal: SymbolId,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let key = (state as u32, terminal.0);
+        let all = self.conflict_reduces;
+        let start = all.partition_point(|&(s, t, _)| (s, t) < key);
+        all[start..]
+            .iter()
+            .take_while(move |&&(s, t, _)| (s, t) == key)
+            .map(|&(_, _, rule)| rule as usize)
+    }
 }
 
 type RecoveryState<'a> = (SimState<'a>, usize, Option<(usize, Repair)>);
@@ -275,20 +334,294 @@ pub trait ErrorContext {
 /// Used with `prec` terminals to resolve operator precedence at runtime.
 /// Higher levels bind tighter. Associativity determines behavior at equal levels.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum Precedence {
     /// Left-associative with the given level (e.g., `+`, `-`).
     Left(u8),
     /// Right-associative with the given level (e.g., `=`, `**`).
     Right(u8),
+    /// Non-associative with the given level (e.g., `<`, `==`): chaining two
+    /// operators of the same level is a parse error.
+    NonAssoc(u8),
+    /// A named group from a [`PrecedenceGroups`] registry, which the parser
+    /// must be given to compare groups. Groups are only partially ordered:
+    /// mixing two unrelated groups (or a group and a numeric level) without
+    /// parentheses is a parse error.
+    Group(PrecGroup),
 }
 
 impl Precedence {
     /// Get the precedence level.
+    ///
+    /// A group's level is its rank when it was looked up: 0 if it binds
+    /// tighter than no other group, else one more than the highest rank below
+    /// it. Groups that bind tighter have higher levels, but unordered groups
+    /// can share one, so the parser never compares groups by level.
     pub fn level(&self) -> u8 {
         match self {
-            Precedence::Left(l) | Precedence::Right(l) => *l,
+            Precedence::Left(l) | Precedence::Right(l) | Precedence::NonAssoc(l) => *l,
+            Precedence::Group(g) => g.rank,
         }
     }
+
+    /// Get the associativity.
+    pub fn associativity(&self) -> Associativity {
+        match self {
+            Precedence::Left(_) => Associativity::Left,
+            Precedence::Right(_) => Associativity::Right,
+            Precedence::NonAssoc(_) => Associativity::NonAssoc,
+            Precedence::Group(g) => g.assoc,
+        }
+    }
+
+    /// How tightly `self` binds compared to `other`. Groups are compared by
+    /// the relations of `groups`, and only if both come from it.
+    fn compare(
+        &self,
+        other: &Precedence,
+        groups: Option<&PrecedenceGroups>,
+    ) -> Result<std::cmp::Ordering, PrecConflict> {
+        match (self, other) {
+            (Precedence::Group(a), Precedence::Group(b)) => match groups {
+                Some(groups) if groups.owns(a) && groups.owns(b) => {
+                    groups.order(a.index as usize, b.index as usize)
+                }
+                _ => Err(PrecConflict::Mismatched),
+            },
+            (Precedence::Group(_), _) | (_, Precedence::Group(_)) => Err(PrecConflict::Unordered),
+            _ => Ok(self.level().cmp(&other.level())),
+        }
+    }
+}
+
+/// Associativity of a precedence level or group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Associativity {
+    Left,
+    Right,
+    NonAssoc,
+}
+
+/// Handle to a precedence group, obtained from [`PrecedenceGroups::get`].
+///
+/// The handle is four bytes: the group's index, rank and associativity, and
+/// a tag of the registry it came from. The relations between groups stay in
+/// the registry, which the parser consults when comparing two handles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PrecGroup {
+    index: u8,
+    rank: u8,
+    assoc: Associativity,
+    /// Tag of the registry (see [`PrecedenceGroups`]).
+    registry: u8,
+}
+
+/// A registry of named precedence groups, ordered by `higher_than` /
+/// `lower_than` relations rather than a single numeric scale (like Swift's
+/// `precedencegroup`). A registry holds at most
+/// [`MAX_GROUPS`](Self::MAX_GROUPS) groups.
+///
+/// Tokens carry [`PrecGroup`] handles, and the parser compares them by the
+/// registry given to it with `set_precedence_groups`. A handle from another
+/// registry is a parse error rather than a silently wrong answer. Registries
+/// are told apart by a one-byte tag, so this check only holds among the
+/// last 256 registries created; a clone counts as a new registry.
+///
+/// ```
+/// use gazelle::{Associativity, PrecedenceGroups};
+///
+/// let mut groups = PrecedenceGroups::new();
+/// groups.declare("Additive", Associativity::Left).unwrap();
+/// groups.declare("Multiplicative", Associativity::Left).unwrap();
+/// groups.declare("Range", Associativity::NonAssoc).unwrap();
+/// groups.higher_than("Multiplicative", "Additive").unwrap();
+///
+/// // `a + b * c` is fine; `a + b .. c` needs parentheses.
+/// let add = groups.get("Additive").unwrap();
+/// ```
+#[derive(Debug)]
+pub struct PrecedenceGroups {
+    names: Vec<String>,
+    assoc: Vec<Associativity>,
+    /// Per group, the bit set of the groups it binds tighter than
+    /// (transitively).
+    higher_than: Vec<u64>,
+    tag: u8,
+}
+
+/// Source of [`PrecedenceGroups`] tags.
+static NEXT_REGISTRY: std::sync::atomic::AtomicU8 = std::sync::atomic::AtomicU8::new(0);
+
+impl Default for PrecedenceGroups {
+    fn default() -> Self {
+        PrecedenceGroups {
+            names: Vec::new(),
+            assoc: Vec::new(),
+            higher_than: Vec::new(),
+            tag: NEXT_REGISTRY.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+        }
+    }
+}
+
+/// A clone is a separate registry: its handles don't compare with the original's.
+impl Clone for PrecedenceGroups {
+    fn clone(&self) -> Self {
+        PrecedenceGroups {
+            names: self.names.clone(),
+            assoc: self.assoc.clone(),
+            higher_than: self.higher_than.clone(),
+            ..Self::default()
+        }
+    }
+}
+
+impl PrecedenceGroups {
+    /// Maximum number of groups in one registry. The relations are kept as
+    /// a 64-bit set per group.
+    pub const MAX_GROUPS: usize = 64;
+
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a new group.
+    pub fn declare(&mut self, name: &str, assoc: Associativity) -> Result<(), String> {
+        if self.index(name).is_some() {
+            return Err(format!("precedence group '{}' is already declared", name));
+        }
+        if self.names.len() == Self::MAX_GROUPS {
+            return Err(format!(
+                "too many precedence groups (at most {})",
+                Self::MAX_GROUPS
+            ));
+        }
+        self.names.push(name.to_string());
+        self.assoc.push(assoc);
+        self.higher_than.push(0);
+        Ok(())
+    }
+
+    /// Declare that `higher` binds tighter than `lower`, and so tighter than
+    /// every group `lower` binds tighter than.
+    pub fn higher_than(&mut self, higher: &str, lower: &str) -> Result<(), String> {
+        let hi = self.lookup(higher)?;
+        let lo = self.lookup(lower)?;
+        if hi == lo || self.higher_than[lo] & (1 << hi) != 0 {
+            return Err(format!(
+                "precedence cycle: '{}' already binds at least as tightly as '{}'",
+                lower, higher
+            ));
+        }
+        // Everything at or above `hi` now also beats `lo` and everything below it.
+        let below = (1 << lo) | self.higher_than[lo];
+        for i in 0..self.names.len() {
+            if i == hi || self.higher_than[i] & (1 << hi) != 0 {
+                self.higher_than[i] |= below;
+            }
+        }
+        Ok(())
+    }
+
+    /// Declare that `lower` binds less tightly than `higher`.
+    pub fn lower_than(&mut self, lower: &str, higher: &str) -> Result<(), String> {
+        self.higher_than(higher, lower)
+    }
+
+    /// Look up a group's precedence, to attach to tokens.
+    pub fn get(&self, name: &str) -> Option<Precedence> {
+        let index = self.index(name)?;
+        Some(Precedence::Group(PrecGroup {
+            index: index as u8,
+            rank: self.rank(index),
+            assoc: self.assoc[index],
+            registry: self.tag,
+        }))
+    }
+
+    /// Whether `group` is a handle from this registry.
+    fn owns(&self, group: &PrecGroup) -> bool {
+        group.registry == self.tag && (group.index as usize) < self.names.len()
+    }
+
+    /// How tightly group `a` binds compared to group `b`.
+    fn order(&self, a: usize, b: usize) -> Result<std::cmp::Ordering, PrecConflict> {
+        if a == b {
+            Ok(std::cmp::Ordering::Equal)
+        } else if self.higher_than[a] & (1 << b) != 0 {
+            Ok(std::cmp::Ordering::Greater)
+        } else if self.higher_than[b] & (1 << a) != 0 {
+            Ok(std::cmp::Ordering::Less)
+        } else {
+            Err(PrecConflict::Unordered)
+        }
+    }
+
+    /// Length of the longest chain of groups below `index`.
+    fn rank(&self, index: usize) -> u8 {
+        (0..self.names.len())
+            .filter(|&i| self.higher_than[index] & (1 << i) != 0)
+            .map(|i| self.rank(i) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn index(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    fn lookup(&self, name: &str) -> Result<usize, String> {
+        self.index(name)
+            .ok_or_else(|| format!("unknown precedence group '{}'", name))
+    }
+}
+
+/// Precedence of a grammar alternative, declared with `prec(...)`.
+///
+/// Overrides the precedence inherited from the stack when the alternative's
+/// reduction competes with shifting a `prec` terminal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RulePrec {
+    /// `prec(N, assoc)`: a fixed precedence.
+    Fixed(Precedence),
+    /// `prec(SYM)`: the precedence of the token at this RHS position.
+    Position(u8),
+}
+
+/// Why a runtime shift/reduce conflict could not be resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PrecConflict {
+    /// A non-associative operator met another of the same precedence.
+    NonAssociative,
+    /// The two precedences are not ordered relative to each other.
+    Unordered,
+    /// A precedence group from a registry the parser was not given.
+    Mismatched,
+}
+
+/// Resolve a runtime shift/reduce conflict: reduce if the lookahead binds
+/// less tightly than the stack, or equally tightly and left-associative.
+///
+/// Fails if a non-associative operator on the stack meets a lookahead of the
+/// same precedence, or if the two precedences are unordered. Precedence
+/// groups are ordered by `groups`.
+pub(crate) fn prec_reduces(
+    stack: Option<Precedence>,
+    lookahead: Option<Precedence>,
+    groups: Option<&PrecedenceGroups>,
+) -> Result<bool, PrecConflict> {
+    let (Some(sp), Some(tp)) = (stack, lookahead) else {
+        return Ok(false);
+    };
+    match sp.compare(&tp, groups)? {
+        std::cmp::Ordering::Greater => Ok(true),
+        std::cmp::Ordering::Less => Ok(false),
+        std::cmp::Ordering::Equal => match sp.associativity() {
+            Associativity::Left => Ok(true),
+            Associativity::Right => Ok(false),
+            Associativity::NonAssoc => Err(PrecConflict::NonAssociative),
+        },
+    }
 }
 
 /// Compute which symbols are nullable (can derive epsilon).
@@ -408,13 +741,59 @@ fn is_sequence_nullable(sequence: &[u32], nullable: &[bool]) -> bool {
 #[derive(Debug, Clone)]
 pub struct ParseError {
     terminal: SymbolId,
+    prec: Option<PrecConflict>,
+    wrong_entry: bool,
 }
 
 impl ParseError {
+    pub(crate) fn new(terminal: SymbolId) -> Self {
+        Self {
+            terminal,
+            prec: None,
+            wrong_entry: false,
+        }
+    }
+
+    /// The error of a generated `finish_<name>` called on a parser created
+    /// for another entry point.
+    #[doc(hidden)]
+    pub fn wrong_entry() -> Self {
+        Self {
+            wrong_entry: true,
+            ..Self::new(SymbolId::EOF)
+        }
+    }
+
     /// The unexpected terminal that caused the error.
     pub fn terminal(&self) -> SymbolId {
         self.terminal
     }
+
+    /// True if the terminal is a [`Precedence::NonAssoc`] operator that
+    /// followed another operator of the same level (as in `a < b < c`).
+    pub fn is_non_associative(&self) -> bool {
+        self.prec == Some(PrecConflict::NonAssociative)
+    }
+
+    /// True if the terminal's precedence is unordered relative to the operator
+    /// before it (unrelated [`PrecedenceGroups`]), so parentheses are needed.
+    pub fn is_ambiguous_precedence(&self) -> bool {
+        self.prec == Some(PrecConflict::Unordered)
+    }
+
+    /// True if the terminal's precedence group can't be compared with the
+    /// operator before it: one of the two handles comes from another
+    /// [`PrecedenceGroups`] than the one the parser was given, or the parser
+    /// was given none.
+    pub fn is_mismatched_precedence(&self) -> bool {
+        self.prec == Some(PrecConflict::Mismatched)
+    }
+
+    /// True if the input was finished for a different start symbol than the
+    /// parser was created for.
+    pub fn is_wrong_entry(&self) -> bool {
+        self.wrong_entry
+    }
 }
 
 /// A token with terminal symbol ID and optional precedence.
@@ -518,7 +897,8 @@ impl std::ops::Index<usize> for LrStack {
 }
 
 /// Push-based LR parser. Call [`maybe_reduce`](Self::maybe_reduce) in a loop,
-/// then [`shift`](Self::shift) each token. Rule 0 signals acceptance.
+/// then [`shift`](Self::shift) each token. Rule 0 signals acceptance (or rule
+/// `i` for the `i`-th start symbol, see [`for_entry`](Self::for_entry)).
 #[derive(Clone)]
 pub struct Parser<'a> {
     table: ParseTable<'a>,
@@ -528,11 +908,16 @@ pub struct Parser<'a> {
     stack: LrStack,
     /// Count of tokens shifted (for span tracking).
     token_count: usize,
+    /// Stack entries below the first parsed symbol: the initial state, plus
+    /// the entry marker for [`for_entry`](Self::for_entry) parsers.
+    base: usize,
     // Checkpoint for error reporting — the state before the current reduction
     // sequence, restored if an error is detected after spurious reductions.
     checkpoint_state: StackEntry,
     checkpoint_len: usize,
     overwrites: Vec<(usize, StackEntry)>,
+    /// The registry that orders precedence groups.
+    groups: Option<Arc<PrecedenceGroups>>,
 }
 
 impl<'a> Parser<'a> {
@@ -548,17 +933,27 @@ impl<'a> Parser<'a> {
             state: initial,
             stack: LrStack::new(),
             token_count: 0,
+            base: 1,
             checkpoint_state: initial,
             checkpoint_len: 0,
             overwrites: Vec::new(),
+            groups: None,
         }
     }
 
+    /// Compare the precedence groups tokens carry by the relations of
+    /// `groups`. Without it, a token with a group is a parse error.
+    pub fn set_precedence_groups(&mut self, groups: Arc<PrecedenceGroups>) {
+        self.groups = Some(groups);
+    }
+
     /// Check if a reduction should happen for the given lookahead.
     ///
     /// Returns `Ok(Some((rule, len, start_idx)))` if a reduction should occur.
     /// The `start_idx` together with `token_count()` forms the half-open range `[start_idx, token_count())`.
-    /// Returns `Ok(None)` if should shift or if accepted.
+    /// Returns `Ok(Some((rule, 0, 0)))` on accept, where `rule` is the accept
+    /// rule of the entry point: 0, unless the grammar has several start symbols.
+    /// Returns `Ok(None)` if should shift.
     /// Returns `Err(ParseError)` on parse error.
     pub fn maybe_reduce(
         &mut self,
@@ -569,8 +964,8 @@ impl<'a> Parser<'a> {
 
         match self.table.action(self.state.state, terminal) {
             ParserOp::Reduce(rule) => {
-                if rule == 0 {
-                    Ok(Some((0, 0, 0))) // Accept
+                if self.table.is_accept(rule) {
+                    Ok(Some((rule, 0, 0))) // Accept
                 } else {
                     let (len, start_idx) = self.do_reduce(rule);
                     Ok(Some((rule, len, start_idx)))
@@ -578,30 +973,63 @@ impl<'a> Parser<'a> {
             }
             ParserOp::Shift(_) => Ok(None),
             ParserOp::ShiftOrReduce { reduce_rule, .. } => {
-                let should_reduce = match (self.state.prec, lookahead_prec) {
-                    (Some(sp), Some(tp)) => {
-                        if tp.level() > sp.level() {
-                            false
-                        } else if tp.level() < sp.level() {
-                            true
-                        } else {
-                            matches!(sp, Precedence::Left(_))
-                        }
+                match prec_reduces(
+                    self.reduce_prec(reduce_rule),
+                    lookahead_prec,
+                    self.groups.as_deref(),
+                ) {
+                    Ok(true) => {
+                        let (len, start_idx) = self.do_reduce(reduce_rule);
+                        Ok(Some((reduce_rule, len, start_idx)))
                     }
-                    _ => false,
-                };
+                    Ok(false) => Ok(None),
+                    Err(conflict) => Err(ParseError {
+                        prec: Some(conflict),
+                        ..ParseError::new(terminal)
+                    }),
+                }
+            }
+            ParserOp::Error => Err(ParseError::new(terminal)),
+        }
+    }
 
-                if should_reduce {
-                    let (len, start_idx) = self.do_reduce(reduce_rule);
-                    Ok(Some((reduce_rule, len, start_idx)))
-                } else {
-                    Ok(None)
+    /// The precedence a reduction competes with: the rule's declared
+    /// precedence, or else the one carried by the top of the stack.
+    fn reduce_prec(&self, rule: usize) -> Option<Precedence> {
+        match self.table.rule_prec(rule) {
+            None => self.state.prec,
+            Some(RulePrec::Fixed(prec)) => Some(prec),
+            Some(RulePrec::Position(pos)) => {
+                // The top of the stack holds the last RHS symbol.
+                let from_top = self.table.rule_info(rule).1 - 1 - pos as usize;
+                match from_top {
+                    0 => self.state.prec,
+                    n => self.stack[self.stack.len() - n].prec,
                 }
             }
-            ParserOp::Error => Err(ParseError { terminal }),
         }
     }
 
+    /// Create a parser for one entry point of a grammar with several start
+    /// symbols. `entry` is the entry's marker terminal (see
+    /// [`CompiledTable::entry`](crate::CompiledTable::entry)), which is shifted
+    /// without counting as a token.
+    pub fn for_entry(table: ParseTable<'a>, entry: SymbolId) -> Self {
+        let mut parser = Self::new(table);
+        let ParserOp::Shift(state) = table.action(0, entry) else {
+            panic!("symbol {} is not an entry point marker", entry.0);
+        };
+        parser.stack.push(parser.state);
+        parser.state = StackEntry {
+            state,
+            prec: None,
+            token_idx: 0,
+        };
+        parser.base = 2;
+        parser.save_checkpoint();
+        parser
+    }
+
     /// Shift a token onto the stack.
     pub fn shift(&mut self, token: Token) {
         let next_state = match self.table.action(self.state.state, token.terminal) {
@@ -701,7 +1129,7 @@ impl<'a> Parser<'a> {
     /// Get the state at a given depth (0 = bottom of stack).
     #[doc(hidden)]
     pub fn state_at(&self, depth: usize) -> usize {
-        let idx = depth + 1;
+        let idx = depth + self.base;
         if idx < self.stack.len() {
             self.stack[idx].state
         } else {
@@ -741,6 +1169,10 @@ impl<'a> Parser<'a> {
         display_names: Option<&HashMap<&str, &str>>,
         tokens: Option<&[&str]>,
     ) -> String {
+        if err.wrong_entry {
+            return "finished for a different start symbol than the parser was created for"
+                .to_string();
+        }
         let empty_map = HashMap::new();
         let display_names = display_names.unwrap_or(&empty_map);
         let tokens = tokens.unwrap_or(&[]);
@@ -790,13 +1222,28 @@ impl<'a> Parser<'a> {
         expected.sort();
 
         // Show actual token text if available, otherwise display name
-        let found_name = tokens
-            .get(error_token_idx)
-            .copied()
-            .unwrap_or_else(|| display(err.terminal));
+        let found_name = match tokens.get(error_token_idx) {
+            Some(text) => format!("'{}'", text),
+            None => quoted(display(err.terminal)),
+        };
 
-        let mut msg = format!("unexpected '{}'", found_name);
-        if !expected.is_empty() {
+        let mut msg = match err.prec {
+            Some(PrecConflict::NonAssociative) => {
+                format!("operator {} is non-associative", found_name)
+            }
+            Some(PrecConflict::Unordered) => {
+                format!("ambiguous operator mix at {}, add parentheses", found_name)
+            }
+            Some(PrecConflict::Mismatched) => {
+                format!(
+                    "operator {} has a precedence group from a registry \
+                     the parser was not given",
+                    found_name
+                )
+            }
+            None => format!("unexpected {}", found_name),
+        };
+        if !expected.is_empty() && err.prec.is_none() {
             msg.push_str(&format!(", expected: {}", expected.join(", ")));
         }
 
@@ -806,7 +1253,7 @@ impl<'a> Parser<'a> {
             // Skip state 0 (initial), show recent entries
             let relevant: Vec<_> = spans
                 .into_iter()
-                .skip(1) // skip initial state
+                .skip(self.base) // skip initial state and entry marker
                 .filter(|(start, end, _)| end > start) // skip empty spans
                 .collect();
 
@@ -840,9 +1287,9 @@ impl<'a> Parser<'a> {
 
                 msg.push_str(&format!("\n  {}\n  {}", token_line, label_line));
             }
-        } else if full_stack.len() > 1 {
+        } else if full_stack.len() > self.base {
             // Fallback: show grammar symbols from stack
-            let path: Vec<_> = full_stack[1..]
+            let path: Vec<_> = full_stack[self.base..]
                 .iter()
                 .map(|e| display(ctx.state_symbol(e.state)))
                 .collect();
@@ -1254,6 +1701,7 @@ struct SimState<'a> {
     token_idx: usize,
     stack: Option<Rc<SimStackNode>>,
     depth: usize,
+    groups: Option<Arc<PrecedenceGroups>>,
 }
 
 struct SimStackNode {
@@ -1281,6 +1729,7 @@ impl<'a> SimState<'a> {
             token_idx: parser.state.token_idx,
             stack: node,
             depth: parser.stack.len(),
+            groups: parser.groups.clone(),
         }
     }
 
@@ -1322,7 +1771,7 @@ impl<'a> SimState<'a> {
     }
 
     /// Check action for lookahead. Returns:
-    /// - Ok(true): accept (rule 0)
+    /// - Ok(true): accept
     /// - Ok(false): should shift
     /// - Err(true): reduced (call again)
     /// - Err(false): parse error
@@ -1332,37 +1781,53 @@ impl<'a> SimState<'a> {
 
         match self.table.action(self.state, terminal) {
             ParserOp::Reduce(rule) => {
-                if rule == 0 {
-                    return Ok(true);
+                if self.table.is_accept(rule) {
+                    return if lookahead.is_none() {
+                        Ok(true)
+                    } else {
+                        Err(false)
+                    };
                 }
                 self.do_reduce(rule);
                 Err(true)
             }
             ParserOp::Shift(_) => Ok(false),
             ParserOp::ShiftOrReduce { reduce_rule, .. } => {
-                let should_reduce = match (self.prec, lookahead_prec) {
-                    (Some(sp), Some(tp)) => {
-                        if tp.level() > sp.level() {
-                            false
-                        } else if tp.level() < sp.level() {
-                            true
-                        } else {
-                            matches!(sp, Precedence::Left(_))
-                        }
+                match prec_reduces(
+                    self.reduce_prec(reduce_rule),
+                    lookahead_prec,
+                    self.groups.as_deref(),
+                ) {
+                    Ok(true) => {
+                        self.do_reduce(reduce_rule);
+                        Err(true)
                     }
-                    _ => false,
-                };
-                if should_reduce {
-                    self.do_reduce(reduce_rule);
-                    Err(true)
-                } else {
-                    Ok(false)
+                    Ok(false) => Ok(false),
+                    Err(_) => Err(false),
                 }
             }
             ParserOp::Error => Err(false),
         }
     }
 
+    fn reduce_prec(&self, rule: usize) -> Option<Precedence> {
+        match self.table.rule_prec(rule) {
+            None => self.prec,
+            Some(RulePrec::Fixed(prec)) => Some(prec),
+            Some(RulePrec::Position(pos)) => {
+                let from_top = self.table.rule_info(rule).1 - 1 - pos as usize;
+                let mut node = self.stack.as_deref();
+                for _ in 1..from_top {
+                    node = node.and_then(|n| n.parent.as_deref());
+                }
+                match from_top {
+                    0 => self.prec,
+                    _ => node.and_then(|n| n.prec),
+                }
+            }
+        }
+    }
+
     fn shift(&mut self, token: Token) {
         let next_state = match self.table.action(self.state, token.terminal) {
             ParserOp::Shift(s) => s,
@@ -1489,11 +1954,25 @@ impl<'a> CstParser<'a> {
         }
     }
 
+    /// Create a tree parser for one entry point (see [`Parser::for_entry`]).
+    pub fn for_entry(table: ParseTable<'a>, entry: SymbolId) -> Self {
+        CstParser {
+            parser: Parser::for_entry(table, entry),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Compare precedence groups by `groups` (see
+    /// [`Parser::set_precedence_groups`]).
+    pub fn set_precedence_groups(&mut self, groups: Arc<PrecedenceGroups>) {
+        self.parser.set_precedence_groups(groups);
+    }
+
     /// Push a token, performing any pending reductions.
     pub fn push(&mut self, token: Token) -> Result<(), ParseError> {
         loop {
             match self.parser.maybe_reduce(Some(token)) {
-                Ok(Some((rule, len, _))) if rule > 0 => {
+                Ok(Some((rule, len, _))) if !self.parser.table.is_accept(rule) => {
                     let children = self.stack.drain(self.stack.len() - len..).collect();
                     self.stack.push(Cst::Node { rule, children });
                 }
@@ -1519,7 +1998,7 @@ impl<'a> CstParser<'a> {
     pub fn finish(mut self) -> Result<Cst, (Self, ParseError)> {
         loop {
             match self.parser.maybe_reduce(None) {
-                Ok(Some((0, _, _))) => {
+                Ok(Some((rule, _, _))) if self.parser.table.is_accept(rule) => {
                     return Ok(self.stack.pop().expect("empty stack after accept"));
                 }
                 Ok(Some((rule, len, _))) => {
@@ -1617,6 +2096,24 @@ mod tests {
         assert!(matches!(result, Ok(Some((0, 0, 0)))));
     }
 
+    #[test]
+    fn test_accept_only_at_eof() {
+        // Accepting is never a state's default action.
+        let grammar =
+            to_grammar_internal(&parse_grammar("start s; terminals { a } s = a => a;").unwrap())
+                .unwrap();
+        let compiled = CompiledTable::build_from_internal(&grammar);
+        let mut parser = Parser::new(compiled.table());
+        let token = Token::new(compiled.symbol_id("a").unwrap());
+        parser.shift(token);
+        assert!(matches!(
+            parser.maybe_reduce(Some(token)),
+            Ok(Some((1, 1, 0)))
+        ));
+        assert!(parser.maybe_reduce(Some(token)).is_err());
+        assert!(matches!(parser.maybe_reduce(None), Ok(Some((0, 0, 0)))));
+    }
+
     #[test]
     fn test_parse_error() {
         let grammar = to_grammar_internal(
@@ -1665,4 +2162,161 @@ mod tests {
         assert!(msg.contains("'b'"), "msg: {}", msg);
         assert!(msg.contains("s"), "msg: {}", msg);
     }
+
+    #[test]
+    fn test_non_assoc_error() {
+        let grammar = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start expr;
+            terminals { prec OP, NUM }
+            expr = expr OP expr => binop | NUM => num;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let compiled = CompiledTable::build_from_internal(&grammar);
+        let num = Token::new(compiled.symbol_id("NUM").unwrap());
+        let op = |prec| Token::with_prec(compiled.symbol_id("OP").unwrap(), prec);
+
+        // Push NUM OP NUM, then return the error (if any) from pushing a second OP.
+        let push_second = |first, second| {
+            let mut parser = Parser::new(compiled.table());
+            for token in [num, op(first), num] {
+                while parser.maybe_reduce(Some(token)).unwrap().is_some() {}
+                parser.shift(token);
+            }
+            loop {
+                match parser.maybe_reduce(Some(op(second))) {
+                    Ok(Some(_)) => {}
+                    Ok(None) => return None,
+                    Err(e) => return Some((parser, e)),
+                }
+            }
+        };
+
+        // a < b < c
+        let (parser, err) = push_second(Precedence::NonAssoc(1), Precedence::NonAssoc(1)).unwrap();
+        assert!(err.is_non_associative());
+        let msg = parser.format_error(&err, &compiled, None, Some(&["a", "<", "b", "<"]));
+        assert!(
+            msg.starts_with("operator '<' is non-associative"),
+            "msg: {}",
+            msg
+        );
+
+        // a < b + c and a + b < c are fine.
+        assert!(push_second(Precedence::NonAssoc(1), Precedence::Left(2)).is_none());
+        assert!(push_second(Precedence::Left(2), Precedence::NonAssoc(1)).is_none());
+    }
+
+    #[test]
+    fn test_precedence_groups() {
+        let mut groups = PrecedenceGroups::new();
+        groups.declare("Additive", Associativity::Left).unwrap();
+        groups
+            .declare("Multiplicative", Associativity::Left)
+            .unwrap();
+        groups.declare("Exponent", Associativity::Right).unwrap();
+        groups.declare("Range", Associativity::NonAssoc).unwrap();
+        groups.higher_than("Multiplicative", "Additive").unwrap();
+        groups.lower_than("Multiplicative", "Exponent").unwrap();
+        assert!(groups.higher_than("Additive", "Exponent").is_err());
+        assert!(groups.declare("Range", Associativity::Left).is_err());
+        assert!(groups.higher_than("Range", "Unknown").is_err());
+
+        let group = |name| groups.get(name).unwrap();
+        let reduces = |stack, lookahead| {
+            prec_reduces(Some(group(stack)), Some(group(lookahead)), Some(&groups))
+        };
+        // Relations are transitive.
+        assert_eq!(reduces("Exponent", "Additive"), Ok(true));
+        assert_eq!(reduces("Additive", "Exponent"), Ok(false));
+        assert_eq!(reduces("Additive", "Additive"), Ok(true));
+        assert_eq!(reduces("Exponent", "Exponent"), Ok(false));
+        assert_eq!(reduces("Range", "Range"), Err(PrecConflict::NonAssociative));
+        assert_eq!(reduces("Range", "Additive"), Err(PrecConflict::Unordered));
+        assert_eq!(
+            prec_reduces(
+                Some(group("Additive")),
+                Some(Precedence::Left(1)),
+                Some(&groups)
+            ),
+            Err(PrecConflict::Unordered)
+        );
+        // Ranks follow the order.
+        let level = |name| group(name).level();
+        assert_eq!(
+            [
+                level("Additive"),
+                level("Multiplicative"),
+                level("Exponent")
+            ],
+            [0, 1, 2]
+        );
+        assert_eq!(level("Range"), 0);
+
+        // Handles are compact; the relations stay in the registry.
+        assert_eq!(std::mem::size_of::<Precedence>(), 4);
+        assert_eq!(std::mem::size_of::<Option<Precedence>>(), 4);
+
+        // Handles from another registry, or without one, don't compare.
+        let mut other = groups.clone();
+        let foreign = other.get("Additive").unwrap();
+        assert_eq!(
+            prec_reduces(Some(group("Additive")), Some(foreign), Some(&groups)),
+            Err(PrecConflict::Mismatched)
+        );
+        assert_eq!(
+            prec_reduces(Some(group("Additive")), Some(group("Additive")), None),
+            Err(PrecConflict::Mismatched)
+        );
+        // A handle from before a later change compares by the current relations.
+        let earlier = other.get("Additive").unwrap();
+        other.declare("Assignment", Associativity::Right).unwrap();
+        other.higher_than("Additive", "Assignment").unwrap();
+        let assign = other.get("Assignment");
+        assert_eq!(prec_reduces(Some(earlier), assign, Some(&other)), Ok(true));
+
+        let grammar = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start expr;
+            terminals { prec OP, NUM }
+            expr = expr OP expr => binop | NUM => num;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let compiled = CompiledTable::build_from_internal(&grammar);
+        let num = Token::new(compiled.symbol_id("NUM").unwrap());
+        let op = |prec| Token::with_prec(compiled.symbol_id("OP").unwrap(), prec);
+
+        // a .. b + c, with the registry shared between lexer and parser
+        let groups = Arc::new(groups);
+        let group = |name| groups.get(name).unwrap();
+        let mut parser = Parser::new(compiled.table());
+        parser.set_precedence_groups(groups.clone());
+        for token in [num, op(group("Range")), num] {
+            while parser.maybe_reduce(Some(token)).unwrap().is_some() {}
+            parser.shift(token);
+        }
+        let err = loop {
+            match parser.maybe_reduce(Some(op(group("Additive")))) {
+                Ok(Some(_)) => {}
+                Ok(None) => panic!("expected an error"),
+                Err(e) => break e,
+            }
+        };
+        assert!(err.is_ambiguous_precedence());
+        assert!(!err.is_non_associative());
+        let msg = parser.format_error(&err, &compiled, None, Some(&["a", "..", "b", "+"]));
+        assert!(
+            msg.starts_with("ambiguous operator mix at '+', add parentheses"),
+            "msg: {}",
+            msg
+        );
+    }
 }
@@ -1,8 +1,8 @@
 use std::collections::{BTreeMap, HashMap};
 
-use crate::grammar::{Grammar, SymbolId};
+use crate::grammar::{Grammar, GrammarError, SymbolId};
 use crate::lr::{GrammarInternal, to_grammar_internal};
-use crate::runtime::{ErrorContext, OpEntry, ParseTable};
+use crate::runtime::{ErrorContext, OpEntry, ParseTable, RulePrec, quoted};
 
 type Row = Vec<(u32, u32)>;
 type RowGroup = (Row, Vec<usize>);
@@ -96,6 +96,11 @@ pub struct CompiledTable {
     default_reduce: Vec<u32>,
     /// Default goto target per non-terminal (u32::MAX = no default).
     default_goto: Vec<u32>,
+    /// Reductions dropped by conflict resolution, as sorted (state, terminal, rule).
+    /// Empty unless built with [`CompiledTable::build_glr`].
+    conflict_reduces: Vec<(u32, u32, u32)>,
+    /// Declared rule precedences as sorted (rule, prec).
+    rule_prec: Vec<(u32, RulePrec)>,
 }
 
 /// Return the most frequent value, or u32::MAX if empty.
@@ -186,14 +191,34 @@ impl CompiledTable {
     /// Build parse tables from a grammar.
     ///
     /// Returns an error if grammar conversion fails (for example, unknown symbols).
-    pub fn build(grammar: &Grammar) -> Result<Self, String> {
+    pub fn build(grammar: &Grammar) -> Result<Self, GrammarError> {
         let internal = to_grammar_internal(grammar)?;
         Ok(Self::build_from_internal(&internal))
     }
 
+    /// Build parse tables for [`GlrParser`](crate::glr::GlrParser).
+    ///
+    /// Conflicts are still reported and resolved as usual, but the losing
+    /// reductions are kept so the GLR parser can explore them.
+    pub fn build_glr(grammar: &Grammar) -> Result<Self, GrammarError> {
+        let internal = to_grammar_internal(grammar)?;
+        Ok(Self::build_glr_from_internal(&internal))
+    }
+
     /// Build parse tables from internal grammar representation using NFA → DFA → Hopcroft.
     pub(crate) fn build_from_internal(grammar: &GrammarInternal) -> Self {
-        let result = crate::lr::build_minimal_automaton(grammar);
+        if !grammar.twins.is_empty() {
+            // Reduce-leaning twins need the dropped reductions, kept apart per state.
+            return Self::build_glr_from_internal(grammar);
+        }
+        Self::from_automaton(grammar, crate::lr::build_minimal_automaton(grammar))
+    }
+
+    pub(crate) fn build_glr_from_internal(grammar: &GrammarInternal) -> Self {
+        Self::from_automaton(grammar, crate::lr::build_glr_automaton(grammar))
+    }
+
+    fn from_automaton(grammar: &GrammarInternal, result: crate::lr::AutomatonResult) -> Self {
         let num_terminals = grammar.symbols.num_terminals();
         let num_item_states = result.num_item_states;
         let num_non_terminals = grammar.symbols.num_non_terminals() as usize;
@@ -227,11 +252,13 @@ impl CompiledTable {
             }
         }
 
-        // Default reduce: most frequent reduce rule per state (skip accept = rule 0).
+        // Default reduce: most frequent reduce rule per state (skip the accept
+        // rules, so only EOF accepts).
+        let num_entries = grammar.num_entries() as u32;
         let default_reduce: Vec<u32> = reduce_rules_per_state
             .iter()
             .map(|rules| {
-                let default = most_frequent(rules.iter().filter(|&&r| r > 0).copied());
+                let default = most_frequent(rules.iter().filter(|&&r| r >= num_entries).copied());
                 if default != u32::MAX { default } else { 0 }
             })
             .collect();
@@ -273,18 +300,34 @@ impl CompiledTable {
             let mut row: Row = Vec::new();
 
             for sym in grammar.symbols.terminal_ids() {
-                let shift = find_target(state, sym.0).filter(|&t| t < num_item_states);
-                let reduce = if let Some(&virtual_id) = real_to_virtual.get(&sym.0) {
+                // A reduce-leaning twin has no transitions of its own; it
+                // follows its original, but reduces where the original shifts
+                // over a (dropped) reduction.
+                let twin_of = grammar.twin_of(sym);
+                let source = twin_of.unwrap_or(sym).0;
+                let mut shift = find_target(state, source).filter(|&t| t < num_item_states);
+                let mut reduce = if let Some(&virtual_id) = real_to_virtual.get(&source) {
                     // Prec terminal: reduce comes from virtual symbol transition.
                     find_target(state, virtual_id)
                         .filter(|&t| t >= num_item_states)
                         .map(|t| t - num_item_states)
                 } else {
                     // Non-prec: reduce comes from the same terminal's transition.
-                    find_target(state, sym.0)
+                    find_target(state, source)
                         .filter(|&t| t >= num_item_states)
                         .map(|t| t - num_item_states)
                 };
+                if twin_of.is_some() && shift.is_some() && reduce.is_none() {
+                    let dropped = result
+                        .conflict_reduces
+                        .iter()
+                        .filter(|&&(s, t, _)| s as usize == state && t == source)
+                        .map(|&(_, _, r)| r as usize)
+                        .min();
+                    if dropped.is_some() {
+                        (shift, reduce) = (None, dropped);
+                    }
+                }
 
                 let entry = match (shift, reduce) {
                     (Some(s), Some(r)) => OpEntry::shift_or_reduce(s, r),
@@ -333,6 +376,13 @@ impl CompiledTable {
             .map(|r| r.rhs.iter().map(|s| s.id().0).collect())
             .collect();
 
+        let rule_prec = grammar
+            .rules
+            .iter()
+            .enumerate()
+            .filter_map(|(i, r)| Some((i as u32, r.prec?)))
+            .collect();
+
         CompiledTable {
             data,
             check,
@@ -342,12 +392,24 @@ impl CompiledTable {
             grammar: grammar.clone(),
             rules,
             num_states: num_item_states,
-            conflicts: result.conflicts,
+            // Shift/reduce conflicts on split terminals are left to the lexer.
+            conflicts: result
+                .conflicts
+                .into_iter()
+                .filter(|c| match c {
+                    Conflict::ShiftReduce { terminal, .. } => {
+                        !grammar.twins.iter().any(|&(t, _)| t == *terminal)
+                    }
+                    Conflict::ReduceReduce { .. } => true,
+                })
+                .collect(),
             state_items: result.state_items,
             rule_rhs,
             state_symbols,
             default_reduce,
             default_goto,
+            conflict_reduces: result.conflict_reduces,
+            rule_prec,
         }
     }
 
@@ -363,6 +425,8 @@ impl CompiledTable {
             &self.default_reduce,
             &self.default_goto,
         )
+        .with_conflict_reduces(&self.conflict_reduces)
+        .with_rule_prec(&self.rule_prec)
     }
 
     /// Returns true if the table has conflicts.
@@ -371,7 +435,21 @@ impl CompiledTable {
     }
 
     /// Format conflicts as human-readable error messages (one string per conflict).
+    ///
+    /// For grammars with `mode split;`, the terminals that were split into
+    /// shift- and reduce-leaning variants are listed after the conflicts.
     pub fn format_conflicts(&self) -> Vec<String> {
+        let splits = self.grammar.twins.iter().map(|&(original, twin)| {
+            let name = quoted(self.grammar.symbols.name(original));
+            format!(
+                "Split {} on shift/reduce conflict:\n  \
+                 Shift:  push {}\n  \
+                 Reduce: push {}",
+                name,
+                name,
+                quoted(self.grammar.symbols.name(twin)),
+            )
+        });
         self.conflicts
             .iter()
             .map(|c| match c {
@@ -380,11 +458,11 @@ impl CompiledTable {
                     reduce_rule,
                     example,
                 } => {
-                    let term_name = self.grammar.symbols.name(*terminal);
+                    let term_name = quoted(self.grammar.symbols.name(*terminal));
                     let reduce_item =
                         self.format_item(*reduce_rule, self.rule_rhs[*reduce_rule].len());
                     let mut msg = format!(
-                        "Shift/reduce conflict on '{}':\n  \
+                        "Shift/reduce conflict on {}:\n  \
                          Shift wins over: {}",
                         term_name, reduce_item,
                     );
@@ -399,11 +477,11 @@ impl CompiledTable {
                     rule2,
                     example,
                 } => {
-                    let term_name = self.grammar.symbols.name(*terminal);
+                    let term_name = quoted(self.grammar.symbols.name(*terminal));
                     let item1 = self.format_item(*rule1, self.rule_rhs[*rule1].len());
                     let item2 = self.format_item(*rule2, self.rule_rhs[*rule2].len());
                     let mut msg = format!(
-                        "Reduce/reduce conflict on '{}':\n  \
+                        "Reduce/reduce conflict on {}:\n  \
                          Reduce: {} (wins)\n  \
                          Reduce: {}",
                         term_name, item1, item2,
@@ -414,6 +492,7 @@ impl CompiledTable {
                     msg
                 }
             })
+            .chain(splits)
             .collect()
     }
 
@@ -441,6 +520,16 @@ impl CompiledTable {
         self.grammar.symbols.get_id(name)
     }
 
+    /// The marker terminal of start symbol `name`, for
+    /// [`Parser::for_entry`](crate::Parser::for_entry). `None` unless the
+    /// grammar declares several start symbols.
+    pub fn entry(&self, name: &str) -> Option<SymbolId> {
+        self.grammar
+            .entries()
+            .find(|&(_, start)| self.grammar.symbols.name(start.id()) == name)
+            .map(|(marker, _)| marker)
+    }
+
     /// Get the name of a symbol by ID.
     pub fn symbol_name(&self, id: SymbolId) -> &str {
         self.grammar.symbols.name(id)
@@ -506,7 +595,7 @@ impl CompiledTable {
     #[doc(hidden)]
     pub fn rule_name(&self, rule: usize) -> Option<&str> {
         self.grammar.rules.get(rule).and_then(|r| {
-            if let crate::lr::AltAction::Named(name) = &r.action {
+            if let crate::lr::AltAction::Named { name, .. } = &r.action {
                 Some(name.as_str())
             } else {
                 None
@@ -528,6 +617,11 @@ impl CompiledTable {
     pub fn default_goto(&self) -> &[u32] {
         &self.default_goto
     }
+
+    #[doc(hidden)]
+    pub fn rule_prec(&self) -> &[(u32, RulePrec)] {
+        &self.rule_prec
+    }
 }
 
 impl ErrorContext for CompiledTable {
@@ -556,7 +650,7 @@ mod tests {
     use super::*;
     use crate::lr::to_grammar_internal;
     use crate::meta::parse_grammar;
-    use crate::runtime::ParserOp;
+    use crate::runtime::{ParserOp, Token};
 
     fn simple_grammar() -> GrammarInternal {
         to_grammar_internal(
@@ -755,6 +849,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_literal_conflict_names() {
+        let grammar = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start expr;
+            terminals { NUM }
+            expr = expr '*' expr => mul | NUM => num;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let compiled = CompiledTable::build_from_internal(&grammar);
+        let messages = compiled.format_conflicts();
+        assert!(
+            messages[0].starts_with("Shift/reduce conflict on '*':"),
+            "{}",
+            messages[0]
+        );
+    }
+
     #[test]
     fn test_no_conflict_examples_for_clean_grammar() {
         let grammar = expr_grammar();
@@ -789,6 +905,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_split_tokens() {
+        let grammar = to_grammar_internal(
+            &parse_grammar(
+                r#"
+            start expr;
+            mode split;
+            terminals { PLUS, NUM }
+            expr = expr PLUS expr => add | NUM => num;
+        "#,
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let compiled = CompiledTable::build_from_internal(&grammar);
+        assert!(!compiled.has_conflicts());
+
+        let messages = compiled.format_conflicts();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("Split 'PLUS'"), "{}", messages[0]);
+        assert!(messages[0].contains("'PLUS(reduce)'"), "{}", messages[0]);
+
+        // The lexer picks the associativity: PLUS shifts, PLUS(reduce) reduces.
+        let num = Token::new(compiled.symbol_id("NUM").unwrap());
+        let reductions = |op: &str| {
+            let op = Token::new(compiled.symbol_id(op).unwrap());
+            let mut parser = crate::runtime::Parser::new(compiled.table());
+            let mut rules = Vec::new();
+            for token in [num, op, num, op, num].map(Some).into_iter().chain([None]) {
+                while let Some((rule, _, _)) = parser.maybe_reduce(token).unwrap() {
+                    if rule == 0 {
+                        return rules;
+                    }
+                    rules.push(rule);
+                }
+                parser.shift(token.unwrap());
+            }
+            unreachable!()
+        };
+        assert_eq!(reductions("PLUS"), [2, 2, 2, 1, 1]);
+        assert_eq!(reductions("PLUS(reduce)"), [2, 2, 1, 2, 1]);
+    }
+
     #[test]
     fn test_goto() {
         let grammar = expr_grammar();
@@ -29,6 +29,28 @@ fn error_unexpected_token_simple() {
     assert_eq!(msg, "unexpected 'b', expected: S");
 }
 
+/// A literal terminal's display name is not quoted again: S -> a '+' a
+#[test]
+fn error_unexpected_literal() {
+    let grammar = parse_grammar(
+        r#"
+        start S;
+        terminals { a }
+        S = a '+' a => add;
+    "#,
+    )
+    .unwrap();
+
+    let compiled = CompiledTable::build(&grammar).unwrap();
+    let mut parser = Parser::new(compiled.table());
+
+    let plus_id = compiled.symbol_id("'+'").unwrap();
+    let err = parser.maybe_reduce(Some(Token::new(plus_id))).unwrap_err();
+    let msg = parser.format_error(&err, &compiled, None, None);
+
+    assert_eq!(msg, "unexpected '+', expected: S");
+}
+
 /// Simple grammar: S -> a, but we send EOF immediately
 #[test]
 fn error_unexpected_eof() {